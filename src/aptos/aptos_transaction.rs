@@ -0,0 +1,285 @@
+//! Aptos transaction
+use serde::{Deserialize, Serialize};
+
+use super::{
+    types::{AccountAddress, TransactionPayload},
+    utils::{encode_bytes, encode_uleb128, raw_transaction_prefix},
+};
+
+/// Variant of the authenticators of transactions signed by a single Ed25519 key.
+const ED25519_AUTHENTICATOR: u64 = 0;
+
+/// A raw transaction of Aptos, BCS encoded, signed with the Ed25519 key of its sender and
+/// submitted as the BCS of its `SignedTransaction` to the `/transactions` endpoint with the
+/// `application/x.aptos.signed_transaction+bcs` content type.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::aptos::types::{
+///     AccountAddress, EntryFunction, TransactionPayload, MAINNET_CHAIN_ID, OCTAS_PER_APT,
+/// };
+/// use signet_rs::aptos::AptosTransaction;
+///
+/// let public_key = [2; 32];
+/// let tx = AptosTransaction {
+///     sender: AccountAddress::from_ed25519_public_key(&public_key),
+///     sequence_number: 7,
+///     payload: TransactionPayload::EntryFunction(EntryFunction::aptos_transfer(
+///         AccountAddress([1; 32]),
+///         OCTAS_PER_APT,
+///     )),
+///     max_gas_amount: 200_000,
+///     gas_unit_price: 100,
+///     expiration_timestamp_secs: 1_700_000_600,
+///     chain_id: MAINNET_CHAIN_ID,
+/// };
+///
+/// // The signing message, signed as is with Ed25519
+/// let message = tx.build_for_signing();
+///
+/// let signature = [7; 64];
+/// let signed_tx = tx.build_with_signature(&public_key, &signature);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AptosTransaction {
+    /// The account sending the transaction, paying its gas.
+    pub sender: AccountAddress,
+    /// The number of transactions of the sender so far.
+    pub sequence_number: u64,
+    pub payload: TransactionPayload,
+    /// The most gas units the transaction can use.
+    pub max_gas_amount: u64,
+    /// The octas paid for each gas unit.
+    pub gas_unit_price: u64,
+    /// The time in seconds after which the transaction is no longer valid.
+    pub expiration_timestamp_secs: u64,
+    /// The id of the chain, like [`super::types::MAINNET_CHAIN_ID`].
+    pub chain_id: u8,
+}
+
+impl AptosTransaction {
+    /// Encode the raw transaction in BCS.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&self.sender.0);
+        buffer.extend_from_slice(&self.sequence_number.to_le_bytes());
+        self.payload.encode(&mut buffer);
+        buffer.extend_from_slice(&self.max_gas_amount.to_le_bytes());
+        buffer.extend_from_slice(&self.gas_unit_price.to_le_bytes());
+        buffer.extend_from_slice(&self.expiration_timestamp_secs.to_le_bytes());
+        buffer.push(self.chain_id);
+        buffer
+    }
+
+    /// Encode the signing message of the transaction, the raw transaction prefixed with the
+    /// hash of its salt.
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        let mut message = raw_transaction_prefix().to_vec();
+        message.extend_from_slice(&self.encode());
+        message
+    }
+
+    /// Encode the `SignedTransaction` of the raw transaction and the Ed25519 `signature` of its
+    /// signing message by `public_key`.
+    pub fn build_with_signature(&self, public_key: &[u8; 32], signature: &[u8; 64]) -> Vec<u8> {
+        let mut buffer = self.encode();
+        encode_uleb128(&mut buffer, ED25519_AUTHENTICATOR);
+        encode_bytes(&mut buffer, public_key);
+        encode_bytes(&mut buffer, signature);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aptos::types::{
+        EntryFunction, ModuleId, StructTag, TypeTag, MAINNET_CHAIN_ID, OCTAS_PER_APT,
+    };
+    use ed25519_dalek::{Signer, SigningKey, Verifier};
+
+    /// Mirrors of the types of the Aptos core, encoded with `bcs`.
+    mod bcs_types {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        pub struct ModuleId {
+            pub address: [u8; 32],
+            pub name: String,
+        }
+
+        #[derive(Serialize)]
+        pub struct StructTag {
+            pub address: [u8; 32],
+            pub module: String,
+            pub name: String,
+            pub type_args: Vec<TypeTag>,
+        }
+
+        // Only constructed for the index of their variants
+        #[allow(dead_code)]
+        #[derive(Serialize)]
+        pub enum TypeTag {
+            Bool,
+            U8,
+            U64,
+            U128,
+            Address,
+            Signer,
+            Vector(Box<TypeTag>),
+            Struct(Box<StructTag>),
+        }
+
+        #[derive(Serialize)]
+        pub struct EntryFunction {
+            pub module: ModuleId,
+            pub function: String,
+            pub ty_args: Vec<TypeTag>,
+            pub args: Vec<Vec<u8>>,
+        }
+
+        #[allow(dead_code)]
+        #[derive(Serialize)]
+        pub enum TransactionPayload {
+            Script,
+            ModuleBundle,
+            EntryFunction(EntryFunction),
+        }
+
+        #[derive(Serialize)]
+        pub struct RawTransaction {
+            pub sender: [u8; 32],
+            pub sequence_number: u64,
+            pub payload: TransactionPayload,
+            pub max_gas_amount: u64,
+            pub gas_unit_price: u64,
+            pub expiration_timestamp_secs: u64,
+            pub chain_id: u8,
+        }
+
+        #[derive(Serialize)]
+        pub enum TransactionAuthenticator {
+            Ed25519 {
+                public_key: Vec<u8>,
+                signature: Vec<u8>,
+            },
+        }
+
+        #[derive(Serialize)]
+        pub struct SignedTransaction {
+            pub raw_txn: RawTransaction,
+            pub authenticator: TransactionAuthenticator,
+        }
+    }
+
+    fn transfer(sender: AccountAddress) -> AptosTransaction {
+        AptosTransaction {
+            sender,
+            sequence_number: 7,
+            payload: TransactionPayload::EntryFunction(EntryFunction::aptos_transfer(
+                AccountAddress([1; 32]),
+                OCTAS_PER_APT,
+            )),
+            max_gas_amount: 200_000,
+            gas_unit_price: 100,
+            expiration_timestamp_secs: 1_700_000_600,
+            chain_id: MAINNET_CHAIN_ID,
+        }
+    }
+
+    fn bcs_transfer(sender: AccountAddress) -> bcs_types::RawTransaction {
+        bcs_types::RawTransaction {
+            sender: sender.0,
+            sequence_number: 7,
+            payload: bcs_types::TransactionPayload::EntryFunction(bcs_types::EntryFunction {
+                module: bcs_types::ModuleId {
+                    address: AccountAddress::ONE.0,
+                    name: "aptos_account".to_string(),
+                },
+                function: "transfer".to_string(),
+                ty_args: vec![],
+                args: vec![
+                    bcs::to_bytes(&[1u8; 32]).unwrap(),
+                    bcs::to_bytes(&OCTAS_PER_APT).unwrap(),
+                ],
+            }),
+            max_gas_amount: 200_000,
+            gas_unit_price: 100,
+            expiration_timestamp_secs: 1_700_000_600,
+            chain_id: MAINNET_CHAIN_ID,
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let sender = AccountAddress([2; 32]);
+        let tx = transfer(sender);
+
+        assert_eq!(tx.encode(), bcs::to_bytes(&bcs_transfer(sender)).unwrap());
+        assert_eq!(
+            tx.build_for_signing(),
+            [raw_transaction_prefix().to_vec(), tx.encode()].concat()
+        );
+    }
+
+    #[test]
+    fn test_encode_generic_entry_function() {
+        // 0x1::coin::transfer<0x1::aptos_coin::AptosCoin>(to, amount)
+        let tx = AptosTransaction {
+            payload: TransactionPayload::EntryFunction(EntryFunction {
+                module: ModuleId {
+                    address: AccountAddress::ONE,
+                    name: "coin".to_string(),
+                },
+                function: "transfer".to_string(),
+                ty_args: vec![TypeTag::Struct(Box::new(StructTag::aptos_coin()))],
+                args: vec![vec![1; 32], 5u64.to_le_bytes().to_vec()],
+            }),
+            ..transfer(AccountAddress([2; 32]))
+        };
+
+        let expected = bcs_types::RawTransaction {
+            payload: bcs_types::TransactionPayload::EntryFunction(bcs_types::EntryFunction {
+                module: bcs_types::ModuleId {
+                    address: AccountAddress::ONE.0,
+                    name: "coin".to_string(),
+                },
+                function: "transfer".to_string(),
+                ty_args: vec![bcs_types::TypeTag::Struct(Box::new(bcs_types::StructTag {
+                    address: AccountAddress::ONE.0,
+                    module: "aptos_coin".to_string(),
+                    name: "AptosCoin".to_string(),
+                    type_args: vec![],
+                }))],
+                args: vec![vec![1; 32], bcs::to_bytes(&5u64).unwrap()],
+            }),
+            ..bcs_transfer(AccountAddress([2; 32]))
+        };
+        assert_eq!(tx.encode(), bcs::to_bytes(&expected).unwrap());
+    }
+
+    #[test]
+    fn test_build_with_signature() {
+        let signing_key = SigningKey::from_bytes(&[3; 32]);
+        let public_key = signing_key.verifying_key().to_bytes();
+        let sender = AccountAddress::from_ed25519_public_key(&public_key);
+        let tx = transfer(sender);
+
+        let signature = signing_key.sign(&tx.build_for_signing());
+        signing_key
+            .verifying_key()
+            .verify(&tx.build_for_signing(), &signature)
+            .unwrap();
+
+        let signed_tx = tx.build_with_signature(&public_key, &signature.to_bytes());
+        let expected = bcs_types::SignedTransaction {
+            raw_txn: bcs_transfer(sender),
+            authenticator: bcs_types::TransactionAuthenticator::Ed25519 {
+                public_key: public_key.to_vec(),
+                signature: signature.to_bytes().to_vec(),
+            },
+        };
+        assert_eq!(signed_tx, bcs::to_bytes(&expected).unwrap());
+    }
+}
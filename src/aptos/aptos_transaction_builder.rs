@@ -0,0 +1,147 @@
+use crate::transaction_builder::TxBuilder;
+
+use super::{
+    aptos_transaction::AptosTransaction,
+    types::{AccountAddress, TransactionPayload},
+};
+
+/// Gas units transactions can use by default, the default of the Aptos SDKs.
+pub const DEFAULT_MAX_GAS_AMOUNT: u64 = 200_000;
+/// Octas paid for each gas unit by default, the minimum gas unit price.
+pub const DEFAULT_GAS_UNIT_PRICE: u64 = 100;
+
+pub struct AptosTransactionBuilder {
+    sender: Option<AccountAddress>,
+    sequence_number: Option<u64>,
+    payload: Option<TransactionPayload>,
+    max_gas_amount: Option<u64>,
+    gas_unit_price: Option<u64>,
+    expiration_timestamp_secs: Option<u64>,
+    chain_id: Option<u8>,
+}
+
+impl Default for AptosTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<AptosTransaction> for AptosTransactionBuilder {
+    fn build(&self) -> AptosTransaction {
+        AptosTransaction {
+            sender: self.sender.expect("sender is mandatory"),
+            sequence_number: self.sequence_number.expect("sequence_number is mandatory"),
+            payload: self.payload.clone().expect("payload is mandatory"),
+            max_gas_amount: self.max_gas_amount.unwrap_or(DEFAULT_MAX_GAS_AMOUNT),
+            gas_unit_price: self.gas_unit_price.unwrap_or(DEFAULT_GAS_UNIT_PRICE),
+            expiration_timestamp_secs: self
+                .expiration_timestamp_secs
+                .expect("expiration_timestamp_secs is mandatory"),
+            chain_id: self.chain_id.expect("chain_id is mandatory"),
+        }
+    }
+}
+
+impl AptosTransactionBuilder {
+    pub const fn new() -> Self {
+        Self {
+            sender: None,
+            sequence_number: None,
+            payload: None,
+            max_gas_amount: None,
+            gas_unit_price: None,
+            expiration_timestamp_secs: None,
+            chain_id: None,
+        }
+    }
+
+    /// Account sending the transaction.
+    pub const fn sender(mut self, sender: AccountAddress) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Sequence number of the sender, from its account resource.
+    pub const fn sequence_number(mut self, sequence_number: u64) -> Self {
+        self.sequence_number = Some(sequence_number);
+        self
+    }
+
+    /// Payload of the transaction, like an entry function.
+    pub fn payload(mut self, payload: TransactionPayload) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    /// Most gas units the transaction can use, [`DEFAULT_MAX_GAS_AMOUNT`] by default.
+    pub const fn max_gas_amount(mut self, max_gas_amount: u64) -> Self {
+        self.max_gas_amount = Some(max_gas_amount);
+        self
+    }
+
+    /// Octas paid for each gas unit, [`DEFAULT_GAS_UNIT_PRICE`] by default.
+    pub const fn gas_unit_price(mut self, gas_unit_price: u64) -> Self {
+        self.gas_unit_price = Some(gas_unit_price);
+        self
+    }
+
+    /// Time in seconds after which the transaction is no longer valid.
+    pub const fn expiration_timestamp_secs(mut self, expiration_timestamp_secs: u64) -> Self {
+        self.expiration_timestamp_secs = Some(expiration_timestamp_secs);
+        self
+    }
+
+    /// Id of the chain.
+    pub const fn chain_id(mut self, chain_id: u8) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aptos::types::{EntryFunction, TESTNET_CHAIN_ID};
+    use crate::TransactionBuilder;
+
+    #[test]
+    fn test_build() {
+        let payload = TransactionPayload::EntryFunction(EntryFunction::aptos_transfer(
+            AccountAddress([1; 32]),
+            5,
+        ));
+
+        let tx: AptosTransaction = TransactionBuilder::new::<AptosTransactionBuilder>()
+            .sender(AccountAddress([2; 32]))
+            .sequence_number(7)
+            .payload(payload.clone())
+            .expiration_timestamp_secs(1_700_000_600)
+            .chain_id(TESTNET_CHAIN_ID)
+            .build();
+
+        assert_eq!(
+            tx,
+            AptosTransaction {
+                sender: AccountAddress([2; 32]),
+                sequence_number: 7,
+                payload: payload.clone(),
+                max_gas_amount: DEFAULT_MAX_GAS_AMOUNT,
+                gas_unit_price: DEFAULT_GAS_UNIT_PRICE,
+                expiration_timestamp_secs: 1_700_000_600,
+                chain_id: TESTNET_CHAIN_ID,
+            }
+        );
+
+        let tx: AptosTransaction = AptosTransactionBuilder::new()
+            .sender(AccountAddress([2; 32]))
+            .sequence_number(7)
+            .payload(payload)
+            .max_gas_amount(2_000)
+            .gas_unit_price(150)
+            .expiration_timestamp_secs(1_700_000_600)
+            .chain_id(TESTNET_CHAIN_ID)
+            .build();
+        assert_eq!(tx.max_gas_amount, 2_000);
+        assert_eq!(tx.gas_unit_price, 150);
+    }
+}
@@ -0,0 +1,12 @@
+//! Transaction builder, encoders, types and utilities for Aptos.
+mod aptos_transaction;
+mod aptos_transaction_builder;
+pub mod types;
+pub mod utils;
+
+/// Aptos transaction
+pub use aptos_transaction::AptosTransaction;
+/// Aptos transaction builder
+pub use aptos_transaction_builder::{
+    AptosTransactionBuilder, DEFAULT_GAS_UNIT_PRICE, DEFAULT_MAX_GAS_AMOUNT,
+};
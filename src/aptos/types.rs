@@ -0,0 +1,297 @@
+//! Types used by the Aptos transaction builder.
+use core::{fmt, str::FromStr};
+
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Sha3_256};
+
+use super::utils::{encode_bytes, encode_str, encode_uleb128};
+
+/// Octas in one APT.
+pub const OCTAS_PER_APT: u64 = 100_000_000;
+/// Chain id of the mainnet.
+pub const MAINNET_CHAIN_ID: u8 = 1;
+/// Chain id of the testnet.
+pub const TESTNET_CHAIN_ID: u8 = 2;
+
+/// Scheme of single Ed25519 keys, appended to the key hashed into the address of its account.
+const ED25519_SCHEME: u8 = 0;
+
+/// An account, or the address of Move modules, displayed as `0x` and its 64 hex digits, or as
+/// `0x1` to `0xf` for the special addresses of the framework.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccountAddress(pub [u8; 32]);
+
+impl AccountAddress {
+    /// The address of the framework, `0x1`.
+    pub const ONE: Self = Self::special(1);
+
+    const fn special(value: u8) -> Self {
+        let mut address = [0; 32];
+        address[31] = value;
+        Self(address)
+    }
+
+    /// The address of the account created for the Ed25519 key `public_key`, the sha3-256 of
+    /// the key and its scheme, which stays the address of the account if its key is rotated.
+    pub fn from_ed25519_public_key(public_key: &[u8; 32]) -> Self {
+        Self(
+            Sha3_256::new()
+                .chain_update(public_key)
+                .chain_update([ED25519_SCHEME])
+                .finalize()
+                .into(),
+        )
+    }
+
+    /// Whether the address is one of the special addresses from `0x0` to `0xf`.
+    pub fn is_special(&self) -> bool {
+        self.0[..31].iter().all(|&byte| byte == 0) && self.0[31] < 0x10
+    }
+}
+
+/// Parses addresses of `0x` and at most 64 hex digits, left padded with zeros.
+impl FromStr for AccountAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .strip_prefix("0x")
+            .ok_or_else(|| format!("Invalid address {s}: missing 0x prefix"))?;
+        if digits.is_empty() || digits.len() > 64 {
+            return Err(format!("Invalid address length: {s}"));
+        }
+
+        let mut padded = "0".repeat(64 - digits.len());
+        padded.push_str(digits);
+        let mut address = [0; 32];
+        hex::decode_to_slice(&padded, &mut address)
+            .map_err(|e| format!("Invalid address {s}: {e}"))?;
+        Ok(Self(address))
+    }
+}
+
+impl fmt::Display for AccountAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_special() {
+            write!(f, "0x{:x}", self.0[31])
+        } else {
+            write!(f, "0x{}", hex::encode(self.0))
+        }
+    }
+}
+
+impl Serialize for AccountAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AccountAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// A Move type, a type argument of generic functions like `0x1::coin::transfer<CoinType>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TypeTag {
+    Bool,
+    U8,
+    U64,
+    U128,
+    Address,
+    Signer,
+    Vector(Box<TypeTag>),
+    Struct(Box<StructTag>),
+    U16,
+    U32,
+    U256,
+}
+
+impl TypeTag {
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Self::Bool => buffer.push(0),
+            Self::U8 => buffer.push(1),
+            Self::U64 => buffer.push(2),
+            Self::U128 => buffer.push(3),
+            Self::Address => buffer.push(4),
+            Self::Signer => buffer.push(5),
+            Self::Vector(type_tag) => {
+                buffer.push(6);
+                type_tag.encode(buffer);
+            }
+            Self::Struct(struct_tag) => {
+                buffer.push(7);
+                struct_tag.encode(buffer);
+            }
+            Self::U16 => buffer.push(8),
+            Self::U32 => buffer.push(9),
+            Self::U256 => buffer.push(10),
+        }
+    }
+}
+
+/// A Move struct, like `0x1::aptos_coin::AptosCoin`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructTag {
+    pub address: AccountAddress,
+    pub module: String,
+    pub name: String,
+    pub type_args: Vec<TypeTag>,
+}
+
+impl StructTag {
+    /// The native coin, `0x1::aptos_coin::AptosCoin`.
+    pub fn aptos_coin() -> Self {
+        Self {
+            address: AccountAddress::ONE,
+            module: "aptos_coin".to_string(),
+            name: "AptosCoin".to_string(),
+            type_args: Vec::new(),
+        }
+    }
+
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.address.0);
+        encode_str(buffer, &self.module);
+        encode_str(buffer, &self.name);
+        encode_uleb128(buffer, self.type_args.len() as u64);
+        for type_arg in &self.type_args {
+            type_arg.encode(buffer);
+        }
+    }
+}
+
+/// A Move module, its address and its name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleId {
+    pub address: AccountAddress,
+    pub name: String,
+}
+
+/// A call of an entry function of a Move module, with its type arguments and its BCS encoded
+/// arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryFunction {
+    pub module: ModuleId,
+    pub function: String,
+    pub ty_args: Vec<TypeTag>,
+    /// The arguments of the function, each encoded in BCS, like the 32 bytes of an address or
+    /// the 8 little-endian bytes of a `u64`.
+    pub args: Vec<Vec<u8>>,
+}
+
+impl EntryFunction {
+    /// The transfer of `amount` octas to `to`, `0x1::aptos_account::transfer`, creating the
+    /// account of `to` if it does not exist.
+    pub fn aptos_transfer(to: AccountAddress, amount: u64) -> Self {
+        Self {
+            module: ModuleId {
+                address: AccountAddress::ONE,
+                name: "aptos_account".to_string(),
+            },
+            function: "transfer".to_string(),
+            ty_args: Vec::new(),
+            args: vec![to.0.to_vec(), amount.to_le_bytes().to_vec()],
+        }
+    }
+
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.module.address.0);
+        encode_str(buffer, &self.module.name);
+        encode_str(buffer, &self.function);
+        encode_uleb128(buffer, self.ty_args.len() as u64);
+        for ty_arg in &self.ty_args {
+            ty_arg.encode(buffer);
+        }
+        encode_uleb128(buffer, self.args.len() as u64);
+        for arg in &self.args {
+            encode_bytes(buffer, arg);
+        }
+    }
+}
+
+/// The payload of a transaction, the code it executes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionPayload {
+    EntryFunction(EntryFunction),
+}
+
+impl TransactionPayload {
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        match self {
+            // After the variants of scripts and of the deprecated module bundles
+            Self::EntryFunction(entry_function) => {
+                encode_uleb128(buffer, 2);
+                entry_function.encode(buffer);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_address() {
+        let one: AccountAddress = "0x1".parse().unwrap();
+        assert_eq!(one, AccountAddress::ONE);
+        assert_eq!(one.to_string(), "0x1");
+        assert!(one.is_special());
+
+        let address: AccountAddress =
+            "0x0000000000000000000000000000000000000000000000000000000000000a10"
+                .parse()
+                .unwrap();
+        assert!(!address.is_special());
+        assert_eq!(
+            address.to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000a10"
+        );
+        assert_eq!("0xa10".parse::<AccountAddress>().unwrap(), address);
+
+        assert!("1".parse::<AccountAddress>().is_err());
+        assert!("0x".parse::<AccountAddress>().is_err());
+        assert!(format!("0x{}", "1".repeat(65))
+            .parse::<AccountAddress>()
+            .is_err());
+
+        let json = serde_json::to_string(&address).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AccountAddress>(&json).unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn test_from_ed25519_public_key() {
+        assert_eq!(
+            AccountAddress::from_ed25519_public_key(&[0; 32]).to_string(),
+            "0xdc33296e4d20f0ef35ff9fd449e23ebbaa5a049a17779db3c2fe194b499aaf74"
+        );
+    }
+
+    #[test]
+    fn test_encode_type_tag() {
+        let coin_store = TypeTag::Struct(Box::new(StructTag {
+            address: AccountAddress::ONE,
+            module: "coin".to_string(),
+            name: "CoinStore".to_string(),
+            type_args: vec![TypeTag::Struct(Box::new(StructTag::aptos_coin()))],
+        }));
+
+        let mut buffer = Vec::new();
+        TypeTag::Vector(Box::new(TypeTag::U8)).encode(&mut buffer);
+        coin_store.encode(&mut buffer);
+
+        let mut expected = vec![6, 1, 7];
+        expected.extend_from_slice(&AccountAddress::ONE.0);
+        expected.extend_from_slice(b"\x04coin\x09CoinStore\x01\x07");
+        expected.extend_from_slice(&AccountAddress::ONE.0);
+        expected.extend_from_slice(b"\x0aaptos_coin\x09AptosCoin\x00");
+        assert_eq!(buffer, expected);
+    }
+}
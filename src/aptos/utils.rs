@@ -0,0 +1,68 @@
+//! BCS encoding of the fields of Aptos transactions, and the prefix of their signing message.
+use sha3::{Digest, Sha3_256};
+
+/// Salt hashed into the prefix of the signing message of raw transactions.
+pub const RAW_TRANSACTION_SALT: &[u8] = b"APTOS::RawTransaction";
+
+/// Encode `value` as an unsigned LEB128, the encoding of the lengths of sequences and of the
+/// variants of enums.
+pub fn encode_uleb128(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// Encode a sequence of bytes, prefixed with its length.
+pub fn encode_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) {
+    encode_uleb128(buffer, bytes.len() as u64);
+    buffer.extend_from_slice(bytes);
+}
+
+/// Encode a string, like an identifier of a Move module or function, prefixed with its length.
+pub fn encode_str(buffer: &mut Vec<u8>, s: &str) {
+    encode_bytes(buffer, s.as_bytes());
+}
+
+/// The prefix of the signing message of raw transactions, the sha3-256 of
+/// [`RAW_TRANSACTION_SALT`].
+pub fn raw_transaction_prefix() -> [u8; 32] {
+    Sha3_256::digest(RAW_TRANSACTION_SALT).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_uleb128() {
+        for (value, encoded) in [
+            (0, "00"),
+            (1, "01"),
+            (127, "7f"),
+            (128, "8001"),
+            (16_384, "808001"),
+            (u64::from(u32::MAX), "ffffffff0f"),
+        ] {
+            let mut buffer = Vec::new();
+            encode_uleb128(&mut buffer, value);
+            assert_eq!(hex::encode(buffer), encoded);
+        }
+    }
+
+    #[test]
+    fn test_encode_str() {
+        let mut buffer = Vec::new();
+        encode_str(&mut buffer, "aptos_account");
+        assert_eq!(buffer, bcs::to_bytes("aptos_account").unwrap());
+    }
+
+    #[test]
+    fn test_raw_transaction_prefix() {
+        assert_eq!(
+            hex::encode(raw_transaction_prefix()),
+            "b5e97db07fa0bd0e5598aa3643a9bc6f6693bddc1a9fec9e674a461eaa00b193"
+        );
+    }
+}
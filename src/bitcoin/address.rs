@@ -0,0 +1,544 @@
+//! Bitcoin addresses and their conversion to and from script pubkeys.
+use core::fmt;
+
+use bech32::{primitives::hrp::Hrp, Fe32};
+
+use super::types::{Network, ScriptBuf};
+
+/// The data encoded in an address, which determines its script pubkey.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Payload {
+    /// P2PKH address, the hash160 of a public key.
+    PubkeyHash([u8; 20]),
+    /// P2SH address, the hash160 of a redeem script.
+    ScriptHash([u8; 20]),
+    /// SegWit address (BIP-141), a witness version and a witness program.
+    WitnessProgram { version: u8, program: Vec<u8> },
+}
+
+/// A Bitcoin address for a given network.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::bitcoin::address::Address;
+/// use signet_rs::bitcoin::types::Network;
+///
+/// let address = Address::parse(
+///     "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx",
+///     Network::Signet,
+/// )
+/// .unwrap();
+/// let script_pubkey = address.script_pubkey();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Address {
+    network: Network,
+    payload: Payload,
+}
+
+impl Address {
+    /// Creates a P2PKH address from the hash160 of a public key.
+    pub const fn p2pkh(pubkey_hash: [u8; 20], network: Network) -> Self {
+        Self {
+            network,
+            payload: Payload::PubkeyHash(pubkey_hash),
+        }
+    }
+
+    /// Creates a P2SH address from the hash160 of a redeem script.
+    pub const fn p2sh(script_hash: [u8; 20], network: Network) -> Self {
+        Self {
+            network,
+            payload: Payload::ScriptHash(script_hash),
+        }
+    }
+
+    /// Creates a SegWit address from a witness version and program.
+    pub fn from_witness_program(
+        version: u8,
+        program: Vec<u8>,
+        network: Network,
+    ) -> Result<Self, String> {
+        if network.bech32_hrp().is_none() {
+            return Err(format!("Network {network} does not support SegWit"));
+        }
+        validate_witness_program(version, &program)?;
+
+        Ok(Self {
+            network,
+            payload: Payload::WitnessProgram { version, program },
+        })
+    }
+
+    /// Extracts the address paying to the given script pubkey.
+    pub fn from_script(script_pubkey: &ScriptBuf, network: Network) -> Result<Self, String> {
+        let script = &script_pubkey.0;
+
+        // OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG
+        if script.len() == 25 && script[..3] == [0x76, 0xa9, 0x14] && script[23..] == [0x88, 0xac] {
+            return Ok(Self::p2pkh(script[3..23].try_into().unwrap(), network));
+        }
+
+        // OP_HASH160 <20 bytes> OP_EQUAL
+        if script.len() == 23 && script[..2] == [0xa9, 0x14] && script[22] == 0x87 {
+            return Ok(Self::p2sh(script[2..22].try_into().unwrap(), network));
+        }
+
+        // <version> <2 to 40 bytes>
+        if (4..=42).contains(&script.len()) && script[1] as usize == script.len() - 2 {
+            let version = match script[0] {
+                0x00 => Some(0),
+                op @ 0x51..=0x60 => Some(op - 0x50),
+                _ => None,
+            };
+            if let Some(version) = version {
+                return Self::from_witness_program(version, script[2..].to_vec(), network);
+            }
+        }
+
+        Err("Script pubkey has no address form".to_string())
+    }
+
+    /// Parses an address string, checking that it belongs to the given network.
+    ///
+    /// Test networks share their address prefixes, so a testnet address is also valid
+    /// for signet and vice versa. Bitcoin Cash addresses are accepted both in CashAddr
+    /// and in legacy base58 form.
+    pub fn parse(address: &str, network: Network) -> Result<Self, String> {
+        if let Some(prefix) = network.cashaddr_prefix() {
+            if let Ok((version, hash)) = decode_cashaddr(address, prefix) {
+                let hash: [u8; 20] = hash
+                    .try_into()
+                    .map_err(|_| "Unsupported CashAddr hash size".to_string())?;
+
+                return match version {
+                    CASHADDR_P2PKH => Ok(Self::p2pkh(hash, network)),
+                    CASHADDR_P2SH => Ok(Self::p2sh(hash, network)),
+                    _ => Err(format!("Unsupported CashAddr version: {version}")),
+                };
+            }
+        }
+
+        if let Ok((hrp, version, program)) = bech32::segwit::decode(address) {
+            if Some(hrp.as_str()) != network.bech32_hrp() {
+                return Err(format!("Address is not valid for network {network}"));
+            }
+
+            return Self::from_witness_program(version.to_u8(), program, network);
+        }
+
+        let data = bs58::decode(address)
+            .with_check(None)
+            .into_vec()
+            .map_err(|e| e.to_string())?;
+        if data.len() != 21 {
+            return Err("Invalid base58 address length".to_string());
+        }
+
+        let hash: [u8; 20] = data[1..].try_into().unwrap();
+        match data[0] {
+            prefix if prefix == network.p2pkh_prefix() => Ok(Self::p2pkh(hash, network)),
+            prefix if prefix == network.p2sh_prefix() => Ok(Self::p2sh(hash, network)),
+            _ => Err(format!("Address is not valid for network {network}")),
+        }
+    }
+
+    /// The network of the address.
+    pub const fn network(&self) -> Network {
+        self.network
+    }
+
+    /// The data encoded in the address.
+    pub const fn payload(&self) -> &Payload {
+        &self.payload
+    }
+
+    /// The script pubkey paying to this address.
+    pub fn script_pubkey(&self) -> ScriptBuf {
+        let mut script = Vec::new();
+
+        match &self.payload {
+            Payload::PubkeyHash(hash) => {
+                script.extend_from_slice(&[0x76, 0xa9, 0x14]);
+                script.extend_from_slice(hash);
+                script.extend_from_slice(&[0x88, 0xac]);
+            }
+            Payload::ScriptHash(hash) => {
+                script.extend_from_slice(&[0xa9, 0x14]);
+                script.extend_from_slice(hash);
+                script.push(0x87);
+            }
+            Payload::WitnessProgram { version, program } => {
+                script.push(if *version == 0 { 0x00 } else { 0x50 + version });
+                script.push(program.len() as u8);
+                script.extend_from_slice(program);
+            }
+        }
+
+        ScriptBuf(script)
+    }
+}
+
+impl fmt::Display for Address {
+    /// Formats the address in its standard encoding, CashAddr for Bitcoin Cash networks.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(prefix) = self.network.cashaddr_prefix() {
+            match &self.payload {
+                Payload::PubkeyHash(hash) => {
+                    return write!(f, "{}", encode_cashaddr(prefix, CASHADDR_P2PKH, hash))
+                }
+                Payload::ScriptHash(hash) => {
+                    return write!(f, "{}", encode_cashaddr(prefix, CASHADDR_P2SH, hash))
+                }
+                Payload::WitnessProgram { .. } => return Err(fmt::Error),
+            }
+        }
+
+        match &self.payload {
+            Payload::PubkeyHash(hash) => {
+                write!(f, "{}", base58check(self.network.p2pkh_prefix(), hash))
+            }
+            Payload::ScriptHash(hash) => {
+                write!(f, "{}", base58check(self.network.p2sh_prefix(), hash))
+            }
+            Payload::WitnessProgram { version, program } => {
+                let hrp = self.network.bech32_hrp().ok_or(fmt::Error)?;
+                let hrp = Hrp::parse(hrp).map_err(|_| fmt::Error)?;
+                let version = Fe32::try_from(*version).map_err(|_| fmt::Error)?;
+                let address =
+                    bech32::segwit::encode(hrp, version, program).map_err(|_| fmt::Error)?;
+                write!(f, "{address}")
+            }
+        }
+    }
+}
+
+fn base58check(prefix: u8, hash: &[u8; 20]) -> String {
+    let mut data = Vec::with_capacity(21);
+    data.push(prefix);
+    data.extend_from_slice(hash);
+    bs58::encode(data).with_check().into_string()
+}
+
+/// CashAddr version byte of a P2PKH address with a 160-bit hash.
+const CASHADDR_P2PKH: u8 = 0x00;
+
+/// CashAddr version byte of a P2SH address with a 160-bit hash.
+const CASHADDR_P2SH: u8 = 0x08;
+
+const CASHADDR_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Encodes a CashAddr address, see
+/// <https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/cashaddr.md>
+fn encode_cashaddr(prefix: &str, version: u8, hash: &[u8]) -> String {
+    let mut data = Vec::with_capacity(hash.len() + 1);
+    data.push(version);
+    data.extend_from_slice(hash);
+    let payload = convert_bits(&data, 8, 5, true).expect("padding is allowed");
+
+    let mut checksum_input = cashaddr_checksum_input(prefix, &payload);
+    checksum_input.extend_from_slice(&[0; 8]);
+    let checksum = cashaddr_polymod(&checksum_input);
+
+    let mut address = String::with_capacity(prefix.len() + 1 + payload.len() + 8);
+    address.push_str(prefix);
+    address.push(':');
+    for value in payload {
+        address.push(CASHADDR_CHARSET[value as usize] as char);
+    }
+    for i in 0..8 {
+        let value = (checksum >> (5 * (7 - i))) & 0x1f;
+        address.push(CASHADDR_CHARSET[value as usize] as char);
+    }
+
+    address
+}
+
+/// Decodes a CashAddr address with an optional prefix, returning the version byte and the hash.
+fn decode_cashaddr(address: &str, prefix: &str) -> Result<(u8, Vec<u8>), String> {
+    if address.chars().any(|c| c.is_ascii_lowercase())
+        && address.chars().any(|c| c.is_ascii_uppercase())
+    {
+        return Err("Mixed case CashAddr".to_string());
+    }
+    let address = address.to_ascii_lowercase();
+    let encoded = match address.split_once(':') {
+        Some((address_prefix, encoded)) if address_prefix == prefix => encoded,
+        Some(_) => return Err("Invalid CashAddr prefix".to_string()),
+        None => address.as_str(),
+    };
+
+    let values = encoded
+        .bytes()
+        .map(|c| {
+            CASHADDR_CHARSET
+                .iter()
+                .position(|x| *x == c)
+                .map(|position| position as u8)
+                .ok_or_else(|| "Invalid CashAddr character".to_string())
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+    if values.len() < 8 || cashaddr_polymod(&cashaddr_checksum_input(prefix, &values)) != 0 {
+        return Err("Invalid CashAddr checksum".to_string());
+    }
+
+    let data = convert_bits(&values[..values.len() - 8], 5, 8, false)?;
+    let (version, hash) = data
+        .split_first()
+        .ok_or_else(|| "Empty CashAddr payload".to_string())?;
+
+    Ok((*version, hash.to_vec()))
+}
+
+/// The input of the CashAddr checksum: the lower 5 bits of the prefix, a zero separator
+/// and the data.
+fn cashaddr_checksum_input(prefix: &str, data: &[u8]) -> Vec<u8> {
+    let mut input: Vec<u8> = prefix.bytes().map(|c| c & 0x1f).collect();
+    input.push(0);
+    input.extend_from_slice(data);
+    input
+}
+
+fn cashaddr_polymod(values: &[u8]) -> u64 {
+    let mut c: u64 = 1;
+    for value in values {
+        let c0 = (c >> 35) as u8;
+        c = ((c & 0x07_ffff_ffff) << 5) ^ *value as u64;
+        if c0 & 0x01 != 0 {
+            c ^= 0x98_f2bc_8e61;
+        }
+        if c0 & 0x02 != 0 {
+            c ^= 0x79_b76d_99e2;
+        }
+        if c0 & 0x04 != 0 {
+            c ^= 0xf3_3e5f_b3c4;
+        }
+        if c0 & 0x08 != 0 {
+            c ^= 0xae_2eab_e2a8;
+        }
+        if c0 & 0x10 != 0 {
+            c ^= 0x1e_4f43_e470;
+        }
+    }
+    c ^ 1
+}
+
+/// Regroups bits, e.g. from 8-bit bytes to 5-bit base32 values.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, String> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value = (1 << to) - 1;
+    let mut result = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+
+    for value in data {
+        acc = (acc << from) | *value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            result.push(((acc << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max_value) != 0 {
+        return Err("Invalid padding".to_string());
+    }
+
+    Ok(result)
+}
+
+fn validate_witness_program(version: u8, program: &[u8]) -> Result<(), String> {
+    if version > 16 {
+        return Err(format!("Invalid witness version: {version}"));
+    }
+    if !(2..=40).contains(&program.len()) {
+        return Err(format!("Invalid witness program length: {}", program.len()));
+    }
+    if version == 0 && program.len() != 20 && program.len() != 32 {
+        return Err(format!(
+            "Invalid SegWit v0 program length: {}",
+            program.len()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::{Address as RustBitcoinAddress, Network as RustBitcoinNetwork};
+    use std::str::FromStr;
+
+    const ADDRESSES: [(&str, Network, RustBitcoinNetwork); 6] = [
+        (
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+            Network::Bitcoin,
+            RustBitcoinNetwork::Bitcoin,
+        ),
+        (
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+            Network::Bitcoin,
+            RustBitcoinNetwork::Bitcoin,
+        ),
+        (
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            Network::Bitcoin,
+            RustBitcoinNetwork::Bitcoin,
+        ),
+        (
+            "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn",
+            Network::Testnet,
+            RustBitcoinNetwork::Testnet,
+        ),
+        (
+            "tb1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqp3mvzv",
+            Network::Signet,
+            RustBitcoinNetwork::Signet,
+        ),
+        (
+            "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080",
+            Network::Regtest,
+            RustBitcoinNetwork::Regtest,
+        ),
+    ];
+
+    #[test]
+    fn test_address_round_trip_against_rust_bitcoin() {
+        for (address_str, network, rust_bitcoin_network) in ADDRESSES {
+            let address = Address::parse(address_str, network).unwrap();
+            let expected = RustBitcoinAddress::from_str(address_str)
+                .unwrap()
+                .require_network(rust_bitcoin_network)
+                .unwrap();
+
+            assert_eq!(address.to_string(), address_str);
+            assert_eq!(
+                address.script_pubkey().0,
+                expected.script_pubkey().to_bytes()
+            );
+            assert_eq!(
+                Address::from_script(&address.script_pubkey(), network).unwrap(),
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn test_address_wrong_network() {
+        assert!(Address::parse("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2", Network::Testnet).is_err());
+        assert!(Address::parse(
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            Network::Regtest
+        )
+        .is_err());
+        assert!(Address::parse("mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn", Network::Signet).is_ok());
+    }
+
+    #[test]
+    fn test_bitcoin_family_addresses() {
+        let hash: [u8; 20] = hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let cases = [
+            (
+                Address::p2pkh(hash, Network::Litecoin),
+                "LVuDpNCSSj6pQ7t9Pv6d6sUkLKoqDEVUnJ",
+            ),
+            (
+                Address::p2sh(hash, Network::Litecoin),
+                "MJaRnao1s62a2zAKSkmG582KbLKianqb7v",
+            ),
+            (
+                Address::p2pkh(hash, Network::Dogecoin),
+                "DFpN6QqFfUm3gKNaxN6tNcab1FArL9cZLE",
+            ),
+            (
+                Address::p2sh(hash, Network::Dogecoin),
+                "A37YDYSwz3438rFtm1SLVcQHyD7JeueC9H",
+            ),
+            (
+                Address::p2pkh(hash, Network::DogecoinTestnet),
+                "nesRpRaAbTDmZHwmzBkLd2AtF7Z9L9z5S2",
+            ),
+        ];
+
+        for (address, expected) in cases {
+            assert_eq!(address.to_string(), expected);
+            assert_eq!(
+                Address::parse(expected, address.network()).unwrap(),
+                address
+            );
+        }
+
+        let segwit = Address::from_witness_program(0, hash.to_vec(), Network::Litecoin).unwrap();
+        assert!(segwit.to_string().starts_with("ltc1q"));
+        assert_eq!(
+            Address::parse(&segwit.to_string(), Network::Litecoin).unwrap(),
+            segwit
+        );
+        assert!(Address::from_witness_program(0, hash.to_vec(), Network::Dogecoin).is_err());
+    }
+
+    #[test]
+    fn test_cashaddr() {
+        let hash: [u8; 20] = hex::decode("76a04053bda0a88bda5177b86a15c3b29f559873")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        // Test vectors from the CashAddr specification
+        let p2pkh = Address::p2pkh(hash, Network::BitcoinCash);
+        assert_eq!(
+            p2pkh.to_string(),
+            "bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a"
+        );
+        let p2sh = Address::p2sh(hash, Network::BitcoinCash);
+        assert_eq!(
+            p2sh.to_string(),
+            "bitcoincash:ppm2qsznhks23z7629mms6s4cwef74vcwvn0h829pq"
+        );
+
+        for address in [
+            "bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a",
+            "qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a",
+            "BITCOINCASH:QPM2QSZNHKS23Z7629MMS6S4CWEF74VCWVY22GDX6A",
+            "1BpEi6DfDAUFd7GtittLSdBeYJvcoaVggu",
+        ] {
+            assert_eq!(
+                Address::parse(address, Network::BitcoinCash).unwrap(),
+                p2pkh
+            );
+        }
+        assert_eq!(
+            Address::parse(
+                "bitcoincash:ppm2qsznhks23z7629mms6s4cwef74vcwvn0h829pq",
+                Network::BitcoinCash
+            )
+            .unwrap(),
+            p2sh
+        );
+
+        assert!(Address::parse(
+            "bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6b",
+            Network::BitcoinCash
+        )
+        .is_err());
+        assert!(Address::parse(
+            "bchtest:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a",
+            Network::BitcoinCash
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_from_script_without_address() {
+        let op_return = ScriptBuf::from_hex("6a0401020304").unwrap();
+        assert!(Address::from_script(&op_return, Network::Bitcoin).is_err());
+    }
+}
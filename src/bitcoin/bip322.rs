@@ -0,0 +1,220 @@
+//! [BIP-322] generic message signing.
+//!
+//! A message is signed by spending a virtual `to_spend` transaction, which commits to the
+//! message and to the address (`message_challenge`), with a virtual `to_sign` transaction.
+//! Neither transaction is ever broadcast, so the ownership of an address can be proven
+//! without moving any funds.
+//!
+//! [BIP-322]: https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki
+use sha2::{Digest, Sha256};
+
+use super::{
+    taproot::tagged_hash,
+    types::{
+        Amount, EcdsaSighashType, LockTime, OutPoint, ScriptBuf, Sequence, TxIn, TxOut, Utxo,
+        Version, Witness,
+    },
+    BitcoinTransaction,
+};
+use crate::bitcoin::encoding::Encodable;
+
+/// The tag of the tagged hash committing to the message.
+pub const BIP322_TAG: &[u8] = b"BIP0322-signed-message";
+
+/// `OP_0`
+const OP_0: u8 = 0x00;
+/// `OP_PUSHBYTES_32`
+const OP_PUSHBYTES_32: u8 = 0x20;
+/// `OP_RETURN`
+const OP_RETURN: u8 = 0x6a;
+
+/// Computes the tagged hash `SHA256(SHA256(tag) || SHA256(tag) || message)` of the message.
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    tagged_hash(BIP322_TAG, message)
+}
+
+/// Builds the `to_spend` transaction, whose single output is locked by the `message_challenge`
+/// (the script pubkey of the address proving ownership).
+pub fn to_spend(message_challenge: &ScriptBuf, message: &[u8]) -> BitcoinTransaction {
+    let mut script_sig = vec![OP_0, OP_PUSHBYTES_32];
+    script_sig.extend_from_slice(&message_hash(message));
+
+    BitcoinTransaction {
+        version: Version::Zero,
+        lock_time: LockTime::from_height(0).unwrap(),
+        input: vec![TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: ScriptBuf(script_sig),
+            sequence: Sequence::ZERO,
+            witness: Witness::default(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: message_challenge.clone(),
+        }],
+    }
+}
+
+/// Builds the unsigned `to_sign` transaction, spending the output of the `to_spend` transaction.
+pub fn to_sign(to_spend: &BitcoinTransaction) -> BitcoinTransaction {
+    BitcoinTransaction {
+        version: Version::Zero,
+        lock_time: LockTime::from_height(0).unwrap(),
+        input: vec![TxIn {
+            previous_output: OutPoint::new(to_spend.txid(), 0),
+            script_sig: ScriptBuf::default(),
+            sequence: Sequence::ZERO,
+            witness: Witness::default(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: ScriptBuf(vec![OP_RETURN]),
+        }],
+    }
+}
+
+/// Encodes the `to_sign` transaction for signing, the result must be double SHA-256 hashed
+/// to obtain the digest signed with the key of the address.
+///
+/// Only P2WPKH message challenges are supported.
+pub fn build_for_signing(message_challenge: &ScriptBuf, message: &[u8]) -> Result<Vec<u8>, String> {
+    let to_spend = to_spend(message_challenge, message);
+    // The output of the `to_spend` transaction is the one spent by the `to_sign` transaction
+    let utxo = Utxo::new(to_spend.txid(), 0, Amount::ZERO, message_challenge.clone());
+    if !utxo.is_p2wpkh() {
+        return Err("Only P2WPKH message challenges are supported".to_string());
+    }
+
+    let to_sign = to_sign(&to_spend);
+
+    let mut buffer = Vec::new();
+    to_sign.encode_for_sighash_for_segwit(
+        &mut buffer,
+        &to_sign.segwit_sighash_midstate(),
+        0,
+        &utxo.script_code(),
+        utxo.value.to_sat(),
+    );
+    buffer.extend_from_slice(&(EcdsaSighashType::All as u32).to_le_bytes());
+
+    Ok(buffer)
+}
+
+/// Computes the digest to sign with the key of the address.
+pub fn signing_digest(message_challenge: &ScriptBuf, message: &[u8]) -> Result<[u8; 32], String> {
+    let preimage = build_for_signing(message_challenge, message)?;
+
+    Ok(Sha256::digest(Sha256::digest(preimage)).into())
+}
+
+/// Encodes a "simple" signature, the consensus encoding of the witness of the `to_sign` input.
+///
+/// The result is usually displayed in base64.
+pub fn encode_simple_signature(witness: &Witness) -> Vec<u8> {
+    let mut buffer = Vec::new();
+
+    let _ = witness.encode(&mut buffer);
+
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Script pubkey of bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l, the address of the BIP-322 test vectors
+    const CHALLENGE: &str = "00142b05d564e6a7a33c087f16e0f730d1440123799d";
+
+    #[test]
+    fn test_message_hash() {
+        assert_eq!(
+            hex::encode(message_hash(b"")),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            hex::encode(message_hash(b"Hello World")),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+    }
+
+    #[test]
+    fn test_virtual_transactions() {
+        let challenge = ScriptBuf::from_hex(CHALLENGE).unwrap();
+
+        let to_spend_tx = to_spend(&challenge, b"");
+        assert_eq!(
+            to_spend_tx.txid().to_string(),
+            "c5680aa69bb8d860bf82d4e9cd3504b55dde018de765a91bb566283c545a99a7"
+        );
+        assert_eq!(
+            to_sign(&to_spend_tx).txid().to_string(),
+            "1e9654e951a5ba44c8604c4de6c67fd78a27e81dcadcfe1edf638ba3aaebaed6"
+        );
+
+        let to_spend_tx = to_spend(&challenge, b"Hello World");
+        assert_eq!(
+            to_spend_tx.txid().to_string(),
+            "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b"
+        );
+        assert_eq!(
+            to_sign(&to_spend_tx).txid().to_string(),
+            "88737ae86f2077145f93cc4b153ae9a1cb8d56afa511988c149c5c8c9d93bddf"
+        );
+    }
+
+    #[test]
+    fn test_signing_digest_against_rust_bitcoin() {
+        use bitcoin::hashes::Hash as _;
+
+        let challenge = ScriptBuf::from_hex(CHALLENGE).unwrap();
+        let digest = signing_digest(&challenge, b"Hello World").unwrap();
+
+        let rust_bitcoin_challenge = bitcoin::ScriptBuf::from_hex(CHALLENGE).unwrap();
+        let to_spend_txid = to_spend(&challenge, b"Hello World").txid();
+        let mut rust_bitcoin_tx = bitcoin::Transaction {
+            version: bitcoin::transaction::Version(0),
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: vec![bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint {
+                    txid: to_spend_txid.to_string().parse().unwrap(),
+                    vout: 0,
+                },
+                script_sig: bitcoin::ScriptBuf::default(),
+                sequence: bitcoin::Sequence::ZERO,
+                witness: bitcoin::Witness::default(),
+            }],
+            output: vec![bitcoin::TxOut {
+                value: bitcoin::Amount::ZERO,
+                script_pubkey: bitcoin::ScriptBuf::from_bytes(vec![OP_RETURN]),
+            }],
+        };
+        let expected = bitcoin::sighash::SighashCache::new(&mut rust_bitcoin_tx)
+            .p2wpkh_signature_hash(
+                0,
+                &rust_bitcoin_challenge,
+                bitcoin::Amount::ZERO,
+                bitcoin::EcdsaSighashType::All,
+            )
+            .unwrap();
+
+        assert_eq!(digest, expected.to_byte_array());
+    }
+
+    #[test]
+    fn test_unsupported_message_challenge() {
+        let p2pkh =
+            ScriptBuf::from_hex("76a9142b05d564e6a7a33c087f16e0f730d1440123799d88ac").unwrap();
+
+        assert!(signing_digest(&p2pkh, b"Hello World").is_err());
+    }
+
+    #[test]
+    fn test_encode_simple_signature() {
+        let witness = Witness::from_slice(&[vec![0x01, 0x02], vec![0x03]]);
+
+        assert_eq!(
+            encode_simple_signature(&witness),
+            vec![0x02, 0x02, 0x01, 0x02, 0x01, 0x03]
+        );
+    }
+}
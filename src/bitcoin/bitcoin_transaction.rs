@@ -1,14 +1,23 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+use core::fmt;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::io::{BufRead, Write};
 
 use super::{
+    address::Address,
     constants::{SEGWIT_FLAG, SEGWIT_MARKER},
     encoding::{decode::MAX_VEC_SIZE, utils::VarInt, Decodable, Encodable, ToU64},
     types::{
-        EcdsaSighashType, LockTime, ScriptBuf, TransactionType, TxIn, TxOut, Version, Witness,
+        Amount, EcdsaSighashType, Hash, LockTime, Network, OutPoint, ScriptBuf, Sequence,
+        TransactionType, TxIn, TxOut, Txid, Version, Witness,
+    },
+    validation::{
+        is_data_carrier, is_push_only, is_standard_script_pubkey, legacy_sigop_count,
+        ValidationError, MAX_SCRIPT_SIZE, MAX_STANDARD_SCRIPTSIG_SIZE, MAX_STANDARD_TX_SIGOPS_COST,
+        MAX_STANDARD_TX_WEIGHT, TRUC_MAX_VSIZE,
     },
 };
 
@@ -23,7 +32,7 @@ use super::{
 ///     Amount, Hash, LockTime, OutPoint, ScriptBuf, Sequence, TxIn, TxOut, Txid, Version, Witness
 /// };
 /// use signet_rs::bitcoin::BitcoinTransaction;
-/// 
+///
 /// // The first case would be as follows:
 /// let omni_tx = BitcoinTransaction {
 ///     version: Version::One,
@@ -78,6 +87,7 @@ use super::{
     Deserialize,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     JsonSchema,
 )]
 pub struct BitcoinTransaction {
@@ -96,11 +106,25 @@ pub struct BitcoinTransaction {
     pub output: Vec<TxOut>,
 }
 
+/// SIGHASH_DEFAULT, signing the whole transaction like SIGHASH_ALL (BIP-341).
+const TAPROOT_SIGHASH_DEFAULT: u8 = 0x00;
+
 // Function to compute sha256d (double SHA-256)
 fn sha256d(data: &[u8]) -> Vec<u8> {
-    let hash1 = Sha256::digest(data);
-    let hash2 = Sha256::digest(hash1);
-    hash2.to_vec()
+    sha256d_array(data).to_vec()
+}
+
+fn sha256d_array(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+/// The hashes of the prevouts, sequences and outputs of a transaction, shared by the BIP-143
+/// sighashes of all of its inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegwitSighashMidstate {
+    pub hash_prevouts: [u8; 32],
+    pub hash_sequence: [u8; 32],
+    pub hash_outputs: [u8; 32],
 }
 
 impl BitcoinTransaction {
@@ -113,6 +137,152 @@ impl BitcoinTransaction {
         buffer
     }
 
+    /// Encode the transaction into a hex string, as expected by `sendrawtransaction`
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.serialize())
+    }
+
+    /// Compute the transaction id, the double SHA-256 of the transaction without witness data
+    pub fn txid(&self) -> Txid {
+        let mut hash: [u8; 32] = sha256d(&self.serialize_without_witness())
+            .try_into()
+            .expect("SHA-256 digests are 32 bytes");
+        // Txids are displayed in reverse byte order
+        hash.reverse();
+
+        Txid(Hash(hash))
+    }
+
+    /// Compute the weight of the transaction as defined by BIP-141: three times its size
+    /// without witness data, plus its full size
+    pub fn weight(&self) -> u64 {
+        (self.serialize_without_witness().len() * 3 + self.serialize().len()) as u64
+    }
+
+    /// Compute the virtual size of the transaction, its weight divided by four and rounded up
+    pub fn vsize(&self) -> u64 {
+        self.weight().div_ceil(4)
+    }
+
+    /// Encode the transaction without its witness data, as hashed by the txid
+    fn serialize_without_witness(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+
+        let _ = self.version.encode(&mut buffer);
+        let _ = self.input.encode(&mut buffer);
+        let _ = self.output.encode(&mut buffer);
+        let _ = self.lock_time.encode(&mut buffer);
+
+        buffer
+    }
+
+    /// Sanity checks the transaction before producing its signing payload: it must have inputs
+    /// and outputs, spend each outpoint once, keep its scripts within [`MAX_SCRIPT_SIZE`] and its
+    /// output values within the money supply.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.input.is_empty() {
+            return Err(ValidationError::EmptyInputs);
+        }
+        if self.output.is_empty() {
+            return Err(ValidationError::EmptyOutputs);
+        }
+
+        for (index, input) in self.input.iter().enumerate() {
+            if self.input[..index]
+                .iter()
+                .any(|other| other.previous_output == input.previous_output)
+            {
+                return Err(ValidationError::DuplicateInput(input.previous_output));
+            }
+            if input.script_sig.0.len() > MAX_SCRIPT_SIZE {
+                return Err(ValidationError::ScriptSigTooLarge {
+                    index,
+                    size: input.script_sig.0.len(),
+                });
+            }
+        }
+
+        let mut total = Amount::ZERO;
+        for (index, output) in self.output.iter().enumerate() {
+            if output.script_pubkey.0.len() > MAX_SCRIPT_SIZE {
+                return Err(ValidationError::ScriptPubkeyTooLarge {
+                    index,
+                    size: output.script_pubkey.0.len(),
+                });
+            }
+            total = total
+                .checked_add(output.value)
+                .filter(|total| *total <= Amount::MAX_MONEY)
+                .ok_or(ValidationError::OutputValueOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that nodes following the relay policy of Bitcoin Core would relay the transaction,
+    /// so no signature is wasted on it: a standard version, a weight of at most
+    /// [`MAX_STANDARD_TX_WEIGHT`], or a virtual size of at most [`TRUC_MAX_VSIZE`] for version 3
+    /// transactions, script sigs only pushing data, standard script pubkeys with at most one
+    /// `OP_RETURN` output, and a sigop cost of at most [`MAX_STANDARD_TX_SIGOPS_COST`].
+    ///
+    /// Only the legacy sigops of the scripts of the transaction are counted, the ones of the
+    /// redeem and witness scripts of the spent outputs are not known.
+    pub fn check_standard(&self) -> Result<(), ValidationError> {
+        if self.version == Version::Zero {
+            return Err(ValidationError::NonStandardVersion);
+        }
+
+        let weight = self.weight();
+        if weight > MAX_STANDARD_TX_WEIGHT {
+            return Err(ValidationError::TransactionTooLarge { weight });
+        }
+        if self.version == Version::Three && self.vsize() > TRUC_MAX_VSIZE {
+            return Err(ValidationError::TrucTooLarge {
+                vsize: self.vsize(),
+                max_vsize: TRUC_MAX_VSIZE,
+            });
+        }
+
+        for (index, input) in self.input.iter().enumerate() {
+            if input.script_sig.0.len() > MAX_STANDARD_SCRIPTSIG_SIZE
+                || !is_push_only(&input.script_sig.0)
+            {
+                return Err(ValidationError::NonStandardScriptSig { index });
+            }
+        }
+
+        let mut data_outputs = 0;
+        for (index, output) in self.output.iter().enumerate() {
+            if !is_standard_script_pubkey(&output.script_pubkey.0) {
+                return Err(ValidationError::NonStandardScriptPubkey { index });
+            }
+            if is_data_carrier(&output.script_pubkey.0) {
+                data_outputs += 1;
+            }
+        }
+        if data_outputs > 1 {
+            return Err(ValidationError::MultipleDataOutputs);
+        }
+
+        let sigops: usize = self
+            .input
+            .iter()
+            .map(|input| legacy_sigop_count(&input.script_sig.0))
+            .chain(
+                self.output
+                    .iter()
+                    .map(|output| legacy_sigop_count(&output.script_pubkey.0)),
+            )
+            .sum();
+        // Legacy sigops cost as much as the witness discount of BIP-141
+        let cost = sigops * 4;
+        if cost > MAX_STANDARD_TX_SIGOPS_COST {
+            return Err(ValidationError::TooManySigops { cost });
+        }
+
+        Ok(())
+    }
+
     /// Encode a legacy transaction into a vector of bytes
     pub fn build_for_signing_legacy(&self, sighash_type: EcdsaSighashType) -> Vec<u8> {
         let mut buffer = Vec::new();
@@ -155,13 +325,28 @@ impl BitcoinTransaction {
         script_code: &ScriptBuf,
         value: u64,
     ) -> Vec<u8> {
-        if self.version != Version::Two {
-            panic!("SegWit transactions must be version 2");
-        }
+        self.build_for_signing_segwit_with_midstate(
+            &self.segwit_sighash_midstate(),
+            sighash_type,
+            input_index,
+            script_code,
+            value,
+        )
+    }
 
+    /// Encode the transaction for signing in SegWit format, reusing the hashes computed by
+    /// [`BitcoinTransaction::segwit_sighash_midstate`]
+    pub fn build_for_signing_segwit_with_midstate(
+        &self,
+        midstate: &SegwitSighashMidstate,
+        sighash_type: EcdsaSighashType,
+        input_index: usize,
+        script_code: &ScriptBuf,
+        value: u64,
+    ) -> Vec<u8> {
         let mut buffer = Vec::new();
 
-        self.encode_for_sighash_for_segwit(&mut buffer, input_index, script_code, value);
+        self.encode_for_sighash_for_segwit(&mut buffer, midstate, input_index, script_code, value);
 
         // Sighash type
         buffer.extend_from_slice(&(sighash_type as u32).to_le_bytes());
@@ -169,6 +354,112 @@ impl BitcoinTransaction {
         buffer
     }
 
+    /// Encode the transaction for signing in the Bitcoin Cash replay protected format
+    ///
+    /// Bitcoin Cash signs every input with the BIP-143 digest algorithm and the
+    /// SIGHASH_FORKID flag set, regardless of the transaction version.
+    pub fn build_for_signing_forkid(
+        &self,
+        sighash_type: EcdsaSighashType,
+        input_index: usize,
+        script_code: &ScriptBuf,
+        value: u64,
+    ) -> Vec<u8> {
+        assert!(
+            sighash_type.has_fork_id(),
+            "Bitcoin Cash sighash types must have SIGHASH_FORKID set"
+        );
+
+        let mut buffer = Vec::new();
+
+        self.encode_for_sighash_for_segwit(
+            &mut buffer,
+            &self.segwit_sighash_midstate(),
+            input_index,
+            script_code,
+            value,
+        );
+
+        // Sighash type, the fork id of Bitcoin Cash is 0
+        buffer.extend_from_slice(&(sighash_type as u32).to_le_bytes());
+
+        buffer
+    }
+
+    /// Encode the transaction for signing the given taproot input, with the BIP-341 digest
+    /// algorithm and the SIGHASH_DEFAULT sighash type
+    ///
+    /// `prevouts` are the outputs spent by every input, in input order. Script path spends
+    /// also commit to the hash of the spent leaf, key path spends pass `None`. The message to
+    /// sign is the `TapSighash` tagged hash of the returned bytes, see [`super::taproot::sighash`].
+    pub fn build_for_signing_taproot(
+        &self,
+        input_index: usize,
+        prevouts: &[TxOut],
+        leaf_hash: Option<&[u8; 32]>,
+    ) -> Vec<u8> {
+        assert_eq!(
+            prevouts.len(),
+            self.input.len(),
+            "Taproot sighashes commit to the outputs spent by every input"
+        );
+
+        let mut buffer = Vec::new();
+
+        // Sighash epoch and sighash type
+        buffer.push(0x00);
+        buffer.push(TAPROOT_SIGHASH_DEFAULT);
+
+        // Version and locktime
+        self.version.encode(&mut buffer).unwrap();
+        self.lock_time.encode(&mut buffer).unwrap();
+
+        // Hash prevouts, amounts, script pubkeys and sequences
+        let mut prevout_hasher = Sha256::new();
+        let mut amount_hasher = Sha256::new();
+        let mut script_pubkey_hasher = Sha256::new();
+        let mut sequence_hasher = Sha256::new();
+        for (input, prevout) in self.input.iter().zip(prevouts) {
+            let mut bytes = Vec::new();
+            input.previous_output.encode(&mut bytes).unwrap();
+            prevout_hasher.update(&bytes);
+
+            amount_hasher.update(prevout.value.to_sat().to_le_bytes());
+
+            bytes.clear();
+            prevout.script_pubkey.encode(&mut bytes).unwrap();
+            script_pubkey_hasher.update(&bytes);
+
+            sequence_hasher.update(input.sequence.0.to_le_bytes());
+        }
+        buffer.extend_from_slice(&prevout_hasher.finalize());
+        buffer.extend_from_slice(&amount_hasher.finalize());
+        buffer.extend_from_slice(&script_pubkey_hasher.finalize());
+        buffer.extend_from_slice(&sequence_hasher.finalize());
+
+        // Hash outputs
+        let mut outputs = Vec::new();
+        for output in &self.output {
+            output.encode(&mut outputs).unwrap();
+        }
+        buffer.extend_from_slice(&Sha256::digest(&outputs));
+
+        // Spend type, no annex is supported
+        buffer.push(if leaf_hash.is_some() { 0x02 } else { 0x00 });
+
+        // Input index
+        buffer.extend_from_slice(&(input_index as u32).to_le_bytes());
+
+        if let Some(leaf_hash) = leaf_hash {
+            buffer.extend_from_slice(leaf_hash);
+            // Key version and no executed OP_CODESEPARATOR
+            buffer.push(0x00);
+            buffer.extend_from_slice(&u32::MAX.to_le_bytes());
+        }
+
+        buffer
+    }
+
     /// Function to attach a witness to the transaction
     pub fn build_with_witness(
         &mut self,
@@ -194,9 +485,36 @@ impl BitcoinTransaction {
         buffer
     }
 
-    fn encode_for_sighash_for_segwit(
+    /// Compute the hashes of the prevouts, sequences and outputs of the transaction, shared by
+    /// the BIP-143 sighashes of all of its inputs
+    ///
+    /// Computing them once and signing every input with
+    /// [`BitcoinTransaction::build_for_signing_segwit_with_midstate`] avoids hashing the whole
+    /// transaction again for each input.
+    pub fn segwit_sighash_midstate(&self) -> SegwitSighashMidstate {
+        let mut prevouts = Vec::new();
+        let mut sequences = Vec::new();
+        for input in &self.input {
+            input.previous_output.encode(&mut prevouts).unwrap();
+            input.sequence.encode(&mut sequences).unwrap();
+        }
+
+        let mut outputs = Vec::new();
+        for output in &self.output {
+            output.encode(&mut outputs).unwrap();
+        }
+
+        SegwitSighashMidstate {
+            hash_prevouts: sha256d_array(&prevouts),
+            hash_sequence: sha256d_array(&sequences),
+            hash_outputs: sha256d_array(&outputs),
+        }
+    }
+
+    pub(super) fn encode_for_sighash_for_segwit(
         &self,
         buffer: &mut Vec<u8>,
+        midstate: &SegwitSighashMidstate,
         input_index: usize,
         script_code: &ScriptBuf,
         value: u64,
@@ -212,21 +530,9 @@ impl BitcoinTransaction {
             buffer.push(SEGWIT_FLAG);
         }
 
-        // Hash prevouts
-        let mut prevouts = Vec::new();
-        for input in &self.input {
-            input.previous_output.encode(&mut prevouts).unwrap();
-        }
-        let prevouts_hash = sha256d(&prevouts);
-        buffer.extend_from_slice(&prevouts_hash);
-
-        // Hash sequences
-        let mut sequences = Vec::new();
-        for input in &self.input {
-            input.sequence.encode(&mut sequences).unwrap();
-        }
-        let sequences_hash = sha256d(&sequences);
-        buffer.extend_from_slice(&sequences_hash);
+        // Hash prevouts and sequences
+        buffer.extend_from_slice(&midstate.hash_prevouts);
+        buffer.extend_from_slice(&midstate.hash_sequence);
 
         // Outpoint
         self.input[input_index]
@@ -244,12 +550,7 @@ impl BitcoinTransaction {
         self.input[input_index].sequence.encode(buffer).unwrap();
 
         // Hash outputs
-        let mut outputs = Vec::new();
-        for output in &self.output {
-            output.encode(&mut outputs).unwrap();
-        }
-        let outputs_hash = sha256d(&outputs);
-        buffer.extend_from_slice(&outputs_hash);
+        buffer.extend_from_slice(&midstate.hash_outputs);
 
         // Locktime
         self.lock_time.encode(buffer).unwrap();
@@ -265,9 +566,101 @@ impl BitcoinTransaction {
     }
 
     /// Serialise a JSON representation of the transaction into a BitcoinTransaction struct
+    ///
+    /// Besides their full form, inputs can be given by the `txid` and `vout` of the output they
+    /// spend, with an optional `sequence`, and outputs can pay to an `address` of the
+    /// transaction's `network` instead of a `script_pubkey`. The values of the inputs are not
+    /// part of the transaction, any `value` given for them is ignored.
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
-        let tx: Self = serde_json::from_str(json)?;
-        Ok(tx)
+        let tx: TransactionJson = serde_json::from_str(json)?;
+        tx.into_transaction().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The JSON representation accepted by [`BitcoinTransaction::from_json`].
+#[derive(Deserialize)]
+struct TransactionJson {
+    version: Version,
+    lock_time: LockTime,
+    network: Option<Network>,
+    input: Vec<TxInJson>,
+    output: Vec<TxOutJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TxInJson {
+    Full(TxIn),
+    Outpoint {
+        #[serde(flatten)]
+        previous_output: OutPoint,
+        #[serde(default)]
+        sequence: Sequence,
+        // Accepted so the inputs can be given as the UTXOs they spend
+        #[serde(default, rename = "value")]
+        _value: Option<Amount>,
+    },
+}
+
+#[derive(Deserialize)]
+struct TxOutJson {
+    value: Amount,
+    script_pubkey: Option<ScriptBuf>,
+    address: Option<String>,
+}
+
+impl TransactionJson {
+    fn into_transaction(self) -> Result<BitcoinTransaction, String> {
+        let input = self
+            .input
+            .into_iter()
+            .map(|input| match input {
+                TxInJson::Full(input) => input,
+                TxInJson::Outpoint {
+                    previous_output,
+                    sequence,
+                    ..
+                } => TxIn {
+                    previous_output,
+                    script_sig: ScriptBuf::default(),
+                    sequence,
+                    witness: Witness::default(),
+                },
+            })
+            .collect();
+
+        let output = self
+            .output
+            .into_iter()
+            .map(|output| {
+                let script_pubkey = match (output.script_pubkey, output.address) {
+                    (Some(script_pubkey), None) => script_pubkey,
+                    (None, Some(address)) => {
+                        let network = self
+                            .network
+                            .ok_or("A network is required to pay to an address")?;
+                        Address::parse(&address, network)?.script_pubkey()
+                    }
+                    _ => {
+                        return Err(
+                            "An output needs either a script pubkey or an address".to_string()
+                        )
+                    }
+                };
+
+                Ok(TxOut {
+                    value: output.value,
+                    script_pubkey,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(BitcoinTransaction {
+            version: self.version,
+            lock_time: self.lock_time,
+            input,
+            output,
+        })
     }
 }
 
@@ -356,6 +749,42 @@ impl Encodable for BitcoinTransaction {
         Ok(len)
     }
 }
+
+impl fmt::Display for BitcoinTransaction {
+    /// Summarizes the transaction for logging, one line per input and output. Use
+    /// [`BitcoinTransaction::to_hex`] for its consensus encoding.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Transaction {} (version {}, lock time {}, {} vB)",
+            self.txid(),
+            self.version,
+            self.lock_time.to_u32(),
+            self.vsize()
+        )?;
+        for (index, input) in self.input.iter().enumerate() {
+            writeln!(
+                f,
+                "  input {index}: {}:{}, sequence {:#010x}",
+                input.previous_output.txid, input.previous_output.vout, input.sequence.0
+            )?;
+        }
+        for (index, output) in self.output.iter().enumerate() {
+            writeln!(
+                f,
+                "  output {index}: {} sat to {}",
+                output.value.to_sat(),
+                hex::encode(&output.script_pubkey.0)
+            )?;
+        }
+
+        let total = self.output.iter().fold(0u64, |total, output| {
+            total.saturating_add(output.value.to_sat())
+        });
+        write!(f, "  total output: {total} sat")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // Omni imports
@@ -568,6 +997,338 @@ mod tests {
         assert_eq!(buffer, serialized);
     }
 
+    #[test]
+    fn test_build_for_signing_forkid() {
+        let script_code =
+            OmniScriptBuf::from_hex("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac").unwrap();
+        let mut omni_tx = OmniBitcoinTransaction {
+            version: Version::Two,
+            lock_time: LockTime::from_height(0).unwrap(),
+            input: vec![TxIn {
+                previous_output: OmniOutPoint {
+                    txid: OmniTxid(OmniHash::all_zeros()),
+                    vout: 0,
+                },
+                script_sig: OmniScriptBuf::default(),
+                sequence: OmniSequence::default(),
+                witness: OmniWitness::default(),
+            }],
+            output: vec![TxOut {
+                value: OmniAmount::from_sat(10000),
+                script_pubkey: script_code.clone(),
+            }],
+        };
+
+        let segwit = omni_tx.build_for_signing_segwit(OmniSighashType::All, 0, &script_code, 20000);
+        let forkid =
+            omni_tx.build_for_signing_forkid(OmniSighashType::AllForkId, 0, &script_code, 20000);
+
+        // Same BIP-143 preimage, only the sighash type differs
+        assert_eq!(segwit.len(), forkid.len());
+        assert_eq!(segwit[..segwit.len() - 4], forkid[..forkid.len() - 4]);
+        assert_eq!(forkid[forkid.len() - 4..], [0x41, 0, 0, 0]);
+
+        // Version 1 transactions are signed the same way
+        omni_tx.version = Version::One;
+        let forkid_v1 =
+            omni_tx.build_for_signing_forkid(OmniSighashType::AllForkId, 0, &script_code, 20000);
+        assert_eq!(forkid_v1[..4], [1, 0, 0, 0]);
+        assert_eq!(forkid_v1[4..], forkid[4..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "SIGHASH_FORKID")]
+    fn test_build_for_signing_forkid_without_fork_id() {
+        let omni_tx = OmniBitcoinTransaction {
+            version: Version::One,
+            lock_time: LockTime::from_height(0).unwrap(),
+            input: vec![TxIn {
+                previous_output: OmniOutPoint::null(),
+                script_sig: OmniScriptBuf::default(),
+                sequence: OmniSequence::default(),
+                witness: OmniWitness::default(),
+            }],
+            output: vec![],
+        };
+
+        omni_tx.build_for_signing_forkid(OmniSighashType::All, 0, &OmniScriptBuf::default(), 0);
+    }
+
+    #[test]
+    fn test_build_for_signing_taproot_key_spend() {
+        let prevouts = vec![
+            TxOut {
+                value: OmniAmount::from_sat(50_000),
+                script_pubkey: OmniScriptBuf::from_hex(
+                    "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+                )
+                .unwrap(),
+            },
+            TxOut {
+                value: OmniAmount::from_sat(20_000),
+                script_pubkey: OmniScriptBuf::from_hex(
+                    "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1",
+                )
+                .unwrap(),
+            },
+        ];
+        let omni_tx = OmniBitcoinTransaction {
+            version: Version::Two,
+            lock_time: LockTime::from_height(0).unwrap(),
+            input: (0..2)
+                .map(|vout| TxIn {
+                    previous_output: OmniOutPoint::new(OmniTxid(OmniHash::all_zeros()), vout),
+                    script_sig: OmniScriptBuf::default(),
+                    sequence: OmniSequence::ENABLE_RBF_NO_LOCKTIME,
+                    witness: OmniWitness::default(),
+                })
+                .collect(),
+            output: vec![TxOut {
+                value: OmniAmount::from_sat(69_000),
+                script_pubkey: prevouts[1].script_pubkey.clone(),
+            }],
+        };
+
+        let tx: RustBitcoinTransaction =
+            bitcoin::consensus::deserialize(&omni_tx.serialize()).unwrap();
+        let rust_bitcoin_prevouts: Vec<RustBitcoinTxOut> = prevouts
+            .iter()
+            .map(|prevout| RustBitcoinTxOut {
+                value: Amount::from_sat(prevout.value.to_sat()),
+                script_pubkey: ScriptBuf::from_bytes(prevout.script_pubkey.0.clone()),
+            })
+            .collect();
+        let sighash = SighashCache::new(&tx)
+            .taproot_key_spend_signature_hash(
+                0,
+                &bitcoin::sighash::Prevouts::All(&rust_bitcoin_prevouts),
+                bitcoin::TapSighashType::Default,
+            )
+            .unwrap();
+
+        assert_eq!(
+            crate::bitcoin::taproot::sighash(
+                &omni_tx.build_for_signing_taproot(0, &prevouts, None)
+            ),
+            sighash.to_byte_array()
+        );
+    }
+
+    #[test]
+    fn test_check_standard() {
+        let p2wpkh =
+            OmniScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let op_return = OmniScriptBuf::from_hex("6a03abcdef").unwrap();
+        let mut omni_tx = OmniBitcoinTransaction {
+            version: Version::Two,
+            lock_time: LockTime::from_height(0).unwrap(),
+            input: vec![TxIn {
+                previous_output: OmniOutPoint::new(OmniTxid(OmniHash::all_zeros()), 0),
+                script_sig: OmniScriptBuf::default(),
+                sequence: OmniSequence::default(),
+                witness: OmniWitness::default(),
+            }],
+            output: vec![
+                TxOut {
+                    value: OmniAmount::from_sat(10000),
+                    script_pubkey: p2wpkh.clone(),
+                },
+                TxOut {
+                    value: OmniAmount::ZERO,
+                    script_pubkey: op_return.clone(),
+                },
+            ],
+        };
+        assert_eq!(omni_tx.check_standard(), Ok(()));
+
+        omni_tx.version = Version::Zero;
+        assert_eq!(
+            omni_tx.check_standard(),
+            Err(ValidationError::NonStandardVersion)
+        );
+        omni_tx.version = Version::Two;
+
+        omni_tx.input[0].script_sig = OmniScriptBuf(vec![0x51, 0x75]);
+        assert_eq!(
+            omni_tx.check_standard(),
+            Err(ValidationError::NonStandardScriptSig { index: 0 })
+        );
+        omni_tx.input[0].script_sig = OmniScriptBuf::default();
+
+        omni_tx.output.push(TxOut {
+            value: OmniAmount::ZERO,
+            script_pubkey: op_return,
+        });
+        assert_eq!(
+            omni_tx.check_standard(),
+            Err(ValidationError::MultipleDataOutputs)
+        );
+        omni_tx.output[2].script_pubkey = OmniScriptBuf(vec![0x51]);
+        assert_eq!(
+            omni_tx.check_standard(),
+            Err(ValidationError::NonStandardScriptPubkey { index: 2 })
+        );
+        omni_tx.output.pop();
+
+        // 1-of-1 bare multisigs count as twenty sigops each
+        let multisig =
+            OmniScriptBuf([vec![0x51, 0x21, 0x02], vec![0x79; 32], vec![0x51, 0xae]].concat());
+        omni_tx.output = vec![
+            TxOut {
+                value: OmniAmount::from_sat(10000),
+                script_pubkey: multisig,
+            };
+            201
+        ];
+        assert_eq!(
+            omni_tx.check_standard(),
+            Err(ValidationError::TooManySigops { cost: 201 * 20 * 4 })
+        );
+
+        omni_tx.output = vec![
+            TxOut {
+                value: OmniAmount::from_sat(10000),
+                script_pubkey: p2wpkh,
+            };
+            3300
+        ];
+        assert_eq!(
+            omni_tx.check_standard(),
+            Err(ValidationError::TransactionTooLarge {
+                weight: omni_tx.weight()
+            })
+        );
+
+        omni_tx.version = Version::Three;
+        omni_tx.output.truncate(400);
+        assert_eq!(
+            omni_tx.check_standard(),
+            Err(ValidationError::TrucTooLarge {
+                vsize: omni_tx.vsize(),
+                max_vsize: TRUC_MAX_VSIZE
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_hex_and_display() {
+        let omni_tx = OmniBitcoinTransaction {
+            version: Version::Two,
+            lock_time: LockTime::from_height(840_000).unwrap(),
+            input: vec![TxIn {
+                previous_output: OmniOutPoint::new(OmniTxid(OmniHash([7; 32])), 3),
+                script_sig: OmniScriptBuf::default(),
+                sequence: OmniSequence(0xfffffffd),
+                witness: OmniWitness::from_slice(&[vec![0x30, 0x01], vec![0x02; 33]]),
+            }],
+            output: vec![
+                TxOut {
+                    value: OmniAmount::from_sat(10000),
+                    script_pubkey: OmniScriptBuf::from_hex(
+                        "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1",
+                    )
+                    .unwrap(),
+                },
+                TxOut {
+                    value: OmniAmount::from_sat(500),
+                    script_pubkey: OmniScriptBuf::from_hex("51024e73").unwrap(),
+                },
+            ],
+        };
+
+        let rust_bitcoin_tx: RustBitcoinTransaction =
+            bitcoin::consensus::deserialize(&omni_tx.serialize()).unwrap();
+        assert_eq!(
+            omni_tx.to_hex(),
+            bitcoin::consensus::encode::serialize_hex(&rust_bitcoin_tx)
+        );
+
+        assert_eq!(
+            omni_tx.to_string(),
+            format!(
+                "Transaction {} (version 2, lock time 840000, {} vB)\n  \
+                 input 0: {}:3, sequence 0xfffffffd\n  \
+                 output 0: 10000 sat to 00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1\n  \
+                 output 1: 500 sat to 51024e73\n  \
+                 total output: 10500 sat",
+                rust_bitcoin_tx.compute_txid(),
+                rust_bitcoin_tx.vsize(),
+                "07".repeat(32)
+            )
+        );
+    }
+
+    #[test]
+    fn test_borsh_roundtrip() {
+        let omni_tx = OmniBitcoinTransaction {
+            version: Version::Two,
+            lock_time: LockTime::from_height(840_000).unwrap(),
+            input: vec![TxIn {
+                previous_output: OmniOutPoint::new(OmniTxid(OmniHash([7; 32])), 3),
+                script_sig: OmniScriptBuf(vec![0x51]),
+                sequence: OmniSequence(0xfffffffd),
+                witness: OmniWitness::from_slice(&[vec![0x30, 0x01], vec![0x02; 33]]),
+            }],
+            output: vec![TxOut {
+                value: OmniAmount::from_sat(10000),
+                script_pubkey: OmniScriptBuf::from_hex(
+                    "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1",
+                )
+                .unwrap(),
+            }],
+        };
+
+        let serialized = borsh::to_vec(&omni_tx).unwrap();
+        assert_eq!(
+            OmniBitcoinTransaction::try_from_slice(&serialized).unwrap(),
+            omni_tx
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut omni_tx = OmniBitcoinTransaction {
+            version: Version::Two,
+            lock_time: LockTime::from_height(0).unwrap(),
+            input: vec![TxIn {
+                previous_output: OmniOutPoint::new(OmniTxid(OmniHash::all_zeros()), 0),
+                script_sig: OmniScriptBuf::default(),
+                sequence: OmniSequence::default(),
+                witness: OmniWitness::default(),
+            }],
+            output: vec![TxOut {
+                value: OmniAmount::from_sat(10000),
+                script_pubkey: OmniScriptBuf::default(),
+            }],
+        };
+        assert_eq!(omni_tx.validate(), Ok(()));
+
+        omni_tx.output[0].script_pubkey = OmniScriptBuf(vec![0; MAX_SCRIPT_SIZE + 1]);
+        assert_eq!(
+            omni_tx.validate(),
+            Err(ValidationError::ScriptPubkeyTooLarge {
+                index: 0,
+                size: MAX_SCRIPT_SIZE + 1
+            })
+        );
+
+        omni_tx.output[0].script_pubkey = OmniScriptBuf::default();
+        omni_tx.output.push(TxOut {
+            value: OmniAmount::MAX_MONEY,
+            script_pubkey: OmniScriptBuf::default(),
+        });
+        assert_eq!(
+            omni_tx.validate(),
+            Err(ValidationError::OutputValueOverflow)
+        );
+
+        omni_tx.output.clear();
+        assert_eq!(omni_tx.validate(), Err(ValidationError::EmptyOutputs));
+
+        omni_tx.input.clear();
+        assert_eq!(omni_tx.validate(), Err(ValidationError::EmptyInputs));
+    }
+
     #[test]
     fn test_from_json_bitcoin_transaction() {
         let json = r#"
@@ -668,6 +1429,76 @@ mod tests {
         assert_eq!(tx.output.len(), 2);
     }
 
+    #[test]
+    fn test_from_json_with_outpoints_and_addresses() {
+        let json = r#"
+        {
+            "version": 2,
+            "lock_time": 0,
+            "network": "bitcoin",
+            "input": [
+                {
+                    "txid": "bc25cc0dddd0a202c21e66521a692c0586330a9a9dcc38ccd9b4d2093037f31a",
+                    "vout": 1,
+                    "value": 50000
+                },
+                {
+                    "txid": "bc25cc0dddd0a202c21e66521a692c0586330a9a9dcc38ccd9b4d2093037f31a",
+                    "vout": 2,
+                    "sequence": 4294967293
+                }
+            ],
+            "output": [
+                {
+                    "value": 40000,
+                    "address": "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+                },
+                {
+                    "value": 9000,
+                    "script_pubkey": "76a9148356ecd5f1761e60c144dc2f4de6bf7d8be7690688ac"
+                }
+            ]
+        }
+        "#;
+
+        let tx = OmniBitcoinTransaction::from_json(json).unwrap();
+
+        let txid = OmniTxid(
+            OmniHash::from_hex("bc25cc0dddd0a202c21e66521a692c0586330a9a9dcc38ccd9b4d2093037f31a")
+                .unwrap(),
+        );
+        assert_eq!(tx.input.len(), 2);
+        assert_eq!(tx.input[0].previous_output, OmniOutPoint::new(txid, 1));
+        assert_eq!(tx.input[0].sequence, OmniSequence::MAX);
+        assert_eq!(tx.input[0].script_sig, OmniScriptBuf::default());
+        assert_eq!(tx.input[1].previous_output, OmniOutPoint::new(txid, 2));
+        assert_eq!(tx.input[1].sequence, OmniSequence(4294967293));
+        assert_eq!(
+            tx.output[0].script_pubkey,
+            OmniScriptBuf::from_hex("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()
+        );
+        assert_eq!(tx.output[1].value, OmniAmount::from_sat(9000));
+    }
+
+    #[test]
+    fn test_from_json_address_errors() {
+        let address = r#"{ "value": 1, "address": "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4" }"#;
+        let no_destination = r#"{ "value": 1 }"#;
+        let tx = |network: &str, output: &str| {
+            OmniBitcoinTransaction::from_json(&format!(
+                r#"{{ "version": 2, "lock_time": 0, {network} "input": [], "output": [{output}] }}"#
+            ))
+        };
+
+        assert!(tx(r#""network": "bitcoin","#, address).is_ok());
+        // No network to parse the address with
+        assert!(tx("", address).is_err());
+        // An address of another network
+        assert!(tx(r#""network": "testnet","#, address).is_err());
+        // Neither an address nor a script pubkey
+        assert!(tx(r#""network": "bitcoin","#, no_destination).is_err());
+    }
+
     #[test]
     fn test_from_json_bitcoin_transaction_3() {
         let json = r#"
@@ -1,7 +1,14 @@
 //! Transaction builder for Bitcoin transactions
 use super::{
+    address::Address,
     bitcoin_transaction::BitcoinTransaction,
-    types::{LockTime, TxIn, TxOut, Version},
+    encoding::utils::VarInt,
+    script::p2a,
+    types::{
+        Amount, EcdsaSighashType, LockTime, Network, OutPoint, ScriptBuf, TxIn, TxOut, Utxo,
+        Version,
+    },
+    validation::{ValidationError, TRUC_CHILD_MAX_VSIZE},
 };
 use crate::transaction_builder::TxBuilder;
 
@@ -10,6 +17,26 @@ pub struct BitcoinTransactionBuilder {
     pub lock_time: Option<LockTime>,
     pub inputs: Option<Vec<TxIn>>,
     pub outputs: Option<Vec<TxOut>>,
+    /// Previous outputs spent by the inputs added through [`BitcoinTransactionBuilder::add_utxo`].
+    pub utxos: Vec<Utxo>,
+    /// Network the transaction is built for, defaults to Bitcoin mainnet.
+    pub network: Network,
+    /// Dust relay fee rate in satoshis per kilo virtual byte used to compute the dust threshold
+    /// of the outputs, defaults to the policy of the network. Zero disables the dust check.
+    pub dust_relay_fee: Option<u64>,
+    /// Fee rate in satoshis per virtual byte used to compute the change output.
+    pub fee_rate: Option<u64>,
+    /// Script pubkey of the change output. When set, the builder appends a change output
+    /// returning the inputs left over after the outputs and the fee.
+    pub change_script_pubkey: Option<ScriptBuf>,
+    /// Whether the fee is paid by the outputs, in proportion to their size, rather than by
+    /// the inputs.
+    pub subtract_fee_from_outputs: bool,
+    /// Virtual size of the unconfirmed parent transaction whose fee this one raises, set by
+    /// [`BitcoinTransactionBuilder::spend_parent_output`].
+    pub parent_vsize: Option<u64>,
+    /// Fee paid by the unconfirmed parent transaction.
+    pub parent_fee: Option<Amount>,
 }
 
 impl Default for BitcoinTransactionBuilder {
@@ -20,11 +47,9 @@ impl Default for BitcoinTransactionBuilder {
 
 impl TxBuilder<BitcoinTransaction> for BitcoinTransactionBuilder {
     fn build(&self) -> BitcoinTransaction {
-        BitcoinTransaction {
-            version: self.version.expect("Missing version"),
-            lock_time: self.lock_time.expect("Missing lock time"),
-            input: self.inputs.clone().expect("Missing inputs"),
-            output: self.outputs.clone().expect("Missing outputs"),
+        match self.assemble() {
+            Ok(tx) => tx,
+            Err(error) => panic!("Invalid transaction: {error}"),
         }
     }
 }
@@ -36,9 +61,47 @@ impl BitcoinTransactionBuilder {
             lock_time: None,
             inputs: None,
             outputs: None,
+            utxos: Vec::new(),
+            network: Network::Bitcoin,
+            dust_relay_fee: None,
+            fee_rate: None,
+            change_script_pubkey: None,
+            subtract_fee_from_outputs: false,
+            parent_vsize: None,
+            parent_fee: None,
         }
     }
 
+    /// Child pays for parent: a builder for a transaction spending the given output of an
+    /// unconfirmed parent transaction back to `change_script_pubkey`, with a fee large enough
+    /// for the parent and the child to pay `sat_per_vbyte` together.
+    ///
+    /// The parent must be signed for its virtual size to be known. Use
+    /// [`BitcoinTransactionBuilder::try_build`] to catch an output too small to pay the fee.
+    /// Returns [`ValidationError::MissingParentOutput`] if the parent has no output `vout`.
+    /// The child of a version 3 (TRUC) parent, such as one bumping a zero-fee parent through
+    /// its [`Self::add_anchor`] output, is version 3 as well.
+    pub fn cpfp(
+        parent: &BitcoinTransaction,
+        vout: u32,
+        parent_fee: Amount,
+        sat_per_vbyte: u64,
+        change_script_pubkey: ScriptBuf,
+    ) -> Result<Self, ValidationError> {
+        let version = match parent.version {
+            Version::Three => Version::Three,
+            _ => Version::Two,
+        };
+
+        Ok(Self::new()
+            .version(version)
+            .lock_time(LockTime::from_consensus(0))
+            .outputs(vec![])
+            .spend_parent_output(parent, vout, parent_fee)?
+            .fee_rate(sat_per_vbyte)
+            .change_script_pubkey(change_script_pubkey))
+    }
+
     pub const fn version(mut self, version: Version) -> Self {
         self.version = Some(version);
         self
@@ -58,11 +121,501 @@ impl BitcoinTransactionBuilder {
         self.outputs = Some(outputs);
         self
     }
+
+    /// Network the transaction is built for, which determines its policy rules.
+    pub const fn network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Dust relay fee rate in satoshis per kilo virtual byte, overriding the one of the network.
+    pub const fn dust_relay_fee(mut self, dust_relay_fee_per_kvb: u64) -> Self {
+        self.dust_relay_fee = Some(dust_relay_fee_per_kvb);
+        self
+    }
+
+    /// Fee rate in satoshis per virtual byte, used to compute the change output.
+    pub const fn fee_rate(mut self, sat_per_vbyte: u64) -> Self {
+        self.fee_rate = Some(sat_per_vbyte);
+        self
+    }
+
+    /// Send the change to the given script pubkey.
+    ///
+    /// The change is the value of the spent UTXOs left over after the outputs and the fee at
+    /// [`Self::fee_rate`]. It is appended as the last output, or left to the fee if it would be
+    /// below the dust threshold. Every input must be added with [`Self::add_utxo`].
+    pub fn change_script_pubkey(mut self, script_pubkey: ScriptBuf) -> Self {
+        self.change_script_pubkey = Some(script_pubkey);
+        self
+    }
+
+    /// Pay the given amount to the address.
+    pub fn add_payment(mut self, address: &Address, amount: Amount) -> Self {
+        self.outputs.get_or_insert_with(Vec::new).push(TxOut {
+            value: amount,
+            script_pubkey: address.script_pubkey(),
+        });
+        self
+    }
+
+    /// Pay every address the amount paired with it, batching the payments in one transaction.
+    pub fn add_payments<'a>(
+        self,
+        payments: impl IntoIterator<Item = (&'a Address, Amount)>,
+    ) -> Self {
+        payments
+            .into_iter()
+            .fold(self, |builder, (address, amount)| {
+                builder.add_payment(address, amount)
+            })
+    }
+
+    /// Append a pay-to-anchor (P2A) output of the given value, which anyone can spend to bump
+    /// the fee of the transaction with a child, see [`Self::cpfp`].
+    ///
+    /// An anchor below the dust threshold, usually of zero value, is only relayed as ephemeral
+    /// dust: the transaction must then pay no fee, which a [`Self::fee_rate`] of zero with a
+    /// change output ensures, and be version 3 (TRUC) for the child to be relayed along with it.
+    pub fn add_anchor(mut self, value: Amount) -> Self {
+        self.outputs.get_or_insert_with(Vec::new).push(TxOut {
+            value,
+            script_pubkey: p2a(),
+        });
+        self
+    }
+
+    /// Have the outputs added so far pay the fee at [`Self::fee_rate`], each paying a share
+    /// proportional to its size, like Bitcoin Core's `subtractfeefromoutputs`.
+    ///
+    /// Useful to batch withdrawals whose recipients cover their fee. The change output, if any,
+    /// then gets the value of the inputs not spent by the outputs. Every input must be added
+    /// with [`Self::add_utxo`].
+    pub const fn subtract_fee_from_outputs(mut self) -> Self {
+        self.subtract_fee_from_outputs = true;
+        self
+    }
+
+    /// Spend the given output of an unconfirmed parent transaction paying `parent_fee`, raising
+    /// the fee at [`Self::fee_rate`] to also cover what the parent is missing to pay that rate.
+    /// Fails with [`ValidationError::MissingParentOutput`] if the parent has no output `vout`.
+    pub fn spend_parent_output(
+        mut self,
+        parent: &BitcoinTransaction,
+        vout: u32,
+        parent_fee: Amount,
+    ) -> Result<Self, ValidationError> {
+        let output = parent
+            .output
+            .get(vout as usize)
+            .ok_or(ValidationError::MissingParentOutput { vout })?;
+
+        self.parent_vsize = Some(parent.vsize());
+        self.parent_fee = Some(parent_fee);
+        Ok(self.add_utxo(Utxo::new(
+            parent.txid(),
+            vout,
+            output.value,
+            output.script_pubkey.clone(),
+        )))
+    }
+
+    /// Spend the given UTXO, appending an input for it and keeping track of its value and
+    /// script pubkey for the sighash computation.
+    pub fn add_utxo(mut self, utxo: Utxo) -> Self {
+        self.inputs
+            .get_or_insert_with(Vec::new)
+            .push(TxIn::from(&utxo));
+        self.utxos.push(utxo);
+        self
+    }
+
+    /// Returns the tracked UTXO spent by the given outpoint, if any.
+    pub fn utxo(&self, outpoint: &OutPoint) -> Option<&Utxo> {
+        self.utxos.iter().find(|utxo| utxo.outpoint() == *outpoint)
+    }
+
+    /// Builds the transaction, returning an error instead if it fails [`Self::validate`].
+    pub fn try_build(&self) -> Result<BitcoinTransaction, ValidationError> {
+        let tx = self.assemble()?;
+        tx.validate()?;
+        self.check_dust(&tx)?;
+
+        Ok(tx)
+    }
+
+    /// Checks the transaction with [`BitcoinTransaction::validate`] and that it can be relayed:
+    /// no output may be below the dust threshold of its script type.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.try_build().map(|_| ())
+    }
+
+    /// Builds the transaction and summarizes it for logging like its [`core::fmt::Display`]
+    /// implementation, adding the fee it pays from the tracked UTXOs and the fee rate once it
+    /// is signed.
+    pub fn summary(&self) -> Result<String, ValidationError> {
+        let tx = self.try_build()?;
+        let fee = self.paid_fee(&tx)?;
+        let vsize = self.estimate_vsize(&tx)?;
+
+        Ok(format!(
+            "{tx}\n  fee: {} sat ({:.1} sat/vB once signed)",
+            fee.to_sat(),
+            fee.to_sat() as f64 / vsize as f64
+        ))
+    }
+
+    /// Validates the transaction with [`Self::validate`] and checks that it would be relayed
+    /// with [`BitcoinTransaction::check_standard`]. A version 3 (TRUC) transaction spending an
+    /// unconfirmed parent must also stay within [`TRUC_CHILD_MAX_VSIZE`] once signed.
+    pub fn check_standard(&self) -> Result<(), ValidationError> {
+        let tx = self.try_build()?;
+        tx.check_standard()?;
+
+        if tx.version == Version::Three && self.parent_vsize.is_some() {
+            let vsize = self.estimate_vsize(&tx)?;
+            if vsize > TRUC_CHILD_MAX_VSIZE {
+                return Err(ValidationError::TrucTooLarge {
+                    vsize,
+                    max_vsize: TRUC_CHILD_MAX_VSIZE,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The fee paid by the transaction, what its inputs hold beyond the value of its outputs.
+    fn paid_fee(&self, tx: &BitcoinTransaction) -> Result<Amount, ValidationError> {
+        let mut available = Amount::ZERO;
+        for input in &tx.input {
+            let utxo = self
+                .utxo(&input.previous_output)
+                .ok_or(ValidationError::MissingUtxo(input.previous_output))?;
+            available = available
+                .checked_add(utxo.value)
+                .ok_or(ValidationError::OutputValueOverflow)?;
+        }
+        let mut spent = Amount::ZERO;
+        for output in &tx.output {
+            spent = spent
+                .checked_add(output.value)
+                .ok_or(ValidationError::OutputValueOverflow)?;
+        }
+
+        available
+            .checked_sub(spent)
+            .ok_or(ValidationError::InsufficientFunds {
+                available,
+                required: spent,
+            })
+    }
+
+    fn check_dust(&self, tx: &BitcoinTransaction) -> Result<(), ValidationError> {
+        // A transaction paying no fee may have one output below the dust threshold, ephemeral
+        // dust that its child must spend for them to be relayed together
+        let mut ephemeral_dust = self.paid_fee(tx) == Ok(Amount::ZERO);
+
+        for (index, output) in tx.output.iter().enumerate() {
+            let dust_threshold = self.dust_threshold(&output.script_pubkey);
+
+            if output.value < dust_threshold {
+                if ephemeral_dust {
+                    ephemeral_dust = false;
+                    continue;
+                }
+
+                return Err(ValidationError::DustOutput {
+                    index,
+                    value: output.value,
+                    dust_threshold,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn dust_threshold(&self, script_pubkey: &ScriptBuf) -> Amount {
+        match self.dust_relay_fee {
+            Some(dust_relay_fee) => self
+                .network
+                .dust_threshold_with_relay_fee(script_pubkey, dust_relay_fee),
+            None => self.network.dust_threshold(script_pubkey),
+        }
+    }
+
+    /// Puts the transaction together, paying the fee and appending the change output if
+    /// requested.
+    fn assemble(&self) -> Result<BitcoinTransaction, ValidationError> {
+        let mut tx = BitcoinTransaction {
+            version: self.version.ok_or(ValidationError::MissingVersion)?,
+            lock_time: self.lock_time.ok_or(ValidationError::MissingLockTime)?,
+            input: self.inputs.clone().ok_or(ValidationError::EmptyInputs)?,
+            output: self.outputs.clone().ok_or(ValidationError::EmptyOutputs)?,
+        };
+
+        if self.change_script_pubkey.is_some() || self.subtract_fee_from_outputs {
+            self.pay_fee(&mut tx)?;
+        }
+
+        Ok(tx)
+    }
+
+    /// Pays the fee at [`Self::fee_rate`], either from the inputs or from the outputs, and
+    /// appends the change output unless it would be dust.
+    fn pay_fee(&self, tx: &mut BitcoinTransaction) -> Result<(), ValidationError> {
+        let mut available = Amount::ZERO;
+        for input in &tx.input {
+            let utxo = self
+                .utxo(&input.previous_output)
+                .ok_or(ValidationError::MissingUtxo(input.previous_output))?;
+            available = available
+                .checked_add(utxo.value)
+                .ok_or(ValidationError::OutputValueOverflow)?;
+        }
+        let mut spent = Amount::ZERO;
+        for output in &tx.output {
+            spent = spent
+                .checked_add(output.value)
+                .ok_or(ValidationError::OutputValueOverflow)?;
+        }
+
+        let fee = self.fee(tx)?;
+        let change = match &self.change_script_pubkey {
+            Some(change_script_pubkey) => {
+                let change = TxOut {
+                    value: Amount::ZERO,
+                    script_pubkey: change_script_pubkey.clone(),
+                };
+                let mut with_change = tx.clone();
+                with_change.output.push(change.clone());
+                let fee = self.fee(&with_change)?;
+
+                Some((change, fee))
+            }
+            None => None,
+        };
+
+        if self.subtract_fee_from_outputs {
+            // The outputs pay the fee, so the change gets everything they do not spend
+            let left = available
+                .checked_sub(spent)
+                .ok_or(ValidationError::InsufficientFunds {
+                    available,
+                    required: spent,
+                })?;
+            let payments = tx.output.len();
+
+            let fee = match change {
+                Some((change, fee_with_change))
+                    if left >= self.dust_threshold(&change.script_pubkey) =>
+                {
+                    tx.output.push(TxOut {
+                        value: left,
+                        ..change
+                    });
+                    fee_with_change
+                }
+                // What is left is not worth an output, it pays part of the fee
+                _ => fee.checked_sub(left).unwrap_or(Amount::ZERO),
+            };
+
+            return subtract_fee(&mut tx.output[..payments], fee);
+        }
+
+        let required = spent
+            .checked_add(fee)
+            .ok_or(ValidationError::OutputValueOverflow)?;
+        if available < required {
+            return Err(ValidationError::InsufficientFunds {
+                available,
+                required,
+            });
+        }
+
+        if let Some((change, fee_with_change)) = change {
+            let required = spent
+                .checked_add(fee_with_change)
+                .ok_or(ValidationError::OutputValueOverflow)?;
+            match available.checked_sub(required) {
+                Some(value) if value >= self.dust_threshold(&change.script_pubkey) => {
+                    tx.output.push(TxOut { value, ..change });
+                }
+                // The change is not worth an output, leave it to the fee
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Computes the fee of the transaction at [`Self::fee_rate`], including what its parent is
+    /// missing to pay that rate.
+    fn fee(&self, tx: &BitcoinTransaction) -> Result<Amount, ValidationError> {
+        let fee_rate = self.fee_rate.ok_or(ValidationError::MissingFeeRate)?;
+        let vsize = self.estimate_vsize(tx)?;
+
+        let fee = vsize
+            .checked_mul(fee_rate)
+            .ok_or(ValidationError::OutputValueOverflow)?;
+        let package_fee = match (self.parent_vsize, self.parent_fee) {
+            (Some(parent_vsize), Some(parent_fee)) => parent_vsize
+                .checked_add(vsize)
+                .and_then(|package_vsize| package_vsize.checked_mul(fee_rate))
+                .ok_or(ValidationError::OutputValueOverflow)?
+                .saturating_sub(parent_fee.to_sat()),
+            _ => 0,
+        };
+
+        Ok(Amount::from_sat(fee.max(package_fee)))
+    }
+
+    /// Estimates the virtual size of the transaction once signed, assuming its inputs are
+    /// spent with a single signature and public key.
+    fn estimate_vsize(&self, tx: &BitcoinTransaction) -> Result<u64, ValidationError> {
+        let mut base_size = 4 // version
+            + VarInt(tx.input.len() as u64).size()
+            + VarInt(tx.output.len() as u64).size()
+            + tx.output.iter().map(TxOut::size).sum::<usize>()
+            + 4; // lock time
+        let mut witness_size = 0;
+        let mut empty_witnesses = 0;
+
+        for (index, input) in tx.input.iter().enumerate() {
+            let utxo = self
+                .utxo(&input.previous_output)
+                .ok_or(ValidationError::MissingUtxo(input.previous_output))?;
+
+            let (script_sig_size, input_witness_size) = if utxo.is_p2wpkh() {
+                (0, P2WPKH_WITNESS_SIZE)
+            } else if utxo.is_p2tr() {
+                (0, P2TR_KEY_SPEND_WITNESS_SIZE)
+            } else if utxo.is_p2a() {
+                // Anchors are spent with an empty witness
+                (0, 0)
+            } else if utxo.is_p2pkh() {
+                (P2PKH_SCRIPT_SIG_SIZE, 0)
+            } else {
+                return Err(ValidationError::UnsupportedInputScript { index });
+            };
+            // outpoint + script sig length + script sig + sequence
+            base_size += 36 + VarInt(script_sig_size as u64).size() + script_sig_size + 4;
+            witness_size += input_witness_size;
+            if input_witness_size == 0 {
+                empty_witnesses += 1;
+            }
+        }
+
+        let weight = if witness_size > 0 {
+            // Marker and flag, plus the item count of the empty witnesses
+            base_size * 4 + 2 + witness_size + empty_witnesses
+        } else {
+            base_size * 4
+        };
+
+        Ok(weight.div_ceil(4) as u64)
+    }
+
+    /// Encode the transaction for signing the given input in SegWit format, using the value
+    /// and script code of the tracked UTXO it spends, or [`ValidationError::MissingUtxo`] if the
+    /// input was not added with [`Self::add_utxo`].
+    pub fn build_for_signing_segwit(
+        &self,
+        sighash_type: EcdsaSighashType,
+        input_index: usize,
+    ) -> Result<Vec<u8>, ValidationError> {
+        let tx = self.assemble()?;
+        let previous_output = tx.input[input_index].previous_output;
+        let utxo = self
+            .utxo(&previous_output)
+            .ok_or(ValidationError::MissingUtxo(previous_output))?;
+
+        Ok(tx.build_for_signing_segwit(
+            sighash_type,
+            input_index,
+            &utxo.script_code(),
+            utxo.value.to_sat(),
+        ))
+    }
+
+    /// Encode the transaction for signing every input in SegWit format, using the values and
+    /// script codes of the tracked UTXOs they spend, or [`ValidationError::MissingUtxo`] if an
+    /// input was not added with [`Self::add_utxo`].
+    ///
+    /// The transaction is built and the hashes of its prevouts, sequences and outputs computed
+    /// once for all the inputs.
+    pub fn build_for_signing_segwit_all(
+        &self,
+        sighash_type: EcdsaSighashType,
+    ) -> Result<Vec<Vec<u8>>, ValidationError> {
+        let tx = self.assemble()?;
+        let midstate = tx.segwit_sighash_midstate();
+
+        tx.input
+            .iter()
+            .enumerate()
+            .map(|(input_index, input)| {
+                let utxo = self
+                    .utxo(&input.previous_output)
+                    .ok_or(ValidationError::MissingUtxo(input.previous_output))?;
+
+                Ok(tx.build_for_signing_segwit_with_midstate(
+                    &midstate,
+                    sighash_type,
+                    input_index,
+                    &utxo.script_code(),
+                    utxo.value.to_sat(),
+                ))
+            })
+            .collect()
+    }
 }
 
+/// Splits the fee among the outputs in proportion to their size, the rounding remainder being
+/// paid by the first output.
+fn subtract_fee(outputs: &mut [TxOut], fee: Amount) -> Result<(), ValidationError> {
+    if outputs.is_empty() {
+        return Err(ValidationError::EmptyOutputs);
+    }
+
+    let total_size: u64 = outputs.iter().map(|output| output.size() as u64).sum();
+    let mut shares: Vec<u64> = outputs
+        .iter()
+        .map(|output| (fee.to_sat() as u128 * output.size() as u128 / total_size as u128) as u64)
+        .collect();
+    shares[0] += fee.to_sat() - shares.iter().sum::<u64>();
+
+    for (output, share) in outputs.iter_mut().zip(shares) {
+        let share = Amount::from_sat(share);
+        output.value =
+            output
+                .value
+                .checked_sub(share)
+                .ok_or(ValidationError::InsufficientFunds {
+                    available: output.value,
+                    required: share,
+                })?;
+    }
+
+    Ok(())
+}
+
+/// Size of a P2WPKH witness: the item count, a signature of up to 72 bytes including the
+/// sighash type, and a compressed public key, each with its length.
+const P2WPKH_WITNESS_SIZE: usize = 1 + 1 + 72 + 1 + 33;
+
+/// Size of a P2TR key path witness: the item count and a 64-byte Schnorr signature.
+const P2TR_KEY_SPEND_WITNESS_SIZE: usize = 1 + 1 + 64;
+
+/// Size of a P2PKH script sig: a signature of up to 72 bytes including the sighash type, and
+/// a compressed public key, each with its push opcode.
+const P2PKH_SCRIPT_SIG_SIZE: usize = 1 + 72 + 1 + 33;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bitcoin::types::{Amount, Hash, ScriptBuf, Sequence, Txid, Witness};
 
     #[test]
     fn test_build() {
@@ -81,6 +634,623 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_add_utxo() {
+        let utxo = Utxo::new(
+            Txid(Hash::all_zeros()),
+            1,
+            Amount::from_sat(10_000),
+            ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap(),
+        );
+        let builder = BitcoinTransactionBuilder::new()
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap())
+            .add_utxo(utxo.clone())
+            .outputs(vec![]);
+
+        let tx = builder.build();
+        assert_eq!(tx.input.len(), 1);
+        assert_eq!(tx.input[0].previous_output, utxo.outpoint());
+        assert_eq!(builder.utxo(&utxo.outpoint()), Some(&utxo));
+    }
+
+    #[test]
+    fn test_build_for_signing_segwit_with_utxo_against_rust_bitcoin() {
+        use bitcoin::hashes::Hash as _;
+        use sha2::{Digest, Sha256};
+
+        let script_pubkey_hex = "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1";
+        let utxo = Utxo::new(
+            Txid(
+                Hash::from_hex("bc25cc0dddd0a202c21e66521a692c0586330a9a9dcc38ccd9b4d2093037f31a")
+                    .unwrap(),
+            ),
+            0,
+            Amount::from_sat(50_000),
+            ScriptBuf::from_hex(script_pubkey_hex).unwrap(),
+        );
+        let output = TxOut {
+            value: Amount::from_sat(40_000),
+            script_pubkey: ScriptBuf::from_hex(script_pubkey_hex).unwrap(),
+        };
+
+        let builder = BitcoinTransactionBuilder::new()
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap())
+            .add_utxo(utxo.clone())
+            .outputs(vec![output]);
+        let preimage = builder
+            .build_for_signing_segwit(EcdsaSighashType::All, 0)
+            .unwrap();
+        let sighash = Sha256::digest(Sha256::digest(&preimage));
+
+        let rust_bitcoin_script_pubkey = bitcoin::ScriptBuf::from_hex(script_pubkey_hex).unwrap();
+        let mut rust_bitcoin_tx = bitcoin::Transaction {
+            version: bitcoin::transaction::Version(2),
+            lock_time: bitcoin::absolute::LockTime::from_height(0).unwrap(),
+            input: vec![bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint {
+                    txid: utxo.txid.to_string().parse().unwrap(),
+                    vout: 0,
+                },
+                script_sig: bitcoin::ScriptBuf::default(),
+                sequence: bitcoin::Sequence::MAX,
+                witness: bitcoin::Witness::default(),
+            }],
+            output: vec![bitcoin::TxOut {
+                value: bitcoin::Amount::from_sat(40_000),
+                script_pubkey: rust_bitcoin_script_pubkey.clone(),
+            }],
+        };
+        let expected = bitcoin::sighash::SighashCache::new(&mut rust_bitcoin_tx)
+            .p2wpkh_signature_hash(
+                0,
+                &rust_bitcoin_script_pubkey,
+                bitcoin::Amount::from_sat(50_000),
+                bitcoin::EcdsaSighashType::All,
+            )
+            .unwrap();
+
+        assert_eq!(sighash.as_slice(), expected.as_byte_array());
+
+        // An input added without its UTXO cannot be signed
+        let builder = BitcoinTransactionBuilder {
+            utxos: vec![],
+            ..builder
+        };
+        assert_eq!(
+            builder.build_for_signing_segwit(EcdsaSighashType::All, 0),
+            Err(ValidationError::MissingUtxo(utxo.outpoint()))
+        );
+    }
+
+    #[test]
+    fn test_build_for_signing_segwit_all_against_rust_bitcoin() {
+        use bitcoin::hashes::Hash as _;
+        use sha2::{Digest, Sha256};
+
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let p2wsh = ScriptBuf::from_hex(
+            "0020a16b5755f7f6f96dbd65f5f0d6ab9418b89af4b1f14a1bb8a09062c35f0dcb54",
+        )
+        .unwrap();
+        let utxos: Vec<Utxo> = [
+            (1, 30_000, &p2wpkh),
+            (2, 50_000, &p2wpkh),
+            (3, 70_000, &p2wsh),
+        ]
+        .into_iter()
+        .map(|(byte, value, script_pubkey)| {
+            Utxo::new(
+                Txid(Hash([byte; 32])),
+                byte as u32,
+                Amount::from_sat(value),
+                script_pubkey.clone(),
+            )
+        })
+        .collect();
+        let builder = utxos
+            .iter()
+            .cloned()
+            .fold(BitcoinTransactionBuilder::new(), |builder, utxo| {
+                builder.add_utxo(utxo)
+            })
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap())
+            .outputs(vec![TxOut {
+                value: Amount::from_sat(140_000),
+                script_pubkey: p2wpkh.clone(),
+            }]);
+
+        let preimages = builder
+            .build_for_signing_segwit_all(EcdsaSighashType::All)
+            .unwrap();
+        assert_eq!(preimages.len(), 3);
+
+        let tx = builder.build();
+        let rust_bitcoin_tx: bitcoin::Transaction =
+            bitcoin::consensus::deserialize(&tx.serialize()).unwrap();
+        let mut cache = bitcoin::sighash::SighashCache::new(&rust_bitcoin_tx);
+        for (input_index, (preimage, utxo)) in preimages.iter().zip(&utxos).enumerate() {
+            assert_eq!(
+                preimage,
+                &builder
+                    .build_for_signing_segwit(EcdsaSighashType::All, input_index)
+                    .unwrap()
+            );
+
+            // The script code of the P2WPKH inputs is passed as is
+            let expected = cache
+                .p2wsh_signature_hash(
+                    input_index,
+                    &bitcoin::ScriptBuf::from_bytes(utxo.script_code().0),
+                    bitcoin::Amount::from_sat(utxo.value.to_sat()),
+                    bitcoin::EcdsaSighashType::All,
+                )
+                .unwrap();
+            assert_eq!(
+                Sha256::digest(Sha256::digest(preimage)).as_slice(),
+                expected.as_byte_array()
+            );
+        }
+
+        let builder = BitcoinTransactionBuilder {
+            utxos: utxos[..2].to_vec(),
+            ..builder
+        };
+        assert_eq!(
+            builder.build_for_signing_segwit_all(EcdsaSighashType::All),
+            Err(ValidationError::MissingUtxo(utxos[2].outpoint()))
+        );
+    }
+
+    #[test]
+    fn test_validate_dust() {
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let builder = BitcoinTransactionBuilder::new()
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap())
+            .add_utxo(Utxo::new(
+                Txid(Hash::all_zeros()),
+                0,
+                Amount::from_sat(20_000),
+                p2wpkh.clone(),
+            ))
+            .outputs(vec![
+                TxOut {
+                    value: Amount::from_sat(10_000),
+                    script_pubkey: p2wpkh.clone(),
+                },
+                TxOut {
+                    value: Amount::from_sat(293),
+                    script_pubkey: p2wpkh.clone(),
+                },
+            ]);
+
+        assert_eq!(
+            builder.validate(),
+            Err(ValidationError::DustOutput {
+                index: 1,
+                value: Amount::from_sat(293),
+                dust_threshold: Amount::from_sat(294),
+            })
+        );
+        assert!(builder.dust_relay_fee(0).validate().is_ok());
+    }
+
+    #[test]
+    fn test_try_build() {
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let utxo = Utxo::new(
+            Txid(Hash::all_zeros()),
+            0,
+            Amount::from_sat(20_000),
+            p2wpkh.clone(),
+        );
+        let output = TxOut {
+            value: Amount::from_sat(10_000),
+            script_pubkey: p2wpkh,
+        };
+        let builder = BitcoinTransactionBuilder::new()
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap());
+
+        assert_eq!(
+            builder.add_utxo(utxo.clone()).outputs(vec![]).try_build(),
+            Err(ValidationError::EmptyOutputs)
+        );
+
+        let builder = BitcoinTransactionBuilder::new()
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap())
+            .add_utxo(utxo.clone())
+            .add_utxo(utxo.clone())
+            .outputs(vec![output.clone()]);
+        assert_eq!(
+            builder.try_build(),
+            Err(ValidationError::DuplicateInput(utxo.outpoint()))
+        );
+
+        let tx = BitcoinTransactionBuilder::new()
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap())
+            .add_utxo(utxo)
+            .outputs(vec![output])
+            .try_build()
+            .unwrap();
+        assert_eq!(tx.output.len(), 1);
+    }
+
+    #[test]
+    fn test_build_with_dust_output() {
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let builder = BitcoinTransactionBuilder::new()
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap())
+            .add_utxo(Utxo::new(
+                Txid(Hash::all_zeros()),
+                0,
+                Amount::from_sat(1_000),
+                p2wpkh.clone(),
+            ))
+            .outputs(vec![TxOut {
+                value: Amount::from_sat(1),
+                script_pubkey: p2wpkh,
+            }]);
+
+        // The dust policy is only enforced by try_build and validate
+        assert_eq!(builder.build().output[0].value, Amount::from_sat(1));
+        assert!(matches!(
+            builder.try_build(),
+            Err(ValidationError::DustOutput { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_change_output() {
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let payment = TxOut {
+            value: Amount::from_sat(50_000),
+            script_pubkey: p2wpkh.clone(),
+        };
+        let builder = |utxo_value| {
+            BitcoinTransactionBuilder::new()
+                .version(Version::Two)
+                .lock_time(LockTime::from_height(0).unwrap())
+                .add_utxo(Utxo::new(
+                    Txid(Hash::all_zeros()),
+                    0,
+                    Amount::from_sat(utxo_value),
+                    p2wpkh.clone(),
+                ))
+                .outputs(vec![payment.clone()])
+                .fee_rate(2)
+                .change_script_pubkey(p2wpkh.clone())
+        };
+
+        // One P2WPKH input and two P2WPKH outputs weigh 141 vbytes
+        let tx = builder(100_000).build();
+        assert_eq!(tx.output.len(), 2);
+        assert_eq!(tx.output[0], payment);
+        assert_eq!(
+            tx.output[1],
+            TxOut {
+                value: Amount::from_sat(100_000 - 50_000 - 141 * 2),
+                script_pubkey: p2wpkh.clone(),
+            }
+        );
+
+        // A change of 218 sat would be dust, so it is left to the fee
+        let tx = builder(50_500).build();
+        assert_eq!(tx.output, vec![payment.clone()]);
+
+        assert_eq!(
+            builder(50_100).try_build(),
+            Err(ValidationError::InsufficientFunds {
+                available: Amount::from_sat(50_100),
+                required: Amount::from_sat(50_000 + 110 * 2),
+            })
+        );
+        assert_eq!(
+            builder(100_000).fee_rate(u64::MAX).try_build(),
+            Err(ValidationError::OutputValueOverflow)
+        );
+
+        let mut builder = builder(100_000);
+        builder.fee_rate = None;
+        assert_eq!(builder.try_build(), Err(ValidationError::MissingFeeRate));
+        builder.version = None;
+        assert_eq!(builder.try_build(), Err(ValidationError::MissingVersion));
+    }
+
+    #[test]
+    fn test_summary() {
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let builder = BitcoinTransactionBuilder::new()
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap())
+            .add_utxo(Utxo::new(
+                Txid(Hash::all_zeros()),
+                0,
+                Amount::from_sat(100_000),
+                p2wpkh.clone(),
+            ))
+            .outputs(vec![TxOut {
+                value: Amount::from_sat(50_000),
+                script_pubkey: p2wpkh.clone(),
+            }])
+            .fee_rate(2)
+            .change_script_pubkey(p2wpkh);
+
+        // One P2WPKH input and two P2WPKH outputs weigh 141 vbytes
+        assert_eq!(
+            builder.summary().unwrap(),
+            format!(
+                "{}\n  fee: 282 sat (2.0 sat/vB once signed)",
+                builder.build()
+            )
+        );
+    }
+
+    #[test]
+    fn test_subtract_fee_from_outputs() {
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let recipients = [
+            Address::parse(
+                "bc1qr583w2swedy2acd7rung055k8t3n7udp7vyzyg",
+                Network::Bitcoin,
+            )
+            .unwrap(),
+            Address::parse(
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+                Network::Bitcoin,
+            )
+            .unwrap(),
+        ];
+        let builder = |utxo_value| {
+            BitcoinTransactionBuilder::new()
+                .version(Version::Two)
+                .lock_time(LockTime::from_height(0).unwrap())
+                .add_utxo(Utxo::new(
+                    Txid(Hash::all_zeros()),
+                    0,
+                    Amount::from_sat(utxo_value),
+                    p2wpkh.clone(),
+                ))
+                .add_payments(
+                    recipients
+                        .iter()
+                        .map(|address| (address, Amount::from_sat(30_000))),
+                )
+                .fee_rate(3)
+                .subtract_fee_from_outputs()
+        };
+
+        // One P2WPKH input, one P2WPKH and one P2TR output weigh 153 vbytes, the outputs of 31
+        // and 43 bytes paying 192 and 266 sat, plus the rounding remainder of 1 sat
+        let tx = builder(60_000).build();
+        assert_eq!(tx.output.len(), 2);
+        assert_eq!(tx.output[0].script_pubkey, recipients[0].script_pubkey());
+        assert_eq!(tx.output[0].value, Amount::from_sat(30_000 - 193));
+        assert_eq!(tx.output[1].script_pubkey, recipients[1].script_pubkey());
+        assert_eq!(tx.output[1].value, Amount::from_sat(30_000 - 266));
+
+        // What the outputs do not spend goes to the change, which does not pay the fee
+        let tx = builder(100_000)
+            .change_script_pubkey(p2wpkh.clone())
+            .build();
+        assert_eq!(tx.output.len(), 3);
+        assert_eq!(
+            tx.output[2],
+            TxOut {
+                value: Amount::from_sat(40_000),
+                script_pubkey: p2wpkh.clone(),
+            }
+        );
+        assert_eq!(
+            tx.output[..2]
+                .iter()
+                .map(|output| output.value.to_sat())
+                .sum::<u64>(),
+            60_000 - 184 * 3
+        );
+
+        assert_eq!(
+            builder(59_999).try_build(),
+            Err(ValidationError::InsufficientFunds {
+                available: Amount::from_sat(59_999),
+                required: Amount::from_sat(60_000),
+            })
+        );
+    }
+
+    #[test]
+    fn test_cpfp() {
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let parent = BitcoinTransaction {
+            version: Version::Two,
+            lock_time: LockTime::from_height(0).unwrap(),
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid(Hash::all_zeros()), 0),
+                script_sig: ScriptBuf::default(),
+                sequence: Sequence::MAX,
+                witness: Witness::from_slice(&[vec![0x30; 72], vec![0x02; 33]]),
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(100_000),
+                script_pubkey: p2wpkh.clone(),
+            }],
+        };
+        let rust_bitcoin_parent: bitcoin::Transaction =
+            bitcoin::consensus::deserialize(&parent.serialize()).unwrap();
+        assert_eq!(parent.weight(), rust_bitcoin_parent.weight().to_wu());
+        assert_eq!(parent.vsize(), 110);
+
+        // The parent pays 1 sat/vB, the child of 110 vbytes brings the package to 10 sat/vB
+        let tx =
+            BitcoinTransactionBuilder::cpfp(&parent, 0, Amount::from_sat(110), 10, p2wpkh.clone())
+                .unwrap()
+                .try_build()
+                .unwrap();
+        assert_eq!(tx.input[0].previous_output, OutPoint::new(parent.txid(), 0));
+        assert_eq!(
+            tx.output,
+            vec![TxOut {
+                value: Amount::from_sat(100_000 - (220 * 10 - 110)),
+                script_pubkey: p2wpkh.clone(),
+            }]
+        );
+
+        // A parent already paying more than the target leaves the child to pay for itself
+        let tx = BitcoinTransactionBuilder::cpfp(
+            &parent,
+            0,
+            Amount::from_sat(5_000),
+            10,
+            p2wpkh.clone(),
+        )
+        .unwrap()
+        .try_build()
+        .unwrap();
+        assert_eq!(tx.output[0].value, Amount::from_sat(100_000 - 110 * 10));
+
+        let mut builder =
+            BitcoinTransactionBuilder::cpfp(&parent, 0, Amount::from_sat(110), 10, p2wpkh.clone())
+                .unwrap();
+        builder.parent_vsize = Some(u64::MAX);
+        assert!(matches!(
+            builder.try_build(),
+            Err(ValidationError::OutputValueOverflow)
+        ));
+
+        assert!(matches!(
+            BitcoinTransactionBuilder::cpfp(&parent, 1, Amount::from_sat(110), 10, p2wpkh),
+            Err(ValidationError::MissingParentOutput { vout: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_anchor() {
+        use bitcoin::hashes::Hash as _;
+        use sha2::{Digest, Sha256};
+
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let parent = |fee_rate| {
+            BitcoinTransactionBuilder::new()
+                .version(Version::Three)
+                .lock_time(LockTime::from_height(0).unwrap())
+                .add_utxo(Utxo::new(
+                    Txid(Hash::all_zeros()),
+                    0,
+                    Amount::from_sat(100_000),
+                    p2wpkh.clone(),
+                ))
+                .outputs(vec![TxOut {
+                    value: Amount::from_sat(50_000),
+                    script_pubkey: p2wpkh.clone(),
+                }])
+                .add_anchor(Amount::ZERO)
+                .fee_rate(fee_rate)
+                .change_script_pubkey(p2wpkh.clone())
+        };
+
+        // The anchor is ephemeral dust, only relayed if the parent pays no fee
+        assert_eq!(
+            parent(2).try_build(),
+            Err(ValidationError::DustOutput {
+                index: 1,
+                value: Amount::ZERO,
+                dust_threshold: Amount::from_sat(240),
+            })
+        );
+        let mut parent_tx = parent(0).try_build().unwrap();
+        assert_eq!(parent_tx.output[1].script_pubkey, p2a());
+        assert_eq!(parent_tx.output[2].value, Amount::from_sat(50_000));
+        assert_eq!(parent(0).check_standard(), Ok(()));
+
+        let rust_bitcoin_anchor = bitcoin::ScriptBuf::from_bytes(p2a().0);
+        assert!(rust_bitcoin_anchor.is_witness_program());
+        assert_eq!(
+            rust_bitcoin_anchor.witness_version(),
+            Some(bitcoin::WitnessVersion::V1)
+        );
+
+        // The child bumps the fee of the package by spending the anchor along with a UTXO
+        parent_tx.input[0].witness = Witness::from_slice(&[vec![0x30; 72], vec![0x02; 33]]);
+        let utxo = Utxo::new(
+            Txid(Hash([1; 32])),
+            0,
+            Amount::from_sat(10_000),
+            p2wpkh.clone(),
+        );
+        let child =
+            BitcoinTransactionBuilder::cpfp(&parent_tx, 1, Amount::ZERO, 10, p2wpkh.clone())
+                .unwrap()
+                .add_utxo(utxo.clone());
+        let child_tx = child.try_build().unwrap();
+        let child_vsize = child.estimate_vsize(&child_tx).unwrap();
+        assert_eq!(child_tx.version, Version::Three);
+        assert_eq!(
+            child_tx.input[0].previous_output,
+            OutPoint::new(parent_tx.txid(), 1)
+        );
+        assert_eq!(
+            child_tx.output[0].value,
+            Amount::from_sat(10_000 - (parent_tx.vsize() + child_vsize) * 10)
+        );
+        assert_eq!(child.check_standard(), Ok(()));
+
+        // The anchor input has an empty witness
+        let mut signed_child = child_tx.clone();
+        signed_child.input[1].witness = Witness::from_slice(&[vec![0x30; 72], vec![0x02; 33]]);
+        assert_eq!(signed_child.vsize(), child_vsize);
+
+        // The TRUC child signs like any other SegWit transaction
+        let rust_bitcoin_child: bitcoin::Transaction =
+            bitcoin::consensus::deserialize(&child_tx.serialize()).unwrap();
+        let expected = bitcoin::sighash::SighashCache::new(&rust_bitcoin_child)
+            .p2wpkh_signature_hash(
+                1,
+                &bitcoin::ScriptBuf::from_bytes(p2wpkh.0.clone()),
+                bitcoin::Amount::from_sat(10_000),
+                bitcoin::EcdsaSighashType::All,
+            )
+            .unwrap();
+        let preimage = child_tx.build_for_signing_segwit(
+            EcdsaSighashType::All,
+            1,
+            &utxo.script_code(),
+            utxo.value.to_sat(),
+        );
+        assert_eq!(
+            Sha256::digest(Sha256::digest(&preimage)).as_slice(),
+            expected.as_byte_array()
+        );
+    }
+
+    #[test]
+    fn test_change_output_without_utxo() {
+        let p2wpkh = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let input = TxIn::from(&Utxo::new(
+            Txid(Hash::all_zeros()),
+            0,
+            Amount::from_sat(100_000),
+            p2wpkh.clone(),
+        ));
+        let builder = BitcoinTransactionBuilder::new()
+            .version(Version::Two)
+            .lock_time(LockTime::from_height(0).unwrap())
+            .inputs(vec![input.clone()])
+            .outputs(vec![])
+            .fee_rate(2)
+            .change_script_pubkey(p2wpkh);
+
+        assert_eq!(
+            builder.try_build(),
+            Err(ValidationError::MissingUtxo(input.previous_output))
+        );
+    }
+
     #[test]
     fn test_sighash() {
         let block_height = 10000;
@@ -0,0 +1,462 @@
+//! Hash time locked contracts (HTLCs), the outputs atomic swaps are built on.
+//!
+//! The receiver claims the output by revealing the preimage of the payment hash, or the sender
+//! gets a refund once the timeout is reached. The contract is available as a P2WSH output with
+//! both branches in a single script, and as a P2TR output with one tapscript leaf per branch
+//! and no key path spend.
+//!
+//! ###### Example:
+//!
+//! ```rust
+//! use signet_rs::bitcoin::htlc::Htlc;
+//! use signet_rs::bitcoin::keys::PrivateKey;
+//! use signet_rs::bitcoin::types::{LockTime, Network};
+//!
+//! let receiver = PrivateKey::from_slice(&[1; 32], Network::Bitcoin, true).unwrap().public_key();
+//! let sender = PrivateKey::from_slice(&[2; 32], Network::Bitcoin, true).unwrap().public_key();
+//!
+//! let htlc = Htlc::new([0xab; 32], receiver, sender, LockTime::from_height(840_000).unwrap());
+//! let p2wsh_address = htlc.address(Network::Bitcoin).unwrap();
+//! let p2tr_address = htlc.taproot_address(Network::Bitcoin).unwrap();
+//! ```
+use super::{
+    address::Address,
+    keys::PublicKey,
+    script::{
+        p2wsh, Builder, OP_CHECKLOCKTIMEVERIFY, OP_CHECKSIG, OP_DROP, OP_ELSE, OP_ENDIF,
+        OP_EQUALVERIFY, OP_IF, OP_SHA256, OP_SIZE,
+    },
+    taproot::{self, OutputKey, NUMS_INTERNAL_KEY},
+    types::{
+        EcdsaSighashType, LockTime, Network, OutPoint, ScriptBuf, Sequence, TxIn, TxOut, Witness,
+    },
+    BitcoinTransaction,
+};
+
+/// Size of the preimage, checked by the script so that it is also valid on chains limiting the
+/// size of the items they hash.
+pub const PREIMAGE_SIZE: i64 = 32;
+
+/// The branch spending an HTLC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HtlcPath {
+    /// The receiver reveals the preimage.
+    Claim,
+    /// The sender takes the funds back after the timeout.
+    Refund,
+}
+
+/// A hash time locked contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Htlc {
+    /// SHA-256 of the preimage revealed by the receiver.
+    pub payment_hash: [u8; 32],
+    /// Key of the receiver, who can claim the output with the preimage.
+    pub receiver: PublicKey,
+    /// Key of the sender, who can get a refund after the timeout.
+    pub sender: PublicKey,
+    /// Lock time, height or timestamp, after which the sender can get a refund.
+    pub timeout: LockTime,
+}
+
+impl Htlc {
+    pub const fn new(
+        payment_hash: [u8; 32],
+        receiver: PublicKey,
+        sender: PublicKey,
+        timeout: LockTime,
+    ) -> Self {
+        Self {
+            payment_hash,
+            receiver,
+            sender,
+            timeout,
+        }
+    }
+
+    /// An input spending the output through the given path. Refunds have a sequence enabling
+    /// the lock time check, and the spending transaction must have a lock time of at least
+    /// [`Htlc::timeout`].
+    pub fn spend_input(&self, previous_output: OutPoint, path: HtlcPath) -> TxIn {
+        TxIn {
+            previous_output,
+            script_sig: ScriptBuf::default(),
+            sequence: match path {
+                HtlcPath::Claim => Sequence::ENABLE_RBF_NO_LOCKTIME,
+                HtlcPath::Refund => Sequence::ENABLE_LOCKTIME_NO_RBF,
+            },
+            witness: Witness::default(),
+        }
+    }
+
+    /// The witness script of the P2WSH output, also the script code to sign its spends with:
+    ///
+    /// ```text
+    /// OP_IF
+    ///     OP_SIZE 32 OP_EQUALVERIFY OP_SHA256 <payment hash> OP_EQUALVERIFY <receiver>
+    /// OP_ELSE
+    ///     <timeout> OP_CHECKLOCKTIMEVERIFY OP_DROP <sender>
+    /// OP_ENDIF
+    /// OP_CHECKSIG
+    /// ```
+    pub fn witness_script(&self) -> ScriptBuf {
+        self.push_hashlock(Builder::new().push_opcode(OP_IF))
+            .push_key(&self.receiver)
+            .push_opcode(OP_ELSE)
+            .push_lock_time(self.timeout)
+            .push_opcode(OP_CHECKLOCKTIMEVERIFY)
+            .push_opcode(OP_DROP)
+            .push_key(&self.sender)
+            .push_opcode(OP_ENDIF)
+            .push_opcode(OP_CHECKSIG)
+            .into_script()
+    }
+
+    /// The script pubkey of the P2WSH output.
+    pub fn script_pubkey(&self) -> ScriptBuf {
+        p2wsh(&self.witness_script())
+    }
+
+    /// The address of the P2WSH output.
+    pub fn address(&self, network: Network) -> Result<Address, String> {
+        Address::from_script(&self.script_pubkey(), network)
+    }
+
+    /// Encode the transaction for signing the given input spending the P2WSH output of the
+    /// given value.
+    pub fn build_for_signing_segwit(
+        &self,
+        tx: &BitcoinTransaction,
+        input_index: usize,
+        value: u64,
+    ) -> Vec<u8> {
+        tx.build_for_signing_segwit(
+            EcdsaSighashType::All,
+            input_index,
+            &self.witness_script(),
+            value,
+        )
+    }
+
+    /// The witness claiming the P2WSH output, from a DER signature of the receiver followed by
+    /// its sighash type.
+    pub fn claim_witness(&self, signature: &[u8], preimage: &[u8; 32]) -> Witness {
+        Witness::from_slice(&[
+            signature.to_vec(),
+            preimage.to_vec(),
+            vec![0x01],
+            self.witness_script().0,
+        ])
+    }
+
+    /// The witness refunding the P2WSH output, from a DER signature of the sender followed by
+    /// its sighash type.
+    pub fn refund_witness(&self, signature: &[u8]) -> Witness {
+        Witness::from_slice(&[signature.to_vec(), vec![], self.witness_script().0])
+    }
+
+    /// The tapscript leaf of the given path:
+    ///
+    /// ```text
+    /// OP_SIZE 32 OP_EQUALVERIFY OP_SHA256 <payment hash> OP_EQUALVERIFY <receiver> OP_CHECKSIG
+    /// <timeout> OP_CHECKLOCKTIMEVERIFY OP_DROP <sender> OP_CHECKSIG
+    /// ```
+    pub fn leaf(&self, path: HtlcPath) -> ScriptBuf {
+        let builder = match path {
+            HtlcPath::Claim => self
+                .push_hashlock(Builder::new())
+                .push_slice(&self.receiver.x_only()),
+            HtlcPath::Refund => Builder::new()
+                .push_lock_time(self.timeout)
+                .push_opcode(OP_CHECKLOCKTIMEVERIFY)
+                .push_opcode(OP_DROP)
+                .push_slice(&self.sender.x_only()),
+        };
+
+        builder.push_opcode(OP_CHECKSIG).into_script()
+    }
+
+    /// The output key of the P2TR output, whose internal key is [`NUMS_INTERNAL_KEY`].
+    pub fn taproot_output_key(&self) -> Result<OutputKey, String> {
+        let merkle_root = taproot::branch_hash(
+            &taproot::leaf_hash(&self.leaf(HtlcPath::Claim)),
+            &taproot::leaf_hash(&self.leaf(HtlcPath::Refund)),
+        );
+
+        OutputKey::new(&NUMS_INTERNAL_KEY, Some(&merkle_root))
+    }
+
+    /// The script pubkey of the P2TR output.
+    pub fn taproot_script_pubkey(&self) -> Result<ScriptBuf, String> {
+        Ok(self.taproot_output_key()?.script_pubkey())
+    }
+
+    /// The address of the P2TR output.
+    pub fn taproot_address(&self, network: Network) -> Result<Address, String> {
+        self.taproot_output_key()?.address(network)
+    }
+
+    /// Encode the transaction for signing the given input spending the P2TR output through the
+    /// given path. The message to sign is the [`taproot::sighash`] of the returned bytes.
+    pub fn build_for_signing_taproot(
+        &self,
+        tx: &BitcoinTransaction,
+        input_index: usize,
+        prevouts: &[TxOut],
+        path: HtlcPath,
+    ) -> Vec<u8> {
+        let leaf_hash = taproot::leaf_hash(&self.leaf(path));
+
+        tx.build_for_signing_taproot(input_index, prevouts, Some(&leaf_hash))
+    }
+
+    /// The witness claiming the P2TR output, from a Schnorr signature of the receiver.
+    pub fn taproot_claim_witness(
+        &self,
+        signature: &[u8],
+        preimage: &[u8; 32],
+    ) -> Result<Witness, String> {
+        let mut witness = vec![signature.to_vec(), preimage.to_vec()];
+        witness.extend(self.taproot_script_path(HtlcPath::Claim)?);
+
+        Ok(Witness::from_slice(&witness))
+    }
+
+    /// The witness refunding the P2TR output, from a Schnorr signature of the sender.
+    pub fn taproot_refund_witness(&self, signature: &[u8]) -> Result<Witness, String> {
+        let mut witness = vec![signature.to_vec()];
+        witness.extend(self.taproot_script_path(HtlcPath::Refund)?);
+
+        Ok(Witness::from_slice(&witness))
+    }
+
+    /// The leaf of the path and its control block, ending the witness of script path spends.
+    fn taproot_script_path(&self, path: HtlcPath) -> Result<[Vec<u8>; 2], String> {
+        let sibling = match path {
+            HtlcPath::Claim => HtlcPath::Refund,
+            HtlcPath::Refund => HtlcPath::Claim,
+        };
+        let control_block = taproot::control_block(
+            &NUMS_INTERNAL_KEY,
+            &self.taproot_output_key()?,
+            &[taproot::leaf_hash(&self.leaf(sibling))],
+        );
+
+        Ok([self.leaf(path).0, control_block])
+    }
+
+    /// `OP_SIZE 32 OP_EQUALVERIFY OP_SHA256 <payment hash> OP_EQUALVERIFY`
+    fn push_hashlock(&self, builder: Builder) -> Builder {
+        builder
+            .push_opcode(OP_SIZE)
+            .push_int(PREIMAGE_SIZE)
+            .push_opcode(OP_EQUALVERIFY)
+            .push_opcode(OP_SHA256)
+            .push_slice(&self.payment_hash)
+            .push_opcode(OP_EQUALVERIFY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::keys::PrivateKey;
+    use crate::bitcoin::types::{Amount, Hash, Txid, Version};
+    use bitcoin::hashes::{sha256d, Hash as _};
+    use bitcoin::key::{Secp256k1, XOnlyPublicKey};
+    use bitcoin::opcodes::all::{
+        OP_CHECKSIG as RUST_BITCOIN_OP_CHECKSIG, OP_CLTV, OP_DROP as RUST_BITCOIN_OP_DROP,
+        OP_ELSE as RUST_BITCOIN_OP_ELSE, OP_ENDIF as RUST_BITCOIN_OP_ENDIF,
+        OP_EQUALVERIFY as RUST_BITCOIN_OP_EQUALVERIFY, OP_IF as RUST_BITCOIN_OP_IF,
+        OP_SHA256 as RUST_BITCOIN_OP_SHA256, OP_SIZE as RUST_BITCOIN_OP_SIZE,
+    };
+    use bitcoin::script::Builder as RustBitcoinBuilder;
+    use bitcoin::sighash::{EcdsaSighashType as RustBitcoinSighashType, Prevouts, SighashCache};
+    use bitcoin::taproot::{LeafVersion, TapLeafHash, TaprootBuilder};
+    use bitcoin::TapSighashType;
+
+    fn htlc() -> Htlc {
+        let receiver = PrivateKey::from_slice(&[1; 32], Network::Bitcoin, true)
+            .unwrap()
+            .public_key();
+        let sender = PrivateKey::from_slice(&[2; 32], Network::Bitcoin, true)
+            .unwrap()
+            .public_key();
+
+        Htlc::new(
+            [0xab; 32],
+            receiver,
+            sender,
+            LockTime::from_height(840_000).unwrap(),
+        )
+    }
+
+    fn spending_tx(htlc: &Htlc, path: HtlcPath) -> BitcoinTransaction {
+        BitcoinTransaction {
+            version: Version::Two,
+            lock_time: htlc.timeout,
+            input: vec![htlc.spend_input(OutPoint::new(Txid(Hash::all_zeros()), 0), path)],
+            output: vec![TxOut {
+                value: Amount::from_sat(99_000),
+                script_pubkey: htlc
+                    .sender
+                    .p2wpkh_address(Network::Bitcoin)
+                    .unwrap()
+                    .script_pubkey(),
+            }],
+        }
+    }
+
+    fn rust_bitcoin_key(public_key: &PublicKey) -> bitcoin::PublicKey {
+        bitcoin::PublicKey::from_slice(&public_key.to_bytes()).unwrap()
+    }
+
+    fn rust_bitcoin_hashlock(builder: RustBitcoinBuilder, htlc: &Htlc) -> RustBitcoinBuilder {
+        builder
+            .push_opcode(RUST_BITCOIN_OP_SIZE)
+            .push_int(32)
+            .push_opcode(RUST_BITCOIN_OP_EQUALVERIFY)
+            .push_opcode(RUST_BITCOIN_OP_SHA256)
+            .push_slice(htlc.payment_hash)
+            .push_opcode(RUST_BITCOIN_OP_EQUALVERIFY)
+    }
+
+    #[test]
+    fn test_p2wsh() {
+        let htlc = htlc();
+        let rust_bitcoin_script = rust_bitcoin_hashlock(
+            RustBitcoinBuilder::new().push_opcode(RUST_BITCOIN_OP_IF),
+            &htlc,
+        )
+        .push_key(&rust_bitcoin_key(&htlc.receiver))
+        .push_opcode(RUST_BITCOIN_OP_ELSE)
+        .push_lock_time(bitcoin::absolute::LockTime::from_consensus(840_000))
+        .push_opcode(OP_CLTV)
+        .push_opcode(RUST_BITCOIN_OP_DROP)
+        .push_key(&rust_bitcoin_key(&htlc.sender))
+        .push_opcode(RUST_BITCOIN_OP_ENDIF)
+        .push_opcode(RUST_BITCOIN_OP_CHECKSIG)
+        .into_script();
+
+        assert_eq!(htlc.witness_script().0, rust_bitcoin_script.to_bytes());
+        assert_eq!(
+            htlc.address(Network::Bitcoin).unwrap().to_string(),
+            bitcoin::Address::p2wsh(&rust_bitcoin_script, bitcoin::Network::Bitcoin).to_string()
+        );
+
+        let tx = spending_tx(&htlc, HtlcPath::Refund);
+        let rust_bitcoin_tx: bitcoin::Transaction =
+            bitcoin::consensus::deserialize(&tx.serialize()).unwrap();
+        let sighash = SighashCache::new(&rust_bitcoin_tx)
+            .p2wsh_signature_hash(
+                0,
+                &rust_bitcoin_script,
+                bitcoin::Amount::from_sat(100_000),
+                RustBitcoinSighashType::All,
+            )
+            .unwrap();
+        assert_eq!(
+            sha256d::Hash::hash(&htlc.build_for_signing_segwit(&tx, 0, 100_000)).to_byte_array(),
+            sighash.to_byte_array()
+        );
+
+        assert_eq!(
+            htlc.claim_witness(&[0x30], &[0x01; 32]).to_vec(),
+            vec![
+                vec![0x30],
+                vec![0x01; 32],
+                vec![0x01],
+                htlc.witness_script().0
+            ]
+        );
+        assert_eq!(
+            htlc.refund_witness(&[0x30]).to_vec(),
+            vec![vec![0x30], vec![], htlc.witness_script().0]
+        );
+    }
+
+    #[test]
+    fn test_taproot() {
+        let secp = Secp256k1::verification_only();
+        let htlc = htlc();
+        let claim_leaf = rust_bitcoin_hashlock(RustBitcoinBuilder::new(), &htlc)
+            .push_x_only_key(&XOnlyPublicKey::from_slice(&htlc.receiver.x_only()).unwrap())
+            .push_opcode(RUST_BITCOIN_OP_CHECKSIG)
+            .into_script();
+        let refund_leaf = RustBitcoinBuilder::new()
+            .push_lock_time(bitcoin::absolute::LockTime::from_consensus(840_000))
+            .push_opcode(OP_CLTV)
+            .push_opcode(RUST_BITCOIN_OP_DROP)
+            .push_x_only_key(&XOnlyPublicKey::from_slice(&htlc.sender.x_only()).unwrap())
+            .push_opcode(RUST_BITCOIN_OP_CHECKSIG)
+            .into_script();
+        let spend_info = TaprootBuilder::new()
+            .add_leaf(1, claim_leaf.clone())
+            .unwrap()
+            .add_leaf(1, refund_leaf.clone())
+            .unwrap()
+            .finalize(
+                &secp,
+                XOnlyPublicKey::from_slice(&NUMS_INTERNAL_KEY).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(htlc.leaf(HtlcPath::Claim).0, claim_leaf.to_bytes());
+        assert_eq!(htlc.leaf(HtlcPath::Refund).0, refund_leaf.to_bytes());
+        assert_eq!(
+            htlc.taproot_address(Network::Bitcoin).unwrap().to_string(),
+            bitcoin::Address::p2tr_tweaked(spend_info.output_key(), bitcoin::Network::Bitcoin)
+                .to_string()
+        );
+
+        let prevouts = vec![TxOut {
+            value: Amount::from_sat(100_000),
+            script_pubkey: htlc.taproot_script_pubkey().unwrap(),
+        }];
+        let tx = spending_tx(&htlc, HtlcPath::Claim);
+        let rust_bitcoin_tx: bitcoin::Transaction =
+            bitcoin::consensus::deserialize(&tx.serialize()).unwrap();
+        let rust_bitcoin_prevouts = [bitcoin::TxOut {
+            value: bitcoin::Amount::from_sat(100_000),
+            script_pubkey: bitcoin::ScriptBuf::from_bytes(prevouts[0].script_pubkey.0.clone()),
+        }];
+        let sighash = SighashCache::new(&rust_bitcoin_tx)
+            .taproot_script_spend_signature_hash(
+                0,
+                &Prevouts::All(&rust_bitcoin_prevouts),
+                TapLeafHash::from_script(&claim_leaf, LeafVersion::TapScript),
+                TapSighashType::Default,
+            )
+            .unwrap();
+        assert_eq!(
+            taproot::sighash(&htlc.build_for_signing_taproot(&tx, 0, &prevouts, HtlcPath::Claim)),
+            sighash.to_byte_array()
+        );
+
+        let witness = htlc
+            .taproot_claim_witness(&[0x01; 64], &[0x02; 32])
+            .unwrap()
+            .to_vec();
+        assert_eq!(
+            witness,
+            vec![
+                vec![0x01; 64],
+                vec![0x02; 32],
+                claim_leaf.to_bytes(),
+                spend_info
+                    .control_block(&(claim_leaf, LeafVersion::TapScript))
+                    .unwrap()
+                    .serialize()
+            ]
+        );
+
+        let witness = htlc.taproot_refund_witness(&[0x01; 64]).unwrap().to_vec();
+        assert_eq!(
+            witness[2],
+            spend_info
+                .control_block(&(refund_leaf, LeafVersion::TapScript))
+                .unwrap()
+                .serialize()
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Conversions between the Bitcoin types of this crate and the ones of
+//! [rust-bitcoin](https://github.com/rust-bitcoin/rust-bitcoin), enabled by the `rust-bitcoin`
+//! feature.
+//!
+//! Amounts, sequences, lock times and versions are converted through their integer values, and
+//! hashes, scripts and witnesses through their bytes, so these conversions are lossless, unlike
+//! going through the BTC `f64` or string forms of amounts. Only the transaction version can fail
+//! to convert, as this crate only supports versions 0 to 3.
+use ::bitcoin::hashes::Hash as _;
+
+use super::{
+    types::{
+        Amount, Hash, LockTime, OutPoint, ScriptBuf, Sequence, TxIn, TxOut, Txid, Version, Witness,
+    },
+    BitcoinTransaction,
+};
+
+impl From<Txid> for ::bitcoin::Txid {
+    fn from(txid: Txid) -> Self {
+        // Our hashes are stored in display order, rust-bitcoin ones in internal byte order
+        let mut bytes = txid.as_byte_array();
+        bytes.reverse();
+        Self::from_byte_array(bytes)
+    }
+}
+
+impl From<::bitcoin::Txid> for Txid {
+    fn from(txid: ::bitcoin::Txid) -> Self {
+        let mut bytes = txid.to_byte_array();
+        bytes.reverse();
+        Self(Hash(bytes))
+    }
+}
+
+impl From<OutPoint> for ::bitcoin::OutPoint {
+    fn from(outpoint: OutPoint) -> Self {
+        Self {
+            txid: outpoint.txid.into(),
+            vout: outpoint.vout,
+        }
+    }
+}
+
+impl From<::bitcoin::OutPoint> for OutPoint {
+    fn from(outpoint: ::bitcoin::OutPoint) -> Self {
+        Self::new(outpoint.txid.into(), outpoint.vout)
+    }
+}
+
+impl From<ScriptBuf> for ::bitcoin::ScriptBuf {
+    fn from(script: ScriptBuf) -> Self {
+        Self::from_bytes(script.0)
+    }
+}
+
+impl From<::bitcoin::ScriptBuf> for ScriptBuf {
+    fn from(script: ::bitcoin::ScriptBuf) -> Self {
+        Self(script.into_bytes())
+    }
+}
+
+impl From<Amount> for ::bitcoin::Amount {
+    fn from(amount: Amount) -> Self {
+        Self::from_sat(amount.to_sat())
+    }
+}
+
+impl From<::bitcoin::Amount> for Amount {
+    fn from(amount: ::bitcoin::Amount) -> Self {
+        Self::from_sat(amount.to_sat())
+    }
+}
+
+impl From<Sequence> for ::bitcoin::Sequence {
+    fn from(sequence: Sequence) -> Self {
+        Self(sequence.0)
+    }
+}
+
+impl From<::bitcoin::Sequence> for Sequence {
+    fn from(sequence: ::bitcoin::Sequence) -> Self {
+        Self(sequence.0)
+    }
+}
+
+impl From<Witness> for ::bitcoin::Witness {
+    fn from(witness: Witness) -> Self {
+        Self::from_slice(&witness.to_vec())
+    }
+}
+
+impl From<::bitcoin::Witness> for Witness {
+    fn from(witness: ::bitcoin::Witness) -> Self {
+        Self::from_slice(&witness.to_vec())
+    }
+}
+
+impl From<LockTime> for ::bitcoin::absolute::LockTime {
+    fn from(lock_time: LockTime) -> Self {
+        Self::from_consensus(lock_time.to_u32())
+    }
+}
+
+impl From<::bitcoin::absolute::LockTime> for LockTime {
+    fn from(lock_time: ::bitcoin::absolute::LockTime) -> Self {
+        Self::from_consensus(lock_time.to_consensus_u32())
+    }
+}
+
+impl From<Version> for ::bitcoin::transaction::Version {
+    fn from(version: Version) -> Self {
+        Self(version as i32)
+    }
+}
+
+impl TryFrom<::bitcoin::transaction::Version> for Version {
+    type Error = String;
+
+    fn try_from(version: ::bitcoin::transaction::Version) -> Result<Self, Self::Error> {
+        match version.0 {
+            0 => Ok(Self::Zero),
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            3 => Ok(Self::Three),
+            n => Err(format!("Unsupported transaction version: {n}")),
+        }
+    }
+}
+
+impl From<TxIn> for ::bitcoin::TxIn {
+    fn from(input: TxIn) -> Self {
+        Self {
+            previous_output: input.previous_output.into(),
+            script_sig: input.script_sig.into(),
+            sequence: input.sequence.into(),
+            witness: input.witness.into(),
+        }
+    }
+}
+
+impl From<::bitcoin::TxIn> for TxIn {
+    fn from(input: ::bitcoin::TxIn) -> Self {
+        Self {
+            previous_output: input.previous_output.into(),
+            script_sig: input.script_sig.into(),
+            sequence: input.sequence.into(),
+            witness: input.witness.into(),
+        }
+    }
+}
+
+impl From<TxOut> for ::bitcoin::TxOut {
+    fn from(output: TxOut) -> Self {
+        Self {
+            value: output.value.into(),
+            script_pubkey: output.script_pubkey.into(),
+        }
+    }
+}
+
+impl From<::bitcoin::TxOut> for TxOut {
+    fn from(output: ::bitcoin::TxOut) -> Self {
+        Self {
+            value: output.value.into(),
+            script_pubkey: output.script_pubkey.into(),
+        }
+    }
+}
+
+impl From<BitcoinTransaction> for ::bitcoin::Transaction {
+    fn from(tx: BitcoinTransaction) -> Self {
+        Self {
+            version: tx.version.into(),
+            lock_time: tx.lock_time.into(),
+            input: tx.input.into_iter().map(Into::into).collect(),
+            output: tx.output.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<::bitcoin::Transaction> for BitcoinTransaction {
+    type Error = String;
+
+    fn try_from(tx: ::bitcoin::Transaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            version: tx.version.try_into()?,
+            lock_time: tx.lock_time.into(),
+            input: tx.input.into_iter().map(Into::into).collect(),
+            output: tx.output.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_round_trip() {
+        let tx = BitcoinTransaction {
+            version: Version::Two,
+            lock_time: LockTime::from_height(840_000).unwrap(),
+            input: vec![TxIn {
+                previous_output: OutPoint::new(
+                    Txid(
+                        Hash::from_hex(
+                            "bc25cc0dddd0a202c21e66521a692c0586330a9a9dcc38ccd9b4d2093037f31a",
+                        )
+                        .unwrap(),
+                    ),
+                    1,
+                ),
+                script_sig: ScriptBuf::default(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::from_slice(&[vec![0x30, 0x44], vec![0x02; 33]]),
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(40_000),
+                script_pubkey: ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1")
+                    .unwrap(),
+            }],
+        };
+
+        let rust_bitcoin_tx = ::bitcoin::Transaction::from(tx.clone());
+
+        assert_eq!(
+            ::bitcoin::consensus::serialize(&rust_bitcoin_tx),
+            tx.serialize()
+        );
+        assert_eq!(
+            rust_bitcoin_tx.input[0].previous_output.txid.to_string(),
+            tx.input[0].previous_output.txid.to_string()
+        );
+        assert_eq!(
+            rust_bitcoin_tx.compute_txid().to_string(),
+            tx.txid().to_string()
+        );
+        assert_eq!(BitcoinTransaction::try_from(rust_bitcoin_tx), Ok(tx));
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let rust_bitcoin_tx = ::bitcoin::Transaction {
+            version: ::bitcoin::transaction::Version(4),
+            lock_time: ::bitcoin::absolute::LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+
+        assert!(BitcoinTransaction::try_from(rust_bitcoin_tx).is_err());
+    }
+}
@@ -0,0 +1,450 @@
+//! Private keys in Wallet Import Format (WIF) and BIP-32 extended public keys.
+//!
+//! Extended public keys only support non-hardened derivation, which is what watch-only
+//! wallets need to derive the addresses of a given account.
+use core::fmt;
+
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::{sec1::ToEncodedPoint, PrimeField};
+use sha2::Sha512;
+
+use super::{address::Address, taproot::OutputKey, types::Network, utils::hash160};
+
+/// The first hardened child index (BIP-32).
+pub const HARDENED_INDEX: u32 = 0x8000_0000;
+
+/// The length of a serialized extended key (BIP-32).
+const EXTENDED_KEY_LENGTH: usize = 78;
+
+/// Suffix of the WIF payload when the public key is compressed.
+const WIF_COMPRESSED_FLAG: u8 = 0x01;
+
+/// A secp256k1 public key, in compressed or uncompressed form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey {
+    inner: k256::PublicKey,
+    compressed: bool,
+}
+
+impl PublicKey {
+    /// Parses a SEC1 encoded public key, keeping its compression.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        let inner = k256::PublicKey::from_sec1_bytes(bytes)
+            .map_err(|_| "Invalid public key".to_string())?;
+
+        Ok(Self {
+            inner,
+            compressed: bytes.len() == 33,
+        })
+    }
+
+    /// Returns whether the public key is serialized in compressed form.
+    pub const fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// SEC1 encoding of the public key, 33 bytes if compressed and 65 bytes otherwise.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner
+            .to_encoded_point(self.compressed)
+            .as_bytes()
+            .to_vec()
+    }
+
+    /// The compressed SEC1 encoding of the public key, whatever its compression.
+    pub fn compressed_bytes(&self) -> [u8; 33] {
+        self.inner
+            .to_encoded_point(true)
+            .as_bytes()
+            .try_into()
+            .expect("compressed points are 33 bytes")
+    }
+
+    /// The x coordinate of the public key, which is how BIP-340 Schnorr signatures and taproot
+    /// commit to keys.
+    pub fn x_only(&self) -> [u8; 32] {
+        self.inner.to_encoded_point(true).as_bytes()[1..]
+            .try_into()
+            .expect("compressed points are 33 bytes")
+    }
+
+    /// The hash160 of the serialized public key.
+    pub fn pubkey_hash(&self) -> [u8; 20] {
+        hash160(&self.to_bytes())
+    }
+
+    /// The P2PKH address of the public key.
+    pub fn p2pkh_address(&self, network: Network) -> Address {
+        Address::p2pkh(self.pubkey_hash(), network)
+    }
+
+    /// The P2WPKH address of the public key, which must be compressed.
+    pub fn p2wpkh_address(&self, network: Network) -> Result<Address, String> {
+        if !self.compressed {
+            return Err("P2WPKH requires a compressed public key".to_string());
+        }
+
+        Address::from_witness_program(0, self.pubkey_hash().to_vec(), network)
+    }
+
+    /// The P2TR address only spendable by the key path of the public key, see
+    /// [`super::script::p2tr_script`].
+    pub fn p2tr_address(&self, network: Network) -> Result<Address, String> {
+        OutputKey::new(&self.x_only(), None)?.address(network)
+    }
+}
+
+/// A secp256k1 private key, along with the network and key compression of its WIF encoding.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::bitcoin::keys::PrivateKey;
+/// use signet_rs::bitcoin::types::Network;
+///
+/// let private_key = PrivateKey::from_wif(
+///     "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617",
+///     Network::Bitcoin,
+/// )
+/// .unwrap();
+/// let address = private_key.public_key().p2wpkh_address(Network::Bitcoin).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateKey {
+    inner: k256::SecretKey,
+    compressed: bool,
+    network: Network,
+}
+
+impl PrivateKey {
+    /// Creates a private key from its 32 bytes, which must be a valid secp256k1 scalar.
+    pub fn from_slice(bytes: &[u8], network: Network, compressed: bool) -> Result<Self, String> {
+        let inner =
+            k256::SecretKey::from_slice(bytes).map_err(|_| "Invalid private key".to_string())?;
+
+        Ok(Self {
+            inner,
+            compressed,
+            network,
+        })
+    }
+
+    /// Decodes a WIF private key, checking that it belongs to the given network.
+    pub fn from_wif(wif: &str, network: Network) -> Result<Self, String> {
+        let data = bs58::decode(wif)
+            .with_check(None)
+            .into_vec()
+            .map_err(|e| format!("Invalid WIF: {e}"))?;
+
+        let compressed = match data.len() {
+            33 => false,
+            34 if data[33] == WIF_COMPRESSED_FLAG => true,
+            _ => return Err("Invalid WIF length".to_string()),
+        };
+        if data[0] != network.wif_prefix() {
+            return Err(format!("WIF does not belong to network {network}"));
+        }
+
+        Self::from_slice(&data[1..33], network, compressed)
+    }
+
+    /// Encodes the private key in Wallet Import Format.
+    pub fn to_wif(&self) -> String {
+        let mut data = vec![self.network.wif_prefix()];
+        data.extend_from_slice(&self.inner.to_bytes());
+        if self.compressed {
+            data.push(WIF_COMPRESSED_FLAG);
+        }
+
+        bs58::encode(data).with_check().into_string()
+    }
+
+    /// The 32 bytes of the private key.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.inner.to_bytes().into()
+    }
+
+    /// The public key of the private key, with the same compression.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            inner: self.inner.public_key(),
+            compressed: self.compressed,
+        }
+    }
+
+    pub const fn network(&self) -> Network {
+        self.network
+    }
+
+    pub const fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+}
+
+/// A BIP-32 extended public key, from which the public keys of non-hardened children
+/// can be derived.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::bitcoin::keys::ExtendedPublicKey;
+/// use signet_rs::bitcoin::types::Network;
+///
+/// let xpub = ExtendedPublicKey::parse(
+///     "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+///     Network::Bitcoin,
+/// )
+/// .unwrap();
+/// let receive_address = xpub
+///     .derive_path(&[0, 0])
+///     .unwrap()
+///     .public_key()
+///     .p2wpkh_address(Network::Bitcoin)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    network: Network,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+    public_key: PublicKey,
+}
+
+impl ExtendedPublicKey {
+    /// Parses a base58 encoded extended public key, checking that it belongs to the given network.
+    pub fn parse(xpub: &str, network: Network) -> Result<Self, String> {
+        let data = bs58::decode(xpub)
+            .with_check(None)
+            .into_vec()
+            .map_err(|e| format!("Invalid extended public key: {e}"))?;
+
+        if data.len() != EXTENDED_KEY_LENGTH {
+            return Err("Invalid extended public key length".to_string());
+        }
+        if data[..4] != network.xpub_prefix() {
+            return Err(format!(
+                "Extended public key does not belong to network {network}"
+            ));
+        }
+
+        Ok(Self {
+            network,
+            depth: data[4],
+            parent_fingerprint: data[5..9].try_into().unwrap(),
+            child_number: u32::from_be_bytes(data[9..13].try_into().unwrap()),
+            chain_code: data[13..45].try_into().unwrap(),
+            public_key: PublicKey::from_slice(&data[45..78])?,
+        })
+    }
+
+    /// Derives the extended public key of a non-hardened child.
+    pub fn derive_child(&self, index: u32) -> Result<Self, String> {
+        if index >= HARDENED_INDEX {
+            return Err("Hardened children cannot be derived from a public key".to_string());
+        }
+        if self.depth == u8::MAX {
+            return Err("Maximum derivation depth exceeded".to_string());
+        }
+
+        let mut mac = Hmac::<Sha512>::new_from_slice(&self.chain_code)
+            .expect("HMAC can take keys of any size");
+        mac.update(&self.public_key.to_bytes());
+        mac.update(&index.to_be_bytes());
+        let result = mac.finalize().into_bytes();
+        let (tweak, chain_code) = result.split_at(32);
+
+        let tweak: Option<k256::Scalar> =
+            k256::Scalar::from_repr(*k256::FieldBytes::from_slice(tweak)).into();
+        let tweak = tweak.ok_or("Invalid child key, proceed with the next index")?;
+
+        let point =
+            k256::ProjectivePoint::GENERATOR * tweak + self.public_key.inner.to_projective();
+        let inner = k256::PublicKey::from_affine(point.to_affine())
+            .map_err(|_| "Invalid child key, proceed with the next index".to_string())?;
+
+        Ok(Self {
+            network: self.network,
+            depth: self.depth + 1,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code: chain_code.try_into().unwrap(),
+            public_key: PublicKey {
+                inner,
+                compressed: true,
+            },
+        })
+    }
+
+    /// Derives the extended public key at the given path of non-hardened indexes.
+    pub fn derive_path(&self, path: &[u32]) -> Result<Self, String> {
+        path.iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    /// The first four bytes of the hash160 of the public key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.public_key.pubkey_hash()[..4].try_into().unwrap()
+    }
+
+    pub const fn network(&self) -> Network {
+        self.network
+    }
+
+    pub const fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub const fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    pub const fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    pub const fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+
+    pub const fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+}
+
+impl fmt::Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut data = Vec::with_capacity(EXTENDED_KEY_LENGTH);
+        data.extend_from_slice(&self.network.xpub_prefix());
+        data.push(self.depth);
+        data.extend_from_slice(&self.parent_fingerprint);
+        data.extend_from_slice(&self.child_number.to_be_bytes());
+        data.extend_from_slice(&self.chain_code);
+        data.extend_from_slice(&self.public_key.to_bytes());
+
+        f.write_str(&bs58::encode(data).with_check().into_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    // BIP-32 test vector 1
+    const MASTER_XPUB: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+    const XPUB_0H: &str = "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw";
+    const XPUB_0H_1: &str = "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ";
+
+    #[test]
+    fn test_wif_round_trip() {
+        let secret =
+            hex::decode("0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d")
+                .unwrap();
+
+        let uncompressed = PrivateKey::from_slice(&secret, Network::Bitcoin, false).unwrap();
+        assert_eq!(
+            uncompressed.to_wif(),
+            "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ"
+        );
+
+        let compressed = PrivateKey::from_slice(&secret, Network::Bitcoin, true).unwrap();
+        assert_eq!(
+            compressed.to_wif(),
+            "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617"
+        );
+
+        let decoded = PrivateKey::from_wif(&compressed.to_wif(), Network::Bitcoin).unwrap();
+        assert_eq!(decoded, compressed);
+        assert_eq!(decoded.to_bytes().to_vec(), secret);
+    }
+
+    #[test]
+    fn test_wif_against_rust_bitcoin() {
+        let rust_bitcoin_key =
+            bitcoin::PrivateKey::from_wif("cVt4o7BGAig1UXywgGSmARhxMdzP5qvQsxKkSsc1XEkw3tDTQFpy")
+                .unwrap();
+        let key = PrivateKey::from_wif(
+            "cVt4o7BGAig1UXywgGSmARhxMdzP5qvQsxKkSsc1XEkw3tDTQFpy",
+            Network::Testnet,
+        )
+        .unwrap();
+
+        assert_eq!(key.to_bytes(), rust_bitcoin_key.inner.secret_bytes());
+        assert_eq!(
+            key.public_key().to_bytes(),
+            rust_bitcoin_key
+                .public_key(&bitcoin::secp256k1::Secp256k1::new())
+                .to_bytes()
+        );
+        assert_eq!(key.to_wif(), rust_bitcoin_key.to_wif());
+    }
+
+    #[test]
+    fn test_wif_wrong_network() {
+        assert!(PrivateKey::from_wif(
+            "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617",
+            Network::Testnet
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_public_key_addresses() {
+        let key = PrivateKey::from_slice(&[1; 32], Network::Bitcoin, true).unwrap();
+        let public_key = key.public_key();
+
+        let rust_bitcoin_public_key =
+            bitcoin::CompressedPublicKey::from_slice(&public_key.to_bytes()).unwrap();
+        assert_eq!(
+            public_key.p2pkh_address(Network::Bitcoin).to_string(),
+            bitcoin::Address::p2pkh(rust_bitcoin_public_key, bitcoin::Network::Bitcoin).to_string()
+        );
+        assert_eq!(
+            public_key
+                .p2wpkh_address(Network::Bitcoin)
+                .unwrap()
+                .to_string(),
+            bitcoin::Address::p2wpkh(&rust_bitcoin_public_key, bitcoin::Network::Bitcoin)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_xpub_parse_and_display() {
+        let xpub = ExtendedPublicKey::parse(MASTER_XPUB, Network::Bitcoin).unwrap();
+
+        assert_eq!(xpub.depth(), 0);
+        assert_eq!(xpub.child_number(), 0);
+        assert_eq!(xpub.to_string(), MASTER_XPUB);
+        assert!(ExtendedPublicKey::parse(MASTER_XPUB, Network::Testnet).is_err());
+    }
+
+    #[test]
+    fn test_xpub_derivation() {
+        let xpub = ExtendedPublicKey::parse(XPUB_0H, Network::Bitcoin).unwrap();
+        let child = xpub.derive_child(1).unwrap();
+
+        assert_eq!(child.to_string(), XPUB_0H_1);
+        assert!(xpub.derive_child(HARDENED_INDEX).is_err());
+    }
+
+    #[test]
+    fn test_xpub_derivation_against_rust_bitcoin() {
+        use bitcoin::bip32::{DerivationPath, Xpub};
+
+        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+        let expected = Xpub::from_str(MASTER_XPUB)
+            .unwrap()
+            .derive_pub(&secp, &DerivationPath::from_str("m/0/1/2").unwrap())
+            .unwrap();
+
+        let derived = ExtendedPublicKey::parse(MASTER_XPUB, Network::Bitcoin)
+            .unwrap()
+            .derive_path(&[0, 1, 2])
+            .unwrap();
+
+        assert_eq!(derived.to_string(), expected.to_string());
+    }
+}
@@ -1,12 +1,32 @@
 //! Transaction builder, encoders, types and utilities for Bitcoin.
+pub mod address;
+pub mod bip322;
 mod bitcoin_transaction;
 mod bitcoin_transaction_builder;
 mod constants;
 mod encoding;
+pub mod htlc;
+#[cfg(feature = "rust-bitcoin")]
+mod interop;
+pub mod keys;
+pub mod musig;
+#[cfg(feature = "rust-bitcoin")]
+pub mod psbt;
+pub mod script;
+pub mod silent_payments;
+pub mod taproot;
 pub mod types;
 pub mod utils;
+mod validation;
 
 /// Bitcoin transaction
 pub use bitcoin_transaction::BitcoinTransaction;
+/// Hashes shared by the BIP-143 sighashes of all the inputs of a transaction
+pub use bitcoin_transaction::SegwitSighashMidstate;
 /// Bitcoin transaction builder
 pub use bitcoin_transaction_builder::BitcoinTransactionBuilder;
+/// Errors returned by the transaction sanity checks
+pub use validation::{
+    ValidationError, MAX_OP_RETURN_RELAY, MAX_SCRIPT_SIZE, MAX_STANDARD_SCRIPTSIG_SIZE,
+    MAX_STANDARD_TX_SIGOPS_COST, MAX_STANDARD_TX_WEIGHT, TRUC_CHILD_MAX_VSIZE, TRUC_MAX_VSIZE,
+};
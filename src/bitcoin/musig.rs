@@ -0,0 +1,584 @@
+//! [BIP-327] MuSig2 multi-signatures for taproot outputs.
+//!
+//! The public keys of the signers, for instance the MPC key and a user key, are aggregated into
+//! a single key, which can be tweaked into the output key of a taproot output. Spending it
+//! takes two rounds: each signer shares a public nonce, then a partial signature over the
+//! aggregate nonce, and the partial signatures add up to a BIP-340 Schnorr signature valid for
+//! the output key.
+//!
+//! ###### Example:
+//!
+//! ```rust
+//! use signet_rs::bitcoin::keys::PrivateKey;
+//! use signet_rs::bitcoin::musig::{aggregate_nonces, KeyAggContext, SecretNonce, Session};
+//! use signet_rs::bitcoin::types::Network;
+//!
+//! let secret_keys = [[1; 32], [2; 32]];
+//! let public_keys: Vec<_> = secret_keys
+//!     .iter()
+//!     .map(|secret_key| {
+//!         PrivateKey::from_slice(secret_key, Network::Bitcoin, true).unwrap().public_key()
+//!     })
+//!     .collect();
+//!
+//! let mut key_agg = KeyAggContext::new(&public_keys).unwrap();
+//! key_agg.apply_taproot_tweak(None).unwrap();
+//! let address = key_agg.output_key().address(Network::Bitcoin).unwrap();
+//!
+//! // The message is the taproot sighash of the spending input
+//! let message = [0x42; 32];
+//! let secret_nonces: Vec<_> = public_keys
+//!     .iter()
+//!     .zip([[3; 32], [4; 32]])
+//!     .map(|(public_key, rand)| SecretNonce::generate(rand, public_key, Some(&message)).unwrap())
+//!     .collect();
+//! let public_nonces: Vec<_> = secret_nonces.iter().map(SecretNonce::public_nonce).collect();
+//!
+//! let session = Session::new(&key_agg, &aggregate_nonces(&public_nonces).unwrap(), &message)
+//!     .unwrap();
+//! let partial_signatures: Vec<_> = secret_nonces
+//!     .into_iter()
+//!     .zip(&secret_keys)
+//!     .map(|(secret_nonce, secret_key)| session.sign(secret_nonce, secret_key).unwrap())
+//!     .collect();
+//! let signature = session.aggregate(&partial_signatures).unwrap();
+//! ```
+//!
+//! [BIP-327]: https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki
+use core::fmt;
+
+use k256::{
+    elliptic_curve::{
+        ops::Reduce,
+        point::AffineCoordinates,
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+        PrimeField,
+    },
+    AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar, U256,
+};
+
+use super::{
+    keys::PublicKey,
+    taproot::{tagged_hash, OutputKey},
+};
+
+/// The key aggregation context of a set of signers: their aggregate public key, along with the
+/// tweaks applied to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyAggContext {
+    /// The aggregate public key, tweaked.
+    q: ProjectivePoint,
+    /// The product of the signs the key was negated with by the x-only tweaks.
+    gacc: Scalar,
+    /// The sum of the tweaks.
+    tacc: Scalar,
+    /// The public keys of the signers, in the order they were aggregated.
+    public_keys: Vec<[u8; 33]>,
+    /// The hash of the public keys.
+    keys_hash: [u8; 32],
+    /// The second distinct public key, whose coefficient is one.
+    second_key: Option<[u8; 33]>,
+}
+
+impl KeyAggContext {
+    /// Aggregates the public keys of the signers. The order of the keys matters, sort them
+    /// with [`sort_public_keys`] to make it irrelevant.
+    pub fn new(public_keys: &[PublicKey]) -> Result<Self, String> {
+        if public_keys.is_empty() {
+            return Err("At least one public key is required".to_string());
+        }
+
+        let public_keys: Vec<[u8; 33]> = public_keys
+            .iter()
+            .map(PublicKey::compressed_bytes)
+            .collect();
+        let keys_hash = tagged_hash(b"KeyAgg list", &public_keys.concat());
+        let second_key = public_keys
+            .iter()
+            .find(|public_key| **public_key != public_keys[0])
+            .copied();
+
+        let mut ctx = Self {
+            q: ProjectivePoint::IDENTITY,
+            gacc: Scalar::ONE,
+            tacc: Scalar::ZERO,
+            public_keys,
+            keys_hash,
+            second_key,
+        };
+        for public_key in &ctx.public_keys {
+            ctx.q += point(public_key)? * ctx.coefficient(public_key);
+        }
+        if ctx.q == ProjectivePoint::IDENTITY {
+            return Err("The aggregate public key is infinite".to_string());
+        }
+
+        Ok(ctx)
+    }
+
+    /// The x coordinate of the aggregate public key, tweaked.
+    pub fn aggregate_public_key(&self) -> [u8; 32] {
+        x_bytes(&self.q)
+    }
+
+    /// The aggregate public key, tweaked, as the output key of a taproot output. Only an output
+    /// key once [`KeyAggContext::apply_taproot_tweak`] has been called.
+    pub fn output_key(&self) -> OutputKey {
+        OutputKey {
+            x_only: self.aggregate_public_key(),
+            odd_y: !has_even_y(&self.q),
+        }
+    }
+
+    /// Tweaks the aggregate public key, either as a plain key (as done by BIP-32 derivation) or
+    /// as an x-only key (as done by taproot).
+    pub fn apply_tweak(&mut self, tweak: &[u8; 32], is_x_only: bool) -> Result<(), String> {
+        let g = if is_x_only && !has_even_y(&self.q) {
+            -Scalar::ONE
+        } else {
+            Scalar::ONE
+        };
+        let t: Option<Scalar> = Scalar::from_repr(*FieldBytes::from_slice(tweak)).into();
+        let t = t.ok_or("The tweak exceeds the curve order")?;
+
+        let q = self.q * g + ProjectivePoint::GENERATOR * t;
+        if q == ProjectivePoint::IDENTITY {
+            return Err("The tweaked public key is infinite".to_string());
+        }
+
+        self.q = q;
+        self.gacc *= g;
+        self.tacc = t + g * self.tacc;
+        Ok(())
+    }
+
+    /// Tweaks the aggregate public key into the output key of a taproot output with the given
+    /// script tree, `None` for a key path only output.
+    pub fn apply_taproot_tweak(&mut self, merkle_root: Option<&[u8; 32]>) -> Result<(), String> {
+        let mut data = self.aggregate_public_key().to_vec();
+        if let Some(merkle_root) = merkle_root {
+            data.extend_from_slice(merkle_root);
+        }
+
+        self.apply_tweak(&tagged_hash(b"TapTweak", &data), true)
+    }
+
+    /// The coefficient the given public key is multiplied by in the aggregate public key.
+    fn coefficient(&self, public_key: &[u8; 33]) -> Scalar {
+        if Some(*public_key) == self.second_key {
+            return Scalar::ONE;
+        }
+
+        let mut data = self.keys_hash.to_vec();
+        data.extend_from_slice(public_key);
+        scalar_reduce(&tagged_hash(b"KeyAgg coefficient", &data))
+    }
+}
+
+/// Sorts public keys by their compressed encoding, the order expected by BIP-327.
+pub fn sort_public_keys(public_keys: &mut [PublicKey]) {
+    public_keys.sort_by_key(PublicKey::compressed_bytes);
+}
+
+/// The public nonce of a signer, the two points shared in the first round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicNonce(pub [u8; 66]);
+
+/// The sum of the public nonces of the signers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AggregateNonce(pub [u8; 66]);
+
+/// The secret nonce of a signer, consumed when signing so it is never used twice.
+pub struct SecretNonce {
+    k1: Scalar,
+    k2: Scalar,
+    public_key: [u8; 33],
+}
+
+/// Only shows the public key of the signer, so the nonce does not end up in logs.
+impl fmt::Debug for SecretNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretNonce")
+            .field("public_key", &hex::encode(self.public_key))
+            .finish_non_exhaustive()
+    }
+}
+
+impl SecretNonce {
+    /// Generates the nonce of the signer owning `public_key` (BIP-327 `NonceGen`).
+    ///
+    /// `rand` must be 32 fresh random bytes, never reused: signing twice with the same nonce
+    /// leaks the secret key. The message, if already known, is committed to as extra safety.
+    pub fn generate(
+        rand: [u8; 32],
+        public_key: &PublicKey,
+        message: Option<&[u8]>,
+    ) -> Result<Self, String> {
+        let public_key = public_key.compressed_bytes();
+
+        let mut data = rand.to_vec();
+        data.push(public_key.len() as u8);
+        data.extend_from_slice(&public_key);
+        // No aggregate public key
+        data.push(0);
+        match message {
+            Some(message) => {
+                data.push(1);
+                data.extend_from_slice(&(message.len() as u64).to_be_bytes());
+                data.extend_from_slice(message);
+            }
+            None => data.push(0),
+        }
+        // No extra input
+        data.extend_from_slice(&0u32.to_be_bytes());
+
+        let k = |i: u8| {
+            let mut data = data.clone();
+            data.push(i);
+            scalar_reduce(&tagged_hash(b"MuSig/nonce", &data))
+        };
+        let (k1, k2) = (k(0), k(1));
+        if k1 == Scalar::ZERO || k2 == Scalar::ZERO {
+            return Err("Invalid nonce".to_string());
+        }
+
+        Ok(Self { k1, k2, public_key })
+    }
+
+    /// The public nonce to share with the other signers.
+    pub fn public_nonce(&self) -> PublicNonce {
+        let mut nonce = [0u8; 66];
+        nonce[..33].copy_from_slice(&point_bytes(&(ProjectivePoint::GENERATOR * self.k1)));
+        nonce[33..].copy_from_slice(&point_bytes(&(ProjectivePoint::GENERATOR * self.k2)));
+        PublicNonce(nonce)
+    }
+}
+
+/// Sums the public nonces of the signers.
+pub fn aggregate_nonces(public_nonces: &[PublicNonce]) -> Result<AggregateNonce, String> {
+    let mut r1 = ProjectivePoint::IDENTITY;
+    let mut r2 = ProjectivePoint::IDENTITY;
+    for nonce in public_nonces {
+        r1 += point(&nonce.0[..33])?;
+        r2 += point(&nonce.0[33..])?;
+    }
+
+    let mut nonce = [0u8; 66];
+    nonce[..33].copy_from_slice(&point_bytes_ext(&r1));
+    nonce[33..].copy_from_slice(&point_bytes_ext(&r2));
+    Ok(AggregateNonce(nonce))
+}
+
+/// A signing session over a message, once the nonces of every signer are known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    key_agg: KeyAggContext,
+    /// The coefficient of the second nonces.
+    b: Scalar,
+    /// The nonce of the final signature.
+    r: ProjectivePoint,
+    /// The BIP-340 challenge.
+    e: Scalar,
+}
+
+impl Session {
+    pub fn new(
+        key_agg: &KeyAggContext,
+        aggregate_nonce: &AggregateNonce,
+        message: &[u8],
+    ) -> Result<Self, String> {
+        let mut data = aggregate_nonce.0.to_vec();
+        data.extend_from_slice(&key_agg.aggregate_public_key());
+        data.extend_from_slice(message);
+        let b = scalar_reduce(&tagged_hash(b"MuSig/noncecoef", &data));
+
+        let r = point_ext(&aggregate_nonce.0[..33])? + point_ext(&aggregate_nonce.0[33..])? * b;
+        let r = if r == ProjectivePoint::IDENTITY {
+            ProjectivePoint::GENERATOR
+        } else {
+            r
+        };
+
+        let mut data = x_bytes(&r).to_vec();
+        data.extend_from_slice(&key_agg.aggregate_public_key());
+        data.extend_from_slice(message);
+        let e = scalar_reduce(&tagged_hash(b"BIP0340/challenge", &data));
+
+        Ok(Self {
+            key_agg: key_agg.clone(),
+            b,
+            r,
+            e,
+        })
+    }
+
+    /// Produces the partial signature of a signer, consuming its secret nonce.
+    pub fn sign(
+        &self,
+        secret_nonce: SecretNonce,
+        secret_key: &[u8; 32],
+    ) -> Result<[u8; 32], String> {
+        let d: Option<Scalar> = Scalar::from_repr(*FieldBytes::from_slice(secret_key)).into();
+        let d = d
+            .filter(|d| *d != Scalar::ZERO)
+            .ok_or("Invalid secret key")?;
+        if point_bytes(&(ProjectivePoint::GENERATOR * d)) != secret_nonce.public_key {
+            return Err("The secret nonce was generated for another key".to_string());
+        }
+
+        let (k1, k2) = if has_even_y(&self.r) {
+            (secret_nonce.k1, secret_nonce.k2)
+        } else {
+            (-secret_nonce.k1, -secret_nonce.k2)
+        };
+        let a = self.key_agg.coefficient(&secret_nonce.public_key);
+        let d = self.g() * self.key_agg.gacc * d;
+
+        let s = k1 + self.b * k2 + self.e * a * d;
+        Ok(s.to_bytes().into())
+    }
+
+    /// Verifies the partial signature of the signer with the given public nonce and key.
+    pub fn verify(
+        &self,
+        partial_signature: &[u8; 32],
+        public_nonce: &PublicNonce,
+        public_key: &PublicKey,
+    ) -> bool {
+        let s: Option<Scalar> =
+            Scalar::from_repr(*FieldBytes::from_slice(partial_signature)).into();
+        let (Some(s), Ok(r1), Ok(r2)) = (
+            s,
+            point(&public_nonce.0[..33]),
+            point(&public_nonce.0[33..]),
+        ) else {
+            return false;
+        };
+        let public_key = public_key.compressed_bytes();
+        let Ok(p) = point(&public_key) else {
+            return false;
+        };
+
+        let r = r1 + r2 * self.b;
+        let r = if has_even_y(&self.r) { r } else { -r };
+        let a = self.key_agg.coefficient(&public_key);
+        let g = self.g() * self.key_agg.gacc;
+
+        ProjectivePoint::GENERATOR * s == r + p * (self.e * a * g)
+    }
+
+    /// Adds up the partial signatures of every signer into a BIP-340 Schnorr signature, valid
+    /// for the aggregate public key.
+    pub fn aggregate(&self, partial_signatures: &[[u8; 32]]) -> Result<[u8; 64], String> {
+        let mut s = self.e * self.g() * self.key_agg.tacc;
+        for partial_signature in partial_signatures {
+            let partial_signature: Option<Scalar> =
+                Scalar::from_repr(*FieldBytes::from_slice(partial_signature)).into();
+            s += partial_signature.ok_or("Invalid partial signature")?;
+        }
+
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&x_bytes(&self.r));
+        signature[32..].copy_from_slice(&s.to_bytes());
+        Ok(signature)
+    }
+
+    /// The sign of the aggregate public key, negated if its y coordinate is odd.
+    fn g(&self) -> Scalar {
+        if has_even_y(&self.key_agg.q) {
+            Scalar::ONE
+        } else {
+            -Scalar::ONE
+        }
+    }
+}
+
+/// Parses a compressed point.
+fn point(bytes: &[u8]) -> Result<ProjectivePoint, String> {
+    if bytes.len() != 33 {
+        return Err("Invalid point".to_string());
+    }
+
+    let point = EncodedPoint::from_bytes(bytes).map_err(|_| "Invalid point".to_string())?;
+    Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&point))
+        .map(ProjectivePoint::from)
+        .ok_or("Invalid point".to_string())
+}
+
+/// Parses a point, 33 zero bytes encoding the point at infinity.
+fn point_ext(bytes: &[u8]) -> Result<ProjectivePoint, String> {
+    if bytes.iter().all(|byte| *byte == 0) {
+        return Ok(ProjectivePoint::IDENTITY);
+    }
+
+    point(bytes)
+}
+
+fn point_bytes(point: &ProjectivePoint) -> [u8; 33] {
+    point
+        .to_affine()
+        .to_encoded_point(true)
+        .as_bytes()
+        .try_into()
+        .expect("compressed points are 33 bytes")
+}
+
+/// Encodes a point, the point at infinity as 33 zero bytes.
+fn point_bytes_ext(point: &ProjectivePoint) -> [u8; 33] {
+    if *point == ProjectivePoint::IDENTITY {
+        return [0; 33];
+    }
+
+    point_bytes(point)
+}
+
+fn x_bytes(point: &ProjectivePoint) -> [u8; 32] {
+    point.to_affine().x().into()
+}
+
+fn has_even_y(point: &ProjectivePoint) -> bool {
+    !bool::from(point.to_affine().y_is_odd())
+}
+
+fn scalar_reduce(bytes: &[u8; 32]) -> Scalar {
+    <Scalar as Reduce<U256>>::reduce_bytes(FieldBytes::from_slice(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::keys::PrivateKey;
+    use crate::bitcoin::types::Network;
+    use bitcoin::key::{Secp256k1, TapTweak, XOnlyPublicKey};
+    use bitcoin::secp256k1::{schnorr, Message};
+
+    fn public_key(hex: &str) -> PublicKey {
+        PublicKey::from_slice(&hex::decode(hex).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_key_aggregation() {
+        // Test vectors of BIP-327
+        let x1 = public_key("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9");
+        let x2 = public_key("03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659");
+        let x3 = public_key("023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66");
+
+        for (public_keys, expected) in [
+            (
+                vec![x1, x2, x3],
+                "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+            ),
+            (
+                vec![x3, x2, x1],
+                "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B",
+            ),
+            (
+                vec![x1, x1, x1],
+                "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935",
+            ),
+            (
+                vec![x1, x1, x2, x2],
+                "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E",
+            ),
+        ] {
+            assert_eq!(
+                hex::encode_upper(
+                    KeyAggContext::new(&public_keys)
+                        .unwrap()
+                        .aggregate_public_key()
+                ),
+                expected
+            );
+        }
+
+        let mut public_keys = vec![x3, x2, x1];
+        sort_public_keys(&mut public_keys);
+        assert_eq!(public_keys, vec![x3, x1, x2]);
+    }
+
+    #[test]
+    fn test_taproot_signature() {
+        let secp = Secp256k1::new();
+        let secret_keys = [[7; 32], [8; 32]];
+        let public_keys: Vec<PublicKey> = secret_keys
+            .iter()
+            .map(|secret_key| {
+                PrivateKey::from_slice(secret_key, Network::Bitcoin, true)
+                    .unwrap()
+                    .public_key()
+            })
+            .collect();
+
+        let mut key_agg = KeyAggContext::new(&public_keys).unwrap();
+        let internal_key = XOnlyPublicKey::from_slice(&key_agg.aggregate_public_key()).unwrap();
+        key_agg.apply_taproot_tweak(None).unwrap();
+
+        let (output_key, parity) = internal_key.tap_tweak(&secp, None);
+        assert_eq!(
+            key_agg.output_key(),
+            OutputKey {
+                x_only: output_key.to_x_only_public_key().serialize(),
+                odd_y: parity == bitcoin::key::Parity::Odd,
+            }
+        );
+
+        for message in [[0x42; 32], [0xff; 32]] {
+            let secret_nonces: Vec<SecretNonce> = public_keys
+                .iter()
+                .zip([[1; 32], [2; 32]])
+                .map(|(public_key, rand)| {
+                    SecretNonce::generate(rand, public_key, Some(&message)).unwrap()
+                })
+                .collect();
+            let public_nonces: Vec<PublicNonce> = secret_nonces
+                .iter()
+                .map(SecretNonce::public_nonce)
+                .collect();
+
+            let session = Session::new(
+                &key_agg,
+                &aggregate_nonces(&public_nonces).unwrap(),
+                &message,
+            )
+            .unwrap();
+            let partial_signatures: Vec<[u8; 32]> = secret_nonces
+                .into_iter()
+                .zip(&secret_keys)
+                .map(|(secret_nonce, secret_key)| session.sign(secret_nonce, secret_key).unwrap())
+                .collect();
+
+            assert!(session.verify(&partial_signatures[0], &public_nonces[0], &public_keys[0]));
+            assert!(session.verify(&partial_signatures[1], &public_nonces[1], &public_keys[1]));
+            assert!(!session.verify(&partial_signatures[0], &public_nonces[1], &public_keys[1]));
+
+            let signature = session.aggregate(&partial_signatures).unwrap();
+            secp.verify_schnorr(
+                &schnorr::Signature::from_slice(&signature).unwrap(),
+                &Message::from_digest(message),
+                &output_key.to_x_only_public_key(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_sign_with_another_key() {
+        let public_key = PrivateKey::from_slice(&[7; 32], Network::Bitcoin, true)
+            .unwrap()
+            .public_key();
+        let key_agg = KeyAggContext::new(&[public_key]).unwrap();
+        let secret_nonce = SecretNonce::generate([1; 32], &public_key, None).unwrap();
+        let debug = format!("{secret_nonce:?}");
+        assert!(!debug.contains(&hex::encode(secret_nonce.k1.to_bytes())));
+        assert!(!debug.contains(&hex::encode(secret_nonce.k2.to_bytes())));
+
+        let session = Session::new(
+            &key_agg,
+            &aggregate_nonces(&[secret_nonce.public_nonce()]).unwrap(),
+            &[0; 32],
+        )
+        .unwrap();
+
+        assert!(session.sign(secret_nonce, &[8; 32]).is_err());
+    }
+}
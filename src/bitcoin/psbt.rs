@@ -0,0 +1,337 @@
+//! Filling and finalizing rust-bitcoin PSBTs (BIP-174) with the signatures returned by the MPC
+//! signer, enabled by the `rust-bitcoin` feature.
+//!
+//! ###### Example:
+//!
+//! ```rust,ignore
+//! // One signature response per input, signing the sighash of each input
+//! signet_rs::bitcoin::psbt::add_signatures(&mut psbt, &public_key, &signatures)?;
+//! let tx = psbt.extract_tx()?;
+//! ```
+use ::bitcoin::{
+    ecdsa, psbt::Input, secp256k1, taproot, Psbt, ScriptBuf as RustBitcoinScriptBuf,
+    Witness as RustBitcoinWitness,
+};
+
+use super::{keys::PublicKey, utils::build_script_sig};
+use crate::signer::BitcoinSignatureResponse;
+
+/// Adds the signature of the given input to the PSBT.
+///
+/// Taproot inputs take a Schnorr signature of their output key as their key path signature
+/// (`tap_key_sig`), any other input takes an ECDSA signature as the partial signature of
+/// `public_key` (`partial_sigs`), using the sighash type of the input.
+pub fn add_signature(
+    psbt: &mut Psbt,
+    input_index: usize,
+    public_key: &PublicKey,
+    signature: &BitcoinSignatureResponse,
+) -> Result<(), String> {
+    let script_pubkey = spent_script_pubkey(psbt, input_index)?;
+    let input = &mut psbt.inputs[input_index];
+
+    match signature {
+        BitcoinSignatureResponse::Schnorr(signature) if script_pubkey.is_p2tr() => {
+            let signature = secp256k1::schnorr::Signature::from_slice(
+                &signature.to_bitcoin_schnorr_signature()?,
+            )
+            .map_err(|e| format!("Invalid Schnorr signature: {e}"))?;
+            let sighash_type = input.taproot_hash_ty().map_err(|e| e.to_string())?;
+
+            input.tap_key_sig = Some(taproot::Signature {
+                signature,
+                sighash_type,
+            });
+        }
+        BitcoinSignatureResponse::Ecdsa(signature) if !script_pubkey.is_p2tr() => {
+            let compact = signature.compact().map_err(|e| e.to_string())?;
+            let mut signature = secp256k1::ecdsa::Signature::from_compact(&compact)
+                .map_err(|e| format!("Invalid ECDSA signature: {e}"))?;
+            signature.normalize_s();
+            let sighash_type = input.ecdsa_hash_ty().map_err(|e| e.to_string())?;
+            let public_key = ::bitcoin::PublicKey::from_slice(&public_key.to_bytes())
+                .map_err(|e| format!("Invalid public key: {e}"))?;
+
+            input.partial_sigs.insert(
+                public_key,
+                ecdsa::Signature {
+                    signature,
+                    sighash_type,
+                },
+            );
+        }
+        BitcoinSignatureResponse::Ecdsa(_) => {
+            return Err(format!(
+                "Input {input_index} is a taproot input and needs a Schnorr signature"
+            ));
+        }
+        BitcoinSignatureResponse::Schnorr(_) => {
+            return Err(format!("Input {input_index} needs an ECDSA signature"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds one signature per input to the PSBT, in input order, and finalizes it.
+pub fn add_signatures(
+    psbt: &mut Psbt,
+    public_key: &PublicKey,
+    signatures: &[BitcoinSignatureResponse],
+) -> Result<(), String> {
+    if signatures.len() != psbt.inputs.len() {
+        return Err(format!(
+            "Expected {} signatures, got {}",
+            psbt.inputs.len(),
+            signatures.len()
+        ));
+    }
+
+    for (input_index, signature) in signatures.iter().enumerate() {
+        add_signature(psbt, input_index, public_key, signature)?;
+    }
+
+    finalize(psbt)
+}
+
+/// Finalizes every input of the PSBT, building its final script sig or witness from its
+/// signature and clearing the fields only needed for signing, as defined by BIP-174.
+///
+/// Supports P2PKH, P2WPKH and P2TR key path inputs.
+pub fn finalize(psbt: &mut Psbt) -> Result<(), String> {
+    for input_index in 0..psbt.inputs.len() {
+        let script_pubkey = spent_script_pubkey(psbt, input_index)?;
+        let input = &mut psbt.inputs[input_index];
+
+        if script_pubkey.is_p2tr() {
+            let signature = input
+                .tap_key_sig
+                .ok_or(format!("Missing Taproot signature for input {input_index}"))?;
+            input.final_script_witness = Some(RustBitcoinWitness::p2tr_key_spend(&signature));
+        } else {
+            let (public_key, signature) = match input.partial_sigs.iter().next() {
+                Some((public_key, signature)) if input.partial_sigs.len() == 1 => {
+                    (public_key.to_bytes(), signature.to_vec())
+                }
+                _ => return Err(format!("Expected one signature for input {input_index}")),
+            };
+
+            if script_pubkey.is_p2wpkh() {
+                input.final_script_witness =
+                    Some(RustBitcoinWitness::from_slice(&[signature, public_key]));
+            } else if script_pubkey.is_p2pkh() {
+                input.final_script_sig = Some(RustBitcoinScriptBuf::from_bytes(build_script_sig(
+                    &signature,
+                    &public_key,
+                )));
+            } else {
+                return Err(format!("Unsupported script pubkey for input {input_index}"));
+            }
+        }
+
+        clear_signing_fields(input);
+    }
+
+    Ok(())
+}
+
+/// The script pubkey of the output spent by the given input.
+fn spent_script_pubkey(psbt: &Psbt, input_index: usize) -> Result<RustBitcoinScriptBuf, String> {
+    let input = psbt
+        .inputs
+        .get(input_index)
+        .ok_or(format!("Input {input_index} does not exist"))?;
+
+    if let Some(witness_utxo) = &input.witness_utxo {
+        return Ok(witness_utxo.script_pubkey.clone());
+    }
+
+    let vout = psbt.unsigned_tx.input[input_index].previous_output.vout as usize;
+    input
+        .non_witness_utxo
+        .as_ref()
+        .and_then(|tx| tx.output.get(vout))
+        .map(|output| output.script_pubkey.clone())
+        .ok_or(format!("Missing UTXO for input {input_index}"))
+}
+
+fn clear_signing_fields(input: &mut Input) {
+    input.partial_sigs.clear();
+    input.sighash_type = None;
+    input.redeem_script = None;
+    input.witness_script = None;
+    input.bip32_derivation.clear();
+    input.tap_key_sig = None;
+    input.tap_script_sigs.clear();
+    input.tap_scripts.clear();
+    input.tap_key_origins.clear();
+    input.tap_internal_key = None;
+    input.tap_merkle_root = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::keys::PrivateKey;
+    use crate::bitcoin::types::Network;
+    use crate::signer::types::{
+        SchnorrSignatureResponse, SerializableAffinePoint, SerializableScalar, SignatureResponse,
+    };
+    use ::bitcoin::{
+        absolute::LockTime, hashes::Hash, sighash::SighashCache, transaction::Version, Amount,
+        EcdsaSighashType, OutPoint, Sequence, Transaction, TxIn, TxOut, Txid,
+    };
+
+    fn unsigned_psbt(script_pubkey: RustBitcoinScriptBuf) -> Psbt {
+        let tx = Transaction {
+            version: Version(2),
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::all_zeros(), 0),
+                script_sig: RustBitcoinScriptBuf::new(),
+                sequence: Sequence::MAX,
+                witness: RustBitcoinWitness::new(),
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(40_000),
+                script_pubkey: script_pubkey.clone(),
+            }],
+        };
+
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        psbt.inputs[0].witness_utxo = Some(TxOut {
+            value: Amount::from_sat(50_000),
+            script_pubkey,
+        });
+
+        psbt
+    }
+
+    fn signature_response(compact: &[u8; 64]) -> SignatureResponse {
+        SignatureResponse {
+            big_r: SerializableAffinePoint {
+                affine_point: format!("02{}", hex::encode(&compact[..32])),
+            },
+            s: SerializableScalar {
+                scalar: hex::encode(&compact[32..]),
+            },
+            recovery_id: 0,
+        }
+    }
+
+    #[test]
+    fn test_add_signatures_p2wpkh() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7; 32]).unwrap();
+        let public_key = PrivateKey::from_slice(&[7; 32], Network::Bitcoin, true)
+            .unwrap()
+            .public_key();
+        let script_pubkey = RustBitcoinScriptBuf::from_bytes(
+            public_key
+                .p2wpkh_address(Network::Bitcoin)
+                .unwrap()
+                .script_pubkey()
+                .0,
+        );
+
+        let mut psbt = unsigned_psbt(script_pubkey.clone());
+        let sighash = SighashCache::new(&psbt.unsigned_tx)
+            .p2wpkh_signature_hash(
+                0,
+                &script_pubkey,
+                Amount::from_sat(50_000),
+                EcdsaSighashType::All,
+            )
+            .unwrap();
+        let message = secp256k1::Message::from_digest(sighash.to_byte_array());
+        let signature = secp.sign_ecdsa(&message, &secret_key);
+
+        add_signatures(
+            &mut psbt,
+            &public_key,
+            &[signature_response(&signature.serialize_compact()).into()],
+        )
+        .unwrap();
+
+        let witness = psbt.inputs[0].final_script_witness.clone().unwrap();
+        assert_eq!(witness.len(), 2);
+        assert_eq!(witness.nth(1).unwrap(), public_key.to_bytes().as_slice());
+        assert!(psbt.inputs[0].partial_sigs.is_empty());
+
+        let parsed = ecdsa::Signature::from_slice(witness.nth(0).unwrap()).unwrap();
+        assert_eq!(parsed.sighash_type, EcdsaSighashType::All);
+        secp.verify_ecdsa(&message, &parsed.signature, &secret_key.public_key(&secp))
+            .unwrap();
+        assert!(psbt.extract_tx().is_ok());
+    }
+
+    #[test]
+    fn test_add_signatures_p2tr() {
+        use ::bitcoin::{
+            key::TapTweak,
+            sighash::{Prevouts, TapSighashType},
+        };
+
+        let secp = secp256k1::Secp256k1::new();
+        let public_key = PrivateKey::from_slice(&[7; 32], Network::Bitcoin, true)
+            .unwrap()
+            .public_key();
+        let script_pubkey = RustBitcoinScriptBuf::from_bytes(
+            public_key
+                .p2tr_address(Network::Bitcoin)
+                .unwrap()
+                .script_pubkey()
+                .0,
+        );
+        let mut psbt = unsigned_psbt(script_pubkey);
+
+        // Signed by the output key, the key tweaked as defined by BIP-341
+        let prevouts = [psbt.inputs[0].witness_utxo.clone().unwrap()];
+        let sighash = SighashCache::new(&psbt.unsigned_tx)
+            .taproot_key_spend_signature_hash(0, &Prevouts::All(&prevouts), TapSighashType::Default)
+            .unwrap();
+        let message = secp256k1::Message::from_digest(sighash.to_byte_array());
+        let keypair = secp256k1::Keypair::from_seckey_slice(&secp, &[7; 32])
+            .unwrap()
+            .tap_tweak(&secp, None)
+            .to_keypair();
+        let signature = secp.sign_schnorr_no_aux_rand(&message, &keypair);
+        let response = SchnorrSignatureResponse {
+            big_r: SerializableAffinePoint {
+                affine_point: format!("02{}", hex::encode(&signature.as_ref()[..32])),
+            },
+            s: SerializableScalar {
+                scalar: hex::encode(&signature.as_ref()[32..]),
+            },
+        };
+
+        // ECDSA signatures cannot sign taproot inputs
+        assert!(add_signature(
+            &mut psbt,
+            0,
+            &public_key,
+            &signature_response(signature.as_ref()).into()
+        )
+        .is_err());
+
+        add_signatures(&mut psbt, &public_key, &[response.into()]).unwrap();
+
+        let witness = psbt.inputs[0].final_script_witness.clone().unwrap();
+        assert_eq!(witness.len(), 1);
+        assert!(psbt.inputs[0].tap_key_sig.is_none());
+
+        let parsed = secp256k1::schnorr::Signature::from_slice(witness.nth(0).unwrap()).unwrap();
+        secp.verify_schnorr(&parsed, &message, &keypair.x_only_public_key().0)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_add_signatures_wrong_count() {
+        let public_key = PrivateKey::from_slice(&[7; 32], Network::Bitcoin, true)
+            .unwrap()
+            .public_key();
+        let mut psbt = unsigned_psbt(RustBitcoinScriptBuf::new());
+
+        assert!(add_signatures(&mut psbt, &public_key, &[]).is_err());
+    }
+}
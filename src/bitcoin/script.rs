@@ -0,0 +1,402 @@
+//! Building scripts: a minimal script builder, the script pubkeys paying to a key, and the
+//! timelocked scripts used to lock outputs to MPC-derived keys.
+use sha2::{Digest, Sha256};
+
+use super::{
+    address::Address,
+    keys::PublicKey,
+    taproot::OutputKey,
+    types::{LockTime, Network, OutPoint, ScriptBuf, Sequence, TxIn, Witness},
+};
+
+/// Push an empty array, also the number zero.
+pub const OP_0: u8 = 0x00;
+/// Push the next byte as the length of the data to push.
+pub const OP_PUSHDATA1: u8 = 0x4c;
+/// Push the next two bytes as the length of the data to push.
+pub const OP_PUSHDATA2: u8 = 0x4d;
+/// Push the next four bytes as the length of the data to push.
+pub const OP_PUSHDATA4: u8 = 0x4e;
+/// Push the number -1.
+pub const OP_1NEGATE: u8 = 0x4f;
+/// Push the number 1, the numbers 2 to 16 following it.
+pub const OP_1: u8 = 0x51;
+/// Push the number 16, the largest of the small number opcodes.
+pub const OP_16: u8 = 0x60;
+/// Execute the following statements if the top stack item is true.
+pub const OP_IF: u8 = 0x63;
+/// Execute the following statements if the statements before it were not executed.
+pub const OP_ELSE: u8 = 0x67;
+/// End an `OP_IF` block.
+pub const OP_ENDIF: u8 = 0x68;
+/// Mark the output as unspendable, the rest of the script carrying arbitrary data.
+pub const OP_RETURN: u8 = 0x6a;
+/// Remove the top stack item.
+pub const OP_DROP: u8 = 0x75;
+/// Push the size of the top stack item.
+pub const OP_SIZE: u8 = 0x82;
+/// Fail unless the two top stack items are equal.
+pub const OP_EQUALVERIFY: u8 = 0x88;
+/// Replace the top stack item with its SHA-256.
+pub const OP_SHA256: u8 = 0xa8;
+/// Check the signature of the transaction against the public key on top of the stack.
+pub const OP_CHECKSIG: u8 = 0xac;
+/// `OP_CHECKSIG` followed by a check that it succeeded.
+pub const OP_CHECKSIGVERIFY: u8 = 0xad;
+/// Check that signatures match a number of the public keys on the stack.
+pub const OP_CHECKMULTISIG: u8 = 0xae;
+/// `OP_CHECKMULTISIG` followed by a check that it succeeded.
+pub const OP_CHECKMULTISIGVERIFY: u8 = 0xaf;
+/// Fail unless the lock time of the transaction has reached the top stack item (BIP-65).
+pub const OP_CHECKLOCKTIMEVERIFY: u8 = 0xb1;
+
+/// Builds a script one push or opcode at a time.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::bitcoin::script::{Builder, OP_DROP};
+///
+/// let script = Builder::new().push_int(500).push_opcode(OP_DROP).into_script();
+/// assert_eq!(script.0, vec![0x02, 0xf4, 0x01, 0x75]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Builder(Vec<u8>);
+
+impl Builder {
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Push an opcode.
+    pub fn push_opcode(mut self, opcode: u8) -> Self {
+        self.0.push(opcode);
+        self
+    }
+
+    /// Push data with the smallest push opcode.
+    pub fn push_slice(mut self, data: &[u8]) -> Self {
+        match data.len() {
+            len if len < OP_PUSHDATA1 as usize => self.0.push(len as u8),
+            len if len <= 0xff => {
+                self.0.push(OP_PUSHDATA1);
+                self.0.push(len as u8);
+            }
+            len if len <= 0xffff => {
+                self.0.push(OP_PUSHDATA2);
+                self.0.extend_from_slice(&(len as u16).to_le_bytes());
+            }
+            len => {
+                self.0.push(OP_PUSHDATA4);
+                self.0.extend_from_slice(&(len as u32).to_le_bytes());
+            }
+        }
+        self.0.extend_from_slice(data);
+        self
+    }
+
+    /// Push a number, with a small number opcode when possible.
+    pub fn push_int(self, n: i64) -> Self {
+        match n {
+            0 => self.push_opcode(OP_0),
+            -1 => self.push_opcode(OP_1NEGATE),
+            1..=16 => self.push_opcode(OP_1 + n as u8 - 1),
+            _ => self.push_slice(&script_num(n)),
+        }
+    }
+
+    /// Push a lock time, as expected by [`OP_CHECKLOCKTIMEVERIFY`].
+    pub fn push_lock_time(self, lock_time: LockTime) -> Self {
+        self.push_int(lock_time.to_u32() as i64)
+    }
+
+    /// Push a public key.
+    pub fn push_key(self, public_key: &PublicKey) -> Self {
+        self.push_slice(&public_key.to_bytes())
+    }
+
+    pub fn into_script(self) -> ScriptBuf {
+        ScriptBuf(self.0)
+    }
+}
+
+/// Encodes a number the way the script interpreter reads it: little-endian in as few bytes as
+/// possible, the most significant bit holding the sign.
+fn script_num(n: i64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut abs = n.unsigned_abs();
+    while abs > 0 {
+        bytes.push(abs as u8);
+        abs >>= 8;
+    }
+
+    // The sign needs a byte of its own if the most significant bit is taken
+    let sign = if n < 0 { 0x80 } else { 0x00 };
+    match bytes.last_mut() {
+        Some(last) if *last & 0x80 != 0 => bytes.push(sign),
+        Some(last) => *last |= sign,
+        None => {}
+    }
+
+    bytes
+}
+
+/// The P2WSH script pubkey paying to the given witness script.
+pub fn p2wsh(witness_script: &ScriptBuf) -> ScriptBuf {
+    Builder::new()
+        .push_opcode(OP_0)
+        .push_slice(&Sha256::digest(&witness_script.0))
+        .into_script()
+}
+
+/// The P2WPKH script pubkey `OP_0 <hash160 of the public key>`, the key having to be
+/// compressed.
+pub fn p2wpkh_script(public_key: &PublicKey) -> Result<ScriptBuf, String> {
+    if !public_key.is_compressed() {
+        return Err("P2WPKH requires a compressed public key".to_string());
+    }
+
+    Ok(Builder::new()
+        .push_opcode(OP_0)
+        .push_slice(&public_key.pubkey_hash())
+        .into_script())
+}
+
+/// The pay-to-anchor (P2A) script pubkey `OP_1 <0x4e73>`, spendable by anyone with an empty
+/// witness so any party can bump the fee of a transaction by spending its anchor output with a
+/// child.
+pub fn p2a() -> ScriptBuf {
+    Builder::new()
+        .push_opcode(OP_1)
+        .push_slice(&[0x4e, 0x73])
+        .into_script()
+}
+
+/// The P2TR script pubkey only spendable by the key path of the given x-only internal key,
+/// tweaked without a script tree as suggested by BIP-86.
+pub fn p2tr_script(internal_key: &[u8; 32]) -> Result<ScriptBuf, String> {
+    Ok(OutputKey::new(internal_key, None)?.script_pubkey())
+}
+
+/// A P2WSH output only spendable by a key once a lock time is reached, with the script
+/// `<lock time> OP_CHECKLOCKTIMEVERIFY OP_DROP <public key> OP_CHECKSIG` (BIP-65).
+///
+/// The spending transaction must have a lock time of the same kind, height or timestamp, at
+/// least as large as [`CltvScript::lock_time`], and the spending input must not be final,
+/// see [`CltvScript::spend_input`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CltvScript {
+    pub lock_time: LockTime,
+    pub public_key: PublicKey,
+}
+
+impl CltvScript {
+    pub const fn new(lock_time: LockTime, public_key: PublicKey) -> Self {
+        Self {
+            lock_time,
+            public_key,
+        }
+    }
+
+    /// The witness script, also the script code to sign the spending input with.
+    pub fn witness_script(&self) -> ScriptBuf {
+        Builder::new()
+            .push_lock_time(self.lock_time)
+            .push_opcode(OP_CHECKLOCKTIMEVERIFY)
+            .push_opcode(OP_DROP)
+            .push_key(&self.public_key)
+            .push_opcode(OP_CHECKSIG)
+            .into_script()
+    }
+
+    /// The P2WSH script pubkey of the output.
+    pub fn script_pubkey(&self) -> ScriptBuf {
+        p2wsh(&self.witness_script())
+    }
+
+    /// The P2WSH address of the output.
+    pub fn address(&self, network: Network) -> Result<Address, String> {
+        Address::from_script(&self.script_pubkey(), network)
+    }
+
+    /// An input spending the output, with a sequence enabling the lock time check.
+    pub fn spend_input(&self, previous_output: OutPoint) -> TxIn {
+        TxIn {
+            previous_output,
+            script_sig: ScriptBuf::default(),
+            sequence: Sequence::ENABLE_LOCKTIME_NO_RBF,
+            witness: Witness::default(),
+        }
+    }
+
+    /// The witness of the spending input, from a DER signature followed by its sighash type.
+    pub fn witness(&self, signature: &[u8]) -> Witness {
+        Witness::from_slice(&[signature.to_vec(), self.witness_script().0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::keys::PrivateKey;
+    use crate::bitcoin::types::{Amount, EcdsaSighashType, Hash, TxOut, Txid, Version};
+    use crate::bitcoin::BitcoinTransaction;
+    use bitcoin::hashes::{sha256d, Hash as _};
+    use bitcoin::opcodes::all::{
+        OP_CHECKSIG as RUST_BITCOIN_OP_CHECKSIG, OP_CLTV, OP_DROP as RUST_BITCOIN_OP_DROP,
+    };
+    use bitcoin::script::Builder as RustBitcoinBuilder;
+    use bitcoin::sighash::{EcdsaSighashType as RustBitcoinSighashType, SighashCache};
+
+    #[test]
+    fn test_push_int() {
+        for n in [
+            0,
+            -1,
+            1,
+            16,
+            17,
+            127,
+            128,
+            255,
+            -255,
+            32_768,
+            840_000,
+            1_700_000_000,
+            i64::MAX,
+        ] {
+            assert_eq!(
+                Builder::new().push_int(n).into_script().0,
+                RustBitcoinBuilder::new()
+                    .push_int(n)
+                    .into_script()
+                    .into_bytes(),
+                "{n}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_push_slice() {
+        for len in [0, 75, 76, 255, 256, 520] {
+            let data = vec![0xab; len];
+            let push_bytes: &bitcoin::script::PushBytes = data.as_slice().try_into().unwrap();
+
+            assert_eq!(
+                Builder::new().push_slice(&data).into_script().0,
+                RustBitcoinBuilder::new()
+                    .push_slice(push_bytes)
+                    .into_script()
+                    .into_bytes(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_p2wpkh_and_p2tr_scripts() {
+        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+        let private_key = PrivateKey::from_slice(&[7; 32], Network::Bitcoin, true).unwrap();
+        let public_key = private_key.public_key();
+        let rust_bitcoin_key =
+            bitcoin::CompressedPublicKey::from_slice(&public_key.to_bytes()).unwrap();
+
+        assert_eq!(
+            p2wpkh_script(&public_key).unwrap().0,
+            bitcoin::ScriptBuf::new_p2wpkh(&rust_bitcoin_key.wpubkey_hash()).into_bytes()
+        );
+        assert_eq!(
+            public_key
+                .p2wpkh_address(Network::Bitcoin)
+                .unwrap()
+                .script_pubkey(),
+            p2wpkh_script(&public_key).unwrap()
+        );
+
+        let x_only = rust_bitcoin_key.0.x_only_public_key().0;
+        assert_eq!(
+            p2tr_script(&public_key.x_only()).unwrap().0,
+            bitcoin::ScriptBuf::new_p2tr(&secp, x_only, None).into_bytes()
+        );
+        assert_eq!(
+            public_key
+                .p2tr_address(Network::Bitcoin)
+                .unwrap()
+                .to_string(),
+            bitcoin::Address::p2tr(&secp, x_only, None, bitcoin::Network::Bitcoin).to_string()
+        );
+
+        let uncompressed = PrivateKey::from_slice(&[7; 32], Network::Bitcoin, false)
+            .unwrap()
+            .public_key();
+        assert!(p2wpkh_script(&uncompressed).is_err());
+    }
+
+    #[test]
+    fn test_cltv_script() {
+        let public_key = PrivateKey::from_slice(&[7; 32], Network::Bitcoin, true)
+            .unwrap()
+            .public_key();
+        let lock_time = LockTime::from_height(840_000).unwrap();
+        let cltv = CltvScript::new(lock_time, public_key);
+
+        let rust_bitcoin_script = RustBitcoinBuilder::new()
+            .push_lock_time(bitcoin::absolute::LockTime::from_consensus(840_000))
+            .push_opcode(OP_CLTV)
+            .push_opcode(RUST_BITCOIN_OP_DROP)
+            .push_key(&bitcoin::PublicKey::from_slice(&public_key.to_bytes()).unwrap())
+            .push_opcode(RUST_BITCOIN_OP_CHECKSIG)
+            .into_script();
+
+        assert_eq!(cltv.witness_script().0, rust_bitcoin_script.to_bytes());
+        assert_eq!(
+            cltv.script_pubkey().0,
+            bitcoin::ScriptBuf::new_p2wsh(&rust_bitcoin_script.wscript_hash()).into_bytes()
+        );
+        assert_eq!(
+            cltv.address(Network::Bitcoin).unwrap().to_string(),
+            bitcoin::Address::p2wsh(&rust_bitcoin_script, bitcoin::Network::Bitcoin).to_string()
+        );
+
+        // Spending it
+        let value = Amount::from_sat(100_000);
+        let tx = BitcoinTransaction {
+            version: Version::Two,
+            lock_time,
+            input: vec![cltv.spend_input(OutPoint::new(Txid(Hash::all_zeros()), 0))],
+            output: vec![TxOut {
+                value: Amount::from_sat(99_000),
+                script_pubkey: public_key
+                    .p2wpkh_address(Network::Bitcoin)
+                    .unwrap()
+                    .script_pubkey(),
+            }],
+        };
+        let preimage = tx.build_for_signing_segwit(
+            EcdsaSighashType::All,
+            0,
+            &cltv.witness_script(),
+            value.to_sat(),
+        );
+
+        let rust_bitcoin_tx: bitcoin::Transaction =
+            bitcoin::consensus::deserialize(&tx.serialize()).unwrap();
+        let sighash = SighashCache::new(&rust_bitcoin_tx)
+            .p2wsh_signature_hash(
+                0,
+                &rust_bitcoin_script,
+                bitcoin::Amount::from_sat(value.to_sat()),
+                RustBitcoinSighashType::All,
+            )
+            .unwrap();
+        assert_eq!(
+            sha256d::Hash::hash(&preimage).to_byte_array(),
+            sighash.to_byte_array()
+        );
+
+        let witness = cltv.witness(&[0x30, 0x01]);
+        assert_eq!(
+            witness.to_vec(),
+            vec![vec![0x30, 0x01], cltv.witness_script().0]
+        );
+    }
+}
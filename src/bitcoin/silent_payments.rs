@@ -0,0 +1,394 @@
+//! [BIP-352] silent payments: paying a static `sp1...` address with a fresh taproot output
+//! only the recipient can find, derived from the keys of the inputs of the transaction.
+//!
+//! The sender adds up the secret keys of the inputs to compute an ECDH shared secret with the
+//! scan key of the recipient. Signers that cannot reveal their secret keys, such as the MPC
+//! network, can compute the shared secret on their side and derive the outputs with
+//! [`output_key`].
+//!
+//! [BIP-352]: https://github.com/bitcoin/bips/blob/master/bip-0352.mediawiki
+use core::fmt;
+
+use bech32::{
+    primitives::{
+        decode::CheckedHrpstring,
+        hrp::Hrp,
+        iter::{ByteIterExt, Fe32IterExt},
+    },
+    Bech32m, Fe32,
+};
+use k256::{
+    elliptic_curve::{ops::Reduce, sec1::ToEncodedPoint, PrimeField},
+    FieldBytes, ProjectivePoint, Scalar, U256,
+};
+
+use super::{
+    encoding::Encodable,
+    keys::PublicKey,
+    taproot::{tagged_hash, OutputKey},
+    types::{Network, OutPoint, ScriptBuf},
+};
+
+/// A silent payment address, made of the scan and spend public keys of the recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SilentPaymentAddress {
+    /// Key the recipient scans the blockchain for its payments with.
+    pub scan_key: PublicKey,
+    /// Key the recipient spends its payments with, which includes the label of the address.
+    pub spend_key: PublicKey,
+    pub network: Network,
+}
+
+impl SilentPaymentAddress {
+    pub const fn new(scan_key: PublicKey, spend_key: PublicKey, network: Network) -> Self {
+        Self {
+            scan_key,
+            spend_key,
+            network,
+        }
+    }
+
+    /// Parses a silent payment address, checking that it belongs to the given network.
+    ///
+    /// Addresses of a later version are accepted as long as they start with the keys, as
+    /// required by BIP-352 for forward compatibility.
+    pub fn parse(address: &str, network: Network) -> Result<Self, String> {
+        let mut checked = CheckedHrpstring::new::<Bech32m>(address)
+            .map_err(|e| format!("Invalid address: {e}"))?;
+        if Some(checked.hrp().as_str()) != network.silent_payment_hrp() {
+            return Err(format!("Address is not valid for network {network}"));
+        }
+
+        let version = checked
+            .remove_witness_version()
+            .ok_or("Missing silent payment version")?
+            .to_u8();
+        let data: Vec<u8> = checked.byte_iter().collect();
+        let keys = match version {
+            0 if data.len() == 66 => &data[..],
+            1..=30 if data.len() >= 66 => &data[..66],
+            0..=30 => return Err("Invalid silent payment address length".to_string()),
+            _ => return Err(format!("Unsupported silent payment version: {version}")),
+        };
+
+        Ok(Self {
+            scan_key: compressed_public_key(&keys[..33])?,
+            spend_key: compressed_public_key(&keys[33..])?,
+            network,
+        })
+    }
+}
+
+impl fmt::Display for SilentPaymentAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hrp = self.network.silent_payment_hrp().ok_or(fmt::Error)?;
+        let hrp = Hrp::parse(hrp).map_err(|_| fmt::Error)?;
+
+        let mut data = self.scan_key.compressed_bytes().to_vec();
+        data.extend_from_slice(&self.spend_key.compressed_bytes());
+
+        for c in data
+            .into_iter()
+            .bytes_to_fes()
+            .with_checksum::<Bech32m>(&hrp)
+            .with_witness_version(Fe32::Q)
+            .chars()
+        {
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The secret key of an input eligible to silent payments: a P2TR key path, P2WPKH,
+/// P2SH-P2WPKH or P2PKH input.
+#[derive(Clone, PartialEq, Eq)]
+pub struct InputKey {
+    pub secret_key: [u8; 32],
+    /// Whether the input spends a P2TR output, whose key is x-only.
+    pub is_taproot: bool,
+}
+
+/// Leaves the secret key out, so it does not end up in logs.
+impl fmt::Debug for InputKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InputKey")
+            .field("is_taproot", &self.is_taproot)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Derives the script pubkeys paying the recipients, in order, from the keys of the eligible
+/// inputs and the outpoints spent by every input of the transaction.
+pub fn derive_outputs(
+    input_keys: &[InputKey],
+    outpoints: &[OutPoint],
+    recipients: &[SilentPaymentAddress],
+) -> Result<Vec<ScriptBuf>, String> {
+    let mut outputs = Vec::with_capacity(recipients.len());
+    // Outputs paying the same scan key are numbered, so that they are all different
+    let mut counters: Vec<(PublicKey, u32)> = Vec::new();
+
+    for recipient in recipients {
+        let k = match counters
+            .iter_mut()
+            .find(|(scan_key, _)| *scan_key == recipient.scan_key)
+        {
+            Some((_, k)) => {
+                *k += 1;
+                *k
+            }
+            None => {
+                counters.push((recipient.scan_key, 0));
+                0
+            }
+        };
+
+        let shared_secret = shared_secret(input_keys, outpoints, &recipient.scan_key)?;
+        outputs.push(output_key(&shared_secret, &recipient.spend_key, k)?.script_pubkey());
+    }
+
+    Ok(outputs)
+}
+
+/// Computes the ECDH shared secret `input_hash * a * B_scan` between the sum `a` of the secret
+/// keys of the inputs and the scan key of the recipient.
+pub fn shared_secret(
+    input_keys: &[InputKey],
+    outpoints: &[OutPoint],
+    scan_key: &PublicKey,
+) -> Result<[u8; 33], String> {
+    let mut a = Scalar::ZERO;
+    for input_key in input_keys {
+        let secret_key: Option<Scalar> =
+            Scalar::from_repr(*FieldBytes::from_slice(&input_key.secret_key)).into();
+        let secret_key = secret_key
+            .filter(|secret_key| *secret_key != Scalar::ZERO)
+            .ok_or("Invalid input secret key")?;
+
+        // Taproot keys are x-only, their secret key is negated if their y coordinate is odd
+        let public_key = (ProjectivePoint::GENERATOR * secret_key).to_encoded_point(true);
+        if input_key.is_taproot && public_key.as_bytes()[0] == 0x03 {
+            a -= secret_key;
+        } else {
+            a += secret_key;
+        }
+    }
+    if a == Scalar::ZERO {
+        return Err("The input keys add up to zero".to_string());
+    }
+
+    let input_public_key = point_bytes(&(ProjectivePoint::GENERATOR * a));
+    let input_hash = input_hash(outpoints, &input_public_key)?;
+
+    let scan_key = k256::PublicKey::from_sec1_bytes(&scan_key.compressed_bytes())
+        .map_err(|_| "Invalid scan key".to_string())?;
+    Ok(point_bytes(&(scan_key.to_projective() * (input_hash * a))))
+}
+
+/// Derives the output key of the `k`-th output paying the spend key of a recipient from their
+/// shared secret: `B_spend + hash(shared_secret || k) * G`.
+pub fn output_key(
+    shared_secret: &[u8; 33],
+    spend_key: &PublicKey,
+    k: u32,
+) -> Result<OutputKey, String> {
+    let mut data = shared_secret.to_vec();
+    data.extend_from_slice(&k.to_be_bytes());
+    let t: Option<Scalar> =
+        Scalar::from_repr(tagged_hash(b"BIP0352/SharedSecret", &data).into()).into();
+    let t = t.ok_or("Invalid shared secret tweak")?;
+
+    let spend_key = k256::PublicKey::from_sec1_bytes(&spend_key.compressed_bytes())
+        .map_err(|_| "Invalid spend key".to_string())?;
+    let output_key = point_bytes(&(spend_key.to_projective() + ProjectivePoint::GENERATOR * t));
+
+    Ok(OutputKey {
+        x_only: output_key[1..]
+            .try_into()
+            .expect("compressed points are 33 bytes"),
+        odd_y: output_key[0] == 0x03,
+    })
+}
+
+/// Hashes the smallest outpoint spent by the transaction with the sum of the input keys,
+/// making the shared secret unique to the transaction.
+fn input_hash(outpoints: &[OutPoint], input_public_key: &[u8; 33]) -> Result<Scalar, String> {
+    let smallest_outpoint = outpoints
+        .iter()
+        .map(|outpoint| {
+            let mut bytes = Vec::with_capacity(36);
+            let _ = outpoint.encode(&mut bytes);
+            bytes
+        })
+        .min()
+        .ok_or("The transaction must spend at least one outpoint")?;
+
+    let mut data = smallest_outpoint;
+    data.extend_from_slice(input_public_key);
+    Ok(<Scalar as Reduce<U256>>::reduce_bytes(
+        FieldBytes::from_slice(&tagged_hash(b"BIP0352/Inputs", &data)),
+    ))
+}
+
+fn compressed_public_key(bytes: &[u8]) -> Result<PublicKey, String> {
+    if bytes.len() != 33 {
+        return Err("Silent payment keys must be compressed".to_string());
+    }
+
+    PublicKey::from_slice(bytes)
+}
+
+fn point_bytes(point: &ProjectivePoint) -> [u8; 33] {
+    point
+        .to_affine()
+        .to_encoded_point(true)
+        .as_bytes()
+        .try_into()
+        .expect("compressed points are 33 bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::keys::PrivateKey;
+    use crate::bitcoin::types::{Hash, Txid};
+
+    fn outpoint(txid: &str, vout: u32) -> OutPoint {
+        OutPoint::new(Txid(Hash::from_hex(txid).unwrap()), vout)
+    }
+
+    fn secret_key(hex: &str) -> [u8; 32] {
+        hex::decode(hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_derive_outputs() {
+        // Test vector "Simple send: two inputs" of BIP-352
+        let recipient = SilentPaymentAddress::parse(
+            "sp1qqgste7k9hx0qftg6qmwlkqtwuy6cycyavzmzj85c6qdfhjdpdjtdgqjuexzk6murw56suy3e0rd2cgqvycxttddwsvgxe2usfpxumr70xc9pkqwv",
+            Network::Bitcoin,
+        )
+        .unwrap();
+        let input_keys = [
+            InputKey {
+                secret_key: secret_key(
+                    "eadc78165ff1f8ea94ad7cfdc54990738a4c53f6e0507b42154201b8e5dff3b1",
+                ),
+                is_taproot: false,
+            },
+            InputKey {
+                secret_key: secret_key(
+                    "93f5ed907ad5b2bdbbdcb5d9116ebc0a4e1f92f910d5260237fa45a9408aad16",
+                ),
+                is_taproot: false,
+            },
+        ];
+        assert_eq!(
+            format!("{:?}", input_keys[0]),
+            "InputKey { is_taproot: false, .. }"
+        );
+        let outpoints = [
+            outpoint(
+                "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16",
+                0,
+            ),
+            outpoint(
+                "a1075db55d416d3ca199f55b6084e2115b9345e16c5cf302fc80e9d5fbf5d48d",
+                0,
+            ),
+        ];
+
+        let outputs = derive_outputs(&input_keys, &outpoints, &[recipient]).unwrap();
+        assert_eq!(
+            hex::encode(&outputs[0].0[2..]),
+            "3e9fce73d4e77a4809908e3c3a2e54ee147b9312dc5044a193d1fc85de46e3c1"
+        );
+    }
+
+    #[test]
+    fn test_derive_outputs_for_the_recipient() {
+        let public_key = |secret_key: &[u8; 32]| {
+            PrivateKey::from_slice(secret_key, Network::Bitcoin, true)
+                .unwrap()
+                .public_key()
+        };
+        let (scan_secret_key, spend_secret_key) = ([0x11; 32], [0x22; 32]);
+        let recipient = SilentPaymentAddress::new(
+            public_key(&scan_secret_key),
+            public_key(&spend_secret_key),
+            Network::Signet,
+        );
+        let input_keys: Vec<InputKey> = [0x01, 0x02, 0x05]
+            .into_iter()
+            .map(|byte| InputKey {
+                secret_key: [byte; 32],
+                is_taproot: byte != 0x05,
+            })
+            .collect();
+        let outpoints = [
+            outpoint(
+                "bc25cc0dddd0a202c21e66521a692c0586330a9a9dcc38ccd9b4d2093037f31a",
+                1,
+            ),
+            outpoint(
+                "bc25cc0dddd0a202c21e66521a692c0586330a9a9dcc38ccd9b4d2093037f31a",
+                0,
+            ),
+        ];
+
+        let outputs = derive_outputs(&input_keys, &outpoints, &[recipient, recipient]).unwrap();
+        assert_ne!(outputs[0], outputs[1]);
+
+        // The recipient finds the outputs from the public keys of the inputs, taproot keys
+        // having an even y coordinate
+        let input_public_key = input_keys
+            .iter()
+            .map(|input_key| {
+                let mut key = public_key(&input_key.secret_key).compressed_bytes();
+                if input_key.is_taproot {
+                    key[0] = 0x02;
+                }
+                k256::PublicKey::from_sec1_bytes(&key)
+                    .unwrap()
+                    .to_projective()
+            })
+            .fold(ProjectivePoint::IDENTITY, |sum, key| sum + key);
+        let input_hash = input_hash(&outpoints, &point_bytes(&input_public_key)).unwrap();
+        let scan_secret_key: Option<Scalar> =
+            Scalar::from_repr(*FieldBytes::from_slice(&scan_secret_key)).into();
+        let shared_secret =
+            point_bytes(&(input_public_key * (input_hash * scan_secret_key.unwrap())));
+
+        for (k, output) in outputs.iter().enumerate() {
+            assert_eq!(
+                *output,
+                output_key(&shared_secret, &recipient.spend_key, k as u32)
+                    .unwrap()
+                    .script_pubkey()
+            );
+        }
+    }
+
+    #[test]
+    fn test_address_round_trip() {
+        let address = "sp1qqgste7k9hx0qftg6qmwlkqtwuy6cycyavzmzj85c6qdfhjdpdjtdgqjuexzk6murw56suy3e0rd2cgqvycxttddwsvgxe2usfpxumr70xc9pkqwv";
+        let recipient = SilentPaymentAddress::parse(address, Network::Bitcoin).unwrap();
+        assert_eq!(recipient.to_string(), address);
+
+        let testnet_recipient = SilentPaymentAddress {
+            network: Network::Testnet,
+            ..recipient
+        };
+        assert!(testnet_recipient.to_string().starts_with("tsp1q"));
+        assert_eq!(
+            SilentPaymentAddress::parse(&testnet_recipient.to_string(), Network::Signet),
+            Ok(SilentPaymentAddress {
+                network: Network::Signet,
+                ..recipient
+            })
+        );
+        assert!(SilentPaymentAddress::parse(address, Network::Testnet).is_err());
+        assert!(SilentPaymentAddress::parse(address, Network::Litecoin).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! [BIP-341] Taproot outputs committing to a tree of [BIP-342] tapscripts.
+//!
+//! [BIP-341]: https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki
+//! [BIP-342]: https://github.com/bitcoin/bips/blob/master/bip-0342.mediawiki
+use k256::elliptic_curve::{ops::Reduce, sec1::ToEncodedPoint, PrimeField};
+use sha2::{Digest, Sha256};
+
+use super::{
+    address::Address,
+    encoding::Encodable,
+    script::{Builder, OP_1},
+    types::{Network, ScriptBuf},
+};
+
+/// The leaf version of tapscripts (BIP-342).
+pub const TAPSCRIPT_LEAF_VERSION: u8 = 0xc0;
+
+/// An internal key nobody knows the private key of, disabling the key path spend: the x
+/// coordinate of the point `H` suggested by BIP-341, the SHA-256 of the encoding of the
+/// generator.
+pub const NUMS_INTERNAL_KEY: [u8; 32] = [
+    0x50, 0x92, 0x9b, 0x74, 0xc1, 0xa0, 0x49, 0x54, 0xb7, 0x8b, 0x4b, 0x60, 0x35, 0xe9, 0x7a, 0x5e,
+    0x07, 0x8a, 0x5a, 0x0f, 0x28, 0xec, 0x96, 0xd5, 0x47, 0xbf, 0xee, 0x9a, 0xce, 0x80, 0x3a, 0xc0,
+];
+
+/// Computes the tagged hash `SHA256(SHA256(tag) || SHA256(tag) || data)` (BIP-340).
+pub fn tagged_hash(tag: &[u8], data: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag);
+
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    hasher.update(data);
+
+    hasher.finalize().into()
+}
+
+/// The hash of a tapscript leaf, committed to by the tree and signed by the script path
+/// spends of the leaf.
+pub fn leaf_hash(script: &ScriptBuf) -> [u8; 32] {
+    let mut data = vec![TAPSCRIPT_LEAF_VERSION];
+    let _ = script.encode(&mut data);
+
+    tagged_hash(b"TapLeaf", &data)
+}
+
+/// The hash of a branch of the tree, its children being sorted so their order does not matter.
+pub fn branch_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(first);
+    data[32..].copy_from_slice(second);
+
+    tagged_hash(b"TapBranch", &data)
+}
+
+/// The message signed by a taproot input, the `TapSighash` tagged hash of its sighash preimage
+/// built by [`super::BitcoinTransaction::build_for_signing_taproot`].
+pub fn sighash(preimage: &[u8]) -> [u8; 32] {
+    tagged_hash(b"TapSighash", preimage)
+}
+
+/// Verifies the BIP-340 Schnorr signature `R.x || s` of `message` by the x-only key `public_key`,
+/// like the output key of a P2TR output for its key path spends.
+pub fn verify_schnorr(public_key: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]) -> bool {
+    let mut sec1 = [0x02; 33];
+    sec1[1..].copy_from_slice(public_key);
+    let Ok(point) = k256::PublicKey::from_sec1_bytes(&sec1) else {
+        return false;
+    };
+    let s: Option<k256::Scalar> =
+        k256::Scalar::from_repr(*k256::FieldBytes::from_slice(&signature[32..])).into();
+    let Some(s) = s else {
+        return false;
+    };
+
+    let mut data = signature[..32].to_vec();
+    data.extend_from_slice(public_key);
+    data.extend_from_slice(message);
+    let e = <k256::Scalar as Reduce<k256::U256>>::reduce_bytes(
+        &tagged_hash(b"BIP0340/challenge", &data).into(),
+    );
+
+    // R = s * G - e * P must have an even y and the x coordinate of the signature
+    let big_r = k256::ProjectivePoint::GENERATOR * s - point.to_projective() * e;
+    if big_r == k256::ProjectivePoint::IDENTITY {
+        return false;
+    }
+    let big_r = big_r.to_affine().to_encoded_point(true);
+    big_r.as_bytes()[0] == 0x02 && big_r.as_bytes()[1..] == signature[..32]
+}
+
+/// The output key of a taproot output, the internal key tweaked with the merkle root of its
+/// script tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputKey {
+    /// The x coordinate of the key, committed to by the script pubkey.
+    pub x_only: [u8; 32],
+    /// Whether the y coordinate of the key is odd, committed to by the control blocks.
+    pub odd_y: bool,
+}
+
+impl OutputKey {
+    /// Tweaks the x-only internal key with the merkle root of the script tree, if any:
+    /// `Q = P + tagged_hash("TapTweak", P || merkle_root) * G`.
+    pub fn new(internal_key: &[u8; 32], merkle_root: Option<&[u8; 32]>) -> Result<Self, String> {
+        let mut sec1 = [0x02; 33];
+        sec1[1..].copy_from_slice(internal_key);
+        let internal_key_point = k256::PublicKey::from_sec1_bytes(&sec1)
+            .map_err(|_| "Invalid internal key".to_string())?;
+
+        let mut data = internal_key.to_vec();
+        if let Some(merkle_root) = merkle_root {
+            data.extend_from_slice(merkle_root);
+        }
+        let tweak: Option<k256::Scalar> =
+            k256::Scalar::from_repr(tagged_hash(b"TapTweak", &data).into()).into();
+        let tweak = tweak.ok_or("Invalid taproot tweak")?;
+
+        let output_key =
+            k256::ProjectivePoint::GENERATOR * tweak + internal_key_point.to_projective();
+        let output_key = k256::PublicKey::from_affine(output_key.to_affine())
+            .map_err(|_| "Invalid taproot tweak".to_string())?;
+        let encoded = output_key.to_encoded_point(true);
+
+        Ok(Self {
+            x_only: encoded.as_bytes()[1..]
+                .try_into()
+                .expect("compressed points are 33 bytes"),
+            odd_y: encoded.as_bytes()[0] == 0x03,
+        })
+    }
+
+    /// The P2TR script pubkey `OP_1 <output key>`.
+    pub fn script_pubkey(&self) -> ScriptBuf {
+        Builder::new()
+            .push_opcode(OP_1)
+            .push_slice(&self.x_only)
+            .into_script()
+    }
+
+    /// The P2TR address of the output.
+    pub fn address(&self, network: Network) -> Result<Address, String> {
+        Address::from_witness_program(1, self.x_only.to_vec(), network)
+    }
+}
+
+/// The control block proving that a leaf is part of the tree of an output key, given the hashes
+/// of the leaf's path to the root.
+pub fn control_block(
+    internal_key: &[u8; 32],
+    output_key: &OutputKey,
+    merkle_path: &[[u8; 32]],
+) -> Vec<u8> {
+    let mut control_block = vec![TAPSCRIPT_LEAF_VERSION | output_key.odd_y as u8];
+    control_block.extend_from_slice(internal_key);
+    for hash in merkle_path {
+        control_block.extend_from_slice(hash);
+    }
+
+    control_block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::hashes::Hash as _;
+    use bitcoin::key::{Secp256k1, XOnlyPublicKey};
+    use bitcoin::taproot::{LeafVersion, TapLeafHash, TaprootBuilder};
+
+    #[test]
+    fn test_nums_internal_key() {
+        let generator = k256::ProjectivePoint::GENERATOR
+            .to_affine()
+            .to_encoded_point(false);
+
+        assert_eq!(
+            NUMS_INTERNAL_KEY,
+            <[u8; 32]>::from(Sha256::digest(generator.as_bytes()))
+        );
+    }
+
+    #[test]
+    fn test_verify_schnorr() {
+        use bitcoin::secp256k1::{Keypair, Message};
+
+        let secp = Secp256k1::new();
+        let keypair = Keypair::from_seckey_slice(&secp, &[7; 32]).unwrap();
+        let (x_only, _) = keypair.x_only_public_key();
+        let message = [9; 32];
+        let signature = secp
+            .sign_schnorr_no_aux_rand(&Message::from_digest(message), &keypair)
+            .serialize();
+
+        assert!(verify_schnorr(&x_only.serialize(), &message, &signature));
+        assert!(!verify_schnorr(&x_only.serialize(), &[8; 32], &signature));
+        assert!(!verify_schnorr(&NUMS_INTERNAL_KEY, &message, &signature));
+
+        let mut high_s = signature;
+        high_s[32..].copy_from_slice(&[0xff; 32]);
+        assert!(!verify_schnorr(&x_only.serialize(), &message, &high_s));
+    }
+
+    #[test]
+    fn test_two_leaf_tree() {
+        let secp = Secp256k1::verification_only();
+        let a = ScriptBuf(vec![0x51]);
+        let b = ScriptBuf(vec![0x52, 0x75, 0x51]);
+
+        let merkle_root = branch_hash(&leaf_hash(&a), &leaf_hash(&b));
+        let output_key = OutputKey::new(&NUMS_INTERNAL_KEY, Some(&merkle_root)).unwrap();
+
+        let internal_key = XOnlyPublicKey::from_slice(&NUMS_INTERNAL_KEY).unwrap();
+        let rust_bitcoin_a = bitcoin::ScriptBuf::from_bytes(a.0.clone());
+        let rust_bitcoin_b = bitcoin::ScriptBuf::from_bytes(b.0.clone());
+        let spend_info = TaprootBuilder::new()
+            .add_leaf(1, rust_bitcoin_a.clone())
+            .unwrap()
+            .add_leaf(1, rust_bitcoin_b.clone())
+            .unwrap()
+            .finalize(&secp, internal_key)
+            .unwrap();
+
+        assert_eq!(
+            leaf_hash(&a),
+            TapLeafHash::from_script(&rust_bitcoin_a, LeafVersion::TapScript).to_byte_array()
+        );
+        assert_eq!(
+            output_key.x_only,
+            spend_info.output_key().to_x_only_public_key().serialize()
+        );
+        assert_eq!(
+            output_key.script_pubkey().0,
+            bitcoin::ScriptBuf::new_p2tr_tweaked(spend_info.output_key()).into_bytes()
+        );
+        assert_eq!(
+            control_block(&NUMS_INTERNAL_KEY, &output_key, &[leaf_hash(&b)]),
+            spend_info
+                .control_block(&(rust_bitcoin_a, LeafVersion::TapScript))
+                .unwrap()
+                .serialize()
+        );
+    }
+}
@@ -9,7 +9,7 @@ use std::{
     io::{BufRead, Write},
 };
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 use serde::Deserializer;
 use serde::{Deserialize, Serialize};
@@ -25,7 +25,16 @@ use serde::{Deserialize, Serialize};
 ///
 /// [Bitcoin Devguide]: https://developer.bitcoin.org/devguide/transactions.html#locktime-and-sequence-number
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Serialize, BorshSerialize, BorshDeserialize, JsonSchema,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    JsonSchema,
 )]
 pub struct LockTime(u32);
 
@@ -33,6 +42,12 @@ impl LockTime {
     /// The number of bytes that the locktime contributes to the size of a transaction.
     pub const SIZE: usize = 4; // Serialized length of a u32.
 
+    /// Creates a lock time from its consensus encoded value, every `u32` being either a
+    /// block height or a timestamp.
+    pub const fn from_consensus(n: u32) -> Self {
+        Self(n)
+    }
+
     pub fn from_height(height: u32) -> Result<Self, String> {
         if Height::is_valid(height) {
             Ok(Self(height))
@@ -1,15 +1,18 @@
 //! Minimal required Bitcoin types, inspired by <https://github.com/rust-bitcoin/rust-bitcoin>
 mod lock_time;
+mod network;
 mod script_buf;
 mod sighash;
 mod transaction_type;
 mod tx_in;
 mod tx_out;
+mod utxo;
 mod version;
 
 pub use self::lock_time::height::Height;
 pub use self::lock_time::time::Time;
 pub use self::lock_time::LockTime;
+pub use self::network::Network;
 pub use self::script_buf::ScriptBuf;
 pub use self::sighash::EcdsaSighashType;
 pub use self::transaction_type::TransactionType;
@@ -21,4 +24,5 @@ pub use self::tx_in::Txid;
 pub use self::tx_in::Witness;
 pub use self::tx_out::Amount;
 pub use self::tx_out::TxOut;
+pub use self::utxo::Utxo;
 pub use self::version::Version;
@@ -0,0 +1,327 @@
+use core::fmt;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{Amount, ScriptBuf};
+
+/// The Bitcoin network a transaction or an address belongs to.
+///
+/// Holds the parameters that differ between networks, such as address prefixes
+/// and the human-readable part of SegWit addresses. Bitcoin-family chains sharing the
+/// transaction format (Litecoin, Dogecoin, Bitcoin Cash) are supported as additional networks.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Network {
+    /// Bitcoin mainnet.
+    #[default]
+    Bitcoin,
+    /// Bitcoin testnet3.
+    Testnet,
+    /// Bitcoin testnet4 (BIP-94).
+    Testnet4,
+    /// Bitcoin signet (BIP-325).
+    Signet,
+    /// Bitcoin regtest.
+    Regtest,
+    /// Litecoin mainnet.
+    Litecoin,
+    /// Litecoin testnet.
+    LitecoinTestnet,
+    /// Dogecoin mainnet.
+    Dogecoin,
+    /// Dogecoin testnet.
+    DogecoinTestnet,
+    /// Bitcoin Cash mainnet.
+    BitcoinCash,
+    /// Bitcoin Cash testnet.
+    BitcoinCashTestnet,
+}
+
+impl Network {
+    /// The message start bytes identifying the network on the P2P layer.
+    pub const fn magic(&self) -> [u8; 4] {
+        match self {
+            Self::Bitcoin => [0xf9, 0xbe, 0xb4, 0xd9],
+            Self::Testnet => [0x0b, 0x11, 0x09, 0x07],
+            Self::Testnet4 => [0x1c, 0x16, 0x3f, 0x28],
+            Self::Signet => [0x0a, 0x03, 0xcf, 0x40],
+            Self::Regtest => [0xfa, 0xbf, 0xb5, 0xda],
+            Self::Litecoin => [0xfb, 0xc0, 0xb6, 0xdb],
+            Self::LitecoinTestnet => [0xfd, 0xd2, 0xc8, 0xf1],
+            Self::Dogecoin => [0xc0, 0xc0, 0xc0, 0xc0],
+            Self::DogecoinTestnet => [0xfc, 0xc1, 0xb7, 0xdc],
+            Self::BitcoinCash => [0xe3, 0xe1, 0xf3, 0xe8],
+            Self::BitcoinCashTestnet => [0xf4, 0xe5, 0xf3, 0xf4],
+        }
+    }
+
+    /// The human-readable part of SegWit addresses (BIP-173), if the network supports SegWit.
+    pub const fn bech32_hrp(&self) -> Option<&'static str> {
+        match self {
+            Self::Bitcoin => Some("bc"),
+            Self::Testnet | Self::Testnet4 | Self::Signet => Some("tb"),
+            Self::Regtest => Some("bcrt"),
+            Self::Litecoin => Some("ltc"),
+            Self::LitecoinTestnet => Some("tltc"),
+            Self::Dogecoin | Self::DogecoinTestnet => None,
+            Self::BitcoinCash | Self::BitcoinCashTestnet => None,
+        }
+    }
+
+    /// The human-readable part of silent payment addresses (BIP-352), if the network
+    /// supports them.
+    pub const fn silent_payment_hrp(&self) -> Option<&'static str> {
+        match self {
+            Self::Bitcoin => Some("sp"),
+            Self::Testnet | Self::Testnet4 | Self::Signet | Self::Regtest => Some("tsp"),
+            _ => None,
+        }
+    }
+
+    /// The prefix of CashAddr addresses, if the network uses them.
+    pub const fn cashaddr_prefix(&self) -> Option<&'static str> {
+        match self {
+            Self::BitcoinCash => Some("bitcoincash"),
+            Self::BitcoinCashTestnet => Some("bchtest"),
+            _ => None,
+        }
+    }
+
+    /// The version byte of base58 encoded P2PKH addresses.
+    pub const fn p2pkh_prefix(&self) -> u8 {
+        match self {
+            Self::Bitcoin | Self::BitcoinCash => 0x00,
+            Self::Testnet | Self::Testnet4 | Self::Signet | Self::Regtest => 0x6f,
+            Self::Litecoin => 0x30,
+            Self::LitecoinTestnet => 0x6f,
+            Self::Dogecoin => 0x1e,
+            Self::DogecoinTestnet => 0x71,
+            Self::BitcoinCashTestnet => 0x6f,
+        }
+    }
+
+    /// The version byte of base58 encoded P2SH addresses.
+    pub const fn p2sh_prefix(&self) -> u8 {
+        match self {
+            Self::Bitcoin | Self::BitcoinCash => 0x05,
+            Self::Testnet | Self::Testnet4 | Self::Signet | Self::Regtest => 0xc4,
+            Self::Litecoin => 0x32,
+            Self::LitecoinTestnet => 0x3a,
+            Self::Dogecoin => 0x16,
+            Self::DogecoinTestnet => 0xc4,
+            Self::BitcoinCashTestnet => 0xc4,
+        }
+    }
+
+    /// The version byte of WIF encoded private keys.
+    pub const fn wif_prefix(&self) -> u8 {
+        match self {
+            Self::Bitcoin | Self::BitcoinCash => 0x80,
+            Self::Testnet | Self::Testnet4 | Self::Signet | Self::Regtest => 0xef,
+            Self::Litecoin => 0xb0,
+            Self::LitecoinTestnet => 0xef,
+            Self::Dogecoin => 0x9e,
+            Self::DogecoinTestnet => 0xf1,
+            Self::BitcoinCashTestnet => 0xef,
+        }
+    }
+
+    /// The version bytes of base58 encoded extended public keys (BIP-32).
+    pub const fn xpub_prefix(&self) -> [u8; 4] {
+        match self {
+            Self::Dogecoin => [0x02, 0xfa, 0xca, 0xfd],
+            _ if self.is_test_network() => [0x04, 0x35, 0x87, 0xcf],
+            _ => [0x04, 0x88, 0xb2, 0x1e],
+        }
+    }
+
+    /// Returns whether this is a test network.
+    pub const fn is_test_network(&self) -> bool {
+        !matches!(
+            self,
+            Self::Bitcoin | Self::Litecoin | Self::Dogecoin | Self::BitcoinCash
+        )
+    }
+
+    /// The minimum value an output paying to `script_pubkey` must have to be relayed.
+    ///
+    /// Bitcoin and Litecoin derive it from the cost of spending the output at the dust relay
+    /// fee rate, like Bitcoin Core's `GetDustThreshold`. Dogecoin uses a flat limit.
+    /// Provably unspendable (`OP_RETURN`) outputs have no dust threshold.
+    pub fn dust_threshold(&self, script_pubkey: &ScriptBuf) -> Amount {
+        match self {
+            Self::Dogecoin | Self::DogecoinTestnet if !is_op_return(script_pubkey) => {
+                Amount::from_sat(DOGECOIN_DUST_LIMIT)
+            }
+            _ => self.dust_threshold_with_relay_fee(script_pubkey, DUST_RELAY_FEE_PER_KVB),
+        }
+    }
+
+    /// The dust threshold of an output paying to `script_pubkey` at a custom dust relay fee
+    /// rate, in satoshis per kilo virtual byte, like Bitcoin Core's `-dustrelayfee`.
+    ///
+    /// A rate of zero disables the dust threshold. The flat limit of Dogecoin does not apply.
+    pub fn dust_threshold_with_relay_fee(
+        &self,
+        script_pubkey: &ScriptBuf,
+        dust_relay_fee_per_kvb: u64,
+    ) -> Amount {
+        if is_op_return(script_pubkey) {
+            return Amount::ZERO;
+        }
+
+        let script_len = script_pubkey.0.len() as u64;
+        // value + compact size of the script + script
+        let output_size = 8 + compact_size_len(script_len) + script_len;
+        // outpoint + empty script sig + sequence, plus the discounted witness
+        let spend_size = if is_witness_program(script_pubkey) {
+            32 + 4 + 1 + 107 / 4 + 4
+        } else {
+            32 + 4 + 1 + 107 + 4
+        };
+
+        Amount::from_sat((output_size + spend_size) * dust_relay_fee_per_kvb / 1000)
+    }
+}
+
+/// `OP_RETURN`, marking an output as provably unspendable.
+const OP_RETURN: u8 = 0x6a;
+
+/// The default dust relay fee of Bitcoin and Litecoin, in satoshis per kilo virtual byte.
+const DUST_RELAY_FEE_PER_KVB: u64 = 3_000;
+
+/// The default dust limit of Dogecoin (0.01 DOGE), in koinu.
+const DOGECOIN_DUST_LIMIT: u64 = 1_000_000;
+
+const fn compact_size_len(n: u64) -> u64 {
+    match n {
+        0..=0xFC => 1,
+        0xFD..=0xFFFF => 3,
+        0x10000..=0xFFFFFFFF => 5,
+        _ => 9,
+    }
+}
+
+fn is_op_return(script_pubkey: &ScriptBuf) -> bool {
+    script_pubkey.0.first() == Some(&OP_RETURN)
+}
+
+fn is_witness_program(script_pubkey: &ScriptBuf) -> bool {
+    let script = &script_pubkey.0;
+    (4..=42).contains(&script.len())
+        && (script[0] == 0x00 || (0x51..=0x60).contains(&script[0]))
+        && script[1] as usize == script.len() - 2
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Bitcoin => "bitcoin",
+            Self::Testnet => "testnet",
+            Self::Testnet4 => "testnet4",
+            Self::Signet => "signet",
+            Self::Regtest => "regtest",
+            Self::Litecoin => "litecoin",
+            Self::LitecoinTestnet => "litecoin_testnet",
+            Self::Dogecoin => "dogecoin",
+            Self::DogecoinTestnet => "dogecoin_testnet",
+            Self::BitcoinCash => "bitcoin_cash",
+            Self::BitcoinCashTestnet => "bitcoin_cash_testnet",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_serde() {
+        let network: Network = serde_json::from_str(r#""signet""#).unwrap();
+        assert_eq!(network, Network::Signet);
+        assert_eq!(
+            serde_json::to_string(&Network::Regtest).unwrap(),
+            r#""regtest""#
+        );
+        assert_eq!(Network::Testnet4.to_string(), "testnet4");
+        assert_eq!(
+            serde_json::to_string(&Network::LitecoinTestnet).unwrap(),
+            r#""litecoin_testnet""#
+        );
+    }
+
+    #[test]
+    fn test_network_params() {
+        assert_eq!(Network::Bitcoin.bech32_hrp(), Some("bc"));
+        assert_eq!(Network::Signet.bech32_hrp(), Some("tb"));
+        assert_eq!(Network::Regtest.bech32_hrp(), Some("bcrt"));
+        assert_eq!(Network::Litecoin.bech32_hrp(), Some("ltc"));
+        assert_eq!(Network::Dogecoin.bech32_hrp(), None);
+        assert_eq!(Network::BitcoinCash.cashaddr_prefix(), Some("bitcoincash"));
+        assert_eq!(Network::Bitcoin.cashaddr_prefix(), None);
+        assert_eq!(Network::Testnet.p2pkh_prefix(), 0x6f);
+        assert_eq!(Network::Bitcoin.p2sh_prefix(), 0x05);
+        assert_eq!(Network::Bitcoin.magic(), [0xf9, 0xbe, 0xb4, 0xd9]);
+        assert_eq!(Network::Signet.xpub_prefix(), [0x04, 0x35, 0x87, 0xcf]);
+        assert_eq!(Network::Dogecoin.xpub_prefix(), [0x02, 0xfa, 0xca, 0xfd]);
+        assert!(!Network::Bitcoin.is_test_network());
+        assert!(!Network::Dogecoin.is_test_network());
+        assert!(Network::LitecoinTestnet.is_test_network());
+    }
+
+    #[test]
+    fn test_dust_threshold() {
+        let p2pkh =
+            ScriptBuf::from_hex("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac").unwrap();
+        let p2wpkh = ScriptBuf::from_hex("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+        let p2tr = ScriptBuf::from_hex(
+            "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+        )
+        .unwrap();
+        let op_return = ScriptBuf::from_hex("6a0401020304").unwrap();
+
+        // Values from Bitcoin Core's default dust relay policy
+        assert_eq!(
+            Network::Bitcoin.dust_threshold(&p2pkh),
+            Amount::from_sat(546)
+        );
+        assert_eq!(
+            Network::Bitcoin.dust_threshold(&p2wpkh),
+            Amount::from_sat(294)
+        );
+        assert_eq!(
+            Network::Bitcoin.dust_threshold(&p2tr),
+            Amount::from_sat(330)
+        );
+        assert_eq!(Network::Bitcoin.dust_threshold(&op_return), Amount::ZERO);
+        assert_eq!(
+            Network::Dogecoin.dust_threshold(&p2pkh),
+            Amount::from_sat(1_000_000)
+        );
+        assert_eq!(
+            Network::Bitcoin.dust_threshold_with_relay_fee(&p2pkh, 6_000),
+            Amount::from_sat(1092)
+        );
+        assert_eq!(
+            Network::Bitcoin.dust_threshold_with_relay_fee(&p2wpkh, 0),
+            Amount::ZERO
+        );
+    }
+}
@@ -1,12 +1,14 @@
 use core::fmt;
 use std::io::{BufRead, Write};
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 
 use crate::bitcoin::encoding::{encode::Encodable, Decodable};
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, JsonSchema)]
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema, JsonSchema,
+)]
 pub struct ScriptBuf(pub Vec<u8>);
 
 impl ScriptBuf {
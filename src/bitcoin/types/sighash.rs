@@ -1,11 +1,32 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
 )]
 #[borsh(use_discriminant = true)]
 pub enum EcdsaSighashType {
     /// 0x1: Sign all outputs.
     All = 0x01,
+    /// 0x41: Sign all outputs with the Bitcoin Cash replay protection flag (SIGHASH_FORKID).
+    AllForkId = 0x41,
+}
+
+impl EcdsaSighashType {
+    /// The SIGHASH_FORKID flag used by Bitcoin Cash.
+    pub const FORKID: u8 = 0x40;
+
+    /// Returns whether the sighash type has the SIGHASH_FORKID flag set.
+    pub const fn has_fork_id(&self) -> bool {
+        (*self as u8) & Self::FORKID != 0
+    }
 }
@@ -1,7 +1,7 @@
 use core::fmt;
 use std::{io::BufRead, str::FromStr};
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +17,7 @@ use crate::bitcoin::encoding::{encode::Encodable, extensions::WriteExt, Decodabl
     Deserialize,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     JsonSchema,
 )]
 pub struct Hash(pub [u8; 32]);
@@ -3,7 +3,7 @@ use std::{
     io::{BufRead, Write},
 };
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 use serde::{de::MapAccess, Deserialize, Deserializer, Serialize};
 
@@ -18,7 +18,16 @@ use crate::bitcoin::encoding::{Decodable, Encodable};
 ///
 /// * [COutPoint definition](https://github.com/bitcoin/bitcoin/blob/345457b542b6a980ccfbc868af0970a6f91d1b82/src/primitives/transaction.h#L26)
 #[derive(
-    Debug, Copy, Clone, Eq, PartialEq, Serialize, BorshSerialize, BorshDeserialize, JsonSchema,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Serialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    JsonSchema,
 )]
 pub struct OutPoint {
     /// The referenced transaction's txid.
@@ -1,6 +1,6 @@
 use std::io::{BufRead, Write};
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +17,7 @@ use crate::bitcoin::encoding::{Decodable, Encodable};
     Deserialize,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     JsonSchema,
 )]
 pub struct Sequence(pub u32);
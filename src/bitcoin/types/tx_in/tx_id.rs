@@ -4,7 +4,7 @@ use std::io::{BufRead, Write};
 use crate::bitcoin::encoding::{Decodable, Encodable};
 
 use super::hash::Hash;
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +18,7 @@ use serde::{Deserialize, Serialize};
     Deserialize,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     JsonSchema,
 )]
 pub struct Txid(pub Hash);
@@ -2,7 +2,7 @@ use std::io::{self, BufRead, Write};
 
 use crate::bitcoin::encoding::{Decodable, Encodable};
 use crate::bitcoin::types::script_buf::ScriptBuf;
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +26,7 @@ use super::{outpoint::OutPoint, sequence::Sequence, witness::Witness};
     Deserialize,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     JsonSchema,
 )]
 pub struct TxIn {
@@ -1,11 +1,11 @@
 use std::io::{BufRead, Write};
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::bitcoin::{
-    encoding::{Decodable, Encodable},
+    encoding::{utils::VarInt, Decodable, Encodable},
     types::script_buf::ScriptBuf,
 };
 
@@ -31,6 +31,7 @@ use super::amount::Amount;
     Deserialize,
     BorshSerialize,
     BorshDeserialize,
+    BorshSchema,
     JsonSchema,
 )]
 pub struct TxOut {
@@ -40,6 +41,14 @@ pub struct TxOut {
     pub script_pubkey: ScriptBuf,
 }
 
+impl TxOut {
+    /// Returns the number of bytes this output contributes to a transaction size.
+    pub fn size(&self) -> usize {
+        let script_len = self.script_pubkey.0.len();
+        Amount::SIZE + VarInt(script_len as u64).size() + script_len
+    }
+}
+
 impl Encodable for TxOut {
     fn encode<W: Write + ?Sized>(&self, w: &mut W) -> Result<usize, std::io::Error> {
         let mut len = 0;
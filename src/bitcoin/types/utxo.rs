@@ -0,0 +1,139 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{Amount, OutPoint, ScriptBuf, Sequence, TxIn, Txid, Witness};
+
+/// An unspent transaction output that is being spent by a transaction.
+///
+/// Besides the outpoint it carries the value and the script pubkey of the previous output,
+/// which are committed to by the SegWit sighash (BIP-143).
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    JsonSchema,
+)]
+pub struct Utxo {
+    /// The txid of the transaction that created the output.
+    pub txid: Txid,
+    /// The index of the output in its transaction.
+    pub vout: u32,
+    /// The value of the output.
+    pub value: Amount,
+    /// The script which must be satisfied for the output to be spent.
+    pub script_pubkey: ScriptBuf,
+}
+
+impl Utxo {
+    pub const fn new(txid: Txid, vout: u32, value: Amount, script_pubkey: ScriptBuf) -> Self {
+        Self {
+            txid,
+            vout,
+            value,
+            script_pubkey,
+        }
+    }
+
+    /// The outpoint referencing this output.
+    pub const fn outpoint(&self) -> OutPoint {
+        OutPoint::new(self.txid, self.vout)
+    }
+
+    /// Returns whether the script pubkey is a P2WPKH script (`OP_0 <20-byte hash>`).
+    pub fn is_p2wpkh(&self) -> bool {
+        self.script_pubkey.0.len() == 22
+            && self.script_pubkey.0[0] == 0x00
+            && self.script_pubkey.0[1] == 0x14
+    }
+
+    /// Returns whether the script pubkey is a P2PKH script
+    /// (`OP_DUP OP_HASH160 <20-byte hash> OP_EQUALVERIFY OP_CHECKSIG`).
+    pub fn is_p2pkh(&self) -> bool {
+        let script = &self.script_pubkey.0;
+        script.len() == 25 && script[..3] == [0x76, 0xa9, 0x14] && script[23..] == [0x88, 0xac]
+    }
+
+    /// Returns whether the script pubkey is a P2TR script (`OP_1 <32-byte key>`).
+    pub fn is_p2tr(&self) -> bool {
+        self.script_pubkey.0.len() == 34
+            && self.script_pubkey.0[0] == 0x51
+            && self.script_pubkey.0[1] == 0x20
+    }
+
+    /// Returns whether the script pubkey is a pay-to-anchor script (`OP_1 <0x4e73>`).
+    pub fn is_p2a(&self) -> bool {
+        self.script_pubkey.0 == [0x51, 0x02, 0x4e, 0x73]
+    }
+
+    /// The script code used when computing the SegWit sighash for this output.
+    ///
+    /// For P2WPKH outputs this is the equivalent P2PKH script, as defined in BIP-143.
+    /// Any other script pubkey is returned as is.
+    pub fn script_code(&self) -> ScriptBuf {
+        if self.is_p2wpkh() {
+            let mut script = vec![0x76, 0xa9, 0x14];
+            script.extend_from_slice(&self.script_pubkey.0[2..]);
+            script.extend_from_slice(&[0x88, 0xac]);
+            ScriptBuf(script)
+        } else {
+            self.script_pubkey.clone()
+        }
+    }
+}
+
+impl From<&Utxo> for TxIn {
+    fn from(utxo: &Utxo) -> Self {
+        Self {
+            previous_output: utxo.outpoint(),
+            script_sig: ScriptBuf::default(),
+            sequence: Sequence::MAX,
+            witness: Witness::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::types::Hash;
+
+    #[test]
+    fn test_script_code_for_p2wpkh() {
+        let utxo = Utxo::new(
+            Txid(Hash::all_zeros()),
+            0,
+            Amount::from_sat(1000),
+            ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap(),
+        );
+
+        assert!(utxo.is_p2wpkh());
+        assert!(!utxo.is_p2pkh());
+        assert!(!utxo.is_p2tr());
+        assert_eq!(
+            utxo.script_code(),
+            ScriptBuf::from_hex("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_into_tx_in() {
+        let utxo = Utxo::new(
+            Txid(Hash::all_zeros()),
+            3,
+            Amount::from_sat(1000),
+            ScriptBuf::default(),
+        );
+        let txin = TxIn::from(&utxo);
+
+        assert_eq!(txin.previous_output, OutPoint::new(Txid::all_zeros(), 3));
+        assert_eq!(txin.sequence, Sequence::MAX);
+        assert!(txin.witness.is_empty());
+    }
+}
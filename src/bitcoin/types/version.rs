@@ -3,7 +3,7 @@ use std::{
     io::{self, BufRead, Write},
 };
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use schemars::JsonSchema;
 use serde::Deserializer;
 use serde::{Deserialize, Serialize};
@@ -12,16 +12,25 @@ use crate::bitcoin::encoding::{Decodable, Encodable};
 
 /// The transaction version.
 ///
-/// Currently, as specified by [BIP-68], only version 1 and 2 are considered standard.
+/// Currently, as specified by [BIP-68] and [BIP-431], only version 1, 2 and 3 are considered
+/// standard.
 ///
 /// [BIP-68]: https://github.com/bitcoin/bips/blob/master/bip-0068.mediawiki
-#[derive(Debug, Copy, PartialEq, Eq, Clone, BorshSerialize, BorshDeserialize, JsonSchema)]
+/// [BIP-431]: https://github.com/bitcoin/bips/blob/master/bip-0431.mediawiki
+#[derive(
+    Debug, Copy, PartialEq, Eq, Clone, BorshSerialize, BorshDeserialize, BorshSchema, JsonSchema,
+)]
 #[borsh(use_discriminant = true)]
 pub enum Version {
+    /// Version 0, non-standard and only used by virtual transactions such as the ones of BIP-322
+    Zero = 0,
     /// The original Bitcoin transaction version (pre-BIP-68)
     One = 1,
     /// The second Bitcoin transaction version (post-BIP-68)
     Two = 2,
+    /// Topologically restricted until confirmation (TRUC) transactions (BIP-431), limited in
+    /// size and to one unconfirmed parent or child so they can be fee-bumped reliably
+    Three = 3,
 }
 
 impl Version {
@@ -51,8 +60,10 @@ impl Decodable for Version {
         let int = i32::from_le_bytes(buf);
 
         match int {
+            0 => Ok(Self::Zero),
             1 => Ok(Self::One),
             2 => Ok(Self::Two),
+            3 => Ok(Self::Three),
             _ => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 "Invalid version number",
@@ -67,8 +78,10 @@ impl Serialize for Version {
         S: serde::Serializer,
     {
         let version_number = match self {
+            Self::Zero => 0,
             Self::One => 1,
             Self::Two => 2,
+            Self::Three => 3,
         };
         serializer.serialize_i32(version_number)
     }
@@ -98,8 +111,10 @@ impl<'de> Deserialize<'de> for Version {
                     .map_err(serde::de::Error::custom)?;
 
                 match value_parsed {
+                    0 => Ok(Version::Zero),
                     1 => Ok(Version::One),
                     2 => Ok(Version::Two),
+                    3 => Ok(Version::Three),
                     _ => Err(serde::de::Error::custom("Invalid version number")),
                 }
             }
@@ -109,8 +124,10 @@ impl<'de> Deserialize<'de> for Version {
                 E: serde::de::Error,
             {
                 match value {
+                    0 => Ok(Version::Zero),
                     1 => Ok(Version::One),
                     2 => Ok(Version::Two),
+                    3 => Ok(Version::Three),
                     _ => Err(serde::de::Error::custom("Invalid version number")),
                 }
             }
@@ -120,8 +137,10 @@ impl<'de> Deserialize<'de> for Version {
                 E: serde::de::Error,
             {
                 match value {
+                    0 => Ok(Version::Zero),
                     1 => Ok(Version::One),
                     2 => Ok(Version::Two),
+                    3 => Ok(Version::Three),
                     _ => Err(serde::de::Error::custom("Invalid version number")),
                 }
             }
@@ -134,8 +153,10 @@ impl<'de> Deserialize<'de> for Version {
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let version_number = match self {
+            Self::Zero => "0",
             Self::One => "1",
             Self::Two => "2",
+            Self::Three => "3",
         };
         write!(f, "{version_number}")
     }
@@ -233,4 +254,14 @@ mod tests {
         let version: Version = serde_json::from_str(json).unwrap();
         assert_eq!(version, Version::Two);
     }
+
+    #[test]
+    fn test_version_three() {
+        let version: Version = serde_json::from_str(r#""3""#).unwrap();
+        assert_eq!(version, Version::Three);
+        assert_eq!(version.to_hex(), "03000000");
+
+        let mut cursor = Cursor::new(version.to_vec());
+        assert_eq!(Version::decode(&mut cursor).unwrap(), Version::Three);
+    }
 }
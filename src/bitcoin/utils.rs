@@ -1,37 +1,9 @@
 //! Utility functions for serialization and encoding of Bitcoin data structures
-fn encode_signature_as_der(signature_bytes: &[u8]) -> Vec<u8> {
-    assert_eq!(
-        signature_bytes.len(),
-        64,
-        "Signature must be 64 bytes long (32 bytes for R and 32 bytes for S)"
-    );
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
 
-    let (r, s) = signature_bytes.split_at(32);
-    let r_der = encode_asn1_integer(r);
-    let s_der = encode_asn1_integer(s);
-
-    let total_len = r_der.len() + s_der.len();
-
-    let mut der = vec![0x30, total_len as u8];
-    der.extend_from_slice(&r_der);
-    der.extend_from_slice(&s_der);
-
-    der
-}
-
-fn encode_asn1_integer(bytes: &[u8]) -> Vec<u8> {
-    let mut integer = bytes.to_vec();
-
-    // if the most significant bit is set, prepend a 0x00 byte
-    if integer[0] & 0x80 != 0 {
-        integer.insert(0, 0x00);
-    }
-
-    let mut result = vec![0x02, integer.len() as u8];
-    result.extend_from_slice(&integer);
-
-    result
-}
+use super::constants::{SECP256K1_HALF_ORDER, SECP256K1_ORDER};
+use super::types::EcdsaSighashType;
 
 /// Build the scriptSig from the DER signature and the public key
 pub fn build_script_sig(der_signature: &[u8], public_key_bytes: &[u8]) -> Vec<u8> {
@@ -45,17 +17,80 @@ pub fn build_script_sig(der_signature: &[u8], public_key_bytes: &[u8]) -> Vec<u8
     script_sig
 }
 
+/// Computes `RIPEMD160(SHA256(data))`, the hash committed to by P2PKH and P2WPKH scripts
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// Returns whether `s` is in the lower half of the curve order, as required by Bitcoin's
+/// standardness rules (BIP-62)
+pub fn is_low_s(s: &[u8; 32]) -> bool {
+    s <= &SECP256K1_HALF_ORDER
+}
+
+/// Normalize `s` to its low-S form by replacing a high `s` with `n - s`
+///
+/// The MPC signer doesn't guarantee low-S output and nodes reject high-S signatures
+/// as non-canonical, so every signature must be normalized before being broadcast.
+pub fn normalize_s(s: &[u8; 32]) -> [u8; 32] {
+    if is_low_s(s) {
+        return *s;
+    }
+
+    let mut normalized = [0u8; 32];
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut diff = SECP256K1_ORDER[i] as i16 - s[i] as i16 - borrow;
+        borrow = 0;
+        if diff < 0 {
+            diff += 256;
+            borrow = 1;
+        }
+        normalized[i] = diff as u8;
+    }
+
+    normalized
+}
+
 /// Serialize the ECDSA signature from the raw bytes and the SIGHASH type
+///
+/// The S value is normalized to low-S before encoding.
 pub fn serialize_ecdsa_signature(signature_bytes: &[u8], sighash_type: u8) -> Vec<u8> {
-    // 1. Encode the signature as DER format
-    let mut der_signature = encode_signature_as_der(signature_bytes);
+    assert_eq!(
+        signature_bytes.len(),
+        64,
+        "Signature must be 64 bytes long (32 bytes for R and 32 bytes for S)"
+    );
+
+    // 1. Normalize S to low-S
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&signature_bytes[..32]);
+    let s: [u8; 32] = signature_bytes[32..].try_into().unwrap();
+    signature[32..].copy_from_slice(&normalize_s(&s));
+
+    // 2. Encode the signature as DER format
+    let mut der_signature = crate::der::encode_signature(&signature[..32], &signature[32..]);
 
-    // 2. Append the SIGHASH type
+    // 3. Append the SIGHASH type
     der_signature.push(sighash_type);
 
     der_signature
 }
 
+/// Serialize the ECDSA signature from the raw 32-byte r and s scalars returned by the signer
+/// and append the SIGHASH type, ready to be placed in a witness or a scriptSig
+pub fn serialize_ecdsa_signature_from_scalars(
+    r: &[u8; 32],
+    s: &[u8; 32],
+    sighash_type: EcdsaSighashType,
+) -> Vec<u8> {
+    let mut signature_bytes = Vec::with_capacity(64);
+    signature_bytes.extend_from_slice(r);
+    signature_bytes.extend_from_slice(s);
+
+    serialize_ecdsa_signature(&signature_bytes, sighash_type as u8)
+}
+
 /// Serialize the ECDSA signature from string representations of big R and S
 pub fn serialize_ecdsa_signature_from_str(big_r: &str, s: &str) -> Vec<u8> {
     // Generate the signature bytes from the hex strings
@@ -100,6 +135,103 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialize_ecdsa_signature_from_scalars() {
+        let r: [u8; 32] =
+            hex::decode("B96BFA3DA6BB4BB74EEEE9C20970725C5782F07724CD1BEFBD265C5AD5C63948")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let s: [u8; 32] =
+            hex::decode("49283B618968DEFB0E660EA703D193BC1D213F5DD811A2D13307FCA01E20C5C0")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&r);
+        compact[32..].copy_from_slice(&s);
+        let signature = bitcoin::ecdsa::Signature {
+            signature: Signature::from_compact(&compact).unwrap(),
+            sighash_type: bitcoin::EcdsaSighashType::All,
+        };
+
+        assert_eq!(
+            serialize_ecdsa_signature_from_scalars(&r, &s, EcdsaSighashType::All),
+            signature.serialize().to_vec()
+        );
+    }
+
+    #[test]
+    fn test_serialize_ecdsa_signature_with_leading_zero_bytes() {
+        let mut r = [0u8; 32];
+        r[2..].copy_from_slice(&[0x7f; 30]);
+        let mut s = [0u8; 32];
+        s[1..].copy_from_slice(&[0x11; 31]);
+
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&r);
+        compact[32..].copy_from_slice(&s);
+        let signature = Signature::from_compact(&compact).unwrap();
+
+        let mut expected = signature.serialize_der().to_vec();
+        expected.push(EcdsaSighashType::All as u8);
+
+        assert_eq!(
+            serialize_ecdsa_signature_from_scalars(&r, &s, EcdsaSighashType::All),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_normalize_s() {
+        let low_s: [u8; 32] =
+            hex::decode("49283B618968DEFB0E660EA703D193BC1D213F5DD811A2D13307FCA01E20C5C0")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        assert!(is_low_s(&low_s));
+        assert_eq!(normalize_s(&low_s), low_s);
+
+        // n - s
+        let high_s: [u8; 32] =
+            hex::decode("B6D7C49E76972104F199F158FC2E6C429D8D9D88D736FD6A8CCA61ECB2157B81")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        assert!(!is_low_s(&high_s));
+        assert_eq!(normalize_s(&high_s), low_s);
+
+        assert!(is_low_s(&SECP256K1_HALF_ORDER));
+    }
+
+    #[test]
+    fn test_serialize_ecdsa_signature_normalizes_high_s() {
+        let r: [u8; 32] =
+            hex::decode("B96BFA3DA6BB4BB74EEEE9C20970725C5782F07724CD1BEFBD265C5AD5C63948")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let high_s: [u8; 32] =
+            hex::decode("B6D7C49E76972104F199F158FC2E6C429D8D9D88D736FD6A8CCA61ECB2157B81")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&r);
+        compact[32..].copy_from_slice(&high_s);
+        let mut signature = Signature::from_compact(&compact).unwrap();
+        signature.normalize_s();
+
+        let mut expected = signature.serialize_der().to_vec();
+        expected.push(EcdsaSighashType::All as u8);
+
+        assert_eq!(
+            serialize_ecdsa_signature_from_scalars(&r, &high_s, EcdsaSighashType::All),
+            expected
+        );
+    }
 
     // using the bitcoin crate
     pub fn create_signature(big_r_hex: &str, s_hex: &str) -> Result<Signature, secp256k1::Error> {
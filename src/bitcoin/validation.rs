@@ -0,0 +1,392 @@
+//! Sanity checks run on Bitcoin transactions before producing their signing payload.
+use core::fmt;
+
+use super::{
+    script::{
+        OP_0, OP_1, OP_16, OP_CHECKMULTISIG, OP_CHECKMULTISIGVERIFY, OP_CHECKSIG,
+        OP_CHECKSIGVERIFY, OP_PUSHDATA1, OP_PUSHDATA2, OP_PUSHDATA4, OP_RETURN,
+    },
+    types::{Amount, OutPoint},
+};
+
+/// Maximum size of a script, in bytes, enforced by consensus.
+pub const MAX_SCRIPT_SIZE: usize = 10_000;
+/// Maximum weight of a transaction relayed by Bitcoin Core, 100 kvB.
+pub const MAX_STANDARD_TX_WEIGHT: u64 = 400_000;
+/// Maximum size of a script sig relayed by Bitcoin Core, in bytes.
+pub const MAX_STANDARD_SCRIPTSIG_SIZE: usize = 1_650;
+/// Maximum sigop cost of a transaction relayed by Bitcoin Core, legacy sigops costing four.
+pub const MAX_STANDARD_TX_SIGOPS_COST: usize = 16_000;
+/// Maximum size of an `OP_RETURN` script pubkey relayed by Bitcoin Core, in bytes.
+pub const MAX_OP_RETURN_RELAY: usize = 83;
+/// Maximum virtual size of a version 3 (TRUC) transaction (BIP-431).
+pub const TRUC_MAX_VSIZE: u64 = 10_000;
+/// Maximum virtual size of a version 3 (TRUC) transaction spending an unconfirmed parent
+/// (BIP-431).
+pub const TRUC_CHILD_MAX_VSIZE: u64 = 1_000;
+
+/// Reasons for a transaction to be rejected by [`BitcoinTransaction::validate`] or
+/// [`BitcoinTransactionBuilder::validate`], or not to be relayed according to
+/// [`BitcoinTransaction::check_standard`].
+///
+/// [`BitcoinTransaction::validate`]: super::BitcoinTransaction::validate
+/// [`BitcoinTransactionBuilder::validate`]: super::BitcoinTransactionBuilder::validate
+/// [`BitcoinTransaction::check_standard`]: super::BitcoinTransaction::check_standard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The transaction has no inputs.
+    EmptyInputs,
+    /// The transaction has no outputs.
+    EmptyOutputs,
+    /// The same outpoint is spent by more than one input.
+    DuplicateInput(OutPoint),
+    /// The value of an output, the sum of the output values or the fee exceeds the money
+    /// supply.
+    OutputValueOverflow,
+    /// The script sig of an input is larger than [`MAX_SCRIPT_SIZE`].
+    ScriptSigTooLarge { index: usize, size: usize },
+    /// The script pubkey of an output is larger than [`MAX_SCRIPT_SIZE`].
+    ScriptPubkeyTooLarge { index: usize, size: usize },
+    /// The builder has no version for the transaction.
+    MissingVersion,
+    /// The builder has no lock time for the transaction.
+    MissingLockTime,
+    /// The builder has no fee rate to compute the fee paid by the change output or the
+    /// outputs.
+    MissingFeeRate,
+    /// An input does not spend a UTXO tracked by the builder, so its value is unknown.
+    MissingUtxo(OutPoint),
+    /// The parent transaction spent by a child paying for it has no output at `vout`.
+    MissingParentOutput { vout: u32 },
+    /// The fee of an input cannot be estimated because its script type is not supported.
+    UnsupportedInputScript { index: usize },
+    /// The inputs do not cover the outputs and the fee.
+    InsufficientFunds { available: Amount, required: Amount },
+    /// The value of an output is below the dust threshold of its script type.
+    DustOutput {
+        index: usize,
+        value: Amount,
+        dust_threshold: Amount,
+    },
+    /// The version of the transaction is not standard.
+    NonStandardVersion,
+    /// The transaction weighs more than [`MAX_STANDARD_TX_WEIGHT`].
+    TransactionTooLarge { weight: u64 },
+    /// The script sig of an input is larger than [`MAX_STANDARD_SCRIPTSIG_SIZE`] or does not
+    /// only push data.
+    NonStandardScriptSig { index: usize },
+    /// The script pubkey of an output is not of a standard type.
+    NonStandardScriptPubkey { index: usize },
+    /// More than one output carries data with `OP_RETURN`.
+    MultipleDataOutputs,
+    /// The sigop cost of the transaction exceeds [`MAX_STANDARD_TX_SIGOPS_COST`].
+    TooManySigops { cost: usize },
+    /// The version 3 transaction exceeds [`TRUC_MAX_VSIZE`], or [`TRUC_CHILD_MAX_VSIZE`] if it
+    /// spends an unconfirmed parent.
+    TrucTooLarge { vsize: u64, max_vsize: u64 },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyInputs => write!(f, "Transaction has no inputs"),
+            Self::EmptyOutputs => write!(f, "Transaction has no outputs"),
+            Self::DuplicateInput(outpoint) => write!(
+                f,
+                "Outpoint {}:{} is spent more than once",
+                outpoint.txid, outpoint.vout
+            ),
+            Self::OutputValueOverflow => {
+                write!(f, "Output values exceed the maximum amount of money")
+            }
+            Self::ScriptSigTooLarge { index, size } => write!(
+                f,
+                "Script sig of input {index} is {size} bytes, the maximum is {MAX_SCRIPT_SIZE}"
+            ),
+            Self::ScriptPubkeyTooLarge { index, size } => write!(
+                f,
+                "Script pubkey of output {index} is {size} bytes, the maximum is {MAX_SCRIPT_SIZE}"
+            ),
+            Self::MissingVersion => write!(f, "Missing transaction version"),
+            Self::MissingLockTime => write!(f, "Missing transaction lock time"),
+            Self::MissingFeeRate => write!(f, "Missing fee rate"),
+            Self::MissingUtxo(outpoint) => write!(
+                f,
+                "Missing UTXO for outpoint {}:{}",
+                outpoint.txid, outpoint.vout
+            ),
+            Self::MissingParentOutput { vout } => {
+                write!(f, "Parent transaction has no output {vout}")
+            }
+            Self::UnsupportedInputScript { index } => write!(
+                f,
+                "Cannot estimate the size of input {index}, its script type is not supported"
+            ),
+            Self::InsufficientFunds {
+                available,
+                required,
+            } => write!(
+                f,
+                "Insufficient funds: {} sat available, {} sat required",
+                available.to_sat(),
+                required.to_sat()
+            ),
+            Self::DustOutput {
+                index,
+                value,
+                dust_threshold,
+            } => write!(
+                f,
+                "Output {index} of {} sat is below the dust threshold of {} sat",
+                value.to_sat(),
+                dust_threshold.to_sat()
+            ),
+            Self::NonStandardVersion => write!(f, "Transaction version is not standard"),
+            Self::TransactionTooLarge { weight } => write!(
+                f,
+                "Transaction weight is {weight}, the standard maximum is {MAX_STANDARD_TX_WEIGHT}"
+            ),
+            Self::NonStandardScriptSig { index } => {
+                write!(f, "Script sig of input {index} is not standard")
+            }
+            Self::NonStandardScriptPubkey { index } => {
+                write!(f, "Script pubkey of output {index} is not standard")
+            }
+            Self::MultipleDataOutputs => {
+                write!(f, "Transaction has more than one OP_RETURN output")
+            }
+            Self::TooManySigops { cost } => write!(
+                f,
+                "Transaction sigop cost is {cost}, the standard maximum is {MAX_STANDARD_TX_SIGOPS_COST}"
+            ),
+            Self::TrucTooLarge { vsize, max_vsize } => write!(
+                f,
+                "TRUC transaction is {vsize} vB, the maximum is {max_vsize} vB"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Iterates over the opcodes of a script, skipping the data they push. Stops at a push running
+/// past the end of the script, which is then flagged as truncated.
+struct Opcodes<'a> {
+    script: &'a [u8],
+    truncated: bool,
+}
+
+impl<'a> Opcodes<'a> {
+    const fn new(script: &'a [u8]) -> Self {
+        Self {
+            script,
+            truncated: false,
+        }
+    }
+}
+
+impl Iterator for Opcodes<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let (&opcode, rest) = self.script.split_first()?;
+
+        let (len_size, len): (usize, usize) = match opcode {
+            len if len < OP_PUSHDATA1 => (0, len as usize),
+            OP_PUSHDATA1 => (
+                1,
+                rest.first().map(|&len| len as usize).unwrap_or(usize::MAX),
+            ),
+            OP_PUSHDATA2 => (
+                2,
+                rest.get(..2)
+                    .map(|len| u16::from_le_bytes([len[0], len[1]]) as usize)
+                    .unwrap_or(usize::MAX),
+            ),
+            OP_PUSHDATA4 => (
+                4,
+                rest.get(..4)
+                    .map(|len| u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize)
+                    .unwrap_or(usize::MAX),
+            ),
+            _ => (0, 0),
+        };
+
+        match len_size.checked_add(len).and_then(|size| rest.get(size..)) {
+            Some(rest) => {
+                self.script = rest;
+                Some(opcode)
+            }
+            None => {
+                self.script = &[];
+                self.truncated = true;
+                None
+            }
+        }
+    }
+}
+
+/// Whether the script only pushes data, as required of standard script sigs.
+pub(super) fn is_push_only(script: &[u8]) -> bool {
+    let mut opcodes = Opcodes::new(script);
+    opcodes.all(|opcode| opcode <= OP_16) && !opcodes.truncated
+}
+
+/// Counts the sigops of a script the legacy way, a multisig counting as twenty.
+pub(super) fn legacy_sigop_count(script: &[u8]) -> usize {
+    Opcodes::new(script)
+        .map(|opcode| match opcode {
+            OP_CHECKSIG | OP_CHECKSIGVERIFY => 1,
+            OP_CHECKMULTISIG | OP_CHECKMULTISIGVERIFY => 20,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Whether the script is an `OP_RETURN` output carrying data.
+pub(super) fn is_data_carrier(script: &[u8]) -> bool {
+    script.first() == Some(&OP_RETURN)
+}
+
+/// Whether the script pubkey is of a type relayed by Bitcoin Core: P2PK, P2PKH, P2SH, bare
+/// multisig of up to three keys, a witness program, or an `OP_RETURN` data carrier.
+pub(super) fn is_standard_script_pubkey(script: &[u8]) -> bool {
+    let is_key = |key: &[u8]| match key.first() {
+        Some(0x02 | 0x03) => key.len() == 33,
+        Some(0x04) => key.len() == 65,
+        _ => false,
+    };
+
+    match script {
+        // OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG
+        [0x76, 0xa9, 0x14, hash @ .., 0x88, OP_CHECKSIG] => hash.len() == 20,
+        // OP_HASH160 <20 bytes> OP_EQUAL
+        [0xa9, 0x14, hash @ .., 0x87] => hash.len() == 20,
+        // <version> <2 to 40 bytes>, version 0 programs being P2WPKH or P2WSH
+        [version @ (OP_0 | OP_1..=OP_16), len, program @ ..]
+            if *len as usize == program.len() && (2..=40).contains(&program.len()) =>
+        {
+            *version != OP_0 || program.len() == 20 || program.len() == 32
+        }
+        // <public key> OP_CHECKSIG
+        [len, key @ .., OP_CHECKSIG] if *len as usize == key.len() => is_key(key),
+        // <m> <public key>... <n> OP_CHECKMULTISIG
+        [m @ OP_1..=OP_16, keys @ .., n @ OP_1..=OP_16, OP_CHECKMULTISIG] => {
+            let n = (n - OP_1 + 1) as usize;
+            let mut rest = keys;
+            let mut count = 0;
+            while let [len, tail @ ..] = rest {
+                let len = *len as usize;
+                if tail.len() < len || !is_key(&tail[..len]) {
+                    return false;
+                }
+                rest = &tail[len..];
+                count += 1;
+            }
+
+            n <= 3 && count == n && (m - OP_1 + 1) as usize <= n
+        }
+        [OP_RETURN, data @ ..] => script.len() <= MAX_OP_RETURN_RELAY && is_push_only(data),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::hashes::Hash as _;
+    use bitcoin::script::{Builder, PushBytesBuf};
+    use bitcoin::{PubkeyHash, ScriptBuf, ScriptHash, WPubkeyHash, WScriptHash};
+
+    fn key(byte: u8) -> bitcoin::PublicKey {
+        let secp = bitcoin::secp256k1::Secp256k1::signing_only();
+        let secret_key = bitcoin::secp256k1::SecretKey::from_slice(&[byte; 32]).unwrap();
+        bitcoin::PublicKey::new(secret_key.public_key(&secp))
+    }
+
+    #[test]
+    fn test_standard_script_pubkeys() {
+        let multisig = |m: i64, n: u8| {
+            let mut builder = Builder::new().push_int(m);
+            for byte in 1..=n {
+                builder = builder.push_key(&key(byte));
+            }
+            builder
+                .push_int(n as i64)
+                .push_opcode(bitcoin::opcodes::all::OP_CHECKMULTISIG)
+                .into_script()
+        };
+        let data =
+            |len: usize| ScriptBuf::new_op_return(PushBytesBuf::try_from(vec![0xab; len]).unwrap());
+
+        let standard = [
+            ScriptBuf::new_p2pk(&key(1)),
+            ScriptBuf::new_p2pk(&bitcoin::PublicKey {
+                compressed: false,
+                ..key(1)
+            }),
+            ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()),
+            ScriptBuf::new_p2sh(&ScriptHash::all_zeros()),
+            ScriptBuf::new_p2wpkh(&WPubkeyHash::all_zeros()),
+            ScriptBuf::new_p2wsh(&WScriptHash::all_zeros()),
+            ScriptBuf::from_hex(
+                "5120a16b5755f7f6f96dbd65f5f0d6ab9418b89af4b1f14a1bb8a09062c35f0dcb54",
+            )
+            .unwrap(),
+            // Pay to anchor
+            ScriptBuf::from_hex("51024e73").unwrap(),
+            multisig(1, 1),
+            multisig(2, 3),
+            data(0),
+            data(MAX_OP_RETURN_RELAY - 3),
+        ];
+        for script in standard {
+            assert!(is_standard_script_pubkey(script.as_bytes()), "{script}");
+        }
+
+        let non_standard = [
+            ScriptBuf::new(),
+            // Version 0 witness program of neither 20 nor 32 bytes
+            ScriptBuf::from_hex("0018000000000000000000000000000000000000000000000000").unwrap(),
+            multisig(2, 4),
+            multisig(3, 2),
+            data(MAX_OP_RETURN_RELAY - 2),
+            ScriptBuf::from_hex("6a51ac").unwrap(),
+            ScriptBuf::from_hex("51").unwrap(),
+        ];
+        for script in non_standard {
+            assert!(!is_standard_script_pubkey(script.as_bytes()), "{script}");
+        }
+    }
+
+    #[test]
+    fn test_opcodes() {
+        for hex in [
+            "",
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac",
+            "52ae",
+            "ac4c02abcdac",
+            "ad",
+            "af51",
+            // Truncated pushes
+            "4c",
+            "4d0100",
+            "4e01000000",
+            "ac05ab",
+        ] {
+            let script = ScriptBuf::from_hex(hex).unwrap();
+
+            assert_eq!(
+                is_push_only(script.as_bytes()),
+                script.is_push_only(),
+                "{hex}"
+            );
+            assert_eq!(
+                legacy_sigop_count(script.as_bytes()),
+                script.count_sigops_legacy(),
+                "{hex}"
+            );
+        }
+    }
+}
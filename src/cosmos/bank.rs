@@ -0,0 +1,35 @@
+//! Messages of the bank module, sending tokens between accounts.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+    types::{Coin, Msg},
+    utils::{encode_message, encode_string},
+};
+
+/// Sends `amount` from `from_address`, which signs, to `to_address`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgSend {
+    pub from_address: String,
+    pub to_address: String,
+    pub amount: Vec<Coin>,
+}
+
+impl Msg for MsgSend {
+    const TYPE_URL: &'static str = "/cosmos.bank.v1beta1.MsgSend";
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgSend";
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_string(&mut buffer, 1, &self.from_address);
+        encode_string(&mut buffer, 2, &self.to_address);
+        for coin in &self.amount {
+            encode_message(&mut buffer, 3, &coin.encode());
+        }
+        buffer
+    }
+
+    fn amino_value(&self) -> Value {
+        serde_json::to_value(self).expect("MsgSend serializes to JSON")
+    }
+}
@@ -0,0 +1,462 @@
+//! Cosmos SDK transaction
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use super::{
+    types::{AminoMsg, Any, Fee, PublicKey, SignMode, Signature},
+    utils::{encode_bytes, encode_message, encode_string, encode_uint64, sorted_json},
+};
+
+/// A Cosmos SDK transaction with a single signer, signed in `SIGN_MODE_DIRECT` or
+/// `SIGN_MODE_LEGACY_AMINO_JSON` and encoded as the `TxRaw` broadcast with the `BroadcastTx`
+/// endpoint.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::cosmos::bank::MsgSend;
+/// use signet_rs::cosmos::types::{Coin, Fee, Msg, PublicKey, SignMode, Signature};
+/// use signet_rs::cosmos::CosmosTransaction;
+///
+/// let msg = MsgSend {
+///     from_address: "cosmos1from".to_string(),
+///     to_address: "cosmos1to".to_string(),
+///     amount: vec![Coin::new(1_000_000, "uatom")],
+/// };
+/// let tx = CosmosTransaction {
+///     chain_id: "cosmoshub-4".to_string(),
+///     account_number: 42,
+///     sequence: 0,
+///     public_key: PublicKey([2; 33]),
+///     messages: vec![msg.to_any()],
+///     memo: String::new(),
+///     timeout_height: 0,
+///     fee: Fee::new(Coin::new(5_000, "uatom"), 200_000),
+///     sign_mode: SignMode::Direct,
+///     amino_messages: vec![msg.to_amino()],
+/// };
+///
+/// // The sha256 of the SignDoc, or of the StdSignDoc in Amino JSON, signed with secp256k1
+/// let payload = tx.signing_hash();
+///
+/// let signature = Signature([7; 64]);
+/// let signed_tx = tx.build_with_signature(&signature);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CosmosTransaction {
+    /// The id of the chain, like `cosmoshub-4`.
+    pub chain_id: String,
+    /// The number of the account of the signer on the chain.
+    pub account_number: u64,
+    /// The number of transactions of the signer so far.
+    pub sequence: u64,
+    /// The public key of the signer.
+    pub public_key: PublicKey,
+    /// The messages of the transaction, executed in order.
+    pub messages: Vec<Any>,
+    pub memo: String,
+    /// The height after which the transaction is no longer valid, 0 for none.
+    pub timeout_height: u64,
+    pub fee: Fee,
+    pub sign_mode: SignMode,
+    /// The messages in their Amino JSON form, in the same order, only signed in
+    /// [`SignMode::LegacyAminoJson`].
+    pub amino_messages: Vec<AminoMsg>,
+}
+
+impl CosmosTransaction {
+    /// Encode the `TxBody` of the transaction.
+    pub fn body_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        for message in &self.messages {
+            encode_message(&mut buffer, 1, &message.encode());
+        }
+        encode_string(&mut buffer, 2, &self.memo);
+        encode_uint64(&mut buffer, 3, self.timeout_height);
+        buffer
+    }
+
+    /// Encode the `AuthInfo` of the transaction, with its signer and fee.
+    pub fn auth_info_bytes(&self) -> Vec<u8> {
+        let mut single = Vec::new();
+        encode_uint64(&mut single, 1, self.sign_mode as u64);
+        let mut mode_info = Vec::new();
+        encode_message(&mut mode_info, 1, &single);
+
+        let mut signer_info = Vec::new();
+        encode_message(&mut signer_info, 1, &self.public_key.to_any().encode());
+        encode_message(&mut signer_info, 2, &mode_info);
+        encode_uint64(&mut signer_info, 3, self.sequence);
+
+        let mut buffer = Vec::new();
+        encode_message(&mut buffer, 1, &signer_info);
+        encode_message(&mut buffer, 2, &self.fee.encode());
+        buffer
+    }
+
+    /// Encode the bytes signed by the signer of the transaction in its sign mode, its
+    /// `SignDoc` or its `StdSignDoc`.
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        match self.sign_mode {
+            SignMode::Direct => self.sign_doc_bytes(),
+            SignMode::LegacyAminoJson => self.std_sign_doc_bytes(),
+        }
+    }
+
+    /// Encode the `SignDoc` of the transaction, the bytes signed in `SIGN_MODE_DIRECT`.
+    pub fn sign_doc_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_bytes(&mut buffer, 1, &self.body_bytes());
+        encode_bytes(&mut buffer, 2, &self.auth_info_bytes());
+        encode_string(&mut buffer, 3, &self.chain_id);
+        encode_uint64(&mut buffer, 4, self.account_number);
+        buffer
+    }
+
+    /// Encode the `StdSignDoc` of the transaction, the sorted Amino JSON signed in
+    /// `SIGN_MODE_LEGACY_AMINO_JSON`, its integers being strings.
+    ///
+    /// Panics if the transaction does not have one Amino JSON message per message.
+    pub fn std_sign_doc_bytes(&self) -> Vec<u8> {
+        assert_eq!(
+            self.amino_messages.len(),
+            self.messages.len(),
+            "Amino JSON transactions need one Amino JSON message per message"
+        );
+
+        let mut sign_doc = json!({
+            "account_number": self.account_number.to_string(),
+            "chain_id": self.chain_id,
+            "fee": {
+                "amount": self.fee.amount,
+                "gas": self.fee.gas_limit.to_string(),
+            },
+            "memo": self.memo,
+            "msgs": self.amino_messages,
+            "sequence": self.sequence.to_string(),
+        });
+        if self.timeout_height != 0 {
+            sign_doc["timeout_height"] = self.timeout_height.to_string().into();
+        }
+
+        sorted_json(&sign_doc).into_bytes()
+    }
+
+    /// The sha256 of the bytes signed in the sign mode of the transaction, the payload signed
+    /// with secp256k1.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.build_for_signing()).into()
+    }
+
+    /// Encode the transaction as a `TxRaw` with the signature of its signer.
+    pub fn build_with_signature(&self, signature: &Signature) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_bytes(&mut buffer, 1, &self.body_bytes());
+        encode_bytes(&mut buffer, 2, &self.auth_info_bytes());
+        encode_message(&mut buffer, 3, &signature.0);
+        buffer
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::cosmos::{
+        bank::MsgSend,
+        types::{Coin, Msg},
+    };
+    use prost::Message;
+
+    /// The protobuf definitions of the Cosmos SDK, encoded with prost.
+    pub(crate) mod proto {
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct Any {
+            #[prost(string, tag = "1")]
+            pub type_url: String,
+            #[prost(bytes, tag = "2")]
+            pub value: Vec<u8>,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct Coin {
+            #[prost(string, tag = "1")]
+            pub denom: String,
+            #[prost(string, tag = "2")]
+            pub amount: String,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct MsgSend {
+            #[prost(string, tag = "1")]
+            pub from_address: String,
+            #[prost(string, tag = "2")]
+            pub to_address: String,
+            #[prost(message, repeated, tag = "3")]
+            pub amount: Vec<Coin>,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct PubKey {
+            #[prost(bytes, tag = "1")]
+            pub key: Vec<u8>,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct TxBody {
+            #[prost(message, repeated, tag = "1")]
+            pub messages: Vec<Any>,
+            #[prost(string, tag = "2")]
+            pub memo: String,
+            #[prost(uint64, tag = "3")]
+            pub timeout_height: u64,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct Single {
+            #[prost(int32, tag = "1")]
+            pub mode: i32,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct ModeInfo {
+            #[prost(message, optional, tag = "1")]
+            pub single: Option<Single>,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct SignerInfo {
+            #[prost(message, optional, tag = "1")]
+            pub public_key: Option<Any>,
+            #[prost(message, optional, tag = "2")]
+            pub mode_info: Option<ModeInfo>,
+            #[prost(uint64, tag = "3")]
+            pub sequence: u64,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct Fee {
+            #[prost(message, repeated, tag = "1")]
+            pub amount: Vec<Coin>,
+            #[prost(uint64, tag = "2")]
+            pub gas_limit: u64,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct AuthInfo {
+            #[prost(message, repeated, tag = "1")]
+            pub signer_infos: Vec<SignerInfo>,
+            #[prost(message, optional, tag = "2")]
+            pub fee: Option<Fee>,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct SignDoc {
+            #[prost(bytes, tag = "1")]
+            pub body_bytes: Vec<u8>,
+            #[prost(bytes, tag = "2")]
+            pub auth_info_bytes: Vec<u8>,
+            #[prost(string, tag = "3")]
+            pub chain_id: String,
+            #[prost(uint64, tag = "4")]
+            pub account_number: u64,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct TxRaw {
+            #[prost(bytes, tag = "1")]
+            pub body_bytes: Vec<u8>,
+            #[prost(bytes, tag = "2")]
+            pub auth_info_bytes: Vec<u8>,
+            #[prost(bytes, repeated, tag = "3")]
+            pub signatures: Vec<Vec<u8>>,
+        }
+
+        pub fn coin(coin: &crate::cosmos::types::Coin) -> Coin {
+            Coin {
+                denom: coin.denom.clone(),
+                amount: coin.amount.to_string(),
+            }
+        }
+
+        pub fn any(type_url: &str, message: &impl prost::Message) -> Any {
+            Any {
+                type_url: type_url.to_string(),
+                value: message.encode_to_vec(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_against_prost() {
+        let msg = MsgSend {
+            from_address: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string(),
+            to_address: "cosmos1g9k4mlnqyk3r4fh0qznjrsu9sgyg60a8e3yaj7".to_string(),
+            amount: vec![Coin::new(1_500_000, "uatom")],
+        };
+        let tx = CosmosTransaction {
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 12_345,
+            sequence: 7,
+            public_key: PublicKey([2; 33]),
+            messages: vec![msg.to_any()],
+            memo: "signet".to_string(),
+            timeout_height: 0,
+            fee: Fee::new(Coin::new(5_000, "uatom"), 200_000),
+            sign_mode: SignMode::Direct,
+            amino_messages: vec![msg.to_amino()],
+        };
+
+        let body = proto::TxBody {
+            messages: vec![proto::any(
+                "/cosmos.bank.v1beta1.MsgSend",
+                &proto::MsgSend {
+                    from_address: msg.from_address.clone(),
+                    to_address: msg.to_address.clone(),
+                    amount: msg.amount.iter().map(proto::coin).collect(),
+                },
+            )],
+            memo: "signet".to_string(),
+            timeout_height: 0,
+        };
+        let auth_info = proto::AuthInfo {
+            signer_infos: vec![proto::SignerInfo {
+                public_key: Some(proto::any(
+                    "/cosmos.crypto.secp256k1.PubKey",
+                    &proto::PubKey { key: vec![2; 33] },
+                )),
+                mode_info: Some(proto::ModeInfo {
+                    single: Some(proto::Single { mode: 1 }),
+                }),
+                sequence: 7,
+            }],
+            fee: Some(proto::Fee {
+                amount: vec![proto::coin(&Coin::new(5_000, "uatom"))],
+                gas_limit: 200_000,
+            }),
+        };
+        assert_eq!(tx.body_bytes(), body.encode_to_vec());
+        assert_eq!(tx.auth_info_bytes(), auth_info.encode_to_vec());
+
+        let sign_doc = proto::SignDoc {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 12_345,
+        };
+        assert_eq!(tx.build_for_signing(), sign_doc.encode_to_vec());
+        assert_eq!(
+            tx.signing_hash(),
+            <[u8; 32]>::from(Sha256::digest(sign_doc.encode_to_vec()))
+        );
+
+        let tx_raw = proto::TxRaw {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures: vec![vec![7; 64]],
+        };
+        assert_eq!(
+            tx.build_with_signature(&Signature([7; 64])),
+            tx_raw.encode_to_vec()
+        );
+    }
+
+    #[test]
+    fn test_encode_defaults_against_prost() {
+        // A new account, with account number and sequence 0, and a timeout height
+        let tx = CosmosTransaction {
+            chain_id: "osmosis-1".to_string(),
+            account_number: 0,
+            sequence: 0,
+            public_key: PublicKey([3; 33]),
+            messages: vec![],
+            memo: String::new(),
+            timeout_height: 1_000_000,
+            fee: Fee::default(),
+            sign_mode: SignMode::Direct,
+            amino_messages: vec![],
+        };
+
+        let sign_doc = proto::SignDoc::decode(tx.build_for_signing().as_slice()).unwrap();
+        assert_eq!(sign_doc.account_number, 0);
+        assert_eq!(sign_doc.encode_to_vec(), tx.build_for_signing());
+
+        let body = proto::TxBody::decode(sign_doc.body_bytes.as_slice()).unwrap();
+        assert_eq!(body.timeout_height, 1_000_000);
+        let auth_info = proto::AuthInfo::decode(sign_doc.auth_info_bytes.as_slice()).unwrap();
+        assert_eq!(auth_info.signer_infos[0].sequence, 0);
+        assert_eq!(auth_info.fee, Some(proto::Fee::default()));
+        assert_eq!(auth_info.encode_to_vec(), tx.auth_info_bytes());
+    }
+
+    #[test]
+    fn test_amino_json() {
+        let msg = MsgSend {
+            from_address: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string(),
+            to_address: "cosmos1g9k4mlnqyk3r4fh0qznjrsu9sgyg60a8e3yaj7".to_string(),
+            amount: vec![Coin::new(1_500_000, "uatom")],
+        };
+        let mut tx = CosmosTransaction {
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 12_345,
+            sequence: 7,
+            public_key: PublicKey([2; 33]),
+            messages: vec![msg.to_any()],
+            memo: "<signet>".to_string(),
+            timeout_height: 0,
+            fee: Fee::new(Coin::new(5_000, "uatom"), 200_000),
+            sign_mode: SignMode::LegacyAminoJson,
+            amino_messages: vec![msg.to_amino()],
+        };
+
+        let expected = concat!(
+            r#"{"account_number":"12345","chain_id":"cosmoshub-4","#,
+            r#""fee":{"amount":[{"amount":"5000","denom":"uatom"}],"gas":"200000"},"#,
+            r#""memo":"\u003csignet\u003e","#,
+            r#""msgs":[{"type":"cosmos-sdk/MsgSend","value":{"amount":[{"amount":"1500000","denom":"uatom"}],"#,
+            r#""from_address":"cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu","#,
+            r#""to_address":"cosmos1g9k4mlnqyk3r4fh0qznjrsu9sgyg60a8e3yaj7"}}],"#,
+            r#""sequence":"7"}"#
+        );
+        assert_eq!(tx.build_for_signing(), expected.as_bytes());
+        assert_eq!(
+            tx.signing_hash(),
+            <[u8; 32]>::from(Sha256::digest(expected))
+        );
+
+        // The signer info holds the sign mode
+        let auth_info = proto::AuthInfo::decode(tx.auth_info_bytes().as_slice()).unwrap();
+        assert_eq!(
+            auth_info.signer_infos[0].mode_info,
+            Some(proto::ModeInfo {
+                single: Some(proto::Single { mode: 127 }),
+            })
+        );
+
+        tx.timeout_height = 100;
+        assert!(String::from_utf8(tx.build_for_signing())
+            .unwrap()
+            .ends_with(r#""sequence":"7","timeout_height":"100"}"#));
+    }
+
+    #[test]
+    #[should_panic(expected = "one Amino JSON message per message")]
+    fn test_amino_json_without_amino_messages() {
+        let tx = CosmosTransaction {
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 1,
+            sequence: 0,
+            public_key: PublicKey([2; 33]),
+            messages: vec![Any {
+                type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                value: vec![],
+            }],
+            memo: String::new(),
+            timeout_height: 0,
+            fee: Fee::default(),
+            sign_mode: SignMode::LegacyAminoJson,
+            amino_messages: vec![],
+        };
+        tx.build_for_signing();
+    }
+}
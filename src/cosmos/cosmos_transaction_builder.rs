@@ -0,0 +1,190 @@
+use crate::transaction_builder::TxBuilder;
+
+use super::{
+    cosmos_transaction::CosmosTransaction,
+    types::{AminoMsg, Any, Fee, Msg, PublicKey, SignMode},
+};
+
+pub struct CosmosTransactionBuilder {
+    chain_id: Option<String>,
+    account_number: Option<u64>,
+    sequence: Option<u64>,
+    public_key: Option<PublicKey>,
+    messages: Option<Vec<Any>>,
+    memo: Option<String>,
+    timeout_height: Option<u64>,
+    fee: Option<Fee>,
+    sign_mode: Option<SignMode>,
+    amino_messages: Option<Vec<AminoMsg>>,
+}
+
+impl Default for CosmosTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<CosmosTransaction> for CosmosTransactionBuilder {
+    fn build(&self) -> CosmosTransaction {
+        CosmosTransaction {
+            chain_id: self.chain_id.clone().expect("chain_id is mandatory"),
+            account_number: self.account_number.expect("account_number is mandatory"),
+            sequence: self.sequence.expect("sequence is mandatory"),
+            public_key: self.public_key.expect("public_key is mandatory"),
+            messages: self.messages.clone().unwrap_or_default(),
+            memo: self.memo.clone().unwrap_or_default(),
+            timeout_height: self.timeout_height.unwrap_or_default(),
+            fee: self.fee.clone().expect("fee is mandatory"),
+            sign_mode: self.sign_mode.unwrap_or_default(),
+            amino_messages: self.amino_messages.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl CosmosTransactionBuilder {
+    pub const fn new() -> Self {
+        Self {
+            chain_id: None,
+            account_number: None,
+            sequence: None,
+            public_key: None,
+            messages: None,
+            memo: None,
+            timeout_height: None,
+            fee: None,
+            sign_mode: None,
+            amino_messages: None,
+        }
+    }
+
+    /// Id of the chain, like `cosmoshub-4`.
+    pub fn chain_id(mut self, chain_id: &str) -> Self {
+        self.chain_id = Some(chain_id.to_string());
+        self
+    }
+
+    /// Number of the account of the signer, from the `auth` module.
+    pub const fn account_number(mut self, account_number: u64) -> Self {
+        self.account_number = Some(account_number);
+        self
+    }
+
+    /// Sequence of the account of the signer, its number of transactions so far.
+    pub const fn sequence(mut self, sequence: u64) -> Self {
+        self.sequence = Some(sequence);
+        self
+    }
+
+    /// Compressed secp256k1 public key of the signer.
+    pub const fn public_key(mut self, public_key: PublicKey) -> Self {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    /// Messages of the transaction, executed in order, signed in [`SignMode::Direct`].
+    pub fn messages(mut self, messages: Vec<Any>) -> Self {
+        self.messages = Some(messages);
+        self
+    }
+
+    /// Append a message to the ones of the transaction, all of them being executed atomically,
+    /// in both its protobuf and Amino JSON forms.
+    pub fn add_message(mut self, message: &impl Msg) -> Self {
+        self.messages
+            .get_or_insert_with(Vec::new)
+            .push(message.to_any());
+        self.amino_messages
+            .get_or_insert_with(Vec::new)
+            .push(message.to_amino());
+        self
+    }
+
+    /// Note attached to the transaction, required by some exchanges to credit deposits.
+    pub fn memo(mut self, memo: &str) -> Self {
+        self.memo = Some(memo.to_string());
+        self
+    }
+
+    /// Block height after which the transaction can no longer be included.
+    pub const fn timeout_height(mut self, timeout_height: u64) -> Self {
+        self.timeout_height = Some(timeout_height);
+        self
+    }
+
+    /// Fee paid by the signer and gas limit of the transaction.
+    pub fn fee(mut self, fee: Fee) -> Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// The way the signer signs the transaction, [`SignMode::Direct`] by default.
+    pub const fn sign_mode(mut self, sign_mode: SignMode) -> Self {
+        self.sign_mode = Some(sign_mode);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosmos::{bank::MsgSend, types::Coin};
+    use crate::TransactionBuilder;
+
+    #[test]
+    fn test_build() {
+        let msg = MsgSend {
+            from_address: "cosmos1from".to_string(),
+            to_address: "cosmos1to".to_string(),
+            amount: vec![Coin::new(1_000, "uatom")],
+        };
+
+        let tx: CosmosTransaction = TransactionBuilder::new::<CosmosTransactionBuilder>()
+            .chain_id("cosmoshub-4")
+            .account_number(1)
+            .sequence(2)
+            .public_key(PublicKey([2; 33]))
+            .add_message(&msg)
+            .fee(Fee::new(Coin::new(500, "uatom"), 100_000))
+            .build();
+
+        assert_eq!(
+            tx,
+            CosmosTransaction {
+                chain_id: "cosmoshub-4".to_string(),
+                account_number: 1,
+                sequence: 2,
+                public_key: PublicKey([2; 33]),
+                messages: vec![msg.to_any()],
+                memo: String::new(),
+                timeout_height: 0,
+                fee: Fee::new(Coin::new(500, "uatom"), 100_000),
+                sign_mode: SignMode::Direct,
+                amino_messages: vec![msg.to_amino()],
+            }
+        );
+
+        let tx: CosmosTransaction = TransactionBuilder::new::<CosmosTransactionBuilder>()
+            .chain_id("cosmoshub-4")
+            .account_number(1)
+            .sequence(2)
+            .public_key(PublicKey([2; 33]))
+            .add_message(&msg)
+            .fee(Fee::new(Coin::new(500, "uatom"), 100_000))
+            .sign_mode(SignMode::LegacyAminoJson)
+            .build();
+        assert!(tx
+            .build_for_signing()
+            .starts_with(b"{\"account_number\":\"1\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "fee is mandatory")]
+    fn test_build_without_fee() {
+        let _: CosmosTransaction = CosmosTransactionBuilder::new()
+            .chain_id("cosmoshub-4")
+            .account_number(1)
+            .sequence(2)
+            .public_key(PublicKey([2; 33]))
+            .build();
+    }
+}
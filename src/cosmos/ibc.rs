@@ -0,0 +1,354 @@
+//! Messages of the IBC transfer application, sending tokens to other chains over IBC channels.
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{
+    types::{Coin, Msg},
+    utils::{encode_message, encode_string, encode_uint64},
+};
+use crate::transaction_builder::TxBuilder;
+
+/// The port of the transfer application, the source port of most transfers.
+pub const TRANSFER_PORT: &str = "transfer";
+
+/// A height on the destination chain, its revision number changing on upgrades resetting the
+/// block height.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Height {
+    pub revision_number: u64,
+    pub revision_height: u64,
+}
+
+impl Height {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_uint64(&mut buffer, 1, self.revision_number);
+        encode_uint64(&mut buffer, 2, self.revision_height);
+        buffer
+    }
+}
+
+/// Sends `token` from `sender`, which signs, to `receiver` on the chain at the other end of
+/// `source_channel`, the transfer being refunded if not received before the timeout height or
+/// timestamp.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgTransfer {
+    pub source_port: String,
+    pub source_channel: String,
+    pub token: Coin,
+    pub sender: String,
+    pub receiver: String,
+    /// The height of the destination chain after which the transfer times out, zero for none.
+    pub timeout_height: Height,
+    /// The time after which the transfer times out, in nanoseconds since the Unix epoch, 0 for
+    /// none.
+    pub timeout_timestamp: u64,
+    pub memo: String,
+}
+
+impl Msg for MsgTransfer {
+    const TYPE_URL: &'static str = "/ibc.applications.transfer.v1.MsgTransfer";
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgTransfer";
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_string(&mut buffer, 1, &self.source_port);
+        encode_string(&mut buffer, 2, &self.source_channel);
+        encode_message(&mut buffer, 3, &self.token.encode());
+        encode_string(&mut buffer, 4, &self.sender);
+        encode_string(&mut buffer, 5, &self.receiver);
+        encode_message(&mut buffer, 6, &self.timeout_height.encode());
+        encode_uint64(&mut buffer, 7, self.timeout_timestamp);
+        encode_string(&mut buffer, 8, &self.memo);
+        buffer
+    }
+
+    /// The Amino JSON of the transfer, omitting its zero timeouts and empty memo like the
+    /// Cosmos SDK does.
+    fn amino_value(&self) -> Value {
+        let mut timeout_height = Map::new();
+        if self.timeout_height.revision_height != 0 {
+            timeout_height.insert(
+                "revision_height".to_string(),
+                self.timeout_height.revision_height.to_string().into(),
+            );
+        }
+        if self.timeout_height.revision_number != 0 {
+            timeout_height.insert(
+                "revision_number".to_string(),
+                self.timeout_height.revision_number.to_string().into(),
+            );
+        }
+
+        let mut value = Map::new();
+        value.insert("source_port".to_string(), self.source_port.clone().into());
+        value.insert(
+            "source_channel".to_string(),
+            self.source_channel.clone().into(),
+        );
+        value.insert(
+            "token".to_string(),
+            serde_json::to_value(&self.token).expect("coins serialize to JSON"),
+        );
+        value.insert("sender".to_string(), self.sender.clone().into());
+        value.insert("receiver".to_string(), self.receiver.clone().into());
+        value.insert("timeout_height".to_string(), timeout_height.into());
+        if self.timeout_timestamp != 0 {
+            value.insert(
+                "timeout_timestamp".to_string(),
+                self.timeout_timestamp.to_string().into(),
+            );
+        }
+        if !self.memo.is_empty() {
+            value.insert("memo".to_string(), self.memo.clone().into());
+        }
+
+        value.into()
+    }
+}
+
+/// Builds a [`MsgTransfer`] from the [`TRANSFER_PORT`], with a timeout height, a timeout
+/// timestamp or both.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::cosmos::ibc::{MsgTransfer, MsgTransferBuilder};
+/// use signet_rs::cosmos::types::Coin;
+/// use signet_rs::TxBuilder;
+///
+/// let msg: MsgTransfer = MsgTransferBuilder::new()
+///     .source_channel("channel-141")
+///     .token(Coin::new(1_000_000, "uatom"))
+///     .sender("cosmos1sender")
+///     .receiver("osmo1receiver")
+///     .timeout_timestamp(1_700_000_000_000_000_000)
+///     .build();
+/// ```
+pub struct MsgTransferBuilder {
+    source_port: Option<String>,
+    source_channel: Option<String>,
+    token: Option<Coin>,
+    sender: Option<String>,
+    receiver: Option<String>,
+    timeout_height: Option<Height>,
+    timeout_timestamp: Option<u64>,
+    memo: Option<String>,
+}
+
+impl Default for MsgTransferBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<MsgTransfer> for MsgTransferBuilder {
+    fn build(&self) -> MsgTransfer {
+        let timeout_height = self.timeout_height.unwrap_or_default();
+        let timeout_timestamp = self.timeout_timestamp.unwrap_or_default();
+        assert!(
+            timeout_height != Height::default() || timeout_timestamp != 0,
+            "timeout_height or timeout_timestamp is mandatory"
+        );
+
+        MsgTransfer {
+            source_port: self
+                .source_port
+                .clone()
+                .unwrap_or_else(|| TRANSFER_PORT.to_string()),
+            source_channel: self
+                .source_channel
+                .clone()
+                .expect("source_channel is mandatory"),
+            token: self.token.clone().expect("token is mandatory"),
+            sender: self.sender.clone().expect("sender is mandatory"),
+            receiver: self.receiver.clone().expect("receiver is mandatory"),
+            timeout_height,
+            timeout_timestamp,
+            memo: self.memo.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl MsgTransferBuilder {
+    pub const fn new() -> Self {
+        Self {
+            source_port: None,
+            source_channel: None,
+            token: None,
+            sender: None,
+            receiver: None,
+            timeout_height: None,
+            timeout_timestamp: None,
+            memo: None,
+        }
+    }
+
+    /// Port of the channel on the source chain, [`TRANSFER_PORT`] by default.
+    pub fn source_port(mut self, source_port: &str) -> Self {
+        self.source_port = Some(source_port.to_string());
+        self
+    }
+
+    /// Channel on the source chain to the destination chain, like `channel-141`.
+    pub fn source_channel(mut self, source_channel: &str) -> Self {
+        self.source_channel = Some(source_channel.to_string());
+        self
+    }
+
+    /// Tokens to transfer, in their denomination on the source chain.
+    pub fn token(mut self, token: Coin) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Address of the signer sending the tokens, on the source chain.
+    pub fn sender(mut self, sender: &str) -> Self {
+        self.sender = Some(sender.to_string());
+        self
+    }
+
+    /// Address receiving the tokens, on the destination chain.
+    pub fn receiver(mut self, receiver: &str) -> Self {
+        self.receiver = Some(receiver.to_string());
+        self
+    }
+
+    /// Height of the destination chain after which the transfer times out.
+    pub const fn timeout_height(mut self, revision_number: u64, revision_height: u64) -> Self {
+        self.timeout_height = Some(Height {
+            revision_number,
+            revision_height,
+        });
+        self
+    }
+
+    /// Time after which the transfer times out, in nanoseconds since the Unix epoch.
+    pub const fn timeout_timestamp(mut self, timeout_timestamp: u64) -> Self {
+        self.timeout_timestamp = Some(timeout_timestamp);
+        self
+    }
+
+    /// Note attached to the transfer, read by the middlewares of the destination chain, like
+    /// packet forwarding or IBC hooks.
+    pub fn memo(mut self, memo: &str) -> Self {
+        self.memo = Some(memo.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosmos::{cosmos_transaction::tests::proto, utils::sorted_json};
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct ProtoHeight {
+        #[prost(uint64, tag = "1")]
+        revision_number: u64,
+        #[prost(uint64, tag = "2")]
+        revision_height: u64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct ProtoMsgTransfer {
+        #[prost(string, tag = "1")]
+        source_port: String,
+        #[prost(string, tag = "2")]
+        source_channel: String,
+        #[prost(message, optional, tag = "3")]
+        token: Option<proto::Coin>,
+        #[prost(string, tag = "4")]
+        sender: String,
+        #[prost(string, tag = "5")]
+        receiver: String,
+        #[prost(message, optional, tag = "6")]
+        timeout_height: Option<ProtoHeight>,
+        #[prost(uint64, tag = "7")]
+        timeout_timestamp: u64,
+        #[prost(string, tag = "8")]
+        memo: String,
+    }
+
+    #[test]
+    fn test_encode_against_prost() {
+        let msg: MsgTransfer = MsgTransferBuilder::new()
+            .source_channel("channel-141")
+            .token(Coin::new(1_000_000, "uatom"))
+            .sender("cosmos1sender")
+            .receiver("osmo1receiver")
+            .timeout_timestamp(1_700_000_000_000_000_000)
+            .memo(r#"{"wasm":{}}"#)
+            .build();
+
+        let expected = ProtoMsgTransfer {
+            source_port: "transfer".to_string(),
+            source_channel: "channel-141".to_string(),
+            token: Some(proto::coin(&Coin::new(1_000_000, "uatom"))),
+            sender: "cosmos1sender".to_string(),
+            receiver: "osmo1receiver".to_string(),
+            // The height is not nullable, encoded even if zero
+            timeout_height: Some(ProtoHeight::default()),
+            timeout_timestamp: 1_700_000_000_000_000_000,
+            memo: r#"{"wasm":{}}"#.to_string(),
+        };
+        assert_eq!(msg.encode(), expected.encode_to_vec());
+        assert_eq!(
+            msg.to_any().type_url,
+            "/ibc.applications.transfer.v1.MsgTransfer"
+        );
+
+        let msg: MsgTransfer = MsgTransferBuilder::new()
+            .source_port("wasm.osmo1contract")
+            .source_channel("channel-0")
+            .token(Coin::new(5, "uosmo"))
+            .sender("osmo1sender")
+            .receiver("cosmos1receiver")
+            .timeout_height(4, 20_000_000)
+            .build();
+        let decoded = ProtoMsgTransfer::decode(msg.encode().as_slice()).unwrap();
+        assert_eq!(decoded.source_port, "wasm.osmo1contract");
+        assert_eq!(
+            decoded.timeout_height,
+            Some(ProtoHeight {
+                revision_number: 4,
+                revision_height: 20_000_000,
+            })
+        );
+        assert_eq!(decoded.encode_to_vec(), msg.encode());
+    }
+
+    #[test]
+    fn test_amino_json() {
+        let msg: MsgTransfer = MsgTransferBuilder::new()
+            .source_channel("channel-141")
+            .token(Coin::new(1_000_000, "uatom"))
+            .sender("cosmos1sender")
+            .receiver("osmo1receiver")
+            .timeout_height(1, 100)
+            .build();
+
+        assert_eq!(msg.to_amino().amino_type, "cosmos-sdk/MsgTransfer");
+        assert_eq!(
+            sorted_json(&msg.amino_value()),
+            concat!(
+                r#"{"receiver":"osmo1receiver","sender":"cosmos1sender","#,
+                r#""source_channel":"channel-141","source_port":"transfer","#,
+                r#""timeout_height":{"revision_height":"100","revision_number":"1"},"#,
+                r#""token":{"amount":"1000000","denom":"uatom"}}"#
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "timeout_height or timeout_timestamp is mandatory")]
+    fn test_build_without_timeout() {
+        let _: MsgTransfer = MsgTransferBuilder::new()
+            .source_channel("channel-141")
+            .token(Coin::new(1_000_000, "uatom"))
+            .sender("cosmos1sender")
+            .receiver("osmo1receiver")
+            .build();
+    }
+}
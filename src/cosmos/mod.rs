@@ -0,0 +1,13 @@
+//! Transaction builder, encoders, types and utilities for Cosmos SDK chains.
+pub mod bank;
+mod cosmos_transaction;
+mod cosmos_transaction_builder;
+pub mod ibc;
+pub mod types;
+pub mod utils;
+pub mod wasm;
+
+/// Cosmos SDK transaction
+pub use cosmos_transaction::CosmosTransaction;
+/// Cosmos SDK transaction builder
+pub use cosmos_transaction_builder::CosmosTransactionBuilder;
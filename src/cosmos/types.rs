@@ -0,0 +1,228 @@
+//! Types used by the Cosmos transaction builder.
+use core::fmt;
+
+use ripemd::Ripemd160;
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::utils::{encode_address, encode_bytes, encode_message, encode_string, encode_uint64};
+
+/// A message of a transaction, encoded as a protobuf [`Any`] tagged with its type URL, or as
+/// an [`AminoMsg`] in `SIGN_MODE_LEGACY_AMINO_JSON`.
+pub trait Msg {
+    /// The type URL of the message, like `/cosmos.bank.v1beta1.MsgSend`.
+    const TYPE_URL: &'static str;
+    /// The Amino type of the message, like `cosmos-sdk/MsgSend`.
+    const AMINO_TYPE: &'static str;
+
+    /// The protobuf encoding of the message.
+    fn encode(&self) -> Vec<u8>;
+
+    /// The message as an [`Any`], added to the messages of a transaction.
+    fn to_any(&self) -> Any {
+        Any {
+            type_url: Self::TYPE_URL.to_string(),
+            value: self.encode(),
+        }
+    }
+
+    /// The value of the Amino JSON form of the message, its fields with integers as strings.
+    fn amino_value(&self) -> Value;
+
+    /// The message in its Amino JSON form, signed in `SIGN_MODE_LEGACY_AMINO_JSON`.
+    fn to_amino(&self) -> AminoMsg {
+        AminoMsg {
+            amino_type: Self::AMINO_TYPE.to_string(),
+            value: self.amino_value(),
+        }
+    }
+}
+
+/// A protobuf message of any type, its encoding tagged with its type URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Any {
+    pub type_url: String,
+    pub value: Vec<u8>,
+}
+
+impl Any {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_string(&mut buffer, 1, &self.type_url);
+        encode_bytes(&mut buffer, 2, &self.value);
+        buffer
+    }
+}
+
+/// A message in its Amino JSON form, tagged with its Amino type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AminoMsg {
+    #[serde(rename = "type")]
+    pub amino_type: String,
+    pub value: Value,
+}
+
+/// The way the signer of a transaction signs it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignMode {
+    /// The signer signs the protobuf `SignDoc` of the transaction.
+    #[default]
+    Direct = 1,
+    /// The signer signs the `StdSignDoc`, the sorted Amino JSON of the transaction, for Ledger
+    /// devices and chains not supporting `SIGN_MODE_DIRECT`.
+    LegacyAminoJson = 127,
+}
+
+/// An amount of tokens of a denomination, like `uatom`, its amount serialized in JSON as a
+/// decimal string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coin {
+    pub denom: String,
+    #[serde(with = "decimal_string")]
+    pub amount: u128,
+}
+
+impl Coin {
+    pub fn new(amount: u128, denom: &str) -> Self {
+        Self {
+            denom: denom.to_string(),
+            amount,
+        }
+    }
+
+    /// Encode the coin, its amount being a decimal string.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_string(&mut buffer, 1, &self.denom);
+        encode_string(&mut buffer, 2, &self.amount.to_string());
+        buffer
+    }
+}
+
+impl fmt::Display for Coin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+/// The fee paid by a transaction, and the gas it may consume.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Fee {
+    pub amount: Vec<Coin>,
+    pub gas_limit: u64,
+}
+
+impl Fee {
+    pub fn new(amount: Coin, gas_limit: u64) -> Self {
+        Self {
+            amount: vec![amount],
+            gas_limit,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        for coin in &self.amount {
+            encode_message(&mut buffer, 1, &coin.encode());
+        }
+        encode_uint64(&mut buffer, 2, self.gas_limit);
+        buffer
+    }
+}
+
+/// A compressed secp256k1 public key, the key of the accounts of most Cosmos SDK chains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PublicKey(pub [u8; 33]);
+
+impl PublicKey {
+    /// The type URL of secp256k1 public keys in the signer infos of transactions.
+    pub const TYPE_URL: &'static str = "/cosmos.crypto.secp256k1.PubKey";
+
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        <[u8; 33]>::try_from(bytes)
+            .map(Self)
+            .map_err(|_| format!("Invalid compressed public key length: {}", bytes.len()))
+    }
+
+    /// The bytes of the account of the key, the ripemd160 of its sha256.
+    pub fn account_id(&self) -> [u8; 20] {
+        Ripemd160::digest(Sha256::digest(self.0)).into()
+    }
+
+    /// The bech32 address of the account of the key on the chain of prefix `hrp`, like
+    /// `cosmos` or `osmo`.
+    pub fn address(&self, hrp: &str) -> Result<String, String> {
+        encode_address(hrp, &self.account_id())
+    }
+
+    /// The public key as an [`Any`], as the signer infos of transactions hold it.
+    pub fn to_any(&self) -> Any {
+        let mut value = Vec::new();
+        encode_bytes(&mut value, 1, &self.0);
+
+        Any {
+            type_url: Self::TYPE_URL.to_string(),
+            value,
+        }
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        let bytes = hex::decode(&s).map_err(D::Error::custom)?;
+        Self::from_slice(&bytes).map_err(D::Error::custom)
+    }
+}
+
+/// A secp256k1 signature of a transaction, `r || s` with a low S.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signature(pub [u8; 64]);
+
+mod decimal_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address() {
+        let public_key = PublicKey::from_slice(
+            &hex::decode("034f04181eeba35391b858633a765c4a0c189697b40d216354d50890d350c70290")
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            hex::encode(public_key.account_id()),
+            "0d82b1e7c96dbfa42462fe612932e6bff111d51b"
+        );
+        assert_eq!(
+            public_key.address("cosmos").unwrap(),
+            "cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6"
+        );
+        assert_eq!(
+            public_key.address("osmo").unwrap(),
+            "osmo1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmnd5nxg"
+        );
+        assert!(PublicKey::from_slice(&[2; 32]).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! Protobuf encoding of the fields of Cosmos SDK messages, the canonical JSON signed in
+//! `SIGN_MODE_LEGACY_AMINO_JSON`, and bech32 addresses.
+//!
+//! Fields holding the default value of their type are omitted, like proto3 encoders do, as the
+//! bytes signed in SIGN_MODE_DIRECT must match the ones the chain decodes and re-encodes.
+use bech32::{primitives::hrp::Hrp, Bech32};
+use serde_json::Value;
+
+/// Wire type of varint fields.
+const WIRE_TYPE_VARINT: u64 = 0;
+/// Wire type of length-delimited fields, strings, bytes and embedded messages.
+const WIRE_TYPE_LEN: u64 = 2;
+
+/// Encode `value` as a base 128 varint.
+pub fn encode_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn encode_key(buffer: &mut Vec<u8>, field: u32, wire_type: u64) {
+    encode_varint(buffer, (u64::from(field) << 3) | wire_type);
+}
+
+/// Encode an unsigned integer field, omitted if zero.
+pub fn encode_uint64(buffer: &mut Vec<u8>, field: u32, value: u64) {
+    if value != 0 {
+        encode_key(buffer, field, WIRE_TYPE_VARINT);
+        encode_varint(buffer, value);
+    }
+}
+
+/// Encode a bytes field, omitted if empty.
+pub fn encode_bytes(buffer: &mut Vec<u8>, field: u32, value: &[u8]) {
+    if !value.is_empty() {
+        encode_message(buffer, field, value);
+    }
+}
+
+/// Encode a string field, omitted if empty.
+pub fn encode_string(buffer: &mut Vec<u8>, field: u32, value: &str) {
+    encode_bytes(buffer, field, value.as_bytes());
+}
+
+/// Encode an embedded message field from its encoding, present even if empty, or an element of
+/// a repeated bytes field.
+pub fn encode_message(buffer: &mut Vec<u8>, field: u32, message: &[u8]) {
+    encode_key(buffer, field, WIRE_TYPE_LEN);
+    encode_varint(buffer, message.len() as u64);
+    buffer.extend_from_slice(message);
+}
+
+/// Encode `value` as compact JSON with the keys of its objects sorted, escaping `<`, `>`, `&`
+/// and the line and paragraph separators like Go does, the encoding of the Amino JSON sign
+/// bytes.
+pub fn sorted_json(value: &Value) -> String {
+    let mut json = String::new();
+    write_sorted_json(&mut json, value);
+
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn write_sorted_json(json: &mut String, value: &Value) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+
+            json.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                json.push_str(&Value::from(key.as_str()).to_string());
+                json.push(':');
+                write_sorted_json(json, value);
+            }
+            json.push('}');
+        }
+        Value::Array(values) => {
+            json.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                write_sorted_json(json, value);
+            }
+            json.push(']');
+        }
+        value => json.push_str(&value.to_string()),
+    }
+}
+
+/// Encode the bytes of an account, or of a contract, as a bech32 address of the chain of prefix
+/// `hrp`, like `cosmos` or `osmo`.
+pub fn encode_address(hrp: &str, bytes: &[u8]) -> Result<String, String> {
+    let hrp = Hrp::parse(hrp).map_err(|e| format!("Invalid address prefix {hrp}: {e}"))?;
+
+    bech32::encode::<Bech32>(hrp, bytes).map_err(|e| format!("Invalid address bytes: {e}"))
+}
+
+/// Decode a bech32 address into its prefix and bytes.
+pub fn decode_address(address: &str) -> Result<(String, Vec<u8>), String> {
+    let (hrp, bytes) =
+        bech32::decode(address).map_err(|e| format!("Invalid address {address}: {e}"))?;
+
+    Ok((hrp.to_lowercase(), bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_varint() {
+        for (value, expected) in [
+            (0, vec![0x00]),
+            (1, vec![0x01]),
+            (127, vec![0x7f]),
+            (128, vec![0x80, 0x01]),
+            (300, vec![0xac, 0x02]),
+            (
+                u64::MAX,
+                vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+            ),
+        ] {
+            let mut buffer = Vec::new();
+            encode_varint(&mut buffer, value);
+            assert_eq!(buffer, expected);
+        }
+    }
+
+    #[test]
+    fn test_encode_fields() {
+        let mut buffer = Vec::new();
+        encode_uint64(&mut buffer, 1, 0);
+        encode_string(&mut buffer, 2, "");
+        assert!(buffer.is_empty());
+
+        encode_uint64(&mut buffer, 1, 150);
+        encode_string(&mut buffer, 2, "hi");
+        encode_message(&mut buffer, 3, &[]);
+        assert_eq!(
+            buffer,
+            [0x08, 0x96, 0x01, 0x12, 0x02, b'h', b'i', 0x1a, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_sorted_json() {
+        let value = serde_json::json!({
+            "memo": "a < b && c > d",
+            "fee": { "gas": "200000", "amount": [] },
+            "account_number": "1",
+        });
+        assert_eq!(
+            sorted_json(&value),
+            r#"{"account_number":"1","fee":{"amount":[],"gas":"200000"},"memo":"a \u003c b \u0026\u0026 c \u003e d"}"#
+        );
+    }
+
+    #[test]
+    fn test_addresses() {
+        let bytes = hex::decode("0d82b1e7c96dbfa42462fe612932e6bff111d51b").unwrap();
+        let address = encode_address("cosmos", &bytes).unwrap();
+        assert_eq!(address, "cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6");
+        assert_eq!(
+            decode_address(&address).unwrap(),
+            ("cosmos".to_string(), bytes.clone())
+        );
+        assert_eq!(
+            decode_address(&address.to_uppercase()).unwrap(),
+            ("cosmos".to_string(), bytes)
+        );
+
+        assert!(encode_address("", &[1; 20]).is_err());
+        assert!(decode_address("cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs7").is_err());
+    }
+}
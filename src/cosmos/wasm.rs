@@ -0,0 +1,135 @@
+//! Messages of the CosmWasm module, calling smart contracts.
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{
+    types::{Coin, Msg},
+    utils::{encode_bytes, encode_message, encode_string},
+};
+
+/// Executes the contract `contract` with the JSON message `msg`, `sender` signing and sending
+/// `funds` to the contract along with the call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgExecuteContract {
+    pub sender: String,
+    pub contract: String,
+    /// The JSON message handled by the contract, like `{"transfer":{...}}`.
+    pub msg: Value,
+    pub funds: Vec<Coin>,
+}
+
+impl MsgExecuteContract {
+    /// Executes `contract` with `msg` serialized to JSON, sending it `funds`.
+    pub fn new(
+        sender: &str,
+        contract: &str,
+        msg: &impl Serialize,
+        funds: Vec<Coin>,
+    ) -> Result<Self, String> {
+        let msg =
+            serde_json::to_value(msg).map_err(|e| format!("Invalid contract message: {e}"))?;
+
+        Ok(Self {
+            sender: sender.to_string(),
+            contract: contract.to_string(),
+            msg,
+            funds,
+        })
+    }
+}
+
+impl Msg for MsgExecuteContract {
+    const TYPE_URL: &'static str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+    const AMINO_TYPE: &'static str = "wasm/MsgExecuteContract";
+
+    fn encode(&self) -> Vec<u8> {
+        let msg = serde_json::to_vec(&self.msg).expect("JSON values serialize");
+
+        let mut buffer = Vec::new();
+        encode_string(&mut buffer, 1, &self.sender);
+        encode_string(&mut buffer, 2, &self.contract);
+        encode_bytes(&mut buffer, 3, &msg);
+        for coin in &self.funds {
+            encode_message(&mut buffer, 5, &coin.encode());
+        }
+        buffer
+    }
+
+    /// The Amino JSON of the call, its message being inlined as JSON rather than bytes.
+    fn amino_value(&self) -> Value {
+        json!({
+            "sender": self.sender,
+            "contract": self.contract,
+            "msg": self.msg,
+            "funds": self.funds,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosmos::{cosmos_transaction::tests::proto, utils::sorted_json};
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct ProtoMsgExecuteContract {
+        #[prost(string, tag = "1")]
+        sender: String,
+        #[prost(string, tag = "2")]
+        contract: String,
+        #[prost(bytes, tag = "3")]
+        msg: Vec<u8>,
+        #[prost(message, repeated, tag = "5")]
+        funds: Vec<proto::Coin>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "snake_case")]
+    enum ExecuteMsg {
+        Transfer { recipient: String, amount: String },
+    }
+
+    #[test]
+    fn test_encode_against_prost() {
+        let msg = MsgExecuteContract::new(
+            "osmo1sender",
+            "osmo1contract",
+            &ExecuteMsg::Transfer {
+                recipient: "osmo1recipient".to_string(),
+                amount: "100".to_string(),
+            },
+            vec![Coin::new(1_000, "uosmo")],
+        )
+        .unwrap();
+
+        let expected = ProtoMsgExecuteContract {
+            sender: "osmo1sender".to_string(),
+            contract: "osmo1contract".to_string(),
+            msg: br#"{"transfer":{"amount":"100","recipient":"osmo1recipient"}}"#.to_vec(),
+            funds: vec![proto::coin(&Coin::new(1_000, "uosmo"))],
+        };
+        assert_eq!(msg.encode(), expected.encode_to_vec());
+        assert_eq!(
+            msg.to_any().type_url,
+            "/cosmwasm.wasm.v1.MsgExecuteContract"
+        );
+    }
+
+    #[test]
+    fn test_amino_json() {
+        let msg = MsgExecuteContract::new(
+            "osmo1sender",
+            "osmo1contract",
+            &json!({ "claim": {} }),
+            vec![],
+        )
+        .unwrap();
+
+        assert_eq!(msg.to_amino().amino_type, "wasm/MsgExecuteContract");
+        assert_eq!(
+            sorted_json(&msg.amino_value()),
+            r#"{"contract":"osmo1contract","funds":[],"msg":{"claim":{}},"sender":"osmo1sender"}"#
+        );
+    }
+}
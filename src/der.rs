@@ -0,0 +1,53 @@
+//! DER encoding of ECDSA signatures, shared by the Bitcoin and XRP Ledger encoders.
+use alloc::vec::Vec;
+
+/// The DER sequence of the two integers `r` and `s` of an ECDSA signature, given as big-endian
+/// bytes.
+pub(crate) fn encode_signature(r: &[u8], s: &[u8]) -> Vec<u8> {
+    let r = encode_integer(r);
+    let s = encode_integer(s);
+
+    let mut der = Vec::with_capacity(2 + r.len() + s.len());
+    der.extend_from_slice(&[0x30, (r.len() + s.len()) as u8]);
+    der.extend_from_slice(&r);
+    der.extend_from_slice(&s);
+    der
+}
+
+/// The DER integer of the positive big-endian `bytes`, minimally encoded: without its leading
+/// zeros, but with a zero before a high first bit.
+fn encode_integer(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes
+        .iter()
+        .position(|&byte| byte != 0)
+        .unwrap_or(bytes.len() - 1);
+    let pad = bytes[start] & 0x80 != 0;
+
+    let mut integer = Vec::with_capacity(3 + bytes.len() - start);
+    integer.extend_from_slice(&[0x02, (bytes.len() - start + usize::from(pad)) as u8]);
+    if pad {
+        integer.push(0);
+    }
+    integer.extend_from_slice(&bytes[start..]);
+    integer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_integer() {
+        assert_eq!(encode_integer(&[0, 0, 0x7f]), [0x02, 1, 0x7f]);
+        assert_eq!(encode_integer(&[0, 0x80, 1]), [0x02, 3, 0, 0x80, 1]);
+        assert_eq!(encode_integer(&[0, 0]), [0x02, 1, 0]);
+    }
+
+    #[test]
+    fn test_encode_signature() {
+        assert_eq!(
+            encode_signature(&[0x80], &[0, 1]),
+            [0x30, 7, 0x02, 2, 0, 0x80, 0x02, 1, 1]
+        );
+    }
+}
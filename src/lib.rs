@@ -6,6 +6,14 @@
 //! ### Supported chains:
 //! - EVM chains (including Ethereum and L2s)
 //! - Bitcoin
+//! - NEAR
+//! - Solana
+//! - Cosmos SDK chains
+//! - XRP Ledger
+//! - Substrate chains (Polkadot and Kusama)
+//! - TON
+//! - Tron
+//! - Aptos
 //!
 //! ### Installation
 //! ```toml
@@ -22,7 +30,7 @@
 //! ```rust
 //! use signet_rs::evm::utils::parse_eth_address;
 //! use signet_rs::{TransactionBuilder, TxBuilder, EVM};
-//! 
+//!
 //! let to_address_str = "d8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
 //! let to_address = parse_eth_address(to_address_str);
 //! let max_gas_fee: u128 = 20_000_000_000;
@@ -51,12 +59,14 @@
 //! ###### Building a Bitcoin transaction:
 //!
 //! ```rust
+//! # #[cfg(feature = "bitcoin")]
+//! # {
 //! use signet_rs::bitcoin::types::{
-//!     Amount, Hash, LockTime, OutPoint, ScriptBuf, Sequence, TxIn, TxOut, Txid, Version, 
+//!     Amount, Hash, LockTime, OutPoint, ScriptBuf, Sequence, TxIn, TxOut, Txid, Version,
 //!     Witness, EcdsaSighashType
 //! };
 //! use signet_rs::{TransactionBuilder, TxBuilder, BITCOIN};
-//! 
+//!
 //! let txid_str = "2ece6cd71fee90ff613cee8f30a52c3ecc58685acf9b817b9c467b7ff199871c";
 //! let hash = Hash::from_hex(txid_str).unwrap();
 //! let txid = Txid(hash);
@@ -93,12 +103,27 @@
 //!
 //! // Prepare the transaction for signing
 //! let encoded_tx = bitcoin_tx.build_for_signing_legacy(EcdsaSighashType::All);
+//! # }
 //! ```
 //!
 //! ### Features
 //!
 //! - bitcoin
 //! - evm
+//! - near
+//! - near-contract (helpers for NEAR contracts calling the chain signatures MPC contract)
+//! - k256 (derivation of the keys the MPC network signs with)
+//! - ed25519 (derivation of the keys the MPC network signs with in its EdDSA domain)
+//! - client (async client requesting signatures from the MPC contract through the NEAR RPC)
+//! - testing (local signer deriving keys like the MPC network, to test without it)
+//! - solana
+//! - cosmos
+//! - xrpl
+//! - substrate
+//! - ton
+//! - tron
+//! - aptos
+//! - rust-bitcoin (conversions to and from the [rust-bitcoin](https://docs.rs/bitcoin) types)
 //!
 //! By default 'all' the features are enabled. However, you can customize the behaviour like this:
 //!
@@ -113,23 +138,65 @@
 //! omni-transaction = { version = "0.2.1", features = ["bitcoin"] }
 //! ```
 
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 
+#[cfg(feature = "aptos")]
+pub mod aptos;
 #[cfg(feature = "bitcoin")]
 pub mod bitcoin;
 mod constants;
+#[cfg(feature = "cosmos")]
+pub mod cosmos;
+#[cfg(any(feature = "bitcoin", all(feature = "xrpl", feature = "k256")))]
+mod der;
 #[cfg(feature = "evm")]
 pub mod evm;
+#[cfg(feature = "near")]
+pub mod near;
 pub mod signer;
+#[cfg(feature = "solana")]
+pub mod solana;
+#[cfg(feature = "substrate")]
+pub mod substrate;
+#[cfg(feature = "ton")]
+pub mod ton;
 mod transaction_builder;
 mod transaction_builders;
+#[cfg(feature = "tron")]
+pub mod tron;
+#[cfg(feature = "xrpl")]
+pub mod xrpl;
 
 pub use transaction_builder::{TransactionBuilder, TxBuilder};
+/// Alias for AptosTransactionBuilder
+#[cfg(feature = "aptos")]
+pub use transaction_builders::APTOS;
 /// Alias for BitcoinTransactionBuilder
 #[cfg(feature = "bitcoin")]
 pub use transaction_builders::BITCOIN;
+/// Alias for CosmosTransactionBuilder
+#[cfg(feature = "cosmos")]
+pub use transaction_builders::COSMOS;
 /// Alias for EVMTransactionBuilder
 #[cfg(feature = "evm")]
 pub use transaction_builders::EVM;
+/// Alias for NEARTransactionBuilder
+#[cfg(feature = "near")]
+pub use transaction_builders::NEAR;
+/// Alias for SolanaTransactionBuilder
+#[cfg(feature = "solana")]
+pub use transaction_builders::SOLANA;
+/// Alias for SubstrateTransactionBuilder
+#[cfg(feature = "substrate")]
+pub use transaction_builders::SUBSTRATE;
+/// Alias for TonTransactionBuilder
+#[cfg(feature = "ton")]
+pub use transaction_builders::TON;
+/// Alias for TronTransactionBuilder
+#[cfg(feature = "tron")]
+pub use transaction_builders::TRON;
+/// Alias for XrplTransactionBuilder
+#[cfg(feature = "xrpl")]
+pub use transaction_builders::XRPL;
@@ -0,0 +1,260 @@
+//! Calls from NEAR contracts to the chain signatures MPC contract (`v1.signer` on mainnet,
+//! `v1.signer-prod.testnet` on testnet).
+//!
+//! The call maps onto `Promise::function_call` of `near-sdk`, the contract receiving the
+//! [`SignatureResponse`] in its callback:
+//!
+//! ```rust,ignore
+//! let call = chain_signatures::sign(&request);
+//!
+//! Promise::new("v1.signer".parse().unwrap())
+//!     .function_call(
+//!         call.method_name,
+//!         call.args,
+//!         NearToken::from_yoctonear(call.deposit.as_yocto()),
+//!         Gas::from_gas(call.gas.as_gas()),
+//!     )
+//!     .then(Self::ext(env::current_account_id()).on_signed(SignCallbackArgs::from(&request)))
+//! ```
+//!
+//! The callback parses the result of the `sign` promise, resumed by the MPC nodes, with
+//! [`parse_sign_result`]:
+//!
+//! ```rust,ignore
+//! #[private]
+//! pub fn on_signed(&mut self, args: SignCallbackArgs) -> SignatureResponse {
+//!     let result = match env::promise_result(0) {
+//!         PromiseResult::Successful(value) => Some(value),
+//!         _ => None,
+//!     };
+//!
+//!     chain_signatures::parse_sign_result(result.as_deref())
+//!         .unwrap_or_else(|e| env::panic_str(&e.to_string()))
+//! }
+//! ```
+use alloc::string::String;
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::types::{FunctionCallAction, NearGas, NearToken};
+use crate::signer::{
+    types::{KeyVersion, SignRequest, SignRequestV2, SignatureResponse},
+    SignerNetwork,
+};
+
+/// The method of the MPC contract requesting a signature.
+pub const SIGN_METHOD_NAME: &str = "sign";
+/// The deposit attached to `sign`, the minimum the MPC contract accepts.
+pub const SIGN_DEPOSIT: NearToken = NearToken::from_yocto(1);
+/// The gas attached to `sign`, enough for the request to wait for the MPC nodes and resume.
+pub const SIGN_GAS: NearGas = NearGas::from_tgas(250);
+
+/// The gas price floor of NEAR, in yoctoNEAR per unit of gas.
+pub const MIN_GAS_PRICE: NearToken = NearToken::from_yocto(100_000_000);
+
+impl SignerNetwork {
+    /// The deposit the MPC contract requires to be attached to `sign`.
+    pub const fn sign_deposit(&self) -> NearToken {
+        match self {
+            Self::Mainnet | Self::Testnet => SIGN_DEPOSIT,
+        }
+    }
+
+    /// The gas recommended to attach to `sign`.
+    pub const fn sign_gas(&self) -> NearGas {
+        match self {
+            Self::Mainnet | Self::Testnet => SIGN_GAS,
+        }
+    }
+
+    /// The most `requests` calls to `sign` can cost at `gas_price` per unit of gas, the
+    /// deposits plus the gas attached, `None` on overflow.
+    ///
+    /// The gas left unused is refunded, so the actual cost is lower.
+    pub const fn sign_cost(&self, requests: u128, gas_price: NearToken) -> Option<NearToken> {
+        let gas = match gas_price.checked_mul(self.sign_gas().as_gas() as u128) {
+            Some(gas) => gas,
+            None => return None,
+        };
+        match self.sign_deposit().checked_add(gas) {
+            Some(cost) => cost.checked_mul(requests),
+            None => None,
+        }
+    }
+}
+
+/// Requests the MPC contract to sign `request.payload` with the key derived from the calling
+/// account and `request.path`.
+pub fn sign(request: &SignRequest) -> FunctionCallAction {
+    FunctionCallAction {
+        method_name: SIGN_METHOD_NAME.to_string(),
+        args: request.to_json_args(),
+        gas: SIGN_GAS,
+        deposit: SIGN_DEPOSIT,
+    }
+}
+
+/// Requests the MPC contract to sign `request.payload` with the key of `request.domain_id`
+/// derived from the calling account and `request.path`.
+pub fn sign_v2(request: &SignRequestV2) -> FunctionCallAction {
+    FunctionCallAction {
+        method_name: SIGN_METHOD_NAME.to_string(),
+        args: request.to_json_args(),
+        gas: SIGN_GAS,
+        deposit: SIGN_DEPOSIT,
+    }
+}
+
+/// Arguments of the callback of a [`sign`] call, identifying the request the signature is for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignCallbackArgs {
+    /// The signed payload.
+    pub payload: [u8; 32],
+    /// The derivation path of the key the payload is signed with.
+    pub path: String,
+    /// The version of the root key of the MPC contract.
+    pub key_version: KeyVersion,
+}
+
+impl From<&SignRequest> for SignCallbackArgs {
+    fn from(request: &SignRequest) -> Self {
+        Self {
+            payload: request.payload,
+            path: request.path.clone(),
+            key_version: request.key_version,
+        }
+    }
+}
+
+/// Errors of a [`sign`] call, as seen by its callback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignCallbackError {
+    /// The `sign` promise failed, the MPC contract failing the requests the MPC nodes did not
+    /// resume before the yield timed out.
+    Timeout,
+    /// The `sign` promise returned a value that is not a signature response.
+    InvalidResponse(String),
+}
+
+impl fmt::Display for SignCallbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "Sign request failed or timed out"),
+            Self::InvalidResponse(e) => write!(f, "Invalid signature response: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SignCallbackError {}
+
+/// Parses the result of a [`sign`] promise, the JSON-encoded signature returned by the MPC
+/// contract, or `None` if the promise failed.
+pub fn parse_sign_result(result: Option<&[u8]>) -> Result<SignatureResponse, SignCallbackError> {
+    let value = result.ok_or(SignCallbackError::Timeout)?;
+
+    serde_json::from_slice(value).map_err(|e| SignCallbackError::InvalidResponse(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::types::{DomainId, Payload};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_sign() {
+        let request = SignRequest {
+            payload: [7; 32],
+            path: "ethereum-1".to_string(),
+            key_version: KeyVersion::V0,
+        };
+
+        let call = sign(&request);
+        assert_eq!(call.method_name, "sign");
+        assert_eq!(call.deposit, NearToken::from_yocto(1));
+        assert_eq!(call.gas, NearGas::from_tgas(250));
+        assert_eq!(
+            call.json_args::<Value>().unwrap(),
+            json!({
+                "request": {
+                    "payload": request.payload,
+                    "path": "ethereum-1",
+                    "key_version": 0
+                }
+            })
+        );
+
+        let args = SignCallbackArgs::from(&request);
+        let json = serde_json::to_string(&args).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SignCallbackArgs>(&json).unwrap(),
+            args
+        );
+    }
+
+    #[test]
+    fn test_sign_v2() {
+        let request = SignRequestV2 {
+            path: "solana-1".to_string(),
+            payload: Payload::eddsa(vec![7; 32]).unwrap(),
+            domain_id: DomainId::Ed25519,
+        };
+
+        let call = sign_v2(&request);
+        assert_eq!(call.method_name, "sign");
+        assert_eq!(call.deposit, NearToken::from_yocto(1));
+        assert_eq!(call.gas, NearGas::from_tgas(250));
+        assert_eq!(
+            call.json_args::<Value>().unwrap(),
+            json!({
+                "request": {
+                    "path": "solana-1",
+                    "payload_v2": { "Eddsa": "07".repeat(32) },
+                    "domain_id": 1
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_sign_result() {
+        let value = br#"{"big_r":{"affine_point":"02AB"},"s":{"scalar":"CD"},"recovery_id":1}"#;
+        let signature = parse_sign_result(Some(value)).unwrap();
+        assert_eq!(signature.big_r.affine_point, "02AB");
+        assert_eq!(signature.recovery_id, 1);
+
+        assert_eq!(
+            parse_sign_result(None).unwrap_err(),
+            SignCallbackError::Timeout
+        );
+        assert!(matches!(
+            parse_sign_result(Some(b"null")),
+            Err(SignCallbackError::InvalidResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_sign_cost() {
+        let network = SignerNetwork::Mainnet;
+        assert_eq!(network.contract_id(), "v1.signer");
+        assert_eq!(
+            SignerNetwork::Testnet.contract_id(),
+            "v1.signer-prod.testnet"
+        );
+        assert_eq!(network.sign_deposit(), SIGN_DEPOSIT);
+        assert_eq!(network.sign_gas(), SIGN_GAS);
+
+        // 250 Tgas at 0.0001 NEAR per Tgas, plus 1 yoctoNEAR
+        assert_eq!(
+            network.sign_cost(1, MIN_GAS_PRICE),
+            Some(NearToken::from_yocto(25_000_000_000_000_000_000_001))
+        );
+        assert_eq!(
+            network.sign_cost(3, MIN_GAS_PRICE),
+            Some(NearToken::from_yocto(75_000_000_000_000_000_000_003))
+        );
+        assert_eq!(network.sign_cost(0, MIN_GAS_PRICE), Some(NearToken::ZERO));
+        assert_eq!(network.sign_cost(u128::MAX, MIN_GAS_PRICE), None);
+    }
+}
@@ -0,0 +1,20 @@
+//! Transaction builder, encoders, types and utilities for NEAR.
+#[cfg(feature = "near-contract")]
+pub mod chain_signatures;
+pub mod multisig;
+mod near_transaction;
+mod near_transaction_builder;
+pub mod nep141;
+pub mod nep145;
+pub mod nep171;
+pub mod nep413;
+pub mod signable_message;
+pub mod types;
+pub mod utils;
+
+/// NEAR transaction
+pub use near_transaction::NEARTransaction;
+/// Signed NEAR transaction
+pub use near_transaction::SignedTransaction;
+/// NEAR transaction builder
+pub use near_transaction_builder::NEARTransactionBuilder;
@@ -0,0 +1,187 @@
+//! Requests to the [multisig contract] of `near/core-contracts`, executing actions once enough of
+//! the keys of the multisig account confirmed them.
+//!
+//! [multisig contract]: https://github.com/near/core-contracts/tree/master/multisig
+use serde::Serialize;
+
+use super::types::{
+    AccessKeyPermission, AccountId, Action, Base64VecU8, FunctionCallAction,
+    FunctionCallPermission, NearGas, NearToken, PublicKey,
+};
+
+#[derive(Serialize)]
+struct AddRequestArgs<'a> {
+    request: MultiSigRequest<'a>,
+}
+
+#[derive(Serialize)]
+struct MultiSigRequest<'a> {
+    receiver_id: &'a AccountId,
+    actions: Vec<MultiSigRequestAction<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum MultiSigRequestAction<'a> {
+    Transfer {
+        amount: NearToken,
+    },
+    CreateAccount,
+    DeployContract {
+        code: &'a Base64VecU8,
+    },
+    AddKey {
+        public_key: &'a PublicKey,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        permission: Option<&'a FunctionCallPermission>,
+    },
+    DeleteKey {
+        public_key: &'a PublicKey,
+    },
+    FunctionCall {
+        method_name: &'a str,
+        args: Base64VecU8,
+        deposit: NearToken,
+        gas: String,
+    },
+}
+
+impl<'a> TryFrom<&'a Action> for MultiSigRequestAction<'a> {
+    type Error = String;
+
+    fn try_from(action: &'a Action) -> Result<Self, Self::Error> {
+        match action {
+            Action::Transfer(action) => Ok(Self::Transfer {
+                amount: action.deposit,
+            }),
+            Action::CreateAccount(_) => Ok(Self::CreateAccount),
+            Action::DeployContract(action) => Ok(Self::DeployContract { code: &action.code }),
+            Action::AddKey(action) => Ok(Self::AddKey {
+                public_key: &action.public_key,
+                permission: match &action.access_key.permission {
+                    AccessKeyPermission::FunctionCall(permission) => Some(permission),
+                    AccessKeyPermission::FullAccess => None,
+                },
+            }),
+            Action::DeleteKey(action) => Ok(Self::DeleteKey {
+                public_key: &action.public_key,
+            }),
+            Action::FunctionCall(action) => Ok(Self::FunctionCall {
+                method_name: &action.method_name,
+                args: Base64VecU8::new(action.args.clone()),
+                deposit: action.deposit,
+                gas: action.gas.as_gas().to_string(),
+            }),
+            _ => Err(format!(
+                "Action not supported by the multisig contract: {:?}",
+                action
+            )),
+        }
+    }
+}
+
+/// Requests the multisig contract of the receiver of the transaction to execute `actions` on
+/// `receiver_id`, confirming the request with the key signing the transaction.
+///
+/// The transaction must be signed by one of the keys of the multisig account, its signer being
+/// the multisig account itself. Only transfers, account creations, contract deployments, function
+/// calls and key additions and deletions can be requested.
+pub fn add_request_and_confirm(
+    receiver_id: &AccountId,
+    actions: &[Action],
+    gas: NearGas,
+) -> Result<FunctionCallAction, String> {
+    let args = AddRequestArgs {
+        request: MultiSigRequest {
+            receiver_id,
+            actions: actions
+                .iter()
+                .map(MultiSigRequestAction::try_from)
+                .collect::<Result<_, _>>()?,
+        },
+    };
+
+    FunctionCallAction::with_json_args("add_request_and_confirm", &args, gas, NearToken::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::near::types::{
+        AccessKey, AddKeyAction, CreateAccountAction, DeleteAccountAction, TransferAction,
+    };
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_add_request_and_confirm() {
+        let receiver_id: AccountId = "bob.near".parse().unwrap();
+        let public_key = PublicKey::Ed25519([1; 32]);
+        let actions = vec![
+            Action::from(CreateAccountAction {}),
+            Action::from(TransferAction {
+                deposit: NearToken::from_yocto(5),
+            }),
+            Action::from(AddKeyAction {
+                public_key,
+                access_key: AccessKey::full_access(),
+            }),
+            Action::from(AddKeyAction {
+                public_key,
+                access_key: AccessKey::function_call(
+                    None,
+                    "app.near".parse().unwrap(),
+                    vec!["act".to_string()],
+                ),
+            }),
+            Action::from(FunctionCallAction {
+                method_name: "sign".to_string(),
+                args: b"{}".to_vec(),
+                gas: NearGas::from_tgas(250),
+                deposit: NearToken::from_yocto(1),
+            }),
+        ];
+
+        let action =
+            add_request_and_confirm(&receiver_id, &actions, NearGas::from_tgas(300)).unwrap();
+        assert_eq!(action.method_name, "add_request_and_confirm");
+        assert_eq!(action.gas, NearGas::from_tgas(300));
+        assert_eq!(action.deposit, NearToken::ZERO);
+        assert_eq!(
+            action.json_args::<Value>().unwrap(),
+            json!({
+                "request": {
+                    "receiver_id": "bob.near",
+                    "actions": [
+                        { "type": "CreateAccount" },
+                        { "type": "Transfer", "amount": "5" },
+                        { "type": "AddKey", "public_key": public_key.to_string() },
+                        {
+                            "type": "AddKey",
+                            "public_key": public_key.to_string(),
+                            "permission": {
+                                "allowance": null,
+                                "receiver_id": "app.near",
+                                "method_names": ["act"]
+                            }
+                        },
+                        {
+                            "type": "FunctionCall",
+                            "method_name": "sign",
+                            "args": "e30=",
+                            "deposit": "1",
+                            "gas": "250000000000000"
+                        }
+                    ]
+                }
+            })
+        );
+
+        let delete_account = Action::from(DeleteAccountAction {
+            beneficiary_id: receiver_id.clone(),
+        });
+        assert!(
+            add_request_and_confirm(&receiver_id, &[delete_account], NearGas::from_tgas(300))
+                .is_err()
+        );
+    }
+}
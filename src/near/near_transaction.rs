@@ -0,0 +1,617 @@
+//! NEAR transaction
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serde::{de::Error as DeError, Deserialize};
+use sha2::{Digest, Sha256};
+
+use super::types::{
+    AccessKey, AccountId, Action, AddKeyAction, Base64VecU8, BlockHash, CreateAccountAction,
+    CryptoHash, DelegateAction, DeleteAccountAction, DeleteKeyAction, DeployContractAction,
+    DeployGlobalContractAction, FunctionCallAction, GlobalContractDeployMode,
+    GlobalContractIdentifier, NearGas, NearToken, PublicKey, Signature, StakeAction,
+    TransferAction, UseGlobalContractAction,
+};
+
+/// A NEAR transaction, borsh-encoded like the `Transaction::V0` of `near-primitives`.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::near::types::{
+///     Action, NearToken, PublicKey, Signature, TransferAction,
+/// };
+/// use signet_rs::near::NEARTransaction;
+///
+/// let tx = NEARTransaction {
+///     signer_id: "alice.near".parse().unwrap(),
+///     signer_public_key: PublicKey::Ed25519([1; 32]),
+///     nonce: 1,
+///     receiver_id: "bob.near".parse().unwrap(),
+///     // The hash of a recent block, `block.header.hash` of the `block` RPC method
+///     block_hash: "EPnLgE7iEq9s7yTkos96M3cWymH5avBAPm3qx3NXqR8H".parse().unwrap(),
+///     actions: vec![Action::Transfer(TransferAction {
+///         deposit: NearToken::from_near(1.5).unwrap(),
+///     })],
+/// };
+///
+/// // The access key signs the SHA-256 of the borsh encoding
+/// let payload = tx.build_for_signing();
+/// let hash = tx.signing_hash();
+///
+/// // The signed transaction is broadcast base64-encoded with `broadcast_tx`
+/// let signature = Signature::Ed25519([3; 64]);
+/// let signed_tx = tx.build_with_signature(&signature);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct NEARTransaction {
+    /// Account signing the transaction and paying for its gas.
+    pub signer_id: AccountId,
+    /// Public key of the access key of the signer the transaction is signed with.
+    pub signer_public_key: PublicKey,
+    /// Nonce of the access key, larger than the one of the previous transaction it signed.
+    pub nonce: u64,
+    /// Account the actions apply to.
+    pub receiver_id: AccountId,
+    /// Hash of a recent block, the transaction expiring about a day after it.
+    pub block_hash: BlockHash,
+    /// Actions applied in order, either all of them or none.
+    pub actions: Vec<Action>,
+}
+
+impl NEARTransaction {
+    /// Encode the transaction with borsh, the payload whose SHA-256 is signed
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("Failed to serialize NEAR transaction")
+    }
+
+    /// Compute the SHA-256 of the borsh encoding of the transaction, the message signed by the
+    /// access key
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.build_for_signing()).into()
+    }
+
+    /// The base58-encoded hash of the transaction, identifying it once signed on explorers and
+    /// RPC queries like `tx`
+    pub fn tx_hash(&self) -> String {
+        CryptoHash(self.signing_hash()).to_string()
+    }
+
+    /// Encode the transaction and its signature with borsh as a [`SignedTransaction`]
+    pub fn build_with_signature(&self, signature: &Signature) -> Vec<u8> {
+        let mut buffer = self.build_for_signing();
+        signature
+            .serialize(&mut buffer)
+            .expect("Failed to serialize NEAR signature");
+
+        buffer
+    }
+
+    /// Parse a transaction in the JSON format of the NEAR RPC: actions externally tagged, keys,
+    /// signatures and hashes in base58, code and args in base64 and amounts as strings.
+    ///
+    /// The transactions returned by the RPC lack the `block_hash` they reference, which must be
+    /// added.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let tx: TransactionJson = serde_json::from_str(json)?;
+
+        Ok(Self {
+            signer_id: tx.signer_id,
+            signer_public_key: tx.public_key,
+            nonce: tx.nonce,
+            receiver_id: tx.receiver_id,
+            block_hash: tx.block_hash,
+            actions: tx
+                .actions
+                .into_iter()
+                .map(Action::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(serde_json::Error::custom)?,
+        })
+    }
+
+    /// Decode a base64-encoded borsh transaction, like the unsigned payloads handed to wallets
+    /// for signing.
+    pub fn from_base64_borsh(encoded: &str) -> Result<Self, String> {
+        decode_base64_borsh(encoded)
+    }
+}
+
+/// A NEAR transaction signed by the access key of its signer, borsh-encoded like the
+/// `SignedTransaction` of `near-primitives`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct SignedTransaction {
+    pub transaction: NEARTransaction,
+    pub signature: Signature,
+}
+
+impl SignedTransaction {
+    /// The base58-encoded hash of the transaction, the signature not being part of it
+    pub fn tx_hash(&self) -> String {
+        self.transaction.tx_hash()
+    }
+
+    /// Decode a base64-encoded borsh signed transaction, like the params of `broadcast_tx` and
+    /// `send_tx` or the ones shown by explorers.
+    pub fn from_base64_borsh(encoded: &str) -> Result<Self, String> {
+        decode_base64_borsh(encoded)
+    }
+}
+
+fn decode_base64_borsh<T: BorshDeserialize>(encoded: &str) -> Result<T, String> {
+    let bytes = Base64VecU8::decode(encoded.trim())?;
+    borsh::from_slice(&bytes.0).map_err(|e| format!("Invalid NEAR transaction: {}", e))
+}
+
+/// A transaction as returned by the `tx` and `EXPERIMENTAL_tx_status` RPC methods.
+#[derive(Deserialize)]
+struct TransactionJson {
+    signer_id: AccountId,
+    #[serde(alias = "signer_public_key")]
+    public_key: PublicKey,
+    nonce: u64,
+    receiver_id: AccountId,
+    block_hash: BlockHash,
+    #[serde(default)]
+    actions: Vec<ActionJson>,
+}
+
+/// An action as returned by the RPC.
+#[derive(Deserialize)]
+enum ActionJson {
+    CreateAccount,
+    DeployContract {
+        code: Base64VecU8,
+    },
+    FunctionCall {
+        method_name: String,
+        args: Base64VecU8,
+        gas: NearGas,
+        deposit: NearToken,
+    },
+    Transfer {
+        deposit: NearToken,
+    },
+    Stake {
+        stake: NearToken,
+        public_key: PublicKey,
+    },
+    AddKey {
+        public_key: PublicKey,
+        access_key: AccessKey,
+    },
+    DeleteKey {
+        public_key: PublicKey,
+    },
+    DeleteAccount {
+        beneficiary_id: AccountId,
+    },
+    Delegate {
+        delegate_action: DelegateActionJson,
+        signature: Signature,
+    },
+    DeployGlobalContract {
+        code: Base64VecU8,
+    },
+    DeployGlobalContractByAccountId {
+        code: Base64VecU8,
+    },
+    UseGlobalContract {
+        code_hash: CryptoHash,
+    },
+    UseGlobalContractByAccountId {
+        account_id: AccountId,
+    },
+}
+
+#[derive(Deserialize)]
+struct DelegateActionJson {
+    sender_id: AccountId,
+    receiver_id: AccountId,
+    actions: Vec<ActionJson>,
+    nonce: u64,
+    max_block_height: u64,
+    public_key: PublicKey,
+}
+
+impl TryFrom<ActionJson> for Action {
+    type Error = String;
+
+    fn try_from(action: ActionJson) -> Result<Self, Self::Error> {
+        Ok(match action {
+            ActionJson::CreateAccount => CreateAccountAction {}.into(),
+            ActionJson::DeployContract { code } => DeployContractAction { code }.into(),
+            ActionJson::FunctionCall {
+                method_name,
+                args,
+                gas,
+                deposit,
+            } => FunctionCallAction {
+                method_name,
+                args: args.into(),
+                gas,
+                deposit,
+            }
+            .into(),
+            ActionJson::Transfer { deposit } => TransferAction { deposit }.into(),
+            ActionJson::Stake { stake, public_key } => StakeAction::new(stake, public_key)?.into(),
+            ActionJson::AddKey {
+                public_key,
+                access_key,
+            } => AddKeyAction {
+                public_key,
+                access_key,
+            }
+            .into(),
+            ActionJson::DeleteKey { public_key } => DeleteKeyAction { public_key }.into(),
+            ActionJson::DeleteAccount { beneficiary_id } => {
+                DeleteAccountAction { beneficiary_id }.into()
+            }
+            ActionJson::Delegate {
+                delegate_action,
+                signature,
+            } => DelegateAction {
+                sender_id: delegate_action.sender_id,
+                receiver_id: delegate_action.receiver_id,
+                actions: delegate_action
+                    .actions
+                    .into_iter()
+                    .map(|action| Action::try_from(action)?.try_into())
+                    .collect::<Result<_, _>>()?,
+                nonce: delegate_action.nonce,
+                max_block_height: delegate_action.max_block_height,
+                public_key: delegate_action.public_key,
+            }
+            .build_with_signature(signature)
+            .into(),
+            ActionJson::DeployGlobalContract { code } => DeployGlobalContractAction {
+                code,
+                deploy_mode: GlobalContractDeployMode::CodeHash,
+            }
+            .into(),
+            ActionJson::DeployGlobalContractByAccountId { code } => DeployGlobalContractAction {
+                code,
+                deploy_mode: GlobalContractDeployMode::AccountId,
+            }
+            .into(),
+            ActionJson::UseGlobalContract { code_hash } => UseGlobalContractAction {
+                contract_identifier: GlobalContractIdentifier::CodeHash(code_hash),
+            }
+            .into(),
+            ActionJson::UseGlobalContractByAccountId { account_id } => UseGlobalContractAction {
+                contract_identifier: GlobalContractIdentifier::AccountId(account_id),
+            }
+            .into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::near::types::{NearToken, TransferAction};
+
+    fn borsh_string(s: &str) -> Vec<u8> {
+        [
+            (s.len() as u32).to_le_bytes().to_vec(),
+            s.as_bytes().to_vec(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_build_for_signing() {
+        let tx = NEARTransaction {
+            signer_id: "alice.near".parse().unwrap(),
+            signer_public_key: PublicKey::Ed25519([1; 32]),
+            nonce: 7,
+            receiver_id: "bob.near".parse().unwrap(),
+            block_hash: CryptoHash([2; 32]),
+            actions: vec![Action::Transfer(TransferAction {
+                deposit: NearToken::from_yocto(1),
+            })],
+        };
+
+        let expected = [
+            borsh_string("alice.near"),
+            // Key type and key
+            vec![0],
+            vec![1; 32],
+            7u64.to_le_bytes().to_vec(),
+            borsh_string("bob.near"),
+            vec![2; 32],
+            // One transfer action
+            1u32.to_le_bytes().to_vec(),
+            vec![3],
+            1u128.to_le_bytes().to_vec(),
+        ]
+        .concat();
+
+        assert_eq!(tx.build_for_signing(), expected);
+        assert_eq!(
+            tx.signing_hash(),
+            <[u8; 32]>::from(Sha256::digest(&expected))
+        );
+        assert_eq!(NEARTransaction::try_from_slice(&expected).unwrap(), tx);
+        assert_eq!(tx.tx_hash(), "EF5GNPE5we8azXhvJ1xVpGHPqUzqy8NUFKpp8YnWXeyT");
+    }
+
+    #[test]
+    fn test_build_with_signature() {
+        let tx = NEARTransaction {
+            signer_id: "alice.near".parse().unwrap(),
+            signer_public_key: PublicKey::Secp256k1([4; 64]),
+            nonce: 7,
+            receiver_id: "bob.near".parse().unwrap(),
+            block_hash: CryptoHash([2; 32]),
+            actions: vec![Action::Transfer(TransferAction {
+                deposit: NearToken::from_yocto(1),
+            })],
+        };
+
+        let signature = Signature::secp256k1(&[5; 32], &[5; 32], 1).unwrap();
+        let encoded = tx.build_with_signature(&signature);
+
+        let expected = [tx.build_for_signing(), vec![1], vec![5; 64], vec![1]].concat();
+        assert_eq!(encoded, expected);
+        let signed_tx = SignedTransaction::try_from_slice(&encoded).unwrap();
+        assert_eq!(signed_tx.tx_hash(), tx.tx_hash());
+        assert_eq!(
+            signed_tx,
+            SignedTransaction {
+                transaction: tx,
+                signature,
+            }
+        );
+
+        assert!(Signature::secp256k1(&[5; 32], &[5; 32], 27).is_err());
+    }
+
+    #[test]
+    fn test_from_json() {
+        let json = r#"{
+            "signer_id": "alice.near",
+            "public_key": "ed25519:11111111111111111111111111111111",
+            "nonce": 7,
+            "receiver_id": "bob.near",
+            "block_hash": "11111111111111111111111111111111",
+            "actions": [
+                "CreateAccount",
+                { "DeployContract": { "code": "AGFzbQ==" } },
+                {
+                    "FunctionCall": {
+                        "method_name": "set",
+                        "args": "eyJ2YWx1ZSI6NDJ9",
+                        "gas": 30000000000000,
+                        "deposit": "1"
+                    }
+                },
+                { "Transfer": { "deposit": "1000000000000000000000000" } },
+                {
+                    "AddKey": {
+                        "public_key": "ed25519:11111111111111111111111111111111",
+                        "access_key": {
+                            "nonce": 0,
+                            "permission": {
+                                "FunctionCall": {
+                                    "allowance": null,
+                                    "receiver_id": "app.near",
+                                    "method_names": []
+                                }
+                            }
+                        }
+                    }
+                },
+                {
+                    "AddKey": {
+                        "public_key": "ed25519:11111111111111111111111111111111",
+                        "access_key": { "nonce": 0, "permission": "FullAccess" }
+                    }
+                },
+                { "DeleteKey": { "public_key": "ed25519:11111111111111111111111111111111" } },
+                { "DeleteAccount": { "beneficiary_id": "carol.near" } },
+                { "DeployGlobalContractByAccountId": { "code": "AGFzbQ==" } },
+                { "UseGlobalContract": { "code_hash": "11111111111111111111111111111111" } }
+            ],
+            "signature": "ed25519:1111111111111111111111111111111111111111111111111111111111111111",
+            "hash": "EF5GNPE5we8azXhvJ1xVpGHPqUzqy8NUFKpp8YnWXeyT"
+        }"#;
+
+        let tx = NEARTransaction::from_json(json).unwrap();
+        let key = PublicKey::Ed25519([0; 32]);
+        assert_eq!(tx.signer_id.as_str(), "alice.near");
+        assert_eq!(tx.signer_public_key, key);
+        assert_eq!(tx.nonce, 7);
+        assert_eq!(tx.receiver_id.as_str(), "bob.near");
+        assert_eq!(tx.block_hash, CryptoHash([0; 32]));
+        assert_eq!(
+            tx.actions,
+            vec![
+                CreateAccountAction {}.into(),
+                DeployContractAction {
+                    code: b"\0asm".to_vec().into()
+                }
+                .into(),
+                FunctionCallAction {
+                    method_name: "set".to_string(),
+                    args: br#"{"value":42}"#.to_vec(),
+                    gas: NearGas::from_tgas(30),
+                    deposit: NearToken::from_yocto(1),
+                }
+                .into(),
+                TransferAction {
+                    deposit: NearToken::from_near(1.0).unwrap()
+                }
+                .into(),
+                AddKeyAction {
+                    public_key: key,
+                    access_key: AccessKey::function_call(None, "app.near".parse().unwrap(), vec![]),
+                }
+                .into(),
+                AddKeyAction {
+                    public_key: key,
+                    access_key: AccessKey::full_access(),
+                }
+                .into(),
+                DeleteKeyAction { public_key: key }.into(),
+                DeleteAccountAction {
+                    beneficiary_id: "carol.near".parse().unwrap()
+                }
+                .into(),
+                DeployGlobalContractAction {
+                    code: b"\0asm".to_vec().into(),
+                    deploy_mode: GlobalContractDeployMode::AccountId,
+                }
+                .into(),
+                UseGlobalContractAction {
+                    contract_identifier: GlobalContractIdentifier::CodeHash(CryptoHash([0; 32])),
+                }
+                .into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_json_delegate() {
+        let delegate = r#"{
+            "Delegate": {
+                "delegate_action": {
+                    "sender_id": "alice.near",
+                    "receiver_id": "bob.near",
+                    "actions": [{ "Transfer": { "deposit": "1" } }],
+                    "nonce": 7,
+                    "max_block_height": 100,
+                    "public_key": "ed25519:11111111111111111111111111111111"
+                },
+                "signature": "ed25519:1111111111111111111111111111111111111111111111111111111111111111"
+            }
+        }"#;
+        let json = format!(
+            r#"{{
+                "signer_id": "relayer.near",
+                "public_key": "ed25519:11111111111111111111111111111111",
+                "nonce": 1,
+                "receiver_id": "alice.near",
+                "block_hash": "11111111111111111111111111111111",
+                "actions": [{}]
+            }}"#,
+            delegate
+        );
+
+        let tx = NEARTransaction::from_json(&json).unwrap();
+        let Action::Delegate(signed_delegate_action) = &tx.actions[0] else {
+            panic!("expected a delegate action");
+        };
+        assert_eq!(
+            signed_delegate_action.signature,
+            Signature::Ed25519([0; 64])
+        );
+        assert_eq!(signed_delegate_action.delegate_action.nonce, 7);
+        assert_eq!(
+            signed_delegate_action.delegate_action.actions[0].action(),
+            &Action::from(TransferAction {
+                deposit: NearToken::from_yocto(1)
+            })
+        );
+
+        // Delegate actions cannot be nested
+        let nested = json.replace(r#"{ "Transfer": { "deposit": "1" } }"#, delegate);
+        assert!(NEARTransaction::from_json(&nested).is_err());
+    }
+
+    #[test]
+    fn test_from_json_errors() {
+        let json = r#"{
+            "signer_id": "alice.near",
+            "public_key": "ed25519:11111111111111111111111111111111",
+            "nonce": 7,
+            "receiver_id": "bob.near",
+            "block_hash": "11111111111111111111111111111111",
+            "actions": []
+        }"#;
+        assert!(NEARTransaction::from_json(json).is_ok());
+
+        let secp256k1_key = format!("secp256k1:{}", "1".repeat(64));
+        for (from, to) in [
+            ("alice.near", "Alice".to_string()),
+            ("ed25519:", "sr25519:".to_string()),
+            (
+                r#""block_hash": "1111"#,
+                r#""block_hash": "0000"#.to_string(),
+            ),
+            ("[]", r#"[{ "Transfer": { "deposit": 1 } }]"#.to_string()),
+            ("[]", r#"[{ "Unknown": {} }]"#.to_string()),
+            (
+                "[]",
+                format!(
+                    r#"[{{ "Stake": {{ "stake": "1", "public_key": "{}" }} }}]"#,
+                    secp256k1_key
+                ),
+            ),
+        ] {
+            assert!(
+                NEARTransaction::from_json(&json.replace(from, &to)).is_err(),
+                "{}",
+                to
+            );
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_signer() {
+        let tx = NEARTransaction {
+            signer_id: "alice.near".parse().unwrap(),
+            signer_public_key: PublicKey::Secp256k1([4; 64]),
+            nonce: 7,
+            receiver_id: "bob.near".parse().unwrap(),
+            block_hash: BlockHash::default(),
+            actions: vec![],
+        };
+
+        let encoded = tx.build_for_signing();
+        let key_start = 4 + "alice.near".len();
+        assert_eq!(encoded[key_start], 1);
+        assert_eq!(encoded[key_start + 1..key_start + 65], [4; 64]);
+        assert_eq!(NEARTransaction::try_from_slice(&encoded).unwrap(), tx);
+    }
+
+    #[test]
+    fn test_borsh_schema() {
+        let schema = borsh::schema_container_of::<SignedTransaction>();
+        assert_eq!(schema.validate(), Ok(()));
+        assert!(schema.get_definition("AccountId").is_some());
+        assert!(schema.get_definition("NonDelegateAction").is_some());
+
+        assert_eq!(borsh::max_serialized_size::<PublicKey>(), Ok(65));
+        assert_eq!(borsh::max_serialized_size::<Signature>(), Ok(66));
+    }
+
+    #[test]
+    fn test_from_base64_borsh() {
+        let tx = NEARTransaction {
+            signer_id: "alice.near".parse().unwrap(),
+            signer_public_key: PublicKey::Ed25519([1; 32]),
+            nonce: 7,
+            receiver_id: "bob.near".parse().unwrap(),
+            block_hash: BlockHash::default(),
+            actions: vec![Action::Transfer(TransferAction {
+                deposit: NearToken::from_yocto(1),
+            })],
+        };
+        let signature = Signature::Ed25519([3; 64]);
+
+        let unsigned = Base64VecU8::new(tx.build_for_signing()).encode();
+        assert_eq!(NEARTransaction::from_base64_borsh(&unsigned).unwrap(), tx);
+
+        let signed = Base64VecU8::new(tx.build_with_signature(&signature)).encode();
+        assert_eq!(
+            SignedTransaction::from_base64_borsh(&signed).unwrap(),
+            SignedTransaction {
+                transaction: tx,
+                signature,
+            }
+        );
+
+        assert!(NEARTransaction::from_base64_borsh(&signed).is_err());
+        assert!(SignedTransaction::from_base64_borsh(&unsigned).is_err());
+        assert!(SignedTransaction::from_base64_borsh("not base64!").is_err());
+    }
+}
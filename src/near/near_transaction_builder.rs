@@ -0,0 +1,209 @@
+use crate::transaction_builder::TxBuilder;
+
+use super::{
+    near_transaction::NEARTransaction,
+    types::{
+        AccountId, Action, BlockHash, FunctionCallAction, NearGas, NearToken, PublicKey,
+        TransferAction,
+    },
+};
+
+pub struct NEARTransactionBuilder {
+    signer_id: Option<AccountId>,
+    signer_public_key: Option<PublicKey>,
+    nonce: Option<u64>,
+    receiver_id: Option<AccountId>,
+    block_hash: Option<BlockHash>,
+    actions: Option<Vec<Action>>,
+}
+
+impl Default for NEARTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<NEARTransaction> for NEARTransactionBuilder {
+    fn build(&self) -> NEARTransaction {
+        NEARTransaction {
+            signer_id: self.signer_id.clone().expect("signer_id is mandatory"),
+            signer_public_key: self
+                .signer_public_key
+                .expect("signer_public_key is mandatory"),
+            nonce: self.nonce.expect("nonce is mandatory"),
+            receiver_id: self.receiver_id.clone().expect("receiver_id is mandatory"),
+            block_hash: self.block_hash.expect("block_hash is mandatory"),
+            actions: self.actions.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl NEARTransactionBuilder {
+    pub const fn new() -> Self {
+        Self {
+            signer_id: None,
+            signer_public_key: None,
+            nonce: None,
+            receiver_id: None,
+            block_hash: None,
+            actions: None,
+        }
+    }
+
+    /// Account signing the transaction.
+    pub fn signer_id(mut self, signer_id: AccountId) -> Self {
+        self.signer_id = Some(signer_id);
+        self
+    }
+
+    /// Public key of the access key signing the transaction.
+    pub const fn signer_public_key(mut self, signer_public_key: PublicKey) -> Self {
+        self.signer_public_key = Some(signer_public_key);
+        self
+    }
+
+    /// Nonce of the transaction, the current nonce of the access key plus one.
+    pub const fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Account the actions apply to.
+    pub fn receiver_id(mut self, receiver_id: AccountId) -> Self {
+        self.receiver_id = Some(receiver_id);
+        self
+    }
+
+    /// Hash of a recent block.
+    pub const fn block_hash(mut self, block_hash: BlockHash) -> Self {
+        self.block_hash = Some(block_hash);
+        self
+    }
+
+    /// Actions of the transaction, applied in order.
+    pub fn actions(mut self, actions: Vec<Action>) -> Self {
+        self.actions = Some(actions);
+        self
+    }
+
+    /// Append an action to the ones of the transaction, all of them being applied atomically.
+    pub fn add_action(mut self, action: impl Into<Action>) -> Self {
+        self.actions
+            .get_or_insert_with(Vec::new)
+            .push(action.into());
+        self
+    }
+
+    /// Append a transfer of `deposit` to the receiver.
+    pub fn transfer(self, deposit: NearToken) -> Self {
+        self.add_action(TransferAction { deposit })
+    }
+
+    /// Append a call to `method_name` of the contract of the receiver.
+    pub fn function_call(
+        self,
+        method_name: &str,
+        args: Vec<u8>,
+        gas: NearGas,
+        deposit: NearToken,
+    ) -> Self {
+        self.add_action(FunctionCallAction {
+            method_name: method_name.to_string(),
+            args,
+            gas,
+            deposit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::near::types::{AccessKey, AddKeyAction, CreateAccountAction, CryptoHash};
+    use crate::{TransactionBuilder, NEAR};
+
+    #[test]
+    fn test_build() {
+        let actions = vec![Action::Transfer(TransferAction {
+            deposit: NearToken::from_yocto(1),
+        })];
+        let tx = TransactionBuilder::new::<NEAR>()
+            .signer_id("alice.near".parse().unwrap())
+            .signer_public_key(PublicKey::Ed25519([1; 32]))
+            .nonce(7)
+            .receiver_id("bob.near".parse().unwrap())
+            .block_hash(CryptoHash([2; 32]))
+            .actions(actions.clone())
+            .build();
+
+        assert_eq!(
+            tx,
+            NEARTransaction {
+                signer_id: "alice.near".parse().unwrap(),
+                signer_public_key: PublicKey::Ed25519([1; 32]),
+                nonce: 7,
+                receiver_id: "bob.near".parse().unwrap(),
+                block_hash: CryptoHash([2; 32]),
+                actions,
+            }
+        );
+    }
+
+    #[test]
+    fn test_add_actions() {
+        let key = PublicKey::Ed25519([1; 32]);
+        let tx = NEARTransactionBuilder::new()
+            .signer_id("alice.near".parse().unwrap())
+            .signer_public_key(key)
+            .nonce(7)
+            .receiver_id("sub.alice.near".parse().unwrap())
+            .block_hash(CryptoHash([2; 32]))
+            .add_action(CreateAccountAction {})
+            .transfer(NearToken::from_near(1.0).unwrap())
+            .add_action(AddKeyAction {
+                public_key: key,
+                access_key: AccessKey::full_access(),
+            })
+            .function_call(
+                "new",
+                b"{}".to_vec(),
+                NearGas::from_tgas(30),
+                NearToken::ZERO,
+            )
+            .build();
+
+        assert_eq!(
+            tx.actions,
+            vec![
+                CreateAccountAction {}.into(),
+                TransferAction {
+                    deposit: NearToken::from_near(1.0).unwrap()
+                }
+                .into(),
+                AddKeyAction {
+                    public_key: key,
+                    access_key: AccessKey::full_access(),
+                }
+                .into(),
+                FunctionCallAction {
+                    method_name: "new".to_string(),
+                    args: b"{}".to_vec(),
+                    gas: NearGas::from_tgas(30),
+                    deposit: NearToken::ZERO,
+                }
+                .into(),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "nonce is mandatory")]
+    fn test_build_without_nonce() {
+        NEARTransactionBuilder::new()
+            .signer_id("alice.near".parse().unwrap())
+            .signer_public_key(PublicKey::Ed25519([1; 32]))
+            .receiver_id("bob.near".parse().unwrap())
+            .block_hash(BlockHash::default())
+            .build();
+    }
+}
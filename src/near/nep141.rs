@@ -0,0 +1,113 @@
+//! [NEP-141] fungible token transfers.
+//!
+//! [NEP-141]: https://github.com/near/NEPs/blob/master/neps/nep-0141.md
+use serde::Serialize;
+
+use super::types::{AccountId, FunctionCallAction, NearGas, NearToken};
+
+/// The deposit attached to the transfers, requiring them to be signed by a full access key.
+pub const ONE_YOCTO: NearToken = NearToken::from_yocto(1);
+
+#[derive(Serialize)]
+struct FtTransferArgs<'a> {
+    receiver_id: &'a AccountId,
+    amount: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memo: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    msg: Option<&'a str>,
+}
+
+/// Transfers `amount` of the token of the receiver of the transaction to `receiver_id`, which
+/// needs to be registered with the token (see [`super::nep145::storage_deposit`]).
+pub fn ft_transfer(
+    receiver_id: &AccountId,
+    amount: u128,
+    memo: Option<&str>,
+    gas: NearGas,
+) -> FunctionCallAction {
+    let args = FtTransferArgs {
+        receiver_id,
+        amount: amount.to_string(),
+        memo,
+        msg: None,
+    };
+
+    FunctionCallAction::with_json_args("ft_transfer", &args, gas, ONE_YOCTO)
+        .expect("Failed to serialize ft_transfer args")
+}
+
+/// Transfers `amount` of the token of the receiver of the transaction to the contract
+/// `receiver_id`, then calls its `ft_on_transfer` with `msg`, refunding the amount it does not
+/// use.
+pub fn ft_transfer_call(
+    receiver_id: &AccountId,
+    amount: u128,
+    memo: Option<&str>,
+    msg: &str,
+    gas: NearGas,
+) -> FunctionCallAction {
+    let args = FtTransferArgs {
+        receiver_id,
+        amount: amount.to_string(),
+        memo,
+        msg: Some(msg),
+    };
+
+    FunctionCallAction::with_json_args("ft_transfer_call", &args, gas, ONE_YOCTO)
+        .expect("Failed to serialize ft_transfer_call args")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ft_transfer() {
+        let receiver_id = "bob.near".parse().unwrap();
+
+        let action = ft_transfer(&receiver_id, 1_000_000, None, NearGas::from_tgas(30));
+        assert_eq!(action.method_name, "ft_transfer");
+        assert_eq!(
+            action.args,
+            br#"{"receiver_id":"bob.near","amount":"1000000"}"#
+        );
+        assert_eq!(action.gas, NearGas::from_tgas(30));
+        assert_eq!(action.deposit, NearToken::from_yocto(1));
+
+        let action = ft_transfer(
+            &receiver_id,
+            u128::MAX,
+            Some("invoice 42"),
+            NearGas::from_tgas(30),
+        );
+        assert_eq!(
+            action.args,
+            format!(
+                r#"{{"receiver_id":"bob.near","amount":"{}","memo":"invoice 42"}}"#,
+                u128::MAX
+            )
+            .as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_ft_transfer_call() {
+        let receiver_id = "pool.near".parse().unwrap();
+
+        let action = ft_transfer_call(
+            &receiver_id,
+            5,
+            None,
+            r#"{"action":"swap"}"#,
+            NearGas::from_tgas(100),
+        );
+        assert_eq!(action.method_name, "ft_transfer_call");
+        assert_eq!(
+            action.args,
+            br#"{"receiver_id":"pool.near","amount":"5","msg":"{\"action\":\"swap\"}"}"#
+        );
+        assert_eq!(action.gas, NearGas::from_tgas(100));
+        assert_eq!(action.deposit, ONE_YOCTO);
+    }
+}
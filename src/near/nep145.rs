@@ -0,0 +1,62 @@
+//! [NEP-145] storage management, registering accounts with contracts like fungible tokens.
+//!
+//! [NEP-145]: https://github.com/near/NEPs/blob/master/neps/nep-0145.md
+use serde::Serialize;
+
+use super::types::{AccountId, FunctionCallAction, NearGas, NearToken};
+
+#[derive(Serialize)]
+struct StorageDepositArgs<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<&'a AccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    registration_only: Option<bool>,
+}
+
+/// Pays `deposit` for the storage of `account_id` in the contract of the receiver of the
+/// transaction, or of the signer if `None`.
+///
+/// With `registration_only`, only the minimum balance registering the account is kept and the
+/// rest of the deposit refunded, the usual way to register an account before a `ft_transfer`.
+pub fn storage_deposit(
+    account_id: Option<&AccountId>,
+    registration_only: Option<bool>,
+    deposit: NearToken,
+    gas: NearGas,
+) -> FunctionCallAction {
+    let args = StorageDepositArgs {
+        account_id,
+        registration_only,
+    };
+
+    FunctionCallAction::with_json_args("storage_deposit", &args, gas, deposit)
+        .expect("Failed to serialize storage_deposit args")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_deposit() {
+        let account_id = "bob.near".parse().unwrap();
+        let deposit = NearToken::from_yocto(1_250_000_000_000_000_000_000);
+
+        let action = storage_deposit(
+            Some(&account_id),
+            Some(true),
+            deposit,
+            NearGas::from_tgas(10),
+        );
+        assert_eq!(action.method_name, "storage_deposit");
+        assert_eq!(
+            action.args,
+            br#"{"account_id":"bob.near","registration_only":true}"#
+        );
+        assert_eq!(action.gas, NearGas::from_tgas(10));
+        assert_eq!(action.deposit, deposit);
+
+        let action = storage_deposit(None, None, deposit, NearGas::from_tgas(10));
+        assert_eq!(action.args, b"{}");
+    }
+}
@@ -0,0 +1,111 @@
+//! [NEP-171] non-fungible token transfers.
+//!
+//! [NEP-171]: https://github.com/near/NEPs/blob/master/neps/nep-0171.md
+use serde::Serialize;
+
+use super::nep141::ONE_YOCTO;
+use super::types::{AccountId, FunctionCallAction, NearGas};
+
+#[derive(Serialize)]
+struct NftTransferArgs<'a> {
+    receiver_id: &'a AccountId,
+    token_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    approval_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memo: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    msg: Option<&'a str>,
+}
+
+/// Transfers the token `token_id` of the receiver of the transaction to `receiver_id`. An account
+/// approved to transfer the token passes the `approval_id` it was given.
+pub fn nft_transfer(
+    receiver_id: &AccountId,
+    token_id: &str,
+    approval_id: Option<u64>,
+    memo: Option<&str>,
+    gas: NearGas,
+) -> FunctionCallAction {
+    let args = NftTransferArgs {
+        receiver_id,
+        token_id,
+        approval_id,
+        memo,
+        msg: None,
+    };
+
+    FunctionCallAction::with_json_args("nft_transfer", &args, gas, ONE_YOCTO)
+        .expect("Failed to serialize nft_transfer args")
+}
+
+/// Transfers the token `token_id` of the receiver of the transaction to the contract
+/// `receiver_id`, then calls its `nft_on_transfer` with `msg`, the token being returned if it
+/// asks for it.
+pub fn nft_transfer_call(
+    receiver_id: &AccountId,
+    token_id: &str,
+    approval_id: Option<u64>,
+    memo: Option<&str>,
+    msg: &str,
+    gas: NearGas,
+) -> FunctionCallAction {
+    let args = NftTransferArgs {
+        receiver_id,
+        token_id,
+        approval_id,
+        memo,
+        msg: Some(msg),
+    };
+
+    FunctionCallAction::with_json_args("nft_transfer_call", &args, gas, ONE_YOCTO)
+        .expect("Failed to serialize nft_transfer_call args")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nft_transfer() {
+        let receiver_id = "bob.near".parse().unwrap();
+
+        let action = nft_transfer(&receiver_id, "1", None, None, NearGas::from_tgas(30));
+        assert_eq!(action.method_name, "nft_transfer");
+        assert_eq!(action.args, br#"{"receiver_id":"bob.near","token_id":"1"}"#);
+        assert_eq!(action.gas, NearGas::from_tgas(30));
+        assert_eq!(action.deposit, ONE_YOCTO);
+
+        let action = nft_transfer(
+            &receiver_id,
+            "1",
+            Some(3),
+            Some("gift"),
+            NearGas::from_tgas(30),
+        );
+        assert_eq!(
+            action.args,
+            br#"{"receiver_id":"bob.near","token_id":"1","approval_id":3,"memo":"gift"}"#
+        );
+    }
+
+    #[test]
+    fn test_nft_transfer_call() {
+        let receiver_id = "market.near".parse().unwrap();
+
+        let action = nft_transfer_call(
+            &receiver_id,
+            "1",
+            None,
+            None,
+            "list",
+            NearGas::from_tgas(100),
+        );
+        assert_eq!(action.method_name, "nft_transfer_call");
+        assert_eq!(
+            action.args,
+            br#"{"receiver_id":"market.near","token_id":"1","msg":"list"}"#
+        );
+        assert_eq!(action.deposit, ONE_YOCTO);
+    }
+}
@@ -0,0 +1,100 @@
+//! [NEP-413] off-chain messages, signed by the access keys of an account to prove its ownership
+//! ("Sign in with NEAR").
+//!
+//! [NEP-413]: https://github.com/near/NEPs/blob/master/neps/nep-0413.md
+use borsh::{BorshDeserialize, BorshSerialize};
+use sha2::{Digest, Sha256};
+
+use super::signable_message::{
+    SignableMessage, SignableMessageType, MIN_OFF_CHAIN_DISCRIMINANT, NEP_413_SIGN_MESSAGE,
+};
+
+/// The [NEP-461] prefix of the payload signed for a NEP-413 message, `2^31 + 413`, keeping it
+/// from being a valid transaction or delegate action.
+///
+/// [NEP-461]: https://github.com/near/NEPs/blob/master/neps/nep-0461.md
+pub const NEP413_PREFIX: u32 = MIN_OFF_CHAIN_DISCRIMINANT + NEP_413_SIGN_MESSAGE;
+
+/// A NEP-413 message.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::near::nep413::Payload;
+///
+/// let payload = Payload {
+///     message: "Sign in to example.com".to_string(),
+///     nonce: [0; 32],
+///     recipient: "example.com".to_string(),
+///     callback_url: None,
+/// };
+///
+/// let hash = payload.signing_hash();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Payload {
+    /// The message to sign.
+    pub message: String,
+    /// A nonce chosen by the recipient, keeping the signature from being replayed.
+    pub nonce: [u8; 32],
+    /// The recipient of the signature, typically an account id or the domain of an app.
+    pub recipient: String,
+    /// A url the wallet redirects to after signing the message.
+    pub callback_url: Option<String>,
+}
+
+impl Payload {
+    /// Encode the message with borsh after its NEP-461 prefix, the payload whose SHA-256 is signed
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        SignableMessage::new(self, SignableMessageType::Nep413).build_for_signing()
+    }
+
+    /// Compute the SHA-256 of the prefixed borsh encoding of the message, the message signed by
+    /// the access key
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.build_for_signing()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_for_signing() {
+        let mut payload = Payload {
+            message: "hello".to_string(),
+            nonce: [7; 32],
+            recipient: "app.near".to_string(),
+            callback_url: None,
+        };
+
+        let expected = [
+            2_147_484_061u32.to_le_bytes().to_vec(),
+            5u32.to_le_bytes().to_vec(),
+            b"hello".to_vec(),
+            vec![7; 32],
+            8u32.to_le_bytes().to_vec(),
+            b"app.near".to_vec(),
+            vec![0],
+        ]
+        .concat();
+
+        assert_eq!(payload.build_for_signing(), expected);
+        assert_eq!(
+            payload.signing_hash(),
+            <[u8; 32]>::from(Sha256::digest(&expected))
+        );
+
+        payload.callback_url = Some("https://app".to_string());
+        let expected = [
+            &expected[..expected.len() - 1],
+            &[1],
+            &11u32.to_le_bytes(),
+            b"https://app",
+        ]
+        .concat();
+        assert_eq!(payload.build_for_signing(), expected);
+        assert_eq!(Payload::try_from_slice(&expected[4..]).unwrap(), payload);
+    }
+}
@@ -0,0 +1,198 @@
+//! [NEP-461] prefixes of the messages signed by access keys that are not transactions, keeping
+//! them from being valid transactions.
+//!
+//! A transaction starts with the length of its signer id, which is at most 64, while the other
+//! messages start with a `u32` discriminant of at least `2^30`: `2^30 + NEP` for messages that are
+//! verified on chain, like delegate actions, and `2^31 + NEP` for the ones that are not, like
+//! NEP-413 messages.
+//!
+//! [NEP-461]: https://github.com/near/NEPs/blob/master/neps/nep-0461.md
+use borsh::{BorshDeserialize, BorshSerialize};
+use sha2::{Digest, Sha256};
+
+/// The smallest discriminant of the messages verified on chain.
+pub const MIN_ON_CHAIN_DISCRIMINANT: u32 = 1 << 30;
+/// The largest discriminant of the messages verified on chain.
+pub const MAX_ON_CHAIN_DISCRIMINANT: u32 = (1 << 31) - 1;
+/// The smallest discriminant of the messages verified off chain.
+pub const MIN_OFF_CHAIN_DISCRIMINANT: u32 = 1 << 31;
+/// The largest discriminant of the messages verified off chain.
+pub const MAX_OFF_CHAIN_DISCRIMINANT: u32 = u32::MAX;
+
+/// The NEP of delegate actions (meta transactions).
+pub const NEP_366_META_TRANSACTIONS: u32 = 366;
+/// The NEP of off-chain messages.
+pub const NEP_413_SIGN_MESSAGE: u32 = 413;
+
+/// The kind of a message signed by an access key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignableMessageType {
+    /// A [`super::types::DelegateAction`].
+    DelegateAction,
+    /// A [`super::nep413::Payload`].
+    Nep413,
+}
+
+/// The `u32` prefix of a message signed by an access key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct MessageDiscriminant(u32);
+
+impl MessageDiscriminant {
+    /// The discriminant of a message verified on chain, defined by the NEP `nep`.
+    pub fn new_on_chain(nep: u32) -> Result<Self, String> {
+        if nep > MAX_ON_CHAIN_DISCRIMINANT - MIN_ON_CHAIN_DISCRIMINANT {
+            return Err(format!("Invalid on-chain NEP: {}", nep));
+        }
+
+        Ok(Self(MIN_ON_CHAIN_DISCRIMINANT + nep))
+    }
+
+    /// The discriminant of a message verified off chain, defined by the NEP `nep`.
+    pub fn new_off_chain(nep: u32) -> Result<Self, String> {
+        if nep > MAX_OFF_CHAIN_DISCRIMINANT - MIN_OFF_CHAIN_DISCRIMINANT {
+            return Err(format!("Invalid off-chain NEP: {}", nep));
+        }
+
+        Ok(Self(MIN_OFF_CHAIN_DISCRIMINANT + nep))
+    }
+
+    /// The discriminant at the start of a payload about to be signed, a transaction having none.
+    pub fn from_payload(payload: &[u8]) -> Option<Self> {
+        let discriminant = u32::from_le_bytes(payload.get(..4)?.try_into().ok()?);
+
+        (discriminant >= MIN_ON_CHAIN_DISCRIMINANT).then_some(Self(discriminant))
+    }
+
+    /// The raw discriminant.
+    pub const fn raw(&self) -> u32 {
+        self.0
+    }
+
+    /// The NEP of a message verified on chain.
+    pub const fn on_chain_nep(&self) -> Option<u32> {
+        if self.0 >= MIN_ON_CHAIN_DISCRIMINANT && self.0 <= MAX_ON_CHAIN_DISCRIMINANT {
+            Some(self.0 - MIN_ON_CHAIN_DISCRIMINANT)
+        } else {
+            None
+        }
+    }
+
+    /// The NEP of a message verified off chain.
+    pub const fn off_chain_nep(&self) -> Option<u32> {
+        if self.0 >= MIN_OFF_CHAIN_DISCRIMINANT {
+            Some(self.0 - MIN_OFF_CHAIN_DISCRIMINANT)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<SignableMessageType> for MessageDiscriminant {
+    fn from(ty: SignableMessageType) -> Self {
+        match ty {
+            SignableMessageType::DelegateAction => {
+                Self(MIN_ON_CHAIN_DISCRIMINANT + NEP_366_META_TRANSACTIONS)
+            }
+            SignableMessageType::Nep413 => Self(MIN_OFF_CHAIN_DISCRIMINANT + NEP_413_SIGN_MESSAGE),
+        }
+    }
+}
+
+/// A message prefixed with its discriminant, borsh-encoded as the payload whose SHA-256 is signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize)]
+pub struct SignableMessage<'a, T> {
+    pub discriminant: MessageDiscriminant,
+    pub msg: &'a T,
+}
+
+impl<'a, T: BorshSerialize> SignableMessage<'a, T> {
+    /// Prefixes `msg` with the discriminant of its type.
+    pub fn new(msg: &'a T, ty: SignableMessageType) -> Self {
+        Self {
+            discriminant: ty.into(),
+            msg,
+        }
+    }
+
+    /// Encode the discriminant and the message with borsh
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("Failed to serialize signable message")
+    }
+
+    /// Compute the SHA-256 of the encoding of the message, the message signed by the access key
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.build_for_signing()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::near::types::{CryptoHash, PublicKey};
+    use crate::near::NEARTransaction;
+
+    #[test]
+    fn test_discriminants() {
+        let delegate_action = MessageDiscriminant::from(SignableMessageType::DelegateAction);
+        assert_eq!(delegate_action.raw(), 1_073_742_190);
+        assert_eq!(
+            delegate_action,
+            MessageDiscriminant::new_on_chain(366).unwrap()
+        );
+        assert_eq!(delegate_action.on_chain_nep(), Some(366));
+        assert_eq!(delegate_action.off_chain_nep(), None);
+
+        let nep413 = MessageDiscriminant::from(SignableMessageType::Nep413);
+        assert_eq!(nep413.raw(), 2_147_484_061);
+        assert_eq!(nep413, MessageDiscriminant::new_off_chain(413).unwrap());
+        assert_eq!(nep413.on_chain_nep(), None);
+        assert_eq!(nep413.off_chain_nep(), Some(413));
+
+        assert!(MessageDiscriminant::new_on_chain(1 << 30).is_err());
+        assert!(MessageDiscriminant::new_off_chain(1 << 31).is_err());
+        assert_eq!(
+            MessageDiscriminant::new_off_chain((1 << 31) - 1)
+                .unwrap()
+                .raw(),
+            u32::MAX
+        );
+    }
+
+    #[test]
+    fn test_from_payload() {
+        let msg = 7u64;
+        let message = SignableMessage::new(&msg, SignableMessageType::DelegateAction);
+        let payload = message.build_for_signing();
+        assert_eq!(
+            payload,
+            [
+                1_073_742_190u32.to_le_bytes().to_vec(),
+                7u64.to_le_bytes().to_vec()
+            ]
+            .concat()
+        );
+        assert_eq!(
+            message.signing_hash(),
+            <[u8; 32]>::from(Sha256::digest(&payload))
+        );
+        assert_eq!(
+            MessageDiscriminant::from_payload(&payload),
+            Some(message.discriminant)
+        );
+
+        // Transactions start with the length of the signer id
+        let tx = NEARTransaction {
+            signer_id: "a".repeat(64).parse().unwrap(),
+            signer_public_key: PublicKey::Ed25519([0xff; 32]),
+            nonce: u64::MAX,
+            receiver_id: "bob.near".parse().unwrap(),
+            block_hash: CryptoHash([0xff; 32]),
+            actions: vec![],
+        };
+        assert_eq!(
+            MessageDiscriminant::from_payload(&tx.build_for_signing()),
+            None
+        );
+        assert_eq!(MessageDiscriminant::from_payload(&[0xff; 3]), None);
+    }
+}
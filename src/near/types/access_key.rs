@@ -0,0 +1,122 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{AccountId, NearToken};
+
+/// An access key of a NEAR account, granting its public key the permission to sign transactions.
+///
+/// Serialized in JSON like the access keys returned by the RPC.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+pub struct AccessKey {
+    /// Nonce of the key, the one of the next transaction it signs needing to be larger. Added
+    /// keys start with a nonce derived from the current block height, whatever this one is.
+    pub nonce: u64,
+    /// What the key is allowed to sign.
+    pub permission: AccessKeyPermission,
+}
+
+impl AccessKey {
+    /// A key allowed to sign any transaction.
+    pub const fn full_access() -> Self {
+        Self {
+            nonce: 0,
+            permission: AccessKeyPermission::FullAccess,
+        }
+    }
+
+    /// A key only allowed to sign calls to `method_names` of `receiver_id`, or to any of its
+    /// methods if empty, paying at most `allowance` of gas if any.
+    pub fn function_call(
+        allowance: Option<NearToken>,
+        receiver_id: AccountId,
+        method_names: Vec<String>,
+    ) -> Self {
+        Self {
+            nonce: 0,
+            permission: AccessKeyPermission::FunctionCall(FunctionCallPermission {
+                allowance,
+                receiver_id,
+                method_names,
+            }),
+        }
+    }
+}
+
+/// The permission of an access key.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+pub enum AccessKeyPermission {
+    /// Only allowed to sign calls to some methods of a contract, without attaching a deposit.
+    FunctionCall(FunctionCallPermission),
+    /// Allowed to sign any transaction.
+    FullAccess,
+}
+
+/// The permission of an access key to call methods of a contract.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+pub struct FunctionCallPermission {
+    /// The NEAR the key can spend on gas, unlimited if `None`.
+    pub allowance: Option<NearToken>,
+    /// The contract the key can call.
+    pub receiver_id: AccountId,
+    /// The methods the key can call, any of them if empty.
+    pub method_names: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schemars::schema_for;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_schema() {
+        let schema = serde_json::to_value(schema_for!(AccessKey)).unwrap();
+
+        assert_eq!(schema["properties"]["nonce"]["type"], "integer");
+        let properties = &schema["definitions"]["FunctionCallPermission"]["properties"];
+        assert_eq!(properties["allowance"]["type"], json!(["string", "null"]));
+        assert_eq!(properties["receiver_id"]["type"], "string");
+        assert_eq!(
+            properties["method_names"]["items"],
+            json!({ "type": "string" })
+        );
+        assert_eq!(
+            schema["definitions"]["AccessKeyPermission"]["oneOf"][1]["enum"],
+            json!(["FullAccess"])
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use core::{fmt, str::FromStr};
+
+use borsh::{
+    io::{Error, ErrorKind, Read, Write},
+    BorshDeserialize, BorshSchema, BorshSerialize,
+};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The minimum length of an account id.
+pub const MIN_ACCOUNT_ID_LEN: usize = 2;
+/// The maximum length of an account id.
+pub const MAX_ACCOUNT_ID_LEN: usize = 64;
+
+/// A NEAR account id, validated when created, deserialized or decoded.
+///
+/// Account ids are 2 to 64 characters long, made of lowercase alphanumerical parts separated by a
+/// single `.`, `-` or `_`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, BorshSchema)]
+pub struct AccountId(String);
+
+impl AccountId {
+    /// Checks that `account_id` follows the NEAR account id rules.
+    pub fn validate(account_id: &str) -> Result<(), String> {
+        if account_id.len() < MIN_ACCOUNT_ID_LEN {
+            return Err(format!("Account id too short: {}", account_id));
+        }
+        if account_id.len() > MAX_ACCOUNT_ID_LEN {
+            return Err(format!("Account id too long: {}", account_id));
+        }
+
+        // Separators can only be between two alphanumerical characters
+        let mut last_is_separator = true;
+        for c in account_id.chars() {
+            match c {
+                'a'..='z' | '0'..='9' => last_is_separator = false,
+                '-' | '_' | '.' if !last_is_separator => last_is_separator = true,
+                '-' | '_' | '.' => {
+                    return Err(format!("Misplaced separator in account id: {}", account_id))
+                }
+                _ => return Err(format!("Invalid character in account id: {}", account_id)),
+            }
+        }
+        if last_is_separator {
+            return Err(format!("Misplaced separator in account id: {}", account_id));
+        }
+
+        Ok(())
+    }
+
+    /// Whether the account id is the 64 hex characters of an ed25519 key, see
+    /// [`super::PublicKey::implicit_account_id`].
+    pub fn is_near_implicit(&self) -> bool {
+        self.0.len() == 64
+            && self
+                .0
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    }
+
+    /// Whether the account id is the `0x`-prefixed Ethereum address of a secp256k1 key, see
+    /// [`super::PublicKey::implicit_account_id`].
+    pub fn is_eth_implicit(&self) -> bool {
+        self.0.len() == 42
+            && self.0.starts_with("0x")
+            && self.0[2..]
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    }
+
+    /// The account id as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for AccountId {
+    type Error = String;
+
+    fn try_from(account_id: String) -> Result<Self, Self::Error> {
+        Self::validate(&account_id)?;
+        Ok(Self(account_id))
+    }
+}
+
+impl FromStr for AccountId {
+    type Err = String;
+
+    fn from_str(account_id: &str) -> Result<Self, Self::Err> {
+        Self::try_from(account_id.to_string())
+    }
+}
+
+impl From<AccountId> for String {
+    fn from(account_id: AccountId) -> Self {
+        account_id.0
+    }
+}
+
+impl AsRef<str> for AccountId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for AccountId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for AccountId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let account_id = <String as Deserialize>::deserialize(deserializer)?;
+        Self::try_from(account_id).map_err(D::Error::custom)
+    }
+}
+
+impl BorshSerialize for AccountId {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        BorshSerialize::serialize(&self.0, writer)
+    }
+}
+
+impl BorshDeserialize for AccountId {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let account_id = String::deserialize_reader(reader)?;
+        Self::try_from(account_id).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+impl JsonSchema for AccountId {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "AccountId".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_account_ids() {
+        for account_id in [
+            "aa",
+            "near",
+            "alice.near",
+            "a-b_c.d",
+            "0xd8da6bf26964af9d7eed9e03e53415d37aa96045",
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+            "a".repeat(64).as_str(),
+        ] {
+            let parsed: AccountId = account_id.parse().unwrap();
+            assert_eq!(parsed.as_str(), account_id);
+        }
+    }
+
+    #[test]
+    fn test_invalid_account_ids() {
+        for account_id in [
+            "",
+            "a",
+            "Alice.near",
+            "alice near",
+            "alice@near",
+            ".near",
+            "alice.",
+            "alice..near",
+            "alice.-near",
+            "_alice",
+            "a".repeat(65).as_str(),
+        ] {
+            assert!(account_id.parse::<AccountId>().is_err(), "{}", account_id);
+        }
+    }
+
+    #[test]
+    fn test_serde_and_borsh() {
+        let account_id: AccountId = "alice.near".parse().unwrap();
+
+        let json = serde_json::to_string(&account_id).unwrap();
+        assert_eq!(json, r#""alice.near""#);
+        assert_eq!(
+            serde_json::from_str::<AccountId>(&json).unwrap(),
+            account_id
+        );
+        assert!(serde_json::from_str::<AccountId>(r#""Alice.near""#).is_err());
+
+        let encoded = borsh::to_vec(&account_id).unwrap();
+        assert_eq!(encoded, borsh::to_vec("alice.near").unwrap());
+        assert_eq!(AccountId::try_from_slice(&encoded).unwrap(), account_id);
+        let invalid = borsh::to_vec("Alice.near").unwrap();
+        assert!(AccountId::try_from_slice(&invalid).is_err());
+    }
+}
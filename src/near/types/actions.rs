@@ -0,0 +1,472 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{
+    AccessKey, AccountId, Base64VecU8, CryptoHash, NearGas, NearToken, PublicKey,
+    SignedDelegateAction,
+};
+
+/// An action of a NEAR transaction.
+///
+/// Actions are borsh-encoded with their index in the `Action` enum of `near-primitives`, which
+/// the discriminants of the variants match.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+pub enum Action {
+    /// Create the receiver of the transaction, a sub-account of the signer or a top-level
+    /// account.
+    CreateAccount(CreateAccountAction) = 0,
+    /// Deploy a contract on the receiver of the transaction, replacing its current one if any.
+    DeployContract(DeployContractAction) = 1,
+    /// Call a method of the contract of the receiver of the transaction.
+    FunctionCall(Box<FunctionCallAction>) = 2,
+    /// Transfer NEAR to the receiver of the transaction.
+    Transfer(TransferAction) = 3,
+    /// Stake NEAR of the receiver of the transaction with its validator key.
+    Stake(Box<StakeAction>) = 4,
+    /// Add an access key to the receiver of the transaction.
+    AddKey(Box<AddKeyAction>) = 5,
+    /// Delete an access key of the receiver of the transaction.
+    DeleteKey(Box<DeleteKeyAction>) = 6,
+    /// Delete the receiver of the transaction, sending its balance to a beneficiary.
+    DeleteAccount(DeleteAccountAction) = 7,
+    /// Execute actions signed by the sender of a delegate action, on its behalf.
+    Delegate(Box<SignedDelegateAction>) = 8,
+    /// Deploy a contract other accounts can use without deploying it themselves.
+    DeployGlobalContract(DeployGlobalContractAction) = 9,
+    /// Use a global contract as the contract of the receiver of the transaction.
+    UseGlobalContract(Box<UseGlobalContractAction>) = 10,
+}
+
+/// Creates the receiver of the transaction, usually followed by a transfer funding its storage
+/// and the key it is controlled by.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+)]
+pub struct CreateAccountAction {}
+
+impl From<CreateAccountAction> for Action {
+    fn from(action: CreateAccountAction) -> Self {
+        Self::CreateAccount(action)
+    }
+}
+
+/// Deploys the WebAssembly `code` of a contract on the receiver of the transaction.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+pub struct DeployContractAction {
+    pub code: Base64VecU8,
+}
+
+impl From<DeployContractAction> for Action {
+    fn from(action: DeployContractAction) -> Self {
+        Self::DeployContract(action)
+    }
+}
+
+/// Calls `method_name` of the contract deployed on the receiver of the transaction, attaching
+/// `gas` and a `deposit` to the call.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct FunctionCallAction {
+    pub method_name: String,
+    pub args: Vec<u8>,
+    pub gas: NearGas,
+    pub deposit: NearToken,
+}
+
+impl FunctionCallAction {
+    /// Creates a call whose arguments are the JSON encoding of `args`, as expected by the
+    /// contracts built with `near-sdk`.
+    pub fn with_json_args<T: Serialize>(
+        method_name: &str,
+        args: &T,
+        gas: NearGas,
+        deposit: NearToken,
+    ) -> Result<Self, String> {
+        let args = serde_json::to_vec(args).map_err(|e| format!("Invalid JSON args: {}", e))?;
+
+        Ok(Self {
+            method_name: method_name.to_string(),
+            args,
+            gas,
+            deposit,
+        })
+    }
+
+    /// Decodes the arguments of the call as JSON.
+    pub fn json_args<T: DeserializeOwned>(&self) -> Result<T, String> {
+        serde_json::from_slice(&self.args).map_err(|e| format!("Invalid JSON args: {}", e))
+    }
+}
+
+impl From<FunctionCallAction> for Action {
+    fn from(action: FunctionCallAction) -> Self {
+        Self::FunctionCall(Box::new(action))
+    }
+}
+
+/// Transfers `deposit` to the receiver of the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct TransferAction {
+    pub deposit: NearToken,
+}
+
+impl From<TransferAction> for Action {
+    fn from(action: TransferAction) -> Self {
+        Self::Transfer(action)
+    }
+}
+
+/// Stakes `stake` of the receiver of the transaction, its validator signing blocks and chunks
+/// with `public_key`. The receiver needs to be the signer, and a stake of zero unstakes all of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct StakeAction {
+    pub stake: NearToken,
+    pub public_key: PublicKey,
+}
+
+impl StakeAction {
+    /// Creates a stake action, validators only being able to use ed25519 keys.
+    pub fn new(stake: NearToken, public_key: PublicKey) -> Result<Self, String> {
+        match public_key {
+            PublicKey::Ed25519(_) => Ok(Self { stake, public_key }),
+            PublicKey::Secp256k1(_) => Err("Validator keys must be ed25519 keys".to_string()),
+        }
+    }
+}
+
+impl From<StakeAction> for Action {
+    fn from(action: StakeAction) -> Self {
+        Self::Stake(Box::new(action))
+    }
+}
+
+/// Adds `public_key` to the access keys of the receiver of the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct AddKeyAction {
+    pub public_key: PublicKey,
+    pub access_key: AccessKey,
+}
+
+impl From<AddKeyAction> for Action {
+    fn from(action: AddKeyAction) -> Self {
+        Self::AddKey(Box::new(action))
+    }
+}
+
+/// Deletes `public_key` from the access keys of the receiver of the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct DeleteKeyAction {
+    pub public_key: PublicKey,
+}
+
+impl From<DeleteKeyAction> for Action {
+    fn from(action: DeleteKeyAction) -> Self {
+        Self::DeleteKey(Box::new(action))
+    }
+}
+
+/// Deletes the receiver of the transaction, its balance going to `beneficiary_id`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct DeleteAccountAction {
+    pub beneficiary_id: AccountId,
+}
+
+impl From<DeleteAccountAction> for Action {
+    fn from(action: DeleteAccountAction) -> Self {
+        Self::DeleteAccount(action)
+    }
+}
+
+/// Deploys the WebAssembly `code` of a global contract, paid by the receiver of the transaction.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+pub struct DeployGlobalContractAction {
+    pub code: Base64VecU8,
+    pub deploy_mode: GlobalContractDeployMode,
+}
+
+impl From<DeployGlobalContractAction> for Action {
+    fn from(action: DeployGlobalContractAction) -> Self {
+        Self::DeployGlobalContract(action)
+    }
+}
+
+/// How a global contract is identified by the accounts using it.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+pub enum GlobalContractDeployMode {
+    /// By the hash of its code, which cannot be updated.
+    CodeHash,
+    /// By the account deploying it, which can update the code of all the accounts using it.
+    AccountId,
+}
+
+/// Uses the global contract identified by `contract_identifier` as the contract of the receiver of
+/// the transaction.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+pub struct UseGlobalContractAction {
+    pub contract_identifier: GlobalContractIdentifier,
+}
+
+impl From<UseGlobalContractAction> for Action {
+    fn from(action: UseGlobalContractAction) -> Self {
+        Self::UseGlobalContract(Box::new(action))
+    }
+}
+
+/// A global contract, deployed with the matching [`GlobalContractDeployMode`].
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+pub enum GlobalContractIdentifier {
+    /// The SHA-256 of the code of the contract.
+    CodeHash(CryptoHash),
+    /// The account that deployed the contract.
+    AccountId(AccountId),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_account_lifecycle_encoding() {
+        let action: Action = CreateAccountAction {}.into();
+        assert_eq!(borsh::to_vec(&action).unwrap(), [0]);
+        assert_eq!(Action::try_from_slice(&[0]).unwrap(), action);
+
+        let action: Action = DeleteAccountAction {
+            beneficiary_id: "bob.near".parse().unwrap(),
+        }
+        .into();
+        let expected = [vec![7], 8u32.to_le_bytes().to_vec(), b"bob.near".to_vec()].concat();
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+    }
+
+    #[test]
+    fn test_deploy_contract_encoding() {
+        let action = DeployContractAction {
+            code: b"\0asm".to_vec().into(),
+        };
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            json!({ "code": "AGFzbQ==" })
+        );
+
+        let action: Action = action.into();
+        let expected = [vec![1], 4u32.to_le_bytes().to_vec(), b"\0asm".to_vec()].concat();
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+    }
+
+    #[test]
+    fn test_global_contract_encoding() {
+        let action: Action = DeployGlobalContractAction {
+            code: b"\0asm".to_vec().into(),
+            deploy_mode: GlobalContractDeployMode::AccountId,
+        }
+        .into();
+        let expected = [
+            vec![9],
+            4u32.to_le_bytes().to_vec(),
+            b"\0asm".to_vec(),
+            vec![1],
+        ]
+        .concat();
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+
+        let action: Action = UseGlobalContractAction {
+            contract_identifier: GlobalContractIdentifier::CodeHash(CryptoHash([1; 32])),
+        }
+        .into();
+        let expected = [vec![10, 0], vec![1; 32]].concat();
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+
+        let action: Action = UseGlobalContractAction {
+            contract_identifier: GlobalContractIdentifier::AccountId("app.near".parse().unwrap()),
+        }
+        .into();
+        let expected = [
+            vec![10, 1],
+            8u32.to_le_bytes().to_vec(),
+            b"app.near".to_vec(),
+        ]
+        .concat();
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+    }
+
+    #[test]
+    fn test_function_call_encoding() {
+        let action: Action = FunctionCallAction::with_json_args(
+            "ft_transfer",
+            &json!({ "receiver_id": "bob.near", "amount": "1" }),
+            NearGas::from_tgas(30),
+            NearToken::from_yocto(1),
+        )
+        .unwrap()
+        .into();
+
+        let args = br#"{"amount":"1","receiver_id":"bob.near"}"#;
+        let expected = [
+            vec![2],
+            11u32.to_le_bytes().to_vec(),
+            b"ft_transfer".to_vec(),
+            (args.len() as u32).to_le_bytes().to_vec(),
+            args.to_vec(),
+            30_000_000_000_000u64.to_le_bytes().to_vec(),
+            1u128.to_le_bytes().to_vec(),
+        ]
+        .concat();
+
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+    }
+
+    #[test]
+    fn test_stake_encoding() {
+        let action: Action =
+            StakeAction::new(NearToken::from_yocto(1), PublicKey::Ed25519([1; 32]))
+                .unwrap()
+                .into();
+        let expected = [vec![4], 1u128.to_le_bytes().to_vec(), vec![0], vec![1; 32]].concat();
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+
+        assert!(StakeAction::new(NearToken::from_yocto(1), PublicKey::Secp256k1([4; 64])).is_err());
+    }
+
+    #[test]
+    fn test_add_key_encoding() {
+        let action: Action = AddKeyAction {
+            public_key: PublicKey::Ed25519([1; 32]),
+            access_key: AccessKey::full_access(),
+        }
+        .into();
+        let expected = [
+            vec![5, 0],
+            vec![1; 32],
+            0u64.to_le_bytes().to_vec(),
+            vec![1],
+        ]
+        .concat();
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+
+        let action: Action = AddKeyAction {
+            public_key: PublicKey::Ed25519([1; 32]),
+            access_key: AccessKey::function_call(
+                Some(NearToken::from_millinear(250)),
+                "app.near".parse().unwrap(),
+                vec!["vote".to_string()],
+            ),
+        }
+        .into();
+        let expected = [
+            vec![5, 0],
+            vec![1; 32],
+            0u64.to_le_bytes().to_vec(),
+            // Function call permission with an allowance
+            vec![0, 1],
+            250_000_000_000_000_000_000_000u128.to_le_bytes().to_vec(),
+            8u32.to_le_bytes().to_vec(),
+            b"app.near".to_vec(),
+            1u32.to_le_bytes().to_vec(),
+            4u32.to_le_bytes().to_vec(),
+            b"vote".to_vec(),
+        ]
+        .concat();
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+    }
+
+    #[test]
+    fn test_delete_key_encoding() {
+        let action: Action = DeleteKeyAction {
+            public_key: PublicKey::Secp256k1([4; 64]),
+        }
+        .into();
+        let expected = [vec![6, 1], vec![4; 64]].concat();
+        assert_eq!(borsh::to_vec(&action).unwrap(), expected);
+        assert_eq!(Action::try_from_slice(&expected).unwrap(), action);
+    }
+
+    #[test]
+    fn test_function_call_json_args() {
+        let action = FunctionCallAction::with_json_args(
+            "set",
+            &json!({ "value": 42 }),
+            NearGas::default(),
+            NearToken::ZERO,
+        )
+        .unwrap();
+        let args: serde_json::Value = action.json_args().unwrap();
+        assert_eq!(args, json!({ "value": 42 }));
+
+        let action = FunctionCallAction {
+            method_name: "set".to_string(),
+            args: vec![0xff],
+            gas: NearGas::default(),
+            deposit: NearToken::ZERO,
+        };
+        assert!(action.json_args::<serde_json::Value>().is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Bytes serialized in JSON as a base58 string, the encoding of the keys, signatures and hashes of
+/// NEAR, and with borsh as a `Vec<u8>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Base58VecU8(pub Vec<u8>);
+
+impl From<Vec<u8>> for Base58VecU8 {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Base58VecU8> for Vec<u8> {
+    fn from(bytes: Base58VecU8) -> Self {
+        bytes.0
+    }
+}
+
+impl Serialize for Base58VecU8 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&bs58::encode(&self.0).into_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Base58VecU8 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        bs58::decode(s)
+            .into_vec()
+            .map(Self)
+            .map_err(|e| D::Error::custom(format!("Invalid base58: {}", e)))
+    }
+}
+
+impl JsonSchema for Base58VecU8 {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "Base58VecU8".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let bytes = Base58VecU8(vec![0, 0, 1, 2, 3]);
+        let json = serde_json::to_string(&bytes).unwrap();
+        assert_eq!(json, r#""11Ldp""#);
+        assert_eq!(serde_json::from_str::<Base58VecU8>(&json).unwrap(), bytes);
+        assert_eq!(
+            borsh::to_vec(&bytes).unwrap(),
+            borsh::to_vec(&bytes.0).unwrap()
+        );
+
+        assert!(serde_json::from_str::<Base58VecU8>(r#""0OIl""#).is_err());
+    }
+}
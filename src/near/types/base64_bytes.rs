@@ -0,0 +1,205 @@
+//! Bytes serialized in JSON as base64 strings, with the standard or URL-safe alphabet and with or
+//! without padding.
+use core::{fmt, marker::PhantomData};
+use std::collections::BTreeMap;
+
+use base64::{
+    engine::{general_purpose, GeneralPurpose},
+    Engine,
+};
+use borsh::{
+    io::{Error, Read, Write},
+    schema::{add_definition, Declaration, Definition, Fields},
+    BorshDeserialize, BorshSchema, BorshSerialize,
+};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A base64 variant, selecting the encoding of [`Base64Bytes`].
+pub trait Base64Engine {
+    /// The engine encoding and decoding the bytes.
+    const ENGINE: GeneralPurpose;
+}
+
+/// The standard alphabet with padding (RFC 4648 §4), used by NEAR.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Standard;
+
+/// The standard alphabet without padding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StandardNoPad;
+
+/// The URL-safe alphabet with padding (RFC 4648 §5).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UrlSafe;
+
+/// The URL-safe alphabet without padding, used by JWTs and WebAuthn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UrlSafeNoPad;
+
+impl Base64Engine for Standard {
+    const ENGINE: GeneralPurpose = general_purpose::STANDARD;
+}
+
+impl Base64Engine for StandardNoPad {
+    const ENGINE: GeneralPurpose = general_purpose::STANDARD_NO_PAD;
+}
+
+impl Base64Engine for UrlSafe {
+    const ENGINE: GeneralPurpose = general_purpose::URL_SAFE;
+}
+
+impl Base64Engine for UrlSafeNoPad {
+    const ENGINE: GeneralPurpose = general_purpose::URL_SAFE_NO_PAD;
+}
+
+/// Bytes serialized in JSON as a base64 string encoded with `E`, and with borsh as a `Vec<u8>`.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Base64Bytes<E = Standard>(pub Vec<u8>, PhantomData<E>);
+
+/// Bytes serialized in JSON as a standard base64 string, like the `Base64VecU8` of `near-sdk`.
+pub type Base64VecU8 = Base64Bytes<Standard>;
+
+impl<E> Base64Bytes<E> {
+    /// Wraps bytes.
+    pub const fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes, PhantomData)
+    }
+}
+
+impl<E: Base64Engine> Base64Bytes<E> {
+    /// Encodes the bytes in base64.
+    pub fn encode(&self) -> String {
+        E::ENGINE.encode(&self.0)
+    }
+
+    /// Decodes base64-encoded bytes.
+    pub fn decode(s: &str) -> Result<Self, String> {
+        E::ENGINE
+            .decode(s)
+            .map(Self::new)
+            .map_err(|e| format!("Invalid base64: {}", e))
+    }
+}
+
+impl<E> fmt::Debug for Base64Bytes<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Base64Bytes").field(&self.0).finish()
+    }
+}
+
+impl<E> From<Vec<u8>> for Base64Bytes<E> {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<E> From<Base64Bytes<E>> for Vec<u8> {
+    fn from(bytes: Base64Bytes<E>) -> Self {
+        bytes.0
+    }
+}
+
+impl<E: Base64Engine> Serialize for Base64Bytes<E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.encode())
+    }
+}
+
+impl<'de, E: Base64Engine> Deserialize<'de> for Base64Bytes<E> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        Self::decode(&s).map_err(D::Error::custom)
+    }
+}
+
+impl<E> BorshSerialize for Base64Bytes<E> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        BorshSerialize::serialize(&self.0, writer)
+    }
+}
+
+impl<E> BorshDeserialize for Base64Bytes<E> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        Vec::deserialize_reader(reader).map(Self::new)
+    }
+}
+
+impl<E> BorshSchema for Base64Bytes<E> {
+    fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        let fields = Fields::UnnamedFields(vec![Vec::<u8>::declaration()]);
+        add_definition(
+            Self::declaration(),
+            Definition::Struct { fields },
+            definitions,
+        );
+        Vec::<u8>::add_definitions_recursively(definitions);
+    }
+
+    fn declaration() -> Declaration {
+        "Base64VecU8".to_string()
+    }
+}
+
+impl<E> JsonSchema for Base64Bytes<E> {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "Base64VecU8".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let bytes = Base64VecU8::new(b"\0asm".to_vec());
+        let json = serde_json::to_string(&bytes).unwrap();
+        assert_eq!(json, r#""AGFzbQ==""#);
+        assert_eq!(serde_json::from_str::<Base64VecU8>(&json).unwrap(), bytes);
+        assert_eq!(
+            borsh::to_vec(&bytes).unwrap(),
+            borsh::to_vec(&bytes.0).unwrap()
+        );
+
+        assert!(serde_json::from_str::<Base64VecU8>(r#""AGFzbQ=""#).is_err());
+    }
+
+    #[test]
+    fn test_engines() {
+        let bytes = vec![0xfb, 0xff, 0xbf];
+
+        assert_eq!(Base64Bytes::<Standard>::new(bytes.clone()).encode(), "+/+/");
+        assert_eq!(Base64Bytes::<UrlSafe>::new(bytes.clone()).encode(), "-_-_");
+
+        let bytes = vec![0xfb, 0xff];
+        assert_eq!(Base64Bytes::<Standard>::new(bytes.clone()).encode(), "+/8=");
+        assert_eq!(
+            Base64Bytes::<StandardNoPad>::new(bytes.clone()).encode(),
+            "+/8"
+        );
+        assert_eq!(Base64Bytes::<UrlSafe>::new(bytes.clone()).encode(), "-_8=");
+        assert_eq!(
+            Base64Bytes::<UrlSafeNoPad>::new(bytes.clone()).encode(),
+            "-_8"
+        );
+
+        let json = r#""-_8""#;
+        assert_eq!(
+            serde_json::from_str::<Base64Bytes<UrlSafeNoPad>>(json)
+                .unwrap()
+                .0,
+            bytes
+        );
+        assert!(serde_json::from_str::<Base64Bytes<StandardNoPad>>(json).is_err());
+        assert!(serde_json::from_str::<Base64Bytes<UrlSafe>>(json).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use core::{fmt, str::FromStr};
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A SHA-256 hash, like the hashes of blocks and transactions.
+///
+/// Hashes are borsh-encoded as their 32 bytes, and formatted, parsed and serialized in JSON in
+/// base58 like the ones returned by the RPC (`block.header.hash`).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct CryptoHash(pub [u8; 32]);
+
+/// The hash of a recent block, referenced by transactions to expire after a while.
+pub type BlockHash = CryptoHash;
+
+impl FromStr for CryptoHash {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hash = bs58::decode(s)
+            .into_vec()
+            .map_err(|e| format!("Invalid base58 hash: {}", e))?;
+
+        hash.try_into()
+            .map(Self)
+            .map_err(|_| format!("Invalid hash length: {}", s))
+    }
+}
+
+impl fmt::Display for CryptoHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&bs58::encode(self.0).into_string())
+    }
+}
+
+impl Serialize for CryptoHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CryptoHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+impl JsonSchema for CryptoHash {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "CryptoHash".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58() {
+        let hash: CryptoHash = "EPnLgE7iEq9s7yTkos96M3cWymH5avBAPm3qx3NXqR8H"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            hex::encode(hash.0),
+            "c6fdf91c8e82f8f917cc1975e9de1c64be11899e321dfdf5febcfbb7313f1486"
+        );
+        assert_eq!(
+            hash.to_string(),
+            "EPnLgE7iEq9s7yTkos96M3cWymH5avBAPm3qx3NXqR8H"
+        );
+
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, r#""EPnLgE7iEq9s7yTkos96M3cWymH5avBAPm3qx3NXqR8H""#);
+        assert_eq!(serde_json::from_str::<CryptoHash>(&json).unwrap(), hash);
+        assert_eq!(borsh::to_vec(&hash).unwrap(), hash.0);
+
+        assert!("EPnLgE7iEq9s7yTkos96M3cWymH5av"
+            .parse::<CryptoHash>()
+            .is_err());
+        assert!("0PnLgE7iEq9s7yTkos96M3cWymH5avBAPm3qx3NXqR8H"
+            .parse::<CryptoHash>()
+            .is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! [NEP-366] delegate actions, signed by a user and submitted on their behalf by a relayer paying
+//! for the gas.
+//!
+//! [NEP-366]: https://github.com/near/NEPs/blob/master/neps/nep-0366.md
+use borsh::{
+    io::{Error, ErrorKind, Read, Write},
+    BorshDeserialize, BorshSchema, BorshSerialize,
+};
+use sha2::{Digest, Sha256};
+
+use super::{AccountId, Action, PublicKey, Signature};
+use crate::near::signable_message::{
+    SignableMessage, SignableMessageType, MIN_ON_CHAIN_DISCRIMINANT, NEP_366_META_TRANSACTIONS,
+};
+
+/// The [NEP-461] prefix of the payload signed for a delegate action, `2^30 + 366`, keeping it
+/// from being a valid transaction.
+///
+/// [NEP-461]: https://github.com/near/NEPs/blob/master/neps/nep-0461.md
+pub const DELEGATE_ACTION_PREFIX: u32 = MIN_ON_CHAIN_DISCRIMINANT + NEP_366_META_TRANSACTIONS;
+
+/// Actions the sender of a transaction delegates to a relayer.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct DelegateAction {
+    /// Account signing the delegate action, the actions being executed on its behalf.
+    pub sender_id: AccountId,
+    /// Account the actions apply to.
+    pub receiver_id: AccountId,
+    /// Actions applied in order, none of them being a delegate action.
+    pub actions: Vec<NonDelegateAction>,
+    /// Nonce of the access key of the sender, larger than the one of the previous transaction
+    /// or delegate action it signed.
+    pub nonce: u64,
+    /// Block height after which the delegate action is no longer valid.
+    pub max_block_height: u64,
+    /// Public key of the access key of the sender signing the delegate action.
+    pub public_key: PublicKey,
+}
+
+impl DelegateAction {
+    /// Encode the delegate action with borsh after its NEP-461 prefix, the payload whose SHA-256
+    /// is signed
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        SignableMessage::new(self, SignableMessageType::DelegateAction).build_for_signing()
+    }
+
+    /// Compute the SHA-256 of the prefixed borsh encoding of the delegate action, the message
+    /// signed by the access key of the sender
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.build_for_signing()).into()
+    }
+
+    /// Attach the signature of the sender, the signed delegate action being submitted by the
+    /// relayer as an [`Action::Delegate`] of a transaction whose receiver is the sender
+    pub fn build_with_signature(self, signature: Signature) -> SignedDelegateAction {
+        SignedDelegateAction {
+            delegate_action: self,
+            signature,
+        }
+    }
+}
+
+/// A delegate action signed by its sender.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct SignedDelegateAction {
+    pub delegate_action: DelegateAction,
+    pub signature: Signature,
+}
+
+impl From<SignedDelegateAction> for Action {
+    fn from(action: SignedDelegateAction) -> Self {
+        Self::Delegate(Box::new(action))
+    }
+}
+
+/// An action other than [`Action::Delegate`], delegate actions not being nestable.
+///
+/// Encoded like the wrapped action, decoding it failing for delegate actions.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSchema)]
+pub struct NonDelegateAction(Action);
+
+impl NonDelegateAction {
+    /// The wrapped action
+    pub fn action(&self) -> &Action {
+        &self.0
+    }
+}
+
+impl TryFrom<Action> for NonDelegateAction {
+    type Error = String;
+
+    fn try_from(action: Action) -> Result<Self, Self::Error> {
+        match action {
+            Action::Delegate(_) => Err("Delegate actions cannot be nested".to_string()),
+            action => Ok(Self(action)),
+        }
+    }
+}
+
+impl From<NonDelegateAction> for Action {
+    fn from(action: NonDelegateAction) -> Self {
+        action.0
+    }
+}
+
+impl BorshSerialize for NonDelegateAction {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        self.0.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for NonDelegateAction {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        Action::deserialize_reader(reader)?
+            .try_into()
+            .map_err(|e: String| Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::near::types::{NearToken, TransferAction};
+
+    fn delegate_action() -> DelegateAction {
+        DelegateAction {
+            sender_id: "alice.near".parse().unwrap(),
+            receiver_id: "bob.near".parse().unwrap(),
+            actions: vec![Action::from(TransferAction {
+                deposit: NearToken::from_yocto(1),
+            })
+            .try_into()
+            .unwrap()],
+            nonce: 7,
+            max_block_height: 100,
+            public_key: PublicKey::Ed25519([1; 32]),
+        }
+    }
+
+    #[test]
+    fn test_build_for_signing() {
+        let delegate_action = delegate_action();
+
+        let expected = [
+            1_073_742_190u32.to_le_bytes().to_vec(),
+            10u32.to_le_bytes().to_vec(),
+            b"alice.near".to_vec(),
+            8u32.to_le_bytes().to_vec(),
+            b"bob.near".to_vec(),
+            1u32.to_le_bytes().to_vec(),
+            vec![3],
+            1u128.to_le_bytes().to_vec(),
+            7u64.to_le_bytes().to_vec(),
+            100u64.to_le_bytes().to_vec(),
+            vec![0],
+            vec![1; 32],
+        ]
+        .concat();
+
+        assert_eq!(delegate_action.build_for_signing(), expected);
+        assert_eq!(
+            delegate_action.signing_hash(),
+            <[u8; 32]>::from(Sha256::digest(&expected))
+        );
+    }
+
+    #[test]
+    fn test_signed_delegate_action() {
+        let delegate_action = delegate_action();
+        let action: Action = delegate_action
+            .clone()
+            .build_with_signature(Signature::Ed25519([5; 64]))
+            .into();
+
+        let encoded = borsh::to_vec(&action).unwrap();
+        assert_eq!(encoded[0], 8);
+        assert_eq!(
+            encoded[1..encoded.len() - 65],
+            borsh::to_vec(&delegate_action).unwrap()
+        );
+        assert_eq!(encoded[encoded.len() - 65], 0);
+        assert_eq!(Action::try_from_slice(&encoded).unwrap(), action);
+
+        // Delegate actions cannot be nested
+        assert!(NonDelegateAction::try_from(action).is_err());
+        let mut nested = borsh::to_vec(&delegate_action).unwrap();
+        let actions_start = 4 + 10 + 4 + 8;
+        nested.splice(actions_start + 4.., encoded);
+        assert!(DelegateAction::try_from_slice(&nested).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+//! Minimal required NEAR types, borsh-encoded like the ones of
+//! [near-primitives](https://github.com/near/nearcore/tree/master/core/primitives)
+mod access_key;
+mod account_id;
+mod actions;
+mod base58_vec_u8;
+pub mod base64_bytes;
+mod crypto_hash;
+pub mod delegate_action;
+mod near_gas;
+mod near_token;
+mod public_key;
+mod signature;
+
+pub use self::access_key::{AccessKey, AccessKeyPermission, FunctionCallPermission};
+pub use self::account_id::{AccountId, MAX_ACCOUNT_ID_LEN, MIN_ACCOUNT_ID_LEN};
+pub use self::actions::Action;
+pub use self::actions::{
+    AddKeyAction, CreateAccountAction, DeleteAccountAction, DeleteKeyAction, DeployContractAction,
+    DeployGlobalContractAction, FunctionCallAction, GlobalContractDeployMode,
+    GlobalContractIdentifier, StakeAction, TransferAction, UseGlobalContractAction,
+};
+pub use self::base58_vec_u8::Base58VecU8;
+pub use self::base64_bytes::{Base64Bytes, Base64VecU8};
+pub use self::crypto_hash::{BlockHash, CryptoHash};
+pub use self::delegate_action::{DelegateAction, NonDelegateAction, SignedDelegateAction};
+pub use self::near_gas::NearGas;
+pub use self::near_token::{NearToken, ONE_NEAR};
+pub use self::public_key::PublicKey;
+pub use self::signature::Signature;
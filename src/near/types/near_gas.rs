@@ -0,0 +1,137 @@
+use core::fmt;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The amount of gas in a teragas, about a millisecond of computation.
+const ONE_TERA_GAS: u64 = 10u64.pow(12);
+
+/// The amount of gas in a gigagas.
+const ONE_GIGA_GAS: u64 = 10u64.pow(9);
+
+/// An amount of gas, attached to function calls.
+///
+/// Amounts are borsh-encoded as a `u64` and serialized in JSON as a number, like the gas of the
+/// actions returned by the RPC.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(transparent)]
+pub struct NearGas(u64);
+
+impl NearGas {
+    /// An amount of gas.
+    pub const fn from_gas(gas: u64) -> Self {
+        Self(gas)
+    }
+
+    /// An amount of gigagas, saturating on overflow.
+    pub const fn from_ggas(ggas: u64) -> Self {
+        Self(ggas.saturating_mul(ONE_GIGA_GAS))
+    }
+
+    /// An amount of teragas, saturating on overflow: 30 Tgas are enough for most function calls,
+    /// and a transaction can attach up to 300 Tgas.
+    pub const fn from_tgas(tgas: u64) -> Self {
+        Self(tgas.saturating_mul(ONE_TERA_GAS))
+    }
+
+    /// The amount of gas.
+    pub const fn as_gas(&self) -> u64 {
+        self.0
+    }
+
+    /// The amount of teragas, rounded down.
+    pub const fn as_tgas(&self) -> u64 {
+        self.0 / ONE_TERA_GAS
+    }
+
+    /// Adds two amounts, saturating on overflow.
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Subtracts two amounts, saturating at zero.
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Multiplies an amount, saturating on overflow.
+    pub const fn saturating_mul(self, rhs: u64) -> Self {
+        Self(self.0.saturating_mul(rhs))
+    }
+
+    /// Divides an amount, rounding down, and returning zero if `rhs` is zero.
+    pub const fn saturating_div(self, rhs: u64) -> Self {
+        match self.0.checked_div(rhs) {
+            Some(gas) => Self(gas),
+            None => Self(0),
+        }
+    }
+}
+
+impl fmt::Display for NearGas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 % ONE_TERA_GAS == 0 {
+            write!(f, "{} Tgas", self.as_tgas())
+        } else {
+            write!(f, "{} gas", self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constructors_and_arithmetic() {
+        let gas = NearGas::from_tgas(30);
+        assert_eq!(gas.as_gas(), 30_000_000_000_000);
+        assert_eq!(gas.as_tgas(), 30);
+        assert_eq!(NearGas::from_ggas(30_000), gas);
+        assert_eq!(NearGas::from_tgas(u64::MAX).as_gas(), u64::MAX);
+
+        assert_eq!(gas.saturating_add(gas), NearGas::from_tgas(60));
+        assert_eq!(
+            gas.saturating_sub(NearGas::from_tgas(31)),
+            NearGas::default()
+        );
+        assert_eq!(gas.saturating_mul(10), NearGas::from_tgas(300));
+        assert_eq!(gas.saturating_mul(u64::MAX).as_gas(), u64::MAX);
+        assert_eq!(gas.saturating_div(3), NearGas::from_tgas(10));
+        assert_eq!(gas.saturating_div(0), NearGas::default());
+
+        assert_eq!(gas.to_string(), "30 Tgas");
+        assert_eq!(NearGas::from_gas(1).to_string(), "1 gas");
+    }
+
+    #[test]
+    fn test_serde_and_borsh() {
+        let gas = NearGas::from_tgas(30);
+        let json = serde_json::to_string(&gas).unwrap();
+        assert_eq!(json, "30000000000000");
+        assert_eq!(serde_json::from_str::<NearGas>(&json).unwrap(), gas);
+        assert!(serde_json::from_str::<NearGas>(r#""30000000000000""#).is_err());
+
+        assert_eq!(
+            borsh::to_vec(&gas).unwrap(),
+            30_000_000_000_000u64.to_le_bytes()
+        );
+    }
+}
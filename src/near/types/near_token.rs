@@ -0,0 +1,266 @@
+use core::{fmt, str::FromStr};
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The number of yoctoNEAR in a NEAR.
+pub const ONE_NEAR: u128 = 10u128.pow(24);
+
+/// The number of decimals of NEAR.
+const DECIMALS: usize = 24;
+
+/// An amount of NEAR, held in yoctoNEAR (10^-24 NEAR).
+///
+/// Amounts are borsh-encoded as a `u128` of yoctoNEAR and serialized in JSON as its string, as
+/// expected by the RPC and the contracts. They are parsed from and formatted as decimal NEAR
+/// amounts, like `1.5`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct NearToken(u128);
+
+impl NearToken {
+    /// No NEAR.
+    pub const ZERO: Self = Self(0);
+
+    /// An amount of yoctoNEAR.
+    pub const fn from_yocto(yocto: u128) -> Self {
+        Self(yocto)
+    }
+
+    /// An amount of milliNEAR, saturating on overflow.
+    pub const fn from_millinear(millinear: u128) -> Self {
+        Self(millinear.saturating_mul(ONE_NEAR / 1_000))
+    }
+
+    /// An amount of NEAR, converted exactly from its shortest decimal representation: `1.1`
+    /// is `1_100_000_000_000_000_000_000_000` yoctoNEAR.
+    pub fn from_near(near: f64) -> Result<Self, String> {
+        if !near.is_finite() || near < 0.0 {
+            return Err(format!("Invalid NEAR amount: {}", near));
+        }
+
+        format!("{}", near).parse()
+    }
+
+    /// The amount in yoctoNEAR.
+    pub const fn as_yocto(&self) -> u128 {
+        self.0
+    }
+
+    /// Adds two amounts, returning `None` on overflow.
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(yocto) => Some(Self(yocto)),
+            None => None,
+        }
+    }
+
+    /// Subtracts two amounts, returning `None` on underflow.
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(yocto) => Some(Self(yocto)),
+            None => None,
+        }
+    }
+
+    /// Multiplies an amount, returning `None` on overflow.
+    pub const fn checked_mul(self, rhs: u128) -> Option<Self> {
+        match self.0.checked_mul(rhs) {
+            Some(yocto) => Some(Self(yocto)),
+            None => None,
+        }
+    }
+
+    /// Divides an amount, rounding down, returning `None` if `rhs` is zero.
+    pub const fn checked_div(self, rhs: u128) -> Option<Self> {
+        match self.0.checked_div(rhs) {
+            Some(yocto) => Some(Self(yocto)),
+            None => None,
+        }
+    }
+}
+
+impl FromStr for NearToken {
+    type Err = String;
+
+    /// Parses a decimal amount of NEAR, with up to 24 decimals, optionally followed by the
+    /// ` NEAR` unit it is displayed with.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid NEAR amount: {}", s);
+
+        let amount = s.strip_suffix(" NEAR").unwrap_or(s);
+        let (whole, fraction) = match amount.split_once('.') {
+            Some((_, "")) => return Err(invalid()),
+            Some((whole, fraction)) => (whole, fraction),
+            None => (amount, ""),
+        };
+        if whole.is_empty()
+            || fraction.len() > DECIMALS
+            || !whole
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let whole: u128 = whole.parse().map_err(|_| invalid())?;
+        let fraction: u128 = format!("{:0<width$}", fraction, width = DECIMALS)
+            .parse()
+            .map_err(|_| invalid())?;
+
+        whole
+            .checked_mul(ONE_NEAR)
+            .and_then(|yocto| yocto.checked_add(fraction))
+            .map(Self)
+            .ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for NearToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / ONE_NEAR;
+        let fraction = self.0 % ONE_NEAR;
+
+        if fraction == 0 {
+            write!(f, "{} NEAR", whole)
+        } else {
+            let fraction = format!("{:0width$}", fraction, width = DECIMALS);
+            write!(f, "{}.{} NEAR", whole, fraction.trim_end_matches('0'))
+        }
+    }
+}
+
+impl Serialize for NearToken {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for NearToken {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse()
+            .map(Self)
+            .map_err(|_| D::Error::custom(format!("Invalid yoctoNEAR amount: {}", s)))
+    }
+}
+
+impl JsonSchema for NearToken {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "NearToken".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(
+            NearToken::from_near(1.5).unwrap().as_yocto(),
+            1_500_000_000_000_000_000_000_000
+        );
+        // Not 1.100000000000000088817841970012523233890533447265625 NEAR
+        assert_eq!(
+            NearToken::from_near(1.1).unwrap().as_yocto(),
+            1_100_000_000_000_000_000_000_000
+        );
+        assert_eq!(NearToken::from_near(0.0).unwrap(), NearToken::ZERO);
+        assert!(NearToken::from_near(-1.0).is_err());
+        assert!(NearToken::from_near(f64::NAN).is_err());
+        assert!(NearToken::from_near(1e-25).is_err());
+
+        assert_eq!(
+            NearToken::from_millinear(250),
+            NearToken::from_yocto(ONE_NEAR / 4)
+        );
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let amount: NearToken = "1.5".parse().unwrap();
+        assert_eq!(amount, NearToken::from_yocto(ONE_NEAR * 3 / 2));
+        assert_eq!(amount.to_string(), "1.5 NEAR");
+        assert_eq!(NearToken::from_yocto(ONE_NEAR * 2).to_string(), "2 NEAR");
+        assert_eq!(
+            NearToken::from_yocto(1).to_string(),
+            "0.000000000000000000000001 NEAR"
+        );
+        assert_eq!(
+            "0.000000000000000000000001".parse::<NearToken>().unwrap(),
+            NearToken::from_yocto(1)
+        );
+
+        // Display round-trips through FromStr
+        for amount in [NearToken::ZERO, amount, NearToken::from_yocto(1)] {
+            assert_eq!(amount.to_string().parse::<NearToken>().unwrap(), amount);
+        }
+
+        for invalid in [
+            "",
+            ".5",
+            "1.",
+            "-1",
+            "1e3",
+            "1.5NEAR",
+            " NEAR",
+            "0.0000000000000000000000001",
+            "340282366920938463463375",
+        ] {
+            assert!(invalid.parse::<NearToken>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let one = NearToken::from_yocto(ONE_NEAR);
+        assert_eq!(
+            one.checked_add(one),
+            Some(NearToken::from_yocto(2 * ONE_NEAR))
+        );
+        assert_eq!(NearToken::ZERO.checked_sub(one), None);
+        assert_eq!(
+            one.checked_mul(3),
+            Some(NearToken::from_yocto(3 * ONE_NEAR))
+        );
+        assert_eq!(NearToken::from_yocto(u128::MAX).checked_add(one), None);
+        assert_eq!(NearToken::from_yocto(u128::MAX).checked_mul(2), None);
+        assert_eq!(one.checked_div(0), None);
+        assert_eq!(one.checked_div(4), Some(NearToken::from_millinear(250)));
+    }
+
+    #[test]
+    fn test_serde_and_borsh() {
+        let amount = NearToken::from_yocto(ONE_NEAR);
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(json, r#""1000000000000000000000000""#);
+        assert_eq!(serde_json::from_str::<NearToken>(&json).unwrap(), amount);
+        assert!(serde_json::from_str::<NearToken>("1").is_err());
+        assert!(serde_json::from_str::<NearToken>(r#""1.5""#).is_err());
+
+        assert_eq!(borsh::to_vec(&amount).unwrap(), ONE_NEAR.to_le_bytes());
+    }
+}
@@ -0,0 +1,164 @@
+use core::{fmt, str::FromStr};
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Keccak256};
+
+use super::AccountId;
+
+/// The public key of a NEAR access key, borsh-encoded as its key type followed by its bytes.
+///
+/// Keys are formatted, parsed and serialized in JSON as their curve followed by their base58
+/// encoding, like `ed25519:11111111111111111111111111111111`. Keys without a curve are ed25519
+/// keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum PublicKey {
+    /// An ed25519 public key.
+    Ed25519([u8; 32]),
+    /// An uncompressed secp256k1 public key, without its `0x04` SEC1 prefix.
+    Secp256k1([u8; 64]),
+}
+
+impl PublicKey {
+    /// The implicit account controlled by the key, existing as soon as it is funded:
+    /// - the 64 hex characters of an ed25519 key (NEAR-implicit account)
+    /// - the `0x`-prefixed Ethereum address of a secp256k1 key (ETH-implicit account, used by
+    ///   chain abstraction wallets)
+    pub fn implicit_account_id(&self) -> AccountId {
+        let account_id = match self {
+            Self::Ed25519(key) => hex::encode(key),
+            Self::Secp256k1(key) => {
+                let hash = Keccak256::digest(key);
+                format!("0x{}", hex::encode(&hash[12..]))
+            }
+        };
+
+        account_id
+            .try_into()
+            .expect("implicit account ids are valid account ids")
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (curve, key) = s.split_once(':').unwrap_or(("ed25519", s));
+        let key = bs58::decode(key)
+            .into_vec()
+            .map_err(|e| format!("Invalid base58 public key: {}", e))?;
+
+        match curve {
+            "ed25519" => key
+                .try_into()
+                .map(Self::Ed25519)
+                .map_err(|_| "Invalid ed25519 public key length".to_string()),
+            "secp256k1" => key
+                .try_into()
+                .map(Self::Secp256k1)
+                .map_err(|_| "Invalid secp256k1 public key length".to_string()),
+            curve => Err(format!("Unknown curve: {}", curve)),
+        }
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ed25519(key) => write!(f, "ed25519:{}", bs58::encode(key).into_string()),
+            Self::Secp256k1(key) => write!(f, "secp256k1:{}", bs58::encode(key).into_string()),
+        }
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+impl JsonSchema for PublicKey {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "PublicKey".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_format() {
+        let key: PublicKey = "ed25519:11111111111111111111111111111111".parse().unwrap();
+        assert_eq!(key, PublicKey::Ed25519([0; 32]));
+        assert_eq!(key.to_string(), "ed25519:11111111111111111111111111111111");
+        assert_eq!(
+            "11111111111111111111111111111111"
+                .parse::<PublicKey>()
+                .unwrap(),
+            key
+        );
+
+        let key = PublicKey::Secp256k1([0xff; 64]);
+        assert_eq!(key.to_string().parse::<PublicKey>().unwrap(), key);
+        assert!(key.to_string().starts_with("secp256k1:"));
+
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(json, format!("\"{}\"", key));
+        assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), key);
+
+        for invalid in [
+            "ed25519:0OIl",
+            "ed25519:1111",
+            "secp256k1:11111111111111111111111111111111",
+            "sr25519:11111111111111111111111111111111",
+        ] {
+            assert!(invalid.parse::<PublicKey>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_implicit_account_id() {
+        let key = PublicKey::Ed25519([0xab; 32]);
+        let account_id = key.implicit_account_id();
+        assert_eq!(account_id.as_str(), "ab".repeat(32));
+        assert!(account_id.is_near_implicit());
+        assert!(!account_id.is_eth_implicit());
+
+        // The generator, public key of the private key 1
+        let mut generator = [0; 64];
+        hex::decode_to_slice(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            &mut generator,
+        )
+        .unwrap();
+        let account_id = PublicKey::Secp256k1(generator).implicit_account_id();
+        assert_eq!(
+            account_id.as_str(),
+            "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+        assert!(account_id.is_eth_implicit());
+        assert!(!account_id.is_near_implicit());
+
+        let account_id: AccountId = "alice.near".parse().unwrap();
+        assert!(!account_id.is_near_implicit());
+        assert!(!account_id.is_eth_implicit());
+    }
+}
@@ -0,0 +1,135 @@
+use core::{fmt, str::FromStr};
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::signer::types::EddsaSignatureResponse;
+
+/// A signature by a NEAR access key, borsh-encoded as its key type followed by its bytes.
+///
+/// Like public keys, signatures are formatted, parsed and serialized in JSON as their curve
+/// followed by their base58 encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum Signature {
+    /// An ed25519 signature.
+    Ed25519([u8; 64]),
+    /// A secp256k1 signature `r || s || v`, the recovery id `v` being 0 or 1.
+    Secp256k1([u8; 65]),
+}
+
+impl Signature {
+    /// Creates a secp256k1 signature from its scalars and the recovery id of the public key.
+    pub fn secp256k1(r: &[u8; 32], s: &[u8; 32], recovery_id: u8) -> Result<Self, String> {
+        if recovery_id > 1 {
+            return Err(format!("Invalid recovery id: {}", recovery_id));
+        }
+
+        let mut signature = [0; 65];
+        signature[..32].copy_from_slice(r);
+        signature[32..64].copy_from_slice(s);
+        signature[64] = recovery_id;
+
+        Ok(Self::Secp256k1(signature))
+    }
+}
+
+impl From<EddsaSignatureResponse> for Signature {
+    fn from(response: EddsaSignatureResponse) -> Self {
+        Self::Ed25519(response.signature)
+    }
+}
+
+impl FromStr for Signature {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (curve, signature) = s.split_once(':').unwrap_or(("ed25519", s));
+        let signature = bs58::decode(signature)
+            .into_vec()
+            .map_err(|e| format!("Invalid base58 signature: {}", e))?;
+
+        match curve {
+            "ed25519" => signature
+                .try_into()
+                .map(Self::Ed25519)
+                .map_err(|_| "Invalid ed25519 signature length".to_string()),
+            "secp256k1" => signature
+                .try_into()
+                .map(Self::Secp256k1)
+                .map_err(|_| "Invalid secp256k1 signature length".to_string()),
+            curve => Err(format!("Unknown curve: {}", curve)),
+        }
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ed25519(signature) => {
+                write!(f, "ed25519:{}", bs58::encode(signature).into_string())
+            }
+            Self::Secp256k1(signature) => {
+                write!(f, "secp256k1:{}", bs58::encode(signature).into_string())
+            }
+        }
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+impl JsonSchema for Signature {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "Signature".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_format() {
+        let signature = Signature::Ed25519([0; 64]);
+        assert_eq!(signature.to_string(), format!("ed25519:{}", "1".repeat(64)));
+        assert_eq!(
+            signature.to_string().parse::<Signature>().unwrap(),
+            signature
+        );
+
+        let signature = Signature::secp256k1(&[7; 32], &[8; 32], 1).unwrap();
+        let json = serde_json::to_string(&signature).unwrap();
+        assert!(json.starts_with("\"secp256k1:"));
+        assert_eq!(serde_json::from_str::<Signature>(&json).unwrap(), signature);
+
+        assert!("secp256k1:1111".parse::<Signature>().is_err());
+        assert!(format!("sr25519:{}", "1".repeat(64))
+            .parse::<Signature>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_from_eddsa_signature_response() {
+        let response = EddsaSignatureResponse::from([7; 64]);
+        assert_eq!(Signature::from(response), Signature::Ed25519([7; 64]));
+    }
+}
@@ -0,0 +1,63 @@
+//! Utility functions for the nonces of NEAR transactions
+
+/// Transactions are rejected if their nonce is not below the height of their block hash times
+/// this multiplier, keeping nonces from being exhausted.
+pub const ACCESS_KEY_NONCE_RANGE_MULTIPLIER: u64 = 1_000_000;
+
+/// The nonce of the next transaction signed by an access key, given the `nonce` of the access
+/// key returned by the `view_access_key` RPC query.
+pub fn next_nonce(access_key_nonce: u64) -> Result<u64, String> {
+    access_key_nonce
+        .checked_add(1)
+        .ok_or_else(|| "Access key nonce exhausted".to_string())
+}
+
+/// Checks that a transaction `nonce` is accepted for an access key whose nonce is
+/// `access_key_nonce`, the transaction referencing the block at `block_height`.
+pub fn validate_nonce(nonce: u64, access_key_nonce: u64, block_height: u64) -> Result<(), String> {
+    if nonce <= access_key_nonce {
+        return Err(format!(
+            "Invalid nonce {}: must be greater than the access key nonce {}",
+            nonce, access_key_nonce
+        ));
+    }
+
+    let upper_bound = block_height.saturating_mul(ACCESS_KEY_NONCE_RANGE_MULTIPLIER);
+    if nonce >= upper_bound {
+        return Err(format!(
+            "Nonce too large {}: must be lower than {}",
+            nonce, upper_bound
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_nonce() {
+        assert_eq!(next_nonce(0), Ok(1));
+        assert_eq!(next_nonce(118_432_000_000_041), Ok(118_432_000_000_042));
+        assert!(next_nonce(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_validate_nonce() {
+        // Added keys start with the height of their block times the multiplier
+        let block_height = 118_432_000;
+        let access_key_nonce = block_height * ACCESS_KEY_NONCE_RANGE_MULTIPLIER;
+
+        let nonce = next_nonce(access_key_nonce).unwrap();
+        assert!(validate_nonce(nonce, access_key_nonce, block_height + 1).is_ok());
+        // A nonce can skip values
+        assert!(validate_nonce(nonce + 10, access_key_nonce, block_height + 1).is_ok());
+
+        assert!(validate_nonce(access_key_nonce, access_key_nonce, block_height + 1).is_err());
+        assert!(validate_nonce(nonce - 2, access_key_nonce, block_height + 1).is_err());
+        // The block hash is older than the key
+        assert!(validate_nonce(nonce, access_key_nonce, block_height).is_err());
+    }
+}
@@ -0,0 +1,508 @@
+//! Conversion of the signatures returned by the MPC signer into the signatures of Bitcoin inputs,
+//! and signing of whole transactions with them.
+use sha2::{Digest, Sha256};
+
+use super::types::{SchnorrSignatureResponse, SignatureResponse};
+use crate::bitcoin::{
+    keys::PublicKey,
+    taproot,
+    types::{EcdsaSighashType, ScriptBuf, TxOut, Utxo, Witness},
+    utils::{build_script_sig, serialize_ecdsa_signature_from_scalars},
+    BitcoinTransaction,
+};
+
+/// The signature of an input of a Bitcoin transaction returned by the signer: an ECDSA signature
+/// for P2PKH and P2WPKH inputs, or a Schnorr signature of a taproot domain for P2TR key path
+/// inputs.
+#[derive(Debug, Clone)]
+pub enum BitcoinSignatureResponse {
+    Ecdsa(SignatureResponse),
+    Schnorr(SchnorrSignatureResponse),
+}
+
+impl From<SignatureResponse> for BitcoinSignatureResponse {
+    fn from(response: SignatureResponse) -> Self {
+        Self::Ecdsa(response)
+    }
+}
+
+impl From<SchnorrSignatureResponse> for BitcoinSignatureResponse {
+    fn from(response: SchnorrSignatureResponse) -> Self {
+        Self::Schnorr(response)
+    }
+}
+
+impl SignatureResponse {
+    /// The DER-encoded ECDSA signature followed by the sighash type, as pushed in the witness of
+    /// SegWit inputs and the scriptSig of legacy ones.
+    ///
+    /// S is normalized to low-S, high-S signatures being rejected by the nodes.
+    pub fn to_bitcoin_ecdsa_signature(
+        &self,
+        sighash_type: EcdsaSighashType,
+    ) -> Result<Vec<u8>, String> {
+        let compact = self.compact().map_err(|e| e.to_string())?;
+        let (r, s) = compact.split_at(32);
+
+        Ok(serialize_ecdsa_signature_from_scalars(
+            r.try_into().unwrap(),
+            s.try_into().unwrap(),
+            sighash_type,
+        ))
+    }
+}
+
+impl SchnorrSignatureResponse {
+    /// The 64-byte BIP-340 Schnorr signature of a taproot key path spend with the default
+    /// sighash type, pushed alone in the witness of the input.
+    pub fn to_bitcoin_schnorr_signature(&self) -> Result<[u8; 64], String> {
+        self.to_bip340_bytes().map_err(|e| e.to_string())
+    }
+}
+
+/// The payloads to request the signatures of for the inputs of `tx`, spending `utxos` in input
+/// order: the SIGHASH_ALL sighashes of P2PKH and P2WPKH inputs, and the SIGHASH_DEFAULT sighashes
+/// of P2TR key path inputs.
+pub fn bitcoin_payloads(tx: &BitcoinTransaction, utxos: &[Utxo]) -> Result<Vec<[u8; 32]>, String> {
+    check_utxos(tx, utxos)?;
+    let prevouts: Vec<TxOut> = utxos
+        .iter()
+        .map(|utxo| TxOut {
+            value: utxo.value,
+            script_pubkey: utxo.script_pubkey.clone(),
+        })
+        .collect();
+    let midstate = tx.segwit_sighash_midstate();
+
+    utxos
+        .iter()
+        .enumerate()
+        .map(|(input_index, utxo)| {
+            if utxo.is_p2tr() {
+                Ok(taproot::sighash(&tx.build_for_signing_taproot(
+                    input_index,
+                    &prevouts,
+                    None,
+                )))
+            } else if utxo.is_p2wpkh() {
+                let preimage = tx.build_for_signing_segwit_with_midstate(
+                    &midstate,
+                    EcdsaSighashType::All,
+                    input_index,
+                    &utxo.script_code(),
+                    utxo.value.to_sat(),
+                );
+                Ok(Sha256::digest(Sha256::digest(preimage)).into())
+            } else if utxo.is_p2pkh() {
+                // The legacy sighash commits to the script pubkey in place of the script sig of
+                // the signed input, and to empty script sigs for the others
+                let mut tx = tx.clone();
+                for (index, input) in tx.input.iter_mut().enumerate() {
+                    input.script_sig = if index == input_index {
+                        utxo.script_pubkey.clone()
+                    } else {
+                        ScriptBuf::default()
+                    };
+                }
+                let preimage = tx.build_for_signing_legacy(EcdsaSighashType::All);
+                Ok(Sha256::digest(Sha256::digest(preimage)).into())
+            } else {
+                Err(format!("Unsupported script pubkey for input {input_index}"))
+            }
+        })
+        .collect()
+}
+
+/// The serialized transaction, ready to be broadcast, of `tx` spending `utxos` with the key
+/// `public_key`, `responses` being the signatures of the [`bitcoin_payloads`] of its inputs.
+///
+/// The DER-encoded ECDSA signatures go in the script sig of P2PKH inputs and in the witness of
+/// P2WPKH ones, followed by the public key. The Schnorr signatures go in the witness of P2TR key
+/// path inputs, and must be signed by the output key of their UTXO, the key of
+/// [`super::derive_p2tr_address`] tweaked as defined by BIP-341 rather than the derived key
+/// itself.
+pub fn sign_bitcoin(
+    tx: &BitcoinTransaction,
+    utxos: &[Utxo],
+    public_key: &PublicKey,
+    responses: &[BitcoinSignatureResponse],
+) -> Result<Vec<u8>, String> {
+    let payloads = bitcoin_payloads(tx, utxos)?;
+    if responses.len() != tx.input.len() {
+        return Err(format!(
+            "Expected {} signatures, got {}",
+            tx.input.len(),
+            responses.len()
+        ));
+    }
+
+    let mut tx = tx.clone();
+    for (input_index, (utxo, response)) in utxos.iter().zip(responses).enumerate() {
+        let input = &mut tx.input[input_index];
+
+        match response {
+            BitcoinSignatureResponse::Schnorr(response) if utxo.is_p2tr() => {
+                let signature = response.to_bitcoin_schnorr_signature()?;
+                let output_key = utxo.script_pubkey.0[2..]
+                    .try_into()
+                    .expect("P2TR script pubkeys push 32 bytes");
+                if !taproot::verify_schnorr(output_key, &payloads[input_index], &signature) {
+                    return Err(format!(
+                        "Invalid Schnorr signature for input {input_index}, not signed by the \
+                         output key of its UTXO"
+                    ));
+                }
+                input.witness = Witness::from_slice(&[signature.to_vec()]);
+            }
+            BitcoinSignatureResponse::Ecdsa(response) if utxo.is_p2wpkh() => {
+                check_public_key(input_index, &utxo.script_pubkey.0[2..], public_key)?;
+                let signature = response.to_bitcoin_ecdsa_signature(EcdsaSighashType::All)?;
+                input.witness = Witness::from_slice(&[signature, public_key.to_bytes()]);
+            }
+            BitcoinSignatureResponse::Ecdsa(response) if utxo.is_p2pkh() => {
+                check_public_key(input_index, &utxo.script_pubkey.0[3..23], public_key)?;
+                let signature = response.to_bitcoin_ecdsa_signature(EcdsaSighashType::All)?;
+                input.script_sig = ScriptBuf(build_script_sig(&signature, &public_key.to_bytes()));
+            }
+            _ if utxo.is_p2tr() => {
+                return Err(format!(
+                    "Input {input_index} spends a P2TR output and needs a Schnorr signature"
+                ));
+            }
+            _ if utxo.is_p2wpkh() || utxo.is_p2pkh() => {
+                return Err(format!("Input {input_index} needs an ECDSA signature"));
+            }
+            _ => return Err(format!("Unsupported script pubkey for input {input_index}")),
+        }
+    }
+
+    Ok(tx.serialize())
+}
+
+/// Checks that `utxos` are the outputs spent by the inputs of `tx`, in input order.
+fn check_utxos(tx: &BitcoinTransaction, utxos: &[Utxo]) -> Result<(), String> {
+    if utxos.len() != tx.input.len() {
+        return Err(format!(
+            "Expected {} UTXOs, got {}",
+            tx.input.len(),
+            utxos.len()
+        ));
+    }
+
+    match tx
+        .input
+        .iter()
+        .zip(utxos)
+        .position(|(input, utxo)| input.previous_output != utxo.outpoint())
+    {
+        Some(input_index) => Err(format!("Input {input_index} does not spend its UTXO")),
+        None => Ok(()),
+    }
+}
+
+/// Checks that `public_key` hashes to `pubkey_hash`, the hash the UTXO spent by the input pays
+/// to, any other key making an invalid witness or script sig.
+fn check_public_key(
+    input_index: usize,
+    pubkey_hash: &[u8],
+    public_key: &PublicKey,
+) -> Result<(), String> {
+    if pubkey_hash != public_key.pubkey_hash() {
+        return Err(format!(
+            "Input {input_index} spends a UTXO not paid to the public key"
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::types::{SerializableAffinePoint, SerializableScalar};
+
+    fn signature_response(big_r: &str, s: &str) -> SignatureResponse {
+        SignatureResponse {
+            big_r: SerializableAffinePoint {
+                affine_point: big_r.to_string(),
+            },
+            s: SerializableScalar {
+                scalar: s.to_string(),
+            },
+            recovery_id: 0,
+        }
+    }
+
+    #[test]
+    fn test_to_bitcoin_ecdsa_signature() {
+        let big_r = "03B96BFA3DA6BB4BB74EEEE9C20970725C5782F07724CD1BEFBD265C5AD5C63948";
+        let s = "49283B618968DEFB0E660EA703D193BC1D213F5DD811A2D13307FCA01E20C5C0";
+
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&hex::decode(&big_r[2..]).unwrap());
+        compact[32..].copy_from_slice(&hex::decode(s).unwrap());
+        let signature = bitcoin::ecdsa::Signature {
+            signature: bitcoin::secp256k1::ecdsa::Signature::from_compact(&compact).unwrap(),
+            sighash_type: bitcoin::EcdsaSighashType::All,
+        };
+
+        assert_eq!(
+            signature_response(big_r, s)
+                .to_bitcoin_ecdsa_signature(EcdsaSighashType::All)
+                .unwrap(),
+            signature.serialize().to_vec()
+        );
+        assert!(signature_response(&big_r[2..], s)
+            .to_bitcoin_ecdsa_signature(EcdsaSighashType::All)
+            .is_err());
+    }
+
+    #[test]
+    fn test_schnorr_signature_response() {
+        use ::bitcoin::secp256k1::{schnorr, Keypair, Message, Secp256k1};
+
+        let secp = Secp256k1::new();
+        let keypair = Keypair::from_seckey_slice(&secp, &[7; 32]).unwrap();
+        let message = Message::from_digest([9; 32]);
+        let signature = secp.sign_schnorr_no_aux_rand(&message, &keypair);
+        let (r, s) = signature.as_ref().split_at(32);
+
+        // The nonce of a BIP-340 signature always has an even y
+        let response = SchnorrSignatureResponse {
+            big_r: SerializableAffinePoint {
+                affine_point: format!("02{}", hex::encode_upper(r)),
+            },
+            s: SerializableScalar {
+                scalar: hex::encode_upper(s),
+            },
+        };
+        let bytes = response.to_bitcoin_schnorr_signature().unwrap();
+
+        let signature = schnorr::Signature::from_slice(&bytes).unwrap();
+        let (x_only, _) = keypair.x_only_public_key();
+        assert!(secp.verify_schnorr(&signature, &message, &x_only).is_ok());
+    }
+
+    #[test]
+    fn test_sign_bitcoin() {
+        use crate::bitcoin::{
+            script::p2wpkh_script,
+            types::{Amount, Hash, LockTime, TxIn, Txid, Version},
+        };
+        use ::bitcoin::{consensus, hashes::Hash as _, secp256k1, sighash::SighashCache};
+        use k256::ecdsa::{signature::hazmat::PrehashSigner, RecoveryId, Signature, SigningKey};
+
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let public_key =
+            PublicKey::from_slice(&signing_key.verifying_key().to_sec1_bytes()).unwrap();
+        let utxos = vec![
+            Utxo::new(
+                Txid(Hash::all_zeros()),
+                0,
+                Amount::from_sat(50_000),
+                p2wpkh_script(&public_key).unwrap(),
+            ),
+            Utxo::new(
+                Txid(Hash::all_zeros()),
+                1,
+                Amount::from_sat(20_000),
+                p2wpkh_script(&public_key).unwrap(),
+            ),
+        ];
+        let tx = BitcoinTransaction {
+            version: Version::Two,
+            lock_time: LockTime::from_height(0).unwrap(),
+            input: utxos.iter().map(TxIn::from).collect(),
+            output: vec![TxOut {
+                value: Amount::from_sat(60_000),
+                script_pubkey: p2wpkh_script(&public_key).unwrap(),
+            }],
+        };
+
+        let payloads = bitcoin_payloads(&tx, &utxos).unwrap();
+        let responses: Vec<BitcoinSignatureResponse> = payloads
+            .iter()
+            .map(|payload| {
+                let (signature, recovery_id): (Signature, RecoveryId) =
+                    signing_key.sign_prehash(payload).unwrap();
+                let prefix = if recovery_id.is_y_odd() { "03" } else { "02" };
+                let (r, s) = signature.split_bytes();
+                signature_response(
+                    &format!("{prefix}{}", hex::encode_upper(r)),
+                    &hex::encode_upper(s),
+                )
+                .into()
+            })
+            .collect();
+
+        let raw = sign_bitcoin(&tx, &utxos, &public_key, &responses).unwrap();
+
+        // Every input is validly signed according to rust-bitcoin
+        let signed: ::bitcoin::Transaction = consensus::deserialize(&raw).unwrap();
+        let secp = secp256k1::Secp256k1::verification_only();
+        let secp_public_key = secp256k1::PublicKey::from_slice(&public_key.to_bytes()).unwrap();
+        let mut cache = SighashCache::new(&signed);
+        for (input_index, utxo) in utxos.iter().enumerate() {
+            let witness = &signed.input[input_index].witness;
+            assert_eq!(witness.nth(1).unwrap(), public_key.to_bytes().as_slice());
+
+            let sighash = cache
+                .p2wpkh_signature_hash(
+                    input_index,
+                    &::bitcoin::ScriptBuf::from_bytes(utxo.script_pubkey.0.clone()),
+                    ::bitcoin::Amount::from_sat(utxo.value.to_sat()),
+                    ::bitcoin::EcdsaSighashType::All,
+                )
+                .unwrap();
+            assert_eq!(sighash.as_byte_array(), &payloads[input_index]);
+
+            let signature = ::bitcoin::ecdsa::Signature::from_slice(&witness[0]).unwrap();
+            let message = secp256k1::Message::from_digest(sighash.to_byte_array());
+            secp.verify_ecdsa(&message, &signature.signature, &secp_public_key)
+                .unwrap();
+        }
+
+        assert!(sign_bitcoin(&tx, &utxos, &public_key, &responses[..1]).is_err());
+        let other_key = PublicKey::from_slice(
+            &SigningKey::from_slice(&[8; 32])
+                .unwrap()
+                .verifying_key()
+                .to_sec1_bytes(),
+        )
+        .unwrap();
+        assert!(sign_bitcoin(&tx, &utxos, &other_key, &responses).is_err());
+        assert!(bitcoin_payloads(&tx, &utxos[1..]).is_err());
+    }
+
+    #[test]
+    fn test_sign_bitcoin_taproot() {
+        use crate::bitcoin::{
+            script::{p2tr_script, p2wpkh_script},
+            types::{Amount, Hash, LockTime, TxIn, Txid, Version},
+        };
+        use ::bitcoin::{
+            consensus,
+            hashes::Hash as _,
+            key::TapTweak,
+            secp256k1::{self, Keypair, Message},
+            sighash::{Prevouts, SighashCache},
+            TapSighashType,
+        };
+        use k256::ecdsa::{signature::hazmat::PrehashSigner, RecoveryId, Signature, SigningKey};
+
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let public_key =
+            PublicKey::from_slice(&signing_key.verifying_key().to_sec1_bytes()).unwrap();
+        let utxos = vec![
+            Utxo::new(
+                Txid(Hash::all_zeros()),
+                0,
+                Amount::from_sat(50_000),
+                p2wpkh_script(&public_key).unwrap(),
+            ),
+            Utxo::new(
+                Txid(Hash::all_zeros()),
+                1,
+                Amount::from_sat(20_000),
+                p2tr_script(&public_key.x_only()).unwrap(),
+            ),
+        ];
+        let tx = BitcoinTransaction {
+            version: Version::One,
+            lock_time: LockTime::from_height(0).unwrap(),
+            input: utxos.iter().map(TxIn::from).collect(),
+            output: vec![TxOut {
+                value: Amount::from_sat(60_000),
+                script_pubkey: p2wpkh_script(&public_key).unwrap(),
+            }],
+        };
+        let payloads = bitcoin_payloads(&tx, &utxos).unwrap();
+
+        let (signature, recovery_id): (Signature, RecoveryId) =
+            signing_key.sign_prehash(&payloads[0]).unwrap();
+        let prefix = if recovery_id.is_y_odd() { "03" } else { "02" };
+        let (r, s) = signature.split_bytes();
+        let ecdsa_response = signature_response(
+            &format!("{prefix}{}", hex::encode_upper(r)),
+            &hex::encode_upper(s),
+        );
+
+        // The taproot domain signs with the derived key tweaked into the output key
+        let secp = secp256k1::Secp256k1::new();
+        let keypair = Keypair::from_seckey_slice(&secp, &[7; 32]).unwrap();
+        let schnorr_response = |keypair: &Keypair| {
+            let signature =
+                secp.sign_schnorr_no_aux_rand(&Message::from_digest(payloads[1]), keypair);
+            let (r, s) = signature.as_ref().split_at(32);
+            SchnorrSignatureResponse {
+                big_r: SerializableAffinePoint {
+                    affine_point: format!("02{}", hex::encode_upper(r)),
+                },
+                s: SerializableScalar {
+                    scalar: hex::encode_upper(s),
+                },
+            }
+        };
+        let tweaked = keypair.tap_tweak(&secp, None).to_keypair();
+        let responses = [
+            ecdsa_response.clone().into(),
+            schnorr_response(&tweaked).into(),
+        ];
+
+        let raw = sign_bitcoin(&tx, &utxos, &public_key, &responses).unwrap();
+
+        // The key path signature is valid for the output key according to rust-bitcoin
+        let signed: ::bitcoin::Transaction = consensus::deserialize(&raw).unwrap();
+        let prevouts: Vec<::bitcoin::TxOut> = utxos
+            .iter()
+            .map(|utxo| ::bitcoin::TxOut {
+                value: ::bitcoin::Amount::from_sat(utxo.value.to_sat()),
+                script_pubkey: ::bitcoin::ScriptBuf::from_bytes(utxo.script_pubkey.0.clone()),
+            })
+            .collect();
+        let sighash = SighashCache::new(&signed)
+            .taproot_key_spend_signature_hash(1, &Prevouts::All(&prevouts), TapSighashType::Default)
+            .unwrap();
+        assert_eq!(sighash.as_byte_array(), &payloads[1]);
+
+        let witness = &signed.input[1].witness;
+        assert_eq!(witness.len(), 1);
+        let signature = secp256k1::schnorr::Signature::from_slice(&witness[0]).unwrap();
+        let (output_key, _) = tweaked.x_only_public_key();
+        secp.verify_schnorr(
+            &signature,
+            &Message::from_digest(sighash.to_byte_array()),
+            &output_key,
+        )
+        .unwrap();
+
+        // Signatures of the untweaked key, or of the wrong kind, are rejected
+        assert!(sign_bitcoin(
+            &tx,
+            &utxos,
+            &public_key,
+            &[
+                ecdsa_response.clone().into(),
+                schnorr_response(&keypair).into()
+            ]
+        )
+        .is_err());
+        assert!(sign_bitcoin(
+            &tx,
+            &utxos,
+            &public_key,
+            &[ecdsa_response.clone().into(), ecdsa_response.clone().into()]
+        )
+        .is_err());
+        assert!(sign_bitcoin(
+            &tx,
+            &utxos,
+            &public_key,
+            &[
+                schnorr_response(&tweaked).into(),
+                schnorr_response(&tweaked).into()
+            ]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,388 @@
+//! Async client requesting signatures from the MPC signer contract through the NEAR JSON-RPC,
+//! enabled by the `client` feature.
+//!
+//! ###### Example:
+//!
+//! ```rust,ignore
+//! let client = MpcClient::new("https://rpc.mainnet.near.org", "v1.signer".parse()?);
+//! let response = client
+//!     .sign(&account_id, public_key, |hash| Signature::Ed25519(sign_ed25519(hash)), &request)
+//!     .await?;
+//! ```
+use core::future::Future;
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+
+use super::{
+    types::{EddsaSignatureResponse, Payload, SignRequest, SignRequestV2, SignatureResponse},
+    Signer,
+};
+use crate::near::{
+    chain_signatures,
+    types::{AccountId, Action, Base64VecU8, BlockHash, FunctionCallAction, PublicKey, Signature},
+    utils::next_nonce,
+    NEARTransaction,
+};
+
+/// The number of times the status of the transaction is queried once `send_tx` timed out, each
+/// query waiting for the RPC timeout itself.
+pub const DEFAULT_MAX_ATTEMPTS: usize = 10;
+
+/// A client calling the `sign` method of the MPC contract with a NEAR account and waiting for
+/// the signature, resumed by the MPC nodes some blocks later.
+#[derive(Debug, Clone)]
+pub struct MpcClient {
+    http: reqwest::Client,
+    rpc_url: String,
+    contract_id: AccountId,
+    max_attempts: usize,
+}
+
+impl MpcClient {
+    /// Creates a client of the MPC contract `contract_id`, like `v1.signer`, through the RPC at
+    /// `rpc_url`.
+    pub fn new(rpc_url: &str, contract_id: AccountId) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            rpc_url: rpc_url.to_string(),
+            contract_id,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    /// Sets the number of times the status of the transaction is queried before giving up.
+    pub const fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Requests the signature of `request` by the key derived for `signer_id`, signing the
+    /// transaction calling the MPC contract with the access key `signer_public_key` of the
+    /// account through `sign_transaction`, given the hash to sign.
+    pub async fn sign<F>(
+        &self,
+        signer_id: &AccountId,
+        signer_public_key: PublicKey,
+        sign_transaction: F,
+        request: &SignRequest,
+    ) -> Result<SignatureResponse, String>
+    where
+        F: FnOnce(&[u8; 32]) -> Signature,
+    {
+        self.request_signature(
+            signer_id,
+            signer_public_key,
+            sign_transaction,
+            chain_signatures::sign(request),
+        )
+        .await
+    }
+
+    /// Requests the signature of the EdDSA payload of `request` by the Ed25519 key derived for
+    /// `signer_id`, like [`Self::sign`].
+    pub async fn sign_eddsa<F>(
+        &self,
+        signer_id: &AccountId,
+        signer_public_key: PublicKey,
+        sign_transaction: F,
+        request: &SignRequestV2,
+    ) -> Result<EddsaSignatureResponse, String>
+    where
+        F: FnOnce(&[u8; 32]) -> Signature,
+    {
+        if !matches!(request.payload, Payload::Eddsa(_)) {
+            return Err("Request is not an EdDSA request".to_string());
+        }
+
+        self.request_signature(
+            signer_id,
+            signer_public_key,
+            sign_transaction,
+            chain_signatures::sign_v2(request),
+        )
+        .await
+    }
+
+    /// Calls `sign` with `call` and parses the signature of the final outcome.
+    async fn request_signature<F, T>(
+        &self,
+        signer_id: &AccountId,
+        signer_public_key: PublicKey,
+        sign_transaction: F,
+        call: FunctionCallAction,
+    ) -> Result<T, String>
+    where
+        F: FnOnce(&[u8; 32]) -> Signature,
+        T: DeserializeOwned,
+    {
+        let access_key = self.view_access_key(signer_id, &signer_public_key).await?;
+        let tx = NEARTransaction {
+            signer_id: signer_id.clone(),
+            signer_public_key,
+            nonce: next_nonce(access_key.nonce)?,
+            receiver_id: self.contract_id.clone(),
+            block_hash: access_key.block_hash,
+            actions: vec![Action::from(call)],
+        };
+        let signature = sign_transaction(&tx.signing_hash());
+        let signed_tx = Base64VecU8::new(tx.build_with_signature(&signature)).encode();
+
+        let mut result = self
+            .call(
+                "send_tx",
+                json!({ "signed_tx_base64": signed_tx, "wait_until": "FINAL" }),
+            )
+            .await;
+        for _ in 0..self.max_attempts {
+            match result {
+                Err(RpcError::Timeout) => {
+                    result = self
+                        .call(
+                            "tx",
+                            json!({
+                                "tx_hash": tx.tx_hash(),
+                                "sender_account_id": signer_id,
+                                "wait_until": "FINAL",
+                            }),
+                        )
+                        .await;
+                }
+                _ => break,
+            }
+        }
+
+        match result {
+            Ok(outcome) => parse_outcome(&outcome),
+            Err(RpcError::Timeout) => Err(format!(
+                "Transaction {} still pending after {} attempts",
+                tx.tx_hash(),
+                self.max_attempts
+            )),
+            Err(RpcError::Other(e)) => Err(e),
+        }
+    }
+
+    async fn view_access_key(
+        &self,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<AccessKeyView, String> {
+        let result = self
+            .call(
+                "query",
+                json!({
+                    "request_type": "view_access_key",
+                    "finality": "final",
+                    "account_id": account_id,
+                    "public_key": public_key,
+                }),
+            )
+            .await
+            .map_err(|e| match e {
+                RpcError::Timeout => "Access key query timed out".to_string(),
+                RpcError::Other(e) => e,
+            })?;
+
+        serde_json::from_value(result).map_err(|e| format!("Invalid access key: {e}"))
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": "signet-rs",
+            "method": method,
+            "params": params,
+        });
+
+        let response: Value = self
+            .http
+            .post(&self.rpc_url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| RpcError::Other(format!("RPC request failed: {e}")))?
+            .json()
+            .await
+            .map_err(|e| RpcError::Other(format!("Invalid RPC response: {e}")))?;
+
+        rpc_result(response)
+    }
+}
+
+/// A [`Signer`] requesting the signatures from the MPC network through a client, calling the
+/// MPC contract with the access key `public_key` of `account_id`.
+pub struct MpcSigner<F> {
+    client: MpcClient,
+    account_id: AccountId,
+    public_key: PublicKey,
+    sign_transaction: F,
+}
+
+impl<F> MpcSigner<F>
+where
+    F: Fn(&[u8; 32]) -> Signature,
+{
+    /// Creates a signer calling the MPC contract through `client`, signing the transactions
+    /// with `sign_transaction`, see [`MpcClient::sign`].
+    pub const fn new(
+        client: MpcClient,
+        account_id: AccountId,
+        public_key: PublicKey,
+        sign_transaction: F,
+    ) -> Self {
+        Self {
+            client,
+            account_id,
+            public_key,
+            sign_transaction,
+        }
+    }
+}
+
+impl<F> Signer for MpcSigner<F>
+where
+    F: Fn(&[u8; 32]) -> Signature,
+{
+    fn sign(
+        &self,
+        request: &SignRequest,
+    ) -> impl Future<Output = Result<SignatureResponse, String>> {
+        self.client.sign(
+            &self.account_id,
+            self.public_key,
+            &self.sign_transaction,
+            request,
+        )
+    }
+
+    fn sign_eddsa(
+        &self,
+        request: &SignRequestV2,
+    ) -> impl Future<Output = Result<EddsaSignatureResponse, String>> {
+        self.client.sign_eddsa(
+            &self.account_id,
+            self.public_key,
+            &self.sign_transaction,
+            request,
+        )
+    }
+}
+
+/// The access key of the account signing the transactions, and the block it was read at.
+#[derive(Deserialize)]
+struct AccessKeyView {
+    nonce: u64,
+    block_hash: BlockHash,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RpcError {
+    /// The transaction was not final before the RPC timed out.
+    Timeout,
+    Other(String),
+}
+
+/// Extracts the result of a JSON-RPC response, timeouts being told apart from other errors.
+fn rpc_result(mut response: Value) -> Result<Value, RpcError> {
+    if let Some(error) = response.get("error") {
+        let cause = error["cause"]["name"].as_str().unwrap_or_default();
+        if cause == "TIMEOUT_ERROR" || cause == "REQUEST_ROUTED" {
+            return Err(RpcError::Timeout);
+        }
+
+        return Err(RpcError::Other(format!("RPC error: {error}")));
+    }
+
+    match response.get_mut("result") {
+        Some(result) => Ok(result.take()),
+        None => Err(RpcError::Other("RPC response has no result".to_string())),
+    }
+}
+
+/// Parses the signature returned by `sign` from the final outcome of its transaction.
+fn parse_outcome<T: DeserializeOwned>(outcome: &Value) -> Result<T, String> {
+    let status = &outcome["status"];
+
+    if let Some(value) = status["SuccessValue"].as_str() {
+        let value = Base64VecU8::decode(value)?;
+        return serde_json::from_slice(&value.0)
+            .map_err(|e| format!("Invalid signature response: {e}"));
+    }
+    if !status["Failure"].is_null() {
+        return Err(format!("Sign transaction failed: {}", status["Failure"]));
+    }
+
+    Err(format!("Unexpected transaction status: {status}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_result() {
+        assert_eq!(
+            rpc_result(json!({ "jsonrpc": "2.0", "id": "1", "result": { "nonce": 1 } })),
+            Ok(json!({ "nonce": 1 }))
+        );
+        assert_eq!(
+            rpc_result(json!({
+                "jsonrpc": "2.0",
+                "id": "1",
+                "error": { "name": "HANDLER_ERROR", "cause": { "name": "TIMEOUT_ERROR" } }
+            })),
+            Err(RpcError::Timeout)
+        );
+        assert!(matches!(
+            rpc_result(json!({
+                "error": { "name": "HANDLER_ERROR", "cause": { "name": "INVALID_TRANSACTION" } }
+            })),
+            Err(RpcError::Other(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_outcome() {
+        let response = r#"{"big_r":{"affine_point":"02AB"},"s":{"scalar":"CD"},"recovery_id":1}"#;
+        let outcome = json!({
+            "final_execution_status": "FINAL",
+            "status": { "SuccessValue": Base64VecU8::new(response.as_bytes().to_vec()).encode() },
+        });
+
+        let signature: SignatureResponse = parse_outcome(&outcome).unwrap();
+        assert_eq!(signature.big_r.affine_point, "02AB");
+        assert_eq!(signature.s.scalar, "CD");
+        assert_eq!(signature.recovery_id, 1);
+
+        let failure = json!({ "status": { "Failure": { "ActionError": {} } } });
+        assert!(parse_outcome::<SignatureResponse>(&failure).is_err());
+
+        // EdDSA signatures are returned as their bytes
+        let response = format!(
+            r#"{{"scheme":"Ed25519","signature":[{}7]}}"#,
+            "7,".repeat(63)
+        );
+        let outcome = json!({
+            "status": { "SuccessValue": Base64VecU8::new(response.into_bytes()).encode() },
+        });
+        let signature: EddsaSignatureResponse = parse_outcome(&outcome).unwrap();
+        assert_eq!(signature.signature, [7; 64]);
+    }
+
+    #[test]
+    fn test_access_key_view() {
+        let access_key: AccessKeyView = serde_json::from_value(json!({
+            "block_hash": "EPnLgE7iEq9s7yTkos96M3cWymH5avBAPm3qx3NXqR8H",
+            "block_height": 100,
+            "nonce": 7,
+            "permission": "FullAccess",
+        }))
+        .unwrap();
+        assert_eq!(access_key.nonce, 7);
+        assert_eq!(
+            access_key.block_hash.to_string(),
+            "EPnLgE7iEq9s7yTkos96M3cWymH5avBAPm3qx3NXqR8H"
+        );
+    }
+}
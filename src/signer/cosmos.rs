@@ -0,0 +1,93 @@
+//! Signing of Cosmos SDK transactions with the signatures returned by the MPC signer.
+use alloc::vec::Vec;
+
+use super::{types::SignatureResponse, Error};
+use crate::cosmos::{types::Signature, CosmosTransaction};
+
+/// The payload to request the signature of for `tx`, the sha256 of its `SignDoc`.
+pub fn cosmos_payload(tx: &CosmosTransaction) -> [u8; 32] {
+    tx.signing_hash()
+}
+
+/// The `TxRaw`, ready to be broadcast, of `tx` signed by `response`, the signature of its
+/// [`cosmos_payload`].
+pub fn sign_cosmos(tx: &CosmosTransaction, response: &SignatureResponse) -> Result<Vec<u8>, Error> {
+    Ok(tx.build_with_signature(&response.to_cosmos_signature()?))
+}
+
+impl SignatureResponse {
+    /// The `r || s` signature of a Cosmos SDK transaction.
+    ///
+    /// S is normalized to low-S, as the Cosmos SDK rejects high-S signatures.
+    pub fn to_cosmos_signature(&self) -> Result<Signature, Error> {
+        let (r, s, _) = self.normalized_rs()?;
+
+        let mut signature = [0; 64];
+        signature[..32].copy_from_slice(&r);
+        signature[32..].copy_from_slice(&s);
+
+        Ok(Signature(signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosmos::{
+        bank::MsgSend,
+        types::{Coin, Fee, Msg, PublicKey, SignMode},
+    };
+    use k256::ecdsa::{
+        signature::hazmat::{PrehashSigner, PrehashVerifier},
+        RecoveryId, Signature as EcdsaSignature, SigningKey,
+    };
+
+    #[test]
+    fn test_sign_cosmos() {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let public_key = signing_key.verifying_key().to_encoded_point(true);
+        let msg = MsgSend {
+            from_address: "cosmos1from".to_string(),
+            to_address: "cosmos1to".to_string(),
+            amount: vec![Coin::new(1_000, "uatom")],
+        };
+        let tx = CosmosTransaction {
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 1,
+            sequence: 0,
+            public_key: PublicKey::from_slice(public_key.as_bytes()).unwrap(),
+            messages: vec![msg.to_any()],
+            memo: String::new(),
+            timeout_height: 0,
+            fee: Fee::new(Coin::new(500, "uatom"), 100_000),
+            sign_mode: SignMode::Direct,
+            amino_messages: vec![msg.to_amino()],
+        };
+
+        let payload = cosmos_payload(&tx);
+        let (signature, recovery_id): (EcdsaSignature, RecoveryId) =
+            signing_key.sign_prehash(&payload).unwrap();
+
+        // The high-S form of the signature is normalized
+        let high_s = EcdsaSignature::from_scalars(signature.r(), -*signature.s()).unwrap();
+        for response in [
+            SignatureResponse::from((signature, recovery_id)),
+            SignatureResponse::from((high_s, recovery_id)),
+        ] {
+            let cosmos_signature = response.to_cosmos_signature().unwrap();
+            assert_eq!(cosmos_signature.0, signature.to_bytes().as_slice());
+
+            let signed = EcdsaSignature::from_slice(&cosmos_signature.0).unwrap();
+            assert!(signed.normalize_s().is_none());
+            signing_key
+                .verifying_key()
+                .verify_prehash(&payload, &signed)
+                .unwrap();
+
+            assert_eq!(
+                sign_cosmos(&tx, &response).unwrap(),
+                tx.build_with_signature(&cosmos_signature)
+            );
+        }
+    }
+}
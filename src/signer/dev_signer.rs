@@ -0,0 +1,181 @@
+//! A local signer deriving its keys like the MPC network, enabled by the `testing` feature.
+//!
+//! ###### Example:
+//!
+//! ```rust,ignore
+//! let signer = DevSigner::new([7; 32], "alice.near")?;
+//! let address = derive_evm_address(&signer.root_public_key(), "alice.near", "ethereum-1")?;
+//! let response = signer.sign(&request).await?;
+//! ```
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::future::{self, Future};
+
+use curve25519_dalek::{constants::ED25519_BASEPOINT_POINT, Scalar};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey};
+use sha2::{Digest, Sha512};
+
+use super::{
+    derive_ed25519_epsilon, derive_epsilon,
+    types::{
+        DomainId, EddsaSignatureResponse, Payload, SignRequest, SignRequestV2, SignatureResponse,
+    },
+    Signer,
+};
+
+/// A signer holding the root secret keys the MPC network splits between its nodes, signing for
+/// `predecessor_id` as if it called the signer contract.
+///
+/// Its keys are derived from its root public keys exactly like the keys of the MPC network are
+/// derived from the keys of the contract, so addresses computed with the derivation helpers
+/// hold the funds it signs for. Only meant for tests.
+#[derive(Debug, Clone)]
+pub struct DevSigner {
+    root_secret_key: k256::SecretKey,
+    root_ed25519_secret_key: Scalar,
+    predecessor_id: String,
+}
+
+impl DevSigner {
+    /// Creates a signer whose root secret keys are read from `seed`, signing for
+    /// `predecessor_id`.
+    pub fn new(seed: [u8; 32], predecessor_id: &str) -> Result<Self, String> {
+        let root_secret_key = k256::SecretKey::from_slice(&seed)
+            .map_err(|_| "Seed is not a secp256k1 secret key".to_string())?;
+
+        Ok(Self {
+            root_secret_key,
+            root_ed25519_secret_key: Scalar::from_bytes_mod_order(seed),
+            predecessor_id: predecessor_id.to_string(),
+        })
+    }
+
+    /// The secp256k1 root public key, standing for the public key of the signer contract.
+    pub fn root_public_key(&self) -> k256::PublicKey {
+        self.root_secret_key.public_key()
+    }
+
+    /// The compressed Ed25519 root public key, standing for the public key of the EdDSA domain
+    /// of the signer contract.
+    pub fn root_ed25519_public_key(&self) -> [u8; 32] {
+        (ED25519_BASEPOINT_POINT * self.root_ed25519_secret_key)
+            .compress()
+            .to_bytes()
+    }
+
+    /// Signs `message` with the Ed25519 key of `path`, as the EdDSA domain of the signer
+    /// contract does.
+    fn sign_ed25519(&self, path: &str, message: &[u8]) -> EddsaSignatureResponse {
+        let secret =
+            self.root_ed25519_secret_key + derive_ed25519_epsilon(&self.predecessor_id, path);
+        let public_key = (ED25519_BASEPOINT_POINT * secret).compress();
+
+        // The nonce is derived from the secret and the message, as RFC 8032 does from the
+        // prefix of the expanded secret key
+        let nonce = Scalar::from_hash(
+            Sha512::new()
+                .chain_update(b"signet-rs dev signer nonce")
+                .chain_update(secret.as_bytes())
+                .chain_update(message),
+        );
+        let big_r = (ED25519_BASEPOINT_POINT * nonce).compress();
+        let challenge = Scalar::from_hash(
+            Sha512::new()
+                .chain_update(big_r.as_bytes())
+                .chain_update(public_key.as_bytes())
+                .chain_update(message),
+        );
+
+        let mut signature = [0; 64];
+        signature[..32].copy_from_slice(big_r.as_bytes());
+        signature[32..].copy_from_slice((nonce + challenge * secret).as_bytes());
+        EddsaSignatureResponse::from(signature)
+    }
+
+    fn sign_ecdsa(&self, request: &SignRequest) -> Result<SignatureResponse, String> {
+        let epsilon = derive_epsilon(&self.predecessor_id, &request.path)?;
+        let secret = *self.root_secret_key.to_nonzero_scalar() + epsilon;
+        let signing_key = SigningKey::from_bytes(&secret.to_bytes())
+            .map_err(|_| "Derived secret key is zero".to_string())?;
+
+        let (signature, recovery_id): (Signature, RecoveryId) = signing_key
+            .sign_prehash_recoverable(&request.payload)
+            .map_err(|e| format!("Failed to sign payload: {e}"))?;
+
+        Ok(SignatureResponse::from((signature, recovery_id)))
+    }
+}
+
+impl Signer for DevSigner {
+    fn sign(
+        &self,
+        request: &SignRequest,
+    ) -> impl Future<Output = Result<SignatureResponse, String>> {
+        future::ready(self.sign_ecdsa(request))
+    }
+
+    fn sign_eddsa(
+        &self,
+        request: &SignRequestV2,
+    ) -> impl Future<Output = Result<EddsaSignatureResponse, String>> {
+        future::ready(match &request.payload {
+            Payload::Eddsa(message) if request.domain_id == DomainId::Ed25519 => {
+                Ok(self.sign_ed25519(&request.path, message))
+            }
+            _ => Err("Request is not an EdDSA request of the Ed25519 domain".to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{derive_ed25519_public_key, derive_public_key, types::KeyVersion, verify};
+    use ed25519_dalek::{Signature as Ed25519Signature, VerifyingKey};
+
+    #[tokio::test]
+    async fn test_sign() {
+        let signer = DevSigner::new([7; 32], "alice.near").unwrap();
+        let request = SignRequest {
+            payload: [9; 32],
+            path: "ethereum-1".to_string(),
+            key_version: KeyVersion::V0,
+        };
+
+        let response = signer.sign(&request).await.unwrap();
+        let public_key =
+            derive_public_key(&signer.root_public_key(), "alice.near", "ethereum-1").unwrap();
+        assert_eq!(verify(&response, &request.payload, &public_key), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_sign_eddsa() {
+        let signer = DevSigner::new([7; 32], "alice.near").unwrap();
+        let message = [5; 64];
+        let request = SignRequestV2 {
+            path: "solana-1".to_string(),
+            payload: Payload::eddsa(message.to_vec()).unwrap(),
+            domain_id: DomainId::Ed25519,
+        };
+
+        let response = signer.sign_eddsa(&request).await.unwrap();
+        let public_key =
+            derive_ed25519_public_key(&signer.root_ed25519_public_key(), "alice.near", "solana-1")
+                .unwrap();
+
+        let verifying_key = VerifyingKey::from_bytes(&public_key).unwrap();
+        let signature = Ed25519Signature::from_bytes(&response.signature);
+        assert!(verifying_key.verify_strict(&message, &signature).is_ok());
+        assert!(verifying_key.verify_strict(b"other", &signature).is_err());
+
+        // ECDSA requests are signed with sign
+        let request = SignRequestV2 {
+            payload: Payload::from([9; 32]),
+            domain_id: DomainId::Secp256k1,
+            ..request
+        };
+        assert!(signer.sign_eddsa(&request).await.is_err());
+    }
+}
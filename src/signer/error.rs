@@ -0,0 +1,47 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Errors returned when decoding the values returned by the MPC signer, or signing a
+/// transaction with them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A value is not hex-encoded.
+    InvalidHex { field: &'static str },
+    /// A value does not have the expected number of bytes.
+    InvalidLength {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// Big R is not a compressed point of the curve.
+    InvalidPoint,
+    /// s is not a scalar of the curve, at least its order.
+    InvalidScalar,
+    /// Big R of a Schnorr signature has an odd y, BIP-340 only committing to even ones.
+    OddBigR,
+    /// The transaction to sign cannot be encoded, like an XRP Ledger fee not in XRP.
+    InvalidTransaction(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHex { field } => write!(f, "Invalid {field}: not hex-encoded"),
+            Self::InvalidLength {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Invalid {field}: expected {expected} bytes, got {actual}"
+            ),
+            Self::InvalidPoint => write!(f, "Big R is not a compressed point of the curve"),
+            Self::InvalidScalar => write!(f, "s is not a scalar of the curve"),
+            Self::OddBigR => write!(f, "Big R of a Schnorr signature must have an even y"),
+            Self::InvalidTransaction(reason) => write!(f, "Invalid transaction: {reason}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,78 @@
+//! Signing of EVM transactions with the signatures returned by the MPC signer.
+use alloc::vec::Vec;
+
+use sha3::{Digest, Keccak256};
+
+use super::{types::SignatureResponse, Error};
+use crate::evm::{types::Signature, EVMTransaction};
+
+/// The payload to request the signature of for `tx`, the keccak256 of its signing encoding.
+pub fn evm_payload(tx: &EVMTransaction) -> [u8; 32] {
+    Keccak256::digest(tx.build_for_signing()).into()
+}
+
+/// The raw signed transaction, ready to be broadcast with `eth_sendRawTransaction`, of `tx`
+/// signed by `response`, the signature of its [`evm_payload`].
+pub fn sign_evm(tx: &EVMTransaction, response: &SignatureResponse) -> Result<Vec<u8>, Error> {
+    Ok(tx.build_with_signature(&response.to_evm_signature()?))
+}
+
+impl SignatureResponse {
+    /// The signature of an EIP-1559 transaction, `v` being the y parity of big R.
+    ///
+    /// S is normalized to low-S, the y parity flipping with it, as EIP-2 rejects high-S
+    /// signatures.
+    pub fn to_evm_signature(&self) -> Result<Signature, Error> {
+        let (r, s, y_parity) = self.normalized_rs()?;
+
+        Ok(Signature {
+            v: u64::from(y_parity),
+            r: trim_leading_zeros(&r),
+            s: trim_leading_zeros(&s),
+        })
+    }
+}
+
+/// The minimal big-endian encoding of an integer, as RLP encodes them.
+fn trim_leading_zeros(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    bytes[start..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm::utils::parse_eth_address;
+    use crate::signer::{evm_address, types::low_and_high_s_responses};
+    use alloy::{consensus::TxEnvelope, eips::eip2718::Decodable2718};
+    use k256::ecdsa::{signature::hazmat::PrehashSigner, RecoveryId, Signature, SigningKey};
+
+    #[test]
+    fn test_sign_evm() {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let address = evm_address(&k256::PublicKey::from(signing_key.verifying_key()));
+        let tx = EVMTransaction {
+            chain_id: 1,
+            nonce: 3,
+            to: Some(parse_eth_address(
+                "d8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+            )),
+            value: 10_000_000_000_000_000,
+            input: Vec::new(),
+            gas_limit: 21_000,
+            max_fee_per_gas: 20_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            access_list: Vec::new(),
+        };
+
+        let (signature, recovery_id): (Signature, RecoveryId) =
+            signing_key.sign_prehash(&evm_payload(&tx)).unwrap();
+        // The signer does not normalize s, both forms give the same transaction
+        let [low_s, high_s] = low_and_high_s_responses(signature, recovery_id);
+        let raw = sign_evm(&tx, &low_s).unwrap();
+        assert_eq!(sign_evm(&tx, &high_s).unwrap(), raw);
+
+        let envelope = TxEnvelope::decode_2718(&mut raw.as_slice()).unwrap();
+        assert_eq!(envelope.recover_signer().unwrap().0, address);
+    }
+}
@@ -0,0 +1,446 @@
+//! Derivation of the keys the MPC network signs with, one per account and derivation path.
+//!
+//! The child key of `(account_id, path)` is the root key of the MPC network tweaked by the
+//! epsilon `sha3_256("near-mpc-recovery v0.1.0 epsilon derivation:{account_id},{path}")`, the
+//! same derivation as the signer contract.
+//!
+//! The secp256k1 keys are derived with the `k256` feature, and the Ed25519 keys of the EdDSA
+//! domain, tweaked by the same epsilon reduced modulo the order of the curve, with the `ed25519`
+//! feature.
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+#[cfg(feature = "ed25519")]
+use curve25519_dalek::{constants::ED25519_BASEPOINT_POINT, edwards::CompressedEdwardsY};
+#[cfg(feature = "k256")]
+use k256::elliptic_curve::{sec1::ToEncodedPoint, PrimeField};
+#[cfg(feature = "k256")]
+use sha3::Keccak256;
+use sha3::{Digest, Sha3_256};
+
+#[cfg(all(feature = "aptos", feature = "ed25519"))]
+use crate::aptos::types::AccountAddress as AptosAccountAddress;
+#[cfg(feature = "bitcoin")]
+use crate::bitcoin::{address::Address, keys::PublicKey, types::Network};
+#[cfg(all(feature = "cosmos", feature = "k256"))]
+use crate::cosmos::types::PublicKey as CosmosPublicKey;
+#[cfg(all(feature = "substrate", feature = "k256"))]
+use crate::substrate::types::AccountId as SubstrateAccountId;
+#[cfg(all(feature = "tron", feature = "k256"))]
+use crate::tron::types::Address as TronAddress;
+#[cfg(all(feature = "xrpl", feature = "k256"))]
+use crate::xrpl::types::{AccountId as XrplAccountId, PublicKey as XrplPublicKey};
+
+/// Prefix of the string hashed into the epsilon of a derived key.
+pub const EPSILON_DERIVATION_PREFIX: &str = "near-mpc-recovery v0.1.0 epsilon derivation:";
+
+/// The hash the epsilon of `path` for `account_id` is read from, on either curve.
+fn epsilon_hash(account_id: &str, path: &str) -> [u8; 32] {
+    let derivation_path = format!("{}{},{}", EPSILON_DERIVATION_PREFIX, account_id, path);
+    Sha3_256::digest(derivation_path.as_bytes()).into()
+}
+
+/// The epsilon tweaking the root key into the key of `path` for `account_id`, the account
+/// calling the signer contract.
+#[cfg(feature = "k256")]
+pub fn derive_epsilon(account_id: &str, path: &str) -> Result<k256::Scalar, String> {
+    let hash = epsilon_hash(account_id, path);
+
+    Option::from(k256::Scalar::from_repr(hash.into()))
+        .ok_or_else(|| "Derived epsilon value falls outside of the field".to_string())
+}
+
+/// The public key of `path` for `account_id`, derived from `root_public_key`, the public key of
+/// the signer contract.
+#[cfg(feature = "k256")]
+pub fn derive_public_key(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+) -> Result<k256::PublicKey, String> {
+    let epsilon = derive_epsilon(account_id, path)?;
+    let point = k256::ProjectivePoint::GENERATOR * epsilon + root_public_key.to_projective();
+
+    k256::PublicKey::from_affine(point.to_affine())
+        .map_err(|_| "Derived public key is the identity".to_string())
+}
+
+/// The Ethereum address of `public_key`, the last 20 bytes of the keccak256 of its uncompressed
+/// encoding.
+#[cfg(feature = "k256")]
+pub fn evm_address(public_key: &k256::PublicKey) -> [u8; 20] {
+    let point = public_key.to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+
+    hash[12..]
+        .try_into()
+        .expect("keccak256 hashes are 32 bytes")
+}
+
+/// The Ethereum address controlled by `account_id` through the key of `path`, derived from
+/// `root_public_key`, the public key of the signer contract.
+#[cfg(feature = "k256")]
+pub fn derive_evm_address(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+) -> Result<[u8; 20], String> {
+    derive_public_key(root_public_key, account_id, path).map(|key| evm_address(&key))
+}
+
+/// The compressed Bitcoin public key of `path` for `account_id`, derived from
+/// `root_public_key`, the public key of the signer contract.
+#[cfg(feature = "bitcoin")]
+pub fn derive_bitcoin_public_key(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+) -> Result<PublicKey, String> {
+    let key = derive_public_key(root_public_key, account_id, path)?;
+    PublicKey::from_slice(key.to_encoded_point(true).as_bytes())
+}
+
+/// The P2WPKH address controlled by `account_id` through the key of `path` on `network`.
+#[cfg(feature = "bitcoin")]
+pub fn derive_p2wpkh_address(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+    network: Network,
+) -> Result<Address, String> {
+    derive_bitcoin_public_key(root_public_key, account_id, path)?.p2wpkh_address(network)
+}
+
+/// The P2TR address, only spendable by its key path, controlled by `account_id` through the key
+/// of `path` on `network`.
+#[cfg(feature = "bitcoin")]
+pub fn derive_p2tr_address(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+    network: Network,
+) -> Result<Address, String> {
+    derive_bitcoin_public_key(root_public_key, account_id, path)?.p2tr_address(network)
+}
+
+/// The compressed public key of `path` for `account_id` on Cosmos SDK chains, derived from
+/// `root_public_key`, the public key of the signer contract.
+#[cfg(all(feature = "cosmos", feature = "k256"))]
+pub fn derive_cosmos_public_key(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+) -> Result<CosmosPublicKey, String> {
+    let key = derive_public_key(root_public_key, account_id, path)?;
+    CosmosPublicKey::from_slice(key.to_encoded_point(true).as_bytes())
+}
+
+/// The bech32 address controlled by `account_id` through the key of `path` on the Cosmos SDK
+/// chain of address prefix `hrp`, like `cosmos` or `osmo`.
+#[cfg(all(feature = "cosmos", feature = "k256"))]
+pub fn derive_cosmos_address(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+    hrp: &str,
+) -> Result<String, String> {
+    derive_cosmos_public_key(root_public_key, account_id, path)?.address(hrp)
+}
+
+/// The compressed public key of `path` for `account_id` on the XRP Ledger, the `SigningPubKey`
+/// of its transactions, derived from `root_public_key`, the public key of the signer contract.
+#[cfg(all(feature = "xrpl", feature = "k256"))]
+pub fn derive_xrpl_public_key(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+) -> Result<XrplPublicKey, String> {
+    let key = derive_public_key(root_public_key, account_id, path)?;
+    XrplPublicKey::from_slice(key.to_encoded_point(true).as_bytes())
+}
+
+/// The XRP Ledger account controlled by `account_id` through the key of `path`, displayed as
+/// its classic address.
+#[cfg(all(feature = "xrpl", feature = "k256"))]
+pub fn derive_xrpl_address(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+) -> Result<XrplAccountId, String> {
+    Ok(derive_xrpl_public_key(root_public_key, account_id, path)?.account_id())
+}
+
+/// The Substrate account controlled by `account_id` through the key of `path`, the blake2 256
+/// of the compressed public key, signing extrinsics with ECDSA.
+#[cfg(all(feature = "substrate", feature = "k256"))]
+pub fn derive_substrate_account_id(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+) -> Result<SubstrateAccountId, String> {
+    let key = derive_public_key(root_public_key, account_id, path)?;
+    let key = key.to_encoded_point(true);
+
+    Ok(SubstrateAccountId::from_ecdsa_public_key(
+        key.as_bytes()
+            .try_into()
+            .expect("compressed public keys are 33 bytes"),
+    ))
+}
+
+/// The Tron account controlled by `account_id` through the key of `path`, the same 20 bytes as
+/// its Ethereum address.
+#[cfg(all(feature = "tron", feature = "k256"))]
+pub fn derive_tron_address(
+    root_public_key: &k256::PublicKey,
+    account_id: &str,
+    path: &str,
+) -> Result<TronAddress, String> {
+    derive_evm_address(root_public_key, account_id, path).map(TronAddress)
+}
+
+/// The epsilon tweaking the Ed25519 root key into the key of `path` for `account_id`, the hash
+/// of [`derive_epsilon`] reduced modulo the order of the curve.
+#[cfg(feature = "ed25519")]
+pub fn derive_ed25519_epsilon(account_id: &str, path: &str) -> curve25519_dalek::Scalar {
+    curve25519_dalek::Scalar::from_bytes_mod_order(epsilon_hash(account_id, path))
+}
+
+/// The Ed25519 public key of `path` for `account_id`, derived from `root_public_key`, the
+/// compressed public key of the EdDSA domain of the signer contract. Its bytes are the address
+/// of the key on chains like Solana.
+#[cfg(feature = "ed25519")]
+pub fn derive_ed25519_public_key(
+    root_public_key: &[u8; 32],
+    account_id: &str,
+    path: &str,
+) -> Result<[u8; 32], String> {
+    let root = CompressedEdwardsY(*root_public_key)
+        .decompress()
+        .ok_or_else(|| "Invalid Ed25519 root public key".to_string())?;
+    let epsilon = derive_ed25519_epsilon(account_id, path);
+
+    Ok((root + ED25519_BASEPOINT_POINT * epsilon)
+        .compress()
+        .to_bytes())
+}
+
+/// The Aptos account created for the Ed25519 key of `path` for `account_id`, the sha3-256 of
+/// the key and its scheme.
+#[cfg(all(feature = "aptos", feature = "ed25519"))]
+pub fn derive_aptos_address(
+    root_public_key: &[u8; 32],
+    account_id: &str,
+    path: &str,
+) -> Result<AptosAccountAddress, String> {
+    let key = derive_ed25519_public_key(root_public_key, account_id, path)?;
+    Ok(AptosAccountAddress::from_ed25519_public_key(&key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "k256")]
+    fn test_derive_epsilon() {
+        let epsilon = derive_epsilon("alice.near", "ethereum-1").unwrap();
+        assert_eq!(
+            hex::encode(epsilon.to_repr()),
+            "e4c9cc34fcc5f69fde83609d2f7739b541faa56c74cee872b220bdc3a85d1d4d"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "k256")]
+    fn test_derive_public_key() {
+        let root_secret_key = k256::SecretKey::from_slice(&[7; 32]).unwrap();
+        let root_public_key = root_secret_key.public_key();
+
+        let derived = derive_public_key(&root_public_key, "alice.near", "ethereum-1").unwrap();
+
+        let epsilon = derive_epsilon("alice.near", "ethereum-1").unwrap();
+        let derived_secret_key = *root_secret_key.to_nonzero_scalar() + epsilon;
+        let expected = k256::SecretKey::from_bytes(&derived_secret_key.to_repr())
+            .unwrap()
+            .public_key();
+        assert_eq!(derived, expected);
+
+        assert_ne!(
+            derive_public_key(&root_public_key, "alice.near", "ethereum-2").unwrap(),
+            derived
+        );
+        assert_ne!(
+            derive_public_key(&root_public_key, "bob.near", "ethereum-1").unwrap(),
+            derived
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "k256")]
+    fn test_evm_address() {
+        let mut secret_key = [0; 32];
+        secret_key[31] = 1;
+        let public_key = k256::SecretKey::from_slice(&secret_key)
+            .unwrap()
+            .public_key();
+        assert_eq!(
+            hex::encode(evm_address(&public_key)),
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+
+        let derived = derive_evm_address(&public_key, "alice.near", "ethereum-1").unwrap();
+        let derived_key = derive_public_key(&public_key, "alice.near", "ethereum-1").unwrap();
+        assert_eq!(derived, evm_address(&derived_key));
+    }
+
+    #[test]
+    #[cfg(feature = "bitcoin")]
+    fn test_derive_bitcoin_addresses() {
+        let root_public_key = k256::SecretKey::from_slice(&[7; 32]).unwrap().public_key();
+        let derived_key = derive_public_key(&root_public_key, "alice.near", "bitcoin-1").unwrap();
+        let public_key =
+            PublicKey::from_slice(derived_key.to_encoded_point(true).as_bytes()).unwrap();
+        assert_eq!(
+            derive_bitcoin_public_key(&root_public_key, "alice.near", "bitcoin-1").unwrap(),
+            public_key
+        );
+
+        let p2wpkh = derive_p2wpkh_address(
+            &root_public_key,
+            "alice.near",
+            "bitcoin-1",
+            Network::Bitcoin,
+        )
+        .unwrap();
+        assert_eq!(p2wpkh, public_key.p2wpkh_address(Network::Bitcoin).unwrap());
+        assert!(p2wpkh.to_string().starts_with("bc1q"));
+
+        let p2tr = derive_p2tr_address(
+            &root_public_key,
+            "alice.near",
+            "bitcoin-1",
+            Network::Testnet,
+        )
+        .unwrap();
+        assert_eq!(p2tr, public_key.p2tr_address(Network::Testnet).unwrap());
+        assert!(p2tr.to_string().starts_with("tb1p"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "cosmos", feature = "k256"))]
+    fn test_derive_cosmos_address() {
+        let root_public_key = k256::SecretKey::from_slice(&[7; 32]).unwrap().public_key();
+        let derived_key = derive_public_key(&root_public_key, "alice.near", "cosmos-1").unwrap();
+        let public_key =
+            CosmosPublicKey::from_slice(derived_key.to_encoded_point(true).as_bytes()).unwrap();
+        assert_eq!(
+            derive_cosmos_public_key(&root_public_key, "alice.near", "cosmos-1").unwrap(),
+            public_key
+        );
+
+        let address =
+            derive_cosmos_address(&root_public_key, "alice.near", "cosmos-1", "cosmos").unwrap();
+        assert_eq!(address, public_key.address("cosmos").unwrap());
+        assert!(address.starts_with("cosmos1"));
+        assert!(
+            derive_cosmos_address(&root_public_key, "alice.near", "cosmos-1", "osmo")
+                .unwrap()
+                .starts_with("osmo1")
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "xrpl", feature = "k256"))]
+    fn test_derive_xrpl_address() {
+        let root_public_key = k256::SecretKey::from_slice(&[7; 32]).unwrap().public_key();
+        let derived_key = derive_public_key(&root_public_key, "alice.near", "xrpl-1").unwrap();
+        let public_key =
+            XrplPublicKey::from_slice(derived_key.to_encoded_point(true).as_bytes()).unwrap();
+        assert_eq!(
+            derive_xrpl_public_key(&root_public_key, "alice.near", "xrpl-1").unwrap(),
+            public_key
+        );
+
+        let address = derive_xrpl_address(&root_public_key, "alice.near", "xrpl-1").unwrap();
+        assert_eq!(address, public_key.account_id());
+        assert!(address.to_string().starts_with('r'));
+    }
+
+    #[test]
+    #[cfg(all(feature = "substrate", feature = "k256"))]
+    fn test_derive_substrate_account_id() {
+        let root_public_key = k256::SecretKey::from_slice(&[7; 32]).unwrap().public_key();
+        let derived_key = derive_public_key(&root_public_key, "alice.near", "polkadot-1").unwrap();
+
+        let account =
+            derive_substrate_account_id(&root_public_key, "alice.near", "polkadot-1").unwrap();
+        assert_eq!(
+            account.0,
+            crate::substrate::utils::blake2_256(derived_key.to_encoded_point(true).as_bytes())
+        );
+        assert!(account.to_ss58(0).starts_with('1'));
+    }
+
+    #[test]
+    #[cfg(all(feature = "tron", feature = "k256"))]
+    fn test_derive_tron_address() {
+        let root_public_key = k256::SecretKey::from_slice(&[7; 32]).unwrap().public_key();
+        let derived_key = derive_public_key(&root_public_key, "alice.near", "tron-1").unwrap();
+
+        let address = derive_tron_address(&root_public_key, "alice.near", "tron-1").unwrap();
+        assert_eq!(address, TronAddress(evm_address(&derived_key)));
+        assert!(address.to_string().starts_with('T'));
+    }
+
+    #[test]
+    #[cfg(feature = "ed25519")]
+    fn test_derive_ed25519_public_key() {
+        let root_secret_key = curve25519_dalek::Scalar::from_bytes_mod_order([7; 32]);
+        let root_public_key = (ED25519_BASEPOINT_POINT * root_secret_key)
+            .compress()
+            .to_bytes();
+
+        let derived =
+            derive_ed25519_public_key(&root_public_key, "alice.near", "solana-1").unwrap();
+
+        let epsilon = derive_ed25519_epsilon("alice.near", "solana-1");
+        let expected = (ED25519_BASEPOINT_POINT * (root_secret_key + epsilon))
+            .compress()
+            .to_bytes();
+        assert_eq!(derived, expected);
+
+        assert_ne!(
+            derive_ed25519_public_key(&root_public_key, "alice.near", "solana-2").unwrap(),
+            derived
+        );
+        assert_ne!(
+            derive_ed25519_public_key(&root_public_key, "bob.near", "solana-1").unwrap(),
+            derived
+        );
+
+        // Not the y coordinate of a point of the curve
+        let mut invalid = [0; 32];
+        invalid[0] = 2;
+        assert!(derive_ed25519_public_key(&invalid, "alice.near", "solana-1").is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "aptos", feature = "ed25519"))]
+    fn test_derive_aptos_address() {
+        let root_public_key = (ED25519_BASEPOINT_POINT * curve25519_dalek::Scalar::from(7u8))
+            .compress()
+            .to_bytes();
+        let derived_key =
+            derive_ed25519_public_key(&root_public_key, "alice.near", "aptos-1").unwrap();
+
+        let address = derive_aptos_address(&root_public_key, "alice.near", "aptos-1").unwrap();
+        assert_eq!(
+            address,
+            AptosAccountAddress::from_ed25519_public_key(&derived_key)
+        );
+        assert!(derive_aptos_address(&[2; 32], "alice.near", "aptos-1").is_err());
+    }
+}
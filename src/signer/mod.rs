@@ -1 +1,73 @@
+#[cfg(feature = "bitcoin")]
+mod bitcoin;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(all(feature = "cosmos", feature = "k256"))]
+mod cosmos;
+#[cfg(feature = "testing")]
+mod dev_signer;
+mod error;
+#[cfg(all(feature = "evm", feature = "k256"))]
+mod evm;
+#[cfg(any(feature = "k256", feature = "ed25519"))]
+mod kdf;
+mod network;
+mod sign_request_builder;
+#[cfg(all(feature = "substrate", feature = "k256"))]
+mod substrate;
+mod traits;
+#[cfg(all(feature = "tron", feature = "k256"))]
+mod tron;
 pub mod types;
+#[cfg(feature = "k256")]
+mod verify;
+#[cfg(all(feature = "xrpl", feature = "k256"))]
+mod xrpl;
+
+/// Errors decoding the values returned by the signer
+pub use error::Error;
+/// MPC contract deployments
+pub use network::{
+    SignerNetwork, MAINNET_ROOT_PUBLIC_KEY, MAINNET_SIGNER_ID, TESTNET_ROOT_PUBLIC_KEY,
+    TESTNET_SIGNER_ID,
+};
+/// Sign request builder
+pub use sign_request_builder::SignRequestBuilder;
+/// Signers of sign requests
+pub use traits::Signer;
+
+#[cfg(feature = "testing")]
+pub use dev_signer::DevSigner;
+
+#[cfg(feature = "bitcoin")]
+pub use bitcoin::{bitcoin_payloads, sign_bitcoin, BitcoinSignatureResponse};
+#[cfg(all(feature = "cosmos", feature = "k256"))]
+pub use cosmos::{cosmos_payload, sign_cosmos};
+#[cfg(all(feature = "evm", feature = "k256"))]
+pub use evm::{evm_payload, sign_evm};
+#[cfg(all(feature = "aptos", feature = "ed25519"))]
+pub use kdf::derive_aptos_address;
+#[cfg(all(feature = "substrate", feature = "k256"))]
+pub use kdf::derive_substrate_account_id;
+#[cfg(all(feature = "tron", feature = "k256"))]
+pub use kdf::derive_tron_address;
+#[cfg(any(feature = "k256", feature = "ed25519"))]
+pub use kdf::EPSILON_DERIVATION_PREFIX;
+#[cfg(feature = "bitcoin")]
+pub use kdf::{derive_bitcoin_public_key, derive_p2tr_address, derive_p2wpkh_address};
+#[cfg(all(feature = "cosmos", feature = "k256"))]
+pub use kdf::{derive_cosmos_address, derive_cosmos_public_key};
+#[cfg(feature = "ed25519")]
+pub use kdf::{derive_ed25519_epsilon, derive_ed25519_public_key};
+#[cfg(feature = "k256")]
+pub use kdf::{derive_epsilon, derive_evm_address, derive_public_key, evm_address};
+#[cfg(all(feature = "xrpl", feature = "k256"))]
+pub use kdf::{derive_xrpl_address, derive_xrpl_public_key};
+#[cfg(all(feature = "substrate", feature = "k256"))]
+pub use substrate::{sign_substrate, substrate_payload};
+#[cfg(all(feature = "tron", feature = "k256"))]
+pub use tron::{sign_tron, tron_payload};
+#[cfg(feature = "k256")]
+pub use verify::{recover_evm_recovery_id, recover_recovery_id, verify};
+#[cfg(all(feature = "xrpl", feature = "k256"))]
+pub use xrpl::{sign_xrpl, xrpl_payload};
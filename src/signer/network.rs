@@ -0,0 +1,105 @@
+//! The deployments of the MPC signer contract and the root public keys their keys are derived
+//! from.
+
+/// The account of the MPC contract on mainnet.
+pub const MAINNET_SIGNER_ID: &str = "v1.signer";
+/// The account of the MPC contract on testnet.
+pub const TESTNET_SIGNER_ID: &str = "v1.signer-prod.testnet";
+
+/// The secp256k1 root public key of the MPC contract on mainnet, as returned by its `public_key`
+/// view method.
+pub const MAINNET_ROOT_PUBLIC_KEY: &str = "secp256k1:3tFRbMqmoa6AAALMrEFAYCEoHcqKxeW38YptwowBVBtXK1vo36HDbUWuR6EZmoK4JcH6HDkNMGGqP1ouV7VZUWya";
+/// The secp256k1 root public key of the MPC contract on testnet, as returned by its `public_key`
+/// view method.
+pub const TESTNET_ROOT_PUBLIC_KEY: &str = "secp256k1:4NfTiv3UsGahebgTaHyD9vF8KYKMBnfd6kh94mK6xv8fGBiJB8TBtFMP5WWXz6B89Ac1fbpzPwAvoyQebemHFwx3";
+
+/// The x and y coordinates of [`MAINNET_ROOT_PUBLIC_KEY`].
+const MAINNET_ROOT_PUBLIC_KEY_BYTES: [u8; 64] = [
+    0x90, 0x3a, 0x9a, 0x99, 0x33, 0xed, 0x92, 0xbd, 0xda, 0x3f, 0xcf, 0x30, 0xac, 0x99, 0x90, 0x60,
+    0xa5, 0xa0, 0xfa, 0x51, 0xc2, 0xb6, 0xc7, 0x48, 0x38, 0xd3, 0x02, 0x9a, 0x5a, 0xad, 0xef, 0xe0,
+    0x38, 0xf7, 0xe4, 0xa9, 0x17, 0x14, 0xf4, 0x2b, 0xb5, 0xa2, 0x45, 0x9a, 0x0d, 0x29, 0x4b, 0xe0,
+    0xcd, 0x04, 0x7b, 0x4a, 0x99, 0x9d, 0x6f, 0xd9, 0x12, 0x70, 0x24, 0x70, 0xf8, 0x43, 0x27, 0x1d,
+];
+/// The x and y coordinates of [`TESTNET_ROOT_PUBLIC_KEY`].
+const TESTNET_ROOT_PUBLIC_KEY_BYTES: [u8; 64] = [
+    0xa8, 0xbb, 0x81, 0x76, 0x74, 0x76, 0x82, 0xaa, 0xb5, 0xc6, 0x81, 0xd4, 0xef, 0x37, 0x5c, 0xa5,
+    0x37, 0x02, 0x3b, 0x2b, 0x28, 0x7d, 0x8b, 0x5f, 0x9d, 0x89, 0x50, 0x52, 0x77, 0xa5, 0xa2, 0x91,
+    0x53, 0x8e, 0xf3, 0x68, 0x08, 0x82, 0xd8, 0xfb, 0x79, 0x3a, 0x62, 0xa5, 0xfd, 0xb4, 0xc6, 0x97,
+    0x4c, 0x7c, 0xd8, 0xf9, 0xeb, 0x0b, 0x9c, 0xbb, 0xf6, 0x59, 0x31, 0x4c, 0x30, 0x34, 0x70, 0x00,
+];
+
+/// The NEAR networks the MPC contract is deployed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignerNetwork {
+    Mainnet,
+    Testnet,
+}
+
+impl SignerNetwork {
+    /// The account of the MPC contract.
+    pub const fn contract_id(&self) -> &'static str {
+        match self {
+            Self::Mainnet => MAINNET_SIGNER_ID,
+            Self::Testnet => TESTNET_SIGNER_ID,
+        }
+    }
+
+    /// The secp256k1 root public key of the MPC contract, in the `secp256k1:<base58>` format of
+    /// NEAR public keys.
+    pub const fn root_public_key(&self) -> &'static str {
+        match self {
+            Self::Mainnet => MAINNET_ROOT_PUBLIC_KEY,
+            Self::Testnet => TESTNET_ROOT_PUBLIC_KEY,
+        }
+    }
+
+    /// The x and y coordinates of the secp256k1 root public key of the MPC contract, the
+    /// uncompressed SEC1 encoding of the key without its `0x04` prefix.
+    pub const fn root_public_key_bytes(&self) -> [u8; 64] {
+        match self {
+            Self::Mainnet => MAINNET_ROOT_PUBLIC_KEY_BYTES,
+            Self::Testnet => TESTNET_ROOT_PUBLIC_KEY_BYTES,
+        }
+    }
+
+    /// The secp256k1 root public key of the MPC contract, to derive keys from with
+    /// [`super::derive_public_key`].
+    #[cfg(feature = "k256")]
+    pub fn root_k256_public_key(&self) -> k256::PublicKey {
+        let mut sec1 = [0x04; 65];
+        sec1[1..].copy_from_slice(&self.root_public_key_bytes());
+
+        k256::PublicKey::from_sec1_bytes(&sec1).expect("root public keys are valid points")
+    }
+}
+
+#[cfg(all(test, any(feature = "near", feature = "k256")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "near")]
+    fn test_root_public_key_bytes() {
+        for network in [SignerNetwork::Mainnet, SignerNetwork::Testnet] {
+            let (curve, key) = network.root_public_key().split_once(':').unwrap();
+            assert_eq!(curve, "secp256k1");
+            assert_eq!(
+                bs58::decode(key).into_vec().unwrap(),
+                network.root_public_key_bytes()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "k256")]
+    fn test_root_k256_public_key() {
+        use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+        let public_key = SignerNetwork::Mainnet.root_k256_public_key();
+        assert_eq!(
+            &public_key.to_encoded_point(false).as_bytes()[1..],
+            &MAINNET_ROOT_PUBLIC_KEY_BYTES
+        );
+        assert_ne!(SignerNetwork::Testnet.root_k256_public_key(), public_key);
+    }
+}
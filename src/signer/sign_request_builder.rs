@@ -0,0 +1,164 @@
+use alloc::string::{String, ToString};
+
+use super::types::{DomainId, KeyVersion, Payload, SignRequest, SignRequestV2};
+use crate::transaction_builder::TxBuilder;
+
+/// Builds the sign requests of both versions of the MPC contract: [`SignRequest`], identifying
+/// the root key by `key_version`, and [`SignRequestV2`], identifying it by `domain_id`.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::signer::types::{DomainId, Payload, SignRequestV2};
+/// use signet_rs::signer::SignRequestBuilder;
+/// use signet_rs::TxBuilder;
+///
+/// let request: SignRequestV2 = SignRequestBuilder::new()
+///     .path("solana-1")
+///     .payload(Payload::eddsa(vec![1; 64]).unwrap())
+///     .domain_id(DomainId::Ed25519)
+///     .build();
+/// ```
+pub struct SignRequestBuilder {
+    path: Option<String>,
+    payload: Option<Payload>,
+    key_version: Option<KeyVersion>,
+    domain_id: Option<DomainId>,
+}
+
+impl Default for SignRequestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<SignRequest> for SignRequestBuilder {
+    fn build(&self) -> SignRequest {
+        let payload = match self.payload.clone().expect("payload is mandatory") {
+            Payload::Ecdsa(hash) => hash,
+            Payload::Eddsa(_) => panic!("legacy sign requests only support ECDSA payloads"),
+        };
+
+        SignRequest {
+            payload,
+            path: self.path.clone().expect("path is mandatory"),
+            key_version: self.key_version.unwrap_or_default(),
+        }
+    }
+}
+
+impl TxBuilder<SignRequestV2> for SignRequestBuilder {
+    fn build(&self) -> SignRequestV2 {
+        let payload = self.payload.clone().expect("payload is mandatory");
+        let domain_id = self.domain_id.expect("domain_id is mandatory");
+        assert!(
+            domain_id.supports(&payload),
+            "domain {domain_id:?} does not support the payload"
+        );
+
+        SignRequestV2 {
+            path: self.path.clone().expect("path is mandatory"),
+            payload,
+            domain_id,
+        }
+    }
+}
+
+impl SignRequestBuilder {
+    pub const fn new() -> Self {
+        Self {
+            path: None,
+            payload: None,
+            key_version: None,
+            domain_id: None,
+        }
+    }
+
+    /// Derivation path of the key signing the payload.
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// Payload to sign, only ECDSA payloads being supported by legacy requests.
+    pub fn payload(mut self, payload: Payload) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    /// Version of the root key of a legacy request, [`KeyVersion::V0`] if not set.
+    pub const fn key_version(mut self, key_version: KeyVersion) -> Self {
+        self.key_version = Some(key_version);
+        self
+    }
+
+    /// Domain of the root key of a request to the newer contract, which must support the
+    /// payload.
+    pub const fn domain_id(mut self, domain_id: DomainId) -> Self {
+        self.domain_id = Some(domain_id);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+
+    #[test]
+    fn test_build_legacy() {
+        let request: SignRequest = SignRequestBuilder::new()
+            .path("ethereum-1")
+            .payload(Payload::from([1; 32]))
+            .build();
+
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            format!(
+                r#"{{"payload":[{}1],"path":"ethereum-1","key_version":0}}"#,
+                "1,".repeat(31)
+            )
+        );
+    }
+
+    #[test]
+    fn test_build_v2() {
+        let request: SignRequestV2 = SignRequestBuilder::new()
+            .path("solana-1")
+            .payload(Payload::eddsa(vec![2; 32]).unwrap())
+            .domain_id(DomainId::Ed25519)
+            .build();
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"path":"solana-1","payload_v2":{{"Eddsa":"{}"}},"domain_id":1}}"#,
+                "02".repeat(32)
+            )
+        );
+        assert_eq!(
+            serde_json::from_str::<SignRequestV2>(&json).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not support the payload")]
+    fn test_build_v2_domain_mismatch() {
+        let _: SignRequestV2 = SignRequestBuilder::new()
+            .path("solana-1")
+            .payload(Payload::from([1; 32]))
+            .domain_id(DomainId::Ed25519)
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "legacy sign requests only support ECDSA payloads")]
+    fn test_build_legacy_eddsa() {
+        let _: SignRequest = SignRequestBuilder::new()
+            .path("solana-1")
+            .payload(Payload::eddsa(vec![2; 32]).unwrap())
+            .build();
+    }
+}
@@ -0,0 +1,100 @@
+//! Signing of Substrate extrinsics with the signatures returned by the MPC signer.
+use alloc::vec::Vec;
+
+use super::{types::SignatureResponse, Error};
+use crate::substrate::{types::MultiSignature, SubstrateTransaction};
+
+/// The payload to request the signature of for `tx`, the blake2 256 of its signing payload
+/// signed by ECDSA accounts.
+pub fn substrate_payload(tx: &SubstrateTransaction) -> [u8; 32] {
+    tx.signing_hash()
+}
+
+/// The signed extrinsic of `tx`, ready to be submitted with `author_submitExtrinsic`, signed by
+/// `response`, the signature of its [`substrate_payload`].
+pub fn sign_substrate(
+    tx: &SubstrateTransaction,
+    response: &SignatureResponse,
+) -> Result<Vec<u8>, Error> {
+    Ok(tx.build_with_signature(&response.to_substrate_signature()?))
+}
+
+impl SignatureResponse {
+    /// The ECDSA signature of a Substrate extrinsic, its `r` and `s` then the y parity of big R.
+    ///
+    /// S is normalized to low-S, the y parity flipping with it, as the runtime recovers the
+    /// signer from the signature.
+    pub fn to_substrate_signature(&self) -> Result<MultiSignature, Error> {
+        let (r, s, y_parity) = self.normalized_rs()?;
+
+        let mut signature = [0; 65];
+        signature[..32].copy_from_slice(&r);
+        signature[32..64].copy_from_slice(&s);
+        signature[64] = y_parity;
+        Ok(MultiSignature::Ecdsa(signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::types::low_and_high_s_responses;
+    use crate::substrate::{
+        config::ChainConfig,
+        types::{AccountId, Era},
+    };
+    use k256::ecdsa::{
+        signature::hazmat::PrehashSigner, RecoveryId, Signature as EcdsaSignature, SigningKey,
+        VerifyingKey,
+    };
+
+    #[test]
+    fn test_sign_substrate() {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let public_key = signing_key.verifying_key().to_encoded_point(true);
+        let signer = AccountId::from_ecdsa_public_key(public_key.as_bytes().try_into().unwrap());
+        let tx = SubstrateTransaction {
+            signer: signer.into(),
+            call: vec![5, 3, 0, 1, 4],
+            era: Era::Immortal,
+            nonce: 0,
+            tip: 0,
+            spec_version: 1_003_000,
+            transaction_version: 26,
+            genesis_hash: [0x91; 32],
+            checkpoint_hash: [0x91; 32],
+            asset_id: None,
+            metadata_hash: None,
+            chain_config: ChainConfig::polkadot(),
+        };
+
+        let payload = substrate_payload(&tx);
+        let (signature, recovery_id): (EcdsaSignature, RecoveryId) =
+            signing_key.sign_prehash(&payload).unwrap();
+
+        // The high-S form of the signature has the opposite big R, and is normalized
+        for response in low_and_high_s_responses(signature, recovery_id) {
+            let MultiSignature::Ecdsa(substrate_signature) =
+                response.to_substrate_signature().unwrap()
+            else {
+                panic!("Signatures of the MPC signer are ECDSA signatures");
+            };
+            assert_eq!(substrate_signature[..64], signature.to_bytes()[..]);
+            assert_eq!(substrate_signature[64], recovery_id.to_byte());
+
+            // The runtime recovers the key of the signer from the signature
+            let recovered = VerifyingKey::recover_from_prehash(
+                &payload,
+                &EcdsaSignature::from_slice(&substrate_signature[..64]).unwrap(),
+                RecoveryId::from_byte(substrate_signature[64]).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(recovered.to_encoded_point(true), public_key);
+
+            assert_eq!(
+                sign_substrate(&tx, &response).unwrap(),
+                tx.build_with_signature(&MultiSignature::Ecdsa(substrate_signature))
+            );
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Abstraction over the signers of sign requests.
+use alloc::string::String;
+use core::future::Future;
+
+use super::types::{EddsaSignatureResponse, SignRequest, SignRequestV2, SignatureResponse};
+
+/// A signer of sign requests, signing their payload with the key derived for its account and
+/// their path.
+///
+/// Implemented by the MPC network through [`super::client::MpcSigner`] and, to test without it,
+/// by [`super::DevSigner`].
+pub trait Signer {
+    /// Signs `request` with the key of its path.
+    fn sign(
+        &self,
+        request: &SignRequest,
+    ) -> impl Future<Output = Result<SignatureResponse, String>>;
+
+    /// Signs the EdDSA payload of `request` with the Ed25519 key of its path, failing on
+    /// requests of other schemes.
+    fn sign_eddsa(
+        &self,
+        request: &SignRequestV2,
+    ) -> impl Future<Output = Result<EddsaSignatureResponse, String>>;
+}
@@ -0,0 +1,87 @@
+//! Signing of Tron transactions with the signatures returned by the MPC signer.
+use alloc::vec::Vec;
+
+use super::{types::SignatureResponse, Error};
+use crate::tron::{types::Signature, TronTransaction};
+
+/// The payload to request the signature of for `tx`, the sha256 of its raw data.
+pub fn tron_payload(tx: &TronTransaction) -> [u8; 32] {
+    tx.signing_hash()
+}
+
+/// The signed `tx`, ready to be broadcast with `broadcasthex`, signed by `response`, the
+/// signature of its [`tron_payload`].
+pub fn sign_tron(tx: &TronTransaction, response: &SignatureResponse) -> Result<Vec<u8>, Error> {
+    Ok(tx.build_with_signature(&response.to_tron_signature()?))
+}
+
+impl SignatureResponse {
+    /// The signature of a Tron transaction, its `r` and `s` then the y parity of big R plus 27.
+    ///
+    /// S is normalized to low-S, the y parity flipping with it, like Ethereum signatures.
+    pub fn to_tron_signature(&self) -> Result<Signature, Error> {
+        let (r, s, y_parity) = self.normalized_rs()?;
+
+        let mut signature = [0; 65];
+        signature[..32].copy_from_slice(&r);
+        signature[32..64].copy_from_slice(&s);
+        signature[64] = 27 + y_parity;
+        Ok(Signature(signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{evm_address, types::low_and_high_s_responses};
+    use crate::tron::types::{Address, Contract};
+    use alloc::string::String;
+    use k256::ecdsa::{
+        signature::hazmat::PrehashSigner, RecoveryId, Signature as EcdsaSignature, SigningKey,
+        VerifyingKey,
+    };
+
+    #[test]
+    fn test_sign_tron() {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let owner_address = Address(evm_address(&signing_key.verifying_key().into()));
+        let tx = TronTransaction {
+            ref_block_bytes: [0xa3, 0xe1],
+            ref_block_hash: [0xc1, 0xb8, 0xef, 0x5b, 0x9a, 0x0b, 0x5e, 0x34],
+            expiration: 1_700_000_060_000,
+            timestamp: 1_700_000_000_000,
+            fee_limit: 0,
+            memo: String::new(),
+            contract: Contract::Transfer {
+                owner_address,
+                to_address: Address([2; 20]),
+                amount: 1,
+            },
+        };
+
+        let payload = tron_payload(&tx);
+        let (signature, recovery_id): (EcdsaSignature, RecoveryId) =
+            signing_key.sign_prehash(&payload).unwrap();
+
+        // The high-S form of the signature has the opposite big R, and is normalized
+        for response in low_and_high_s_responses(signature, recovery_id) {
+            let tron_signature = response.to_tron_signature().unwrap();
+            assert_eq!(tron_signature.0[..64], signature.to_bytes()[..]);
+            assert_eq!(tron_signature.0[64], 27 + recovery_id.to_byte());
+
+            // Nodes recover the owner from the signature
+            let recovered = VerifyingKey::recover_from_prehash(
+                &payload,
+                &EcdsaSignature::from_slice(&tron_signature.0[..64]).unwrap(),
+                RecoveryId::from_byte(tron_signature.0[64] - 27).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(Address(evm_address(&recovered.into())), owner_address);
+
+            assert_eq!(
+                sign_tron(&tx, &response).unwrap(),
+                tx.build_with_signature(&tron_signature)
+            );
+        }
+    }
+}
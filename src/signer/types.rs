@@ -1,6 +1,13 @@
+use alloc::{format, string::String, vec::Vec};
 use serde::{Deserialize, Serialize};
-use alloc::string::String;
 
+use super::Error;
+
+/// An ECDSA signature returned by the signer contract.
+///
+/// Responses of every version of the contract parse: the `scheme` tag of the newer contract is
+/// ignored, and big R and s are read either as objects, with snake or pascal case fields, or as
+/// bare hex strings.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SignatureResponse {
     pub big_r: SerializableAffinePoint,
@@ -8,19 +15,695 @@ pub struct SignatureResponse {
     pub recovery_id: u8,
 }
 
+impl SignatureResponse {
+    /// The compact `r || s` form of the signature, `r` being the x coordinate of big R.
+    pub fn compact(&self) -> Result<[u8; 64], Error> {
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&self.big_r.x()?);
+        compact[32..].copy_from_slice(&self.s.to_bytes()?);
+
+        Ok(compact)
+    }
+
+    /// The `r` and `s` of the signature with s normalized to low-S, and the y parity of big R,
+    /// flipped when s is negated as `(r, -s)` is the signature of the point `-R`.
+    ///
+    /// The form of the signature taken by the chains rejecting high-S signatures, like EIP-2.
+    #[cfg(feature = "k256")]
+    pub fn normalized_rs(&self) -> Result<([u8; 32], [u8; 32], u8), Error> {
+        use k256::elliptic_curve::scalar::IsHigh;
+
+        let big_r = self.big_r.to_bytes()?;
+        let mut s = self.s.to_scalar()?;
+        let mut y_parity = big_r[0] & 1;
+        if bool::from(s.is_high()) {
+            s = -s;
+            y_parity ^= 1;
+        }
+
+        let mut r = [0u8; 32];
+        r.copy_from_slice(&big_r[1..]);
+        Ok((r, s.to_bytes().into(), y_parity))
+    }
+}
+
+/// A Schnorr signature returned by the MPC network for the taproot domains, signed with FROST.
+///
+/// The signature of P2TR key path inputs, which ECDSA signatures cannot sign, through the
+/// `Schnorr` variant of `BitcoinSignatureResponse` with the `bitcoin` feature.
 #[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SchnorrSignatureResponse {
+    pub big_r: SerializableAffinePoint,
+    pub s: SerializableScalar,
+}
+
+impl SchnorrSignatureResponse {
+    /// The 64-byte BIP-340 `R.x || s` form of the signature, big R needing an even y as BIP-340
+    /// only commits to its x coordinate.
+    pub fn to_bip340_bytes(&self) -> Result<[u8; 64], Error> {
+        let big_r = self.big_r.to_bytes()?;
+        if big_r[0] != 0x02 {
+            return Err(Error::OddBigR);
+        }
+
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&big_r[1..]);
+        signature[32..].copy_from_slice(&self.s.to_bytes()?);
+
+        Ok(signature)
+    }
+}
+
+/// An Ed25519 signature returned by the MPC network for the EdDSA domains, serialized in JSON as
+/// an array of its 64 bytes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EddsaSignatureResponse {
+    #[serde(with = "signature_bytes")]
+    pub signature: [u8; 64],
+}
+
+impl From<[u8; 64]> for EddsaSignatureResponse {
+    fn from(signature: [u8; 64]) -> Self {
+        Self { signature }
+    }
+}
+
+mod signature_bytes {
+    use alloc::vec::Vec;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error> {
+        bytes.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 64], D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| D::Error::invalid_length(bytes.len(), &"64 bytes"))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "AffinePointRepr")]
 pub struct SerializableAffinePoint {
     pub affine_point: String,
 }
 
+/// The shapes of big R across the versions of the signer contract.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AffinePointRepr {
+    Object {
+        #[serde(alias = "AffinePoint")]
+        affine_point: String,
+    },
+    Hex(String),
+}
+
+impl From<AffinePointRepr> for SerializableAffinePoint {
+    fn from(repr: AffinePointRepr) -> Self {
+        match repr {
+            AffinePointRepr::Object { affine_point } | AffinePointRepr::Hex(affine_point) => {
+                Self { affine_point }
+            }
+        }
+    }
+}
+
+impl SerializableAffinePoint {
+    /// The 33-byte compressed encoding of the point, its first byte being the parity of y.
+    pub fn to_bytes(&self) -> Result<[u8; 33], Error> {
+        let bytes: [u8; 33] = decode_hex(&self.affine_point, "big R")?;
+        if bytes[0] != 0x02 && bytes[0] != 0x03 {
+            return Err(Error::InvalidPoint);
+        }
+
+        Ok(bytes)
+    }
+
+    /// The x coordinate of the point, the `r` of the signature.
+    pub fn x(&self) -> Result<[u8; 32], Error> {
+        let bytes = self.to_bytes()?;
+        Ok(bytes[1..]
+            .try_into()
+            .expect("compressed points are 33 bytes"))
+    }
+
+    /// The point on the curve.
+    #[cfg(feature = "k256")]
+    pub fn to_affine_point(&self) -> Result<k256::AffinePoint, Error> {
+        k256::PublicKey::from_sec1_bytes(&self.to_bytes()?)
+            .map(|point| *point.as_affine())
+            .map_err(|_| Error::InvalidPoint)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "ScalarRepr")]
 pub struct SerializableScalar {
     pub scalar: String,
 }
 
+/// The shapes of s across the versions of the signer contract.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScalarRepr {
+    Object {
+        #[serde(alias = "Scalar")]
+        scalar: String,
+    },
+    Hex(String),
+}
+
+impl From<ScalarRepr> for SerializableScalar {
+    fn from(repr: ScalarRepr) -> Self {
+        match repr {
+            ScalarRepr::Object { scalar } | ScalarRepr::Hex(scalar) => Self { scalar },
+        }
+    }
+}
+
+impl SerializableScalar {
+    /// The 32 big-endian bytes of the scalar.
+    pub fn to_bytes(&self) -> Result<[u8; 32], Error> {
+        decode_hex(&self.scalar, "s")
+    }
+
+    /// The scalar, which must be less than the order of the curve.
+    #[cfg(feature = "k256")]
+    pub fn to_scalar(&self) -> Result<k256::Scalar, Error> {
+        use k256::elliptic_curve::PrimeField;
+
+        Option::from(k256::Scalar::from_repr(self.to_bytes()?.into())).ok_or(Error::InvalidScalar)
+    }
+}
+
+#[cfg(all(feature = "k256", any(test, feature = "testing")))]
+impl From<(k256::ecdsa::Signature, k256::ecdsa::RecoveryId)> for SignatureResponse {
+    /// Encodes the signature like the signer, big R being compressed from r and the y parity of
+    /// the recovery id.
+    fn from((signature, recovery_id): (k256::ecdsa::Signature, k256::ecdsa::RecoveryId)) -> Self {
+        let (r, s) = signature.split_bytes();
+        let prefix = if recovery_id.is_y_odd() { "03" } else { "02" };
+
+        Self {
+            big_r: SerializableAffinePoint {
+                affine_point: format!("{}{}", prefix, hex::encode_upper(r)),
+            },
+            s: SerializableScalar {
+                scalar: hex::encode_upper(s),
+            },
+            recovery_id: recovery_id.to_byte(),
+        }
+    }
+}
+
+/// The responses of `signature` and of its high-S form, the signature of the opposite big R, as
+/// the signer does not normalize s.
+#[cfg(all(test, feature = "k256"))]
+pub(crate) fn low_and_high_s_responses(
+    signature: k256::ecdsa::Signature,
+    recovery_id: k256::ecdsa::RecoveryId,
+) -> [SignatureResponse; 2] {
+    let high_s = k256::ecdsa::Signature::from_scalars(signature.r(), -*signature.s()).unwrap();
+    let negated_r = k256::ecdsa::RecoveryId::new(!recovery_id.is_y_odd(), false);
+
+    [
+        SignatureResponse::from((signature, recovery_id)),
+        SignatureResponse::from((high_s, negated_r)),
+    ]
+}
+
+#[cfg(feature = "k256")]
+impl TryFrom<&SerializableAffinePoint> for k256::AffinePoint {
+    type Error = Error;
+
+    fn try_from(point: &SerializableAffinePoint) -> Result<Self, Self::Error> {
+        point.to_affine_point()
+    }
+}
+
+#[cfg(feature = "k256")]
+impl From<k256::AffinePoint> for SerializableAffinePoint {
+    /// Encodes the point compressed in uppercase hex, like the signer.
+    fn from(point: k256::AffinePoint) -> Self {
+        use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+        Self {
+            affine_point: hex::encode_upper(point.to_encoded_point(true)),
+        }
+    }
+}
+
+#[cfg(feature = "k256")]
+impl TryFrom<&SerializableScalar> for k256::Scalar {
+    type Error = Error;
+
+    fn try_from(scalar: &SerializableScalar) -> Result<Self, Self::Error> {
+        scalar.to_scalar()
+    }
+}
+
+#[cfg(feature = "k256")]
+impl From<k256::Scalar> for SerializableScalar {
+    /// Encodes the scalar in uppercase hex, like the signer.
+    fn from(scalar: k256::Scalar) -> Self {
+        Self {
+            scalar: hex::encode_upper(scalar.to_bytes()),
+        }
+    }
+}
+
+fn decode_hex<const N: usize>(s: &str, field: &'static str) -> Result<[u8; N], Error> {
+    let bytes = hex::decode(s).map_err(|_| Error::InvalidHex { field })?;
+    let actual = bytes.len();
+
+    bytes.try_into().map_err(|_| Error::InvalidLength {
+        field,
+        expected: N,
+        actual,
+    })
+}
+
+/// The minimum length of an EdDSA payload.
+pub const MIN_EDDSA_PAYLOAD_LEN: usize = 32;
+/// The maximum length of an EdDSA payload, the size of a Solana transaction.
+pub const MAX_EDDSA_PAYLOAD_LEN: usize = 1232;
+
+/// The payload of a sign request to the MPC contract, serialized in JSON as its scheme and its
+/// hex-encoded bytes, like `{"Ecdsa": "0101..."}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum Payload {
+    /// The 32-byte hash signed with ECDSA.
+    Ecdsa(#[serde(with = "hex_bytes")] [u8; 32]),
+    /// The message signed with EdDSA, between 32 and 1232 bytes.
+    Eddsa(
+        #[serde(
+            serialize_with = "hex_bytes::serialize",
+            deserialize_with = "hex_bytes::deserialize_eddsa"
+        )]
+        Vec<u8>,
+    ),
+}
+
+impl Payload {
+    /// Creates an EdDSA payload, checking the length of the message.
+    pub fn eddsa(message: Vec<u8>) -> Result<Self, String> {
+        check_eddsa_len(message.len())?;
+        Ok(Self::Eddsa(message))
+    }
+
+    /// The bytes to sign.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Ecdsa(hash) => hash,
+            Self::Eddsa(message) => message,
+        }
+    }
+}
+
+/// Checks that an EdDSA message of `len` bytes is between [`MIN_EDDSA_PAYLOAD_LEN`] and
+/// [`MAX_EDDSA_PAYLOAD_LEN`] bytes.
+fn check_eddsa_len(len: usize) -> Result<(), String> {
+    if !(MIN_EDDSA_PAYLOAD_LEN..=MAX_EDDSA_PAYLOAD_LEN).contains(&len) {
+        return Err(format!(
+            "EdDSA payloads must be between {MIN_EDDSA_PAYLOAD_LEN} and \
+             {MAX_EDDSA_PAYLOAD_LEN} bytes, got {len}"
+        ));
+    }
+
+    Ok(())
+}
+
+impl From<[u8; 32]> for Payload {
+    fn from(hash: [u8; 32]) -> Self {
+        Self::Ecdsa(hash)
+    }
+}
+
+mod hex_bytes {
+    use alloc::{string::String, vec::Vec};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, T: AsRef<[u8]>>(
+        bytes: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<Vec<u8>>,
+    {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        let bytes = hex::decode(s).map_err(D::Error::custom)?;
+        let len = bytes.len();
+
+        T::try_from(bytes).map_err(|_| D::Error::invalid_length(len, &"a valid payload"))
+    }
+
+    /// Deserializes the message of an EdDSA payload, checking its length like
+    /// [`super::Payload::eddsa`].
+    pub fn deserialize_eddsa<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let message: Vec<u8> = deserialize(deserializer)?;
+        super::check_eddsa_len(message.len()).map_err(D::Error::custom)?;
+        Ok(message)
+    }
+}
+
+/// The versions of the root key of the legacy MPC contract, serialized as their number.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(try_from = "u32", into = "u32")]
+pub enum KeyVersion {
+    /// The secp256k1 root key the contract was deployed with.
+    #[default]
+    V0 = 0,
+}
+
+impl TryFrom<u32> for KeyVersion {
+    type Error = String;
+
+    fn try_from(version: u32) -> Result<Self, Self::Error> {
+        match version {
+            0 => Ok(Self::V0),
+            _ => Err(format!("Unsupported key version: {version}")),
+        }
+    }
+}
+
+impl From<KeyVersion> for u32 {
+    fn from(version: KeyVersion) -> Self {
+        version as u32
+    }
+}
+
+/// The domains of the MPC contract, each one a root key signing with its own scheme, serialized
+/// as their id.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "u64", into = "u64")]
+pub enum DomainId {
+    /// The secp256k1 key signing [`Payload::Ecdsa`] payloads.
+    Secp256k1 = 0,
+    /// The Ed25519 key signing [`Payload::Eddsa`] payloads.
+    Ed25519 = 1,
+}
+
+impl DomainId {
+    /// Returns whether the domain signs `payload`, ECDSA payloads being signed by the secp256k1
+    /// domain and EdDSA ones by the Ed25519 domain.
+    pub const fn supports(&self, payload: &Payload) -> bool {
+        matches!(
+            (self, payload),
+            (Self::Secp256k1, Payload::Ecdsa(_)) | (Self::Ed25519, Payload::Eddsa(_))
+        )
+    }
+}
+
+impl TryFrom<u64> for DomainId {
+    type Error = String;
+
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        match id {
+            0 => Ok(Self::Secp256k1),
+            1 => Ok(Self::Ed25519),
+            _ => Err(format!("Unsupported domain id: {id}")),
+        }
+    }
+}
+
+impl From<DomainId> for u64 {
+    fn from(domain_id: DomainId) -> Self {
+        domain_id as u64
+    }
+}
+
+/// A sign request to the legacy MPC contract, identifying the root key by its version.
 #[derive(Debug, Serialize)]
 pub struct SignRequest {
     pub payload: [u8; 32],
     pub path: String,
-    pub key_version: u32,
+    pub key_version: KeyVersion,
+}
+
+impl SignRequest {
+    /// The JSON args of the `sign` method of the legacy MPC contract (`v1.signer`),
+    /// `{"request": {...}}` with the payload as an array of bytes.
+    pub fn to_json_args(&self) -> Vec<u8> {
+        serde_json::to_vec(&SignArgs { request: self }).expect("Failed to serialize sign args")
+    }
+}
+
+/// A sign request to the MPC contract supporting several signature schemes, identifying the root
+/// key by the id of its domain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignRequestV2 {
+    pub path: String,
+    #[serde(rename = "payload_v2")]
+    pub payload: Payload,
+    pub domain_id: DomainId,
+}
+
+impl SignRequestV2 {
+    /// The JSON args of the `sign` method of the MPC contract, `{"request": {...}}` with the
+    /// payload hex-encoded.
+    pub fn to_json_args(&self) -> Vec<u8> {
+        serde_json::to_vec(&SignArgs { request: self }).expect("Failed to serialize sign args")
+    }
+}
+
+#[derive(Serialize)]
+struct SignArgs<'a, T> {
+    request: &'a T,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_eddsa_signature_response() {
+        let response = EddsaSignatureResponse::from([7; 64]);
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(json, format!(r#"{{"signature":[{}7]}}"#, "7,".repeat(63)));
+        assert_eq!(
+            serde_json::from_str::<EddsaSignatureResponse>(&json).unwrap(),
+            response
+        );
+
+        assert!(
+            serde_json::from_str::<EddsaSignatureResponse>(r#"{"signature":[1,2,3]}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn test_payload() {
+        let payload = Payload::from([1; 32]);
+        let json = serde_json::to_string(&payload).unwrap();
+        assert_eq!(json, format!(r#"{{"Ecdsa":"{}"}}"#, "01".repeat(32)));
+        assert_eq!(serde_json::from_str::<Payload>(&json).unwrap(), payload);
+        assert_eq!(payload.as_bytes(), [1; 32]);
+
+        let payload = Payload::eddsa(vec![2; 40]).unwrap();
+        let json = serde_json::to_string(&payload).unwrap();
+        assert_eq!(json, format!(r#"{{"Eddsa":"{}"}}"#, "02".repeat(40)));
+        assert_eq!(serde_json::from_str::<Payload>(&json).unwrap(), payload);
+
+        assert!(Payload::eddsa(vec![2; 31]).is_err());
+        assert!(Payload::eddsa(vec![2; 1233]).is_err());
+        assert!(serde_json::from_str::<Payload>(r#"{"Ecdsa":"0101"}"#).is_err());
+
+        // Deserialized EdDSA payloads are checked like created ones
+        for len in [31, 1233] {
+            let json = format!(r#"{{"Eddsa":"{}"}}"#, "02".repeat(len));
+            assert!(serde_json::from_str::<Payload>(&json).is_err());
+        }
+    }
+
+    #[test]
+    fn test_to_json_args() {
+        let request = SignRequest {
+            payload: [1; 32],
+            path: "ethereum-1".into(),
+            key_version: KeyVersion::V0,
+        };
+        assert_eq!(
+            String::from_utf8(request.to_json_args()).unwrap(),
+            format!(
+                r#"{{"request":{{"payload":[{}1],"path":"ethereum-1","key_version":0}}}}"#,
+                "1,".repeat(31)
+            )
+        );
+
+        let request = SignRequestV2 {
+            path: "ethereum-1".into(),
+            payload: Payload::from([1; 32]),
+            domain_id: DomainId::Secp256k1,
+        };
+        assert_eq!(
+            String::from_utf8(request.to_json_args()).unwrap(),
+            format!(
+                r#"{{"request":{{"path":"ethereum-1","payload_v2":{{"Ecdsa":"{}"}},"domain_id":0}}}}"#,
+                "01".repeat(32)
+            )
+        );
+    }
+
+    #[test]
+    fn test_signature_response_accessors() {
+        let response = SignatureResponse {
+            big_r: SerializableAffinePoint {
+                affine_point: format!("03{}", "ab".repeat(32)),
+            },
+            s: SerializableScalar {
+                scalar: "CD".repeat(32),
+            },
+            recovery_id: 1,
+        };
+        assert_eq!(response.big_r.x().unwrap(), [0xab; 32]);
+        assert_eq!(response.s.to_bytes().unwrap(), [0xcd; 32]);
+        assert_eq!(response.compact().unwrap()[..32], [0xab; 32]);
+
+        let point = |affine_point: &str| SerializableAffinePoint {
+            affine_point: affine_point.into(),
+        };
+        assert_eq!(
+            point("zz").to_bytes(),
+            Err(Error::InvalidHex { field: "big R" })
+        );
+        assert_eq!(
+            point(&"ab".repeat(32)).to_bytes(),
+            Err(Error::InvalidLength {
+                field: "big R",
+                expected: 33,
+                actual: 32
+            })
+        );
+        assert_eq!(
+            point(&format!("04{}", "ab".repeat(32))).to_bytes(),
+            Err(Error::InvalidPoint)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "k256")]
+    fn test_k256_accessors() {
+        let generator = SerializableAffinePoint {
+            affine_point: "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798"
+                .into(),
+        };
+        assert_eq!(
+            generator.to_affine_point().unwrap(),
+            k256::AffinePoint::GENERATOR
+        );
+
+        let one = SerializableScalar {
+            scalar: format!("{}01", "00".repeat(31)),
+        };
+        assert_eq!(one.to_scalar().unwrap(), k256::Scalar::ONE);
+
+        let order = SerializableScalar {
+            scalar: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141".into(),
+        };
+        assert_eq!(order.to_scalar(), Err(Error::InvalidScalar));
+    }
+
+    #[test]
+    #[cfg(feature = "k256")]
+    fn test_k256_conversions() {
+        let point = (k256::ProjectivePoint::GENERATOR * k256::Scalar::from(7u64)).to_affine();
+        let serializable = SerializableAffinePoint::from(point);
+        assert_eq!(serializable.affine_point.len(), 66);
+        assert_eq!(
+            serializable.affine_point,
+            serializable.affine_point.to_uppercase()
+        );
+        assert_eq!(k256::AffinePoint::try_from(&serializable).unwrap(), point);
+
+        let scalar = k256::Scalar::from(7u64);
+        let serializable = SerializableScalar::from(scalar);
+        assert_eq!(serializable.scalar, format!("{}07", "00".repeat(31)));
+        assert_eq!(k256::Scalar::try_from(&serializable).unwrap(), scalar);
+
+        let signing_key = k256::ecdsa::SigningKey::from_slice(&[7; 32]).unwrap();
+        let (signature, recovery_id) = signing_key.sign_prehash_recoverable(&[9; 32]).unwrap();
+        let response = SignatureResponse::from((signature, recovery_id));
+        assert_eq!(response.compact().unwrap(), signature.to_bytes().as_slice());
+        assert_eq!(response.recovery_id, recovery_id.to_byte());
+        assert_eq!(
+            response.big_r.to_bytes().unwrap()[0],
+            0x02 | u8::from(recovery_id.is_y_odd())
+        );
+
+        // The high-S form of the signature, of the point -R, normalizes to the same r and s
+        let (r, s, y_parity) = response.normalized_rs().unwrap();
+        assert_eq!([r, s].concat(), signature.to_bytes().as_slice());
+        assert_eq!(y_parity, u8::from(recovery_id.is_y_odd()));
+
+        let [_, high_s_response] = low_and_high_s_responses(signature, recovery_id);
+        assert_eq!(high_s_response.normalized_rs(), Ok((r, s, y_parity)));
+    }
+
+    #[test]
+    fn test_key_version_and_domain_id() {
+        assert_eq!(serde_json::to_string(&KeyVersion::V0).unwrap(), "0");
+        assert_eq!(
+            serde_json::from_str::<KeyVersion>("0").unwrap(),
+            KeyVersion::V0
+        );
+        assert!(serde_json::from_str::<KeyVersion>("1").is_err());
+
+        assert_eq!(serde_json::to_string(&DomainId::Ed25519).unwrap(), "1");
+        assert_eq!(DomainId::try_from(0), Ok(DomainId::Secp256k1));
+        assert!(DomainId::try_from(7).is_err());
+        assert!(serde_json::from_str::<DomainId>("7").is_err());
+
+        assert!(DomainId::Secp256k1.supports(&Payload::from([1; 32])));
+        assert!(!DomainId::Ed25519.supports(&Payload::from([1; 32])));
+        assert!(DomainId::Ed25519.supports(&Payload::eddsa(vec![1; 32]).unwrap()));
+    }
+
+    #[test]
+    fn test_signature_response_versions() {
+        let expected = r#"{"big_r":{"affine_point":"02AB"},"s":{"scalar":"CD"},"recovery_id":1}"#;
+        let responses = [
+            expected,
+            r#"{"big_r":{"AffinePoint":"02AB"},"s":{"Scalar":"CD"},"recovery_id":1}"#,
+            r#"{"big_r":"02AB","s":"CD","recovery_id":1}"#,
+            r#"{"scheme":"Secp256k1","big_r":{"affine_point":"02AB"},"s":{"scalar":"CD"},"recovery_id":1}"#,
+        ];
+
+        for response in responses {
+            let response: SignatureResponse = serde_json::from_str(response).unwrap();
+            assert_eq!(serde_json::to_string(&response).unwrap(), expected);
+        }
+
+        assert!(serde_json::from_str::<SignatureResponse>(
+            r#"{"big_r":2,"s":"CD","recovery_id":1}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_schnorr_signature_response() {
+        let r = "B96BFA3DA6BB4BB74EEEE9C20970725C5782F07724CD1BEFBD265C5AD5C63948";
+        let s = "49283B618968DEFB0E660EA703D193BC1D213F5DD811A2D13307FCA01E20C5C0";
+        let json = format!(r#"{{"big_r":{{"affine_point":"02{r}"}},"s":{{"scalar":"{s}"}}}}"#);
+
+        let response: SchnorrSignatureResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            hex::encode_upper(response.to_bip340_bytes().unwrap()),
+            format!("{r}{s}")
+        );
+
+        let mut odd = response.clone();
+        odd.big_r.affine_point = format!("03{r}");
+        assert_eq!(odd.to_bip340_bytes(), Err(Error::OddBigR));
+    }
 }
@@ -0,0 +1,183 @@
+//! Verification of the ECDSA signatures returned by the MPC signer.
+use alloc::string::{String, ToString};
+
+use k256::elliptic_curve::{
+    bigint::{Encoding, Limb, U256},
+    ops::Reduce,
+    point::{AffineCoordinates, DecompressPoint},
+    subtle::Choice,
+    Curve,
+};
+
+use super::{kdf::evm_address, types::SignatureResponse};
+
+/// Checks that `response` is a valid ECDSA signature of `payload` by `derived_public_key`, the
+/// key derived for the account and path of the request (see [`super::derive_public_key`]).
+///
+/// Both low-S and high-S signatures are accepted, the signer not normalizing S.
+pub fn verify(
+    response: &SignatureResponse,
+    payload: &[u8; 32],
+    derived_public_key: &k256::PublicKey,
+) -> Result<(), String> {
+    let big_r = response
+        .big_r
+        .to_affine_point()
+        .map_err(|e| e.to_string())?;
+    let s = response.s.to_scalar().map_err(|e| e.to_string())?;
+
+    let r = <k256::Scalar as Reduce<U256>>::reduce_bytes(&big_r.x());
+    if bool::from(r.is_zero()) || bool::from(s.is_zero()) {
+        return Err("Invalid signature, r and s must be non-zero".to_string());
+    }
+
+    let z = <k256::Scalar as Reduce<U256>>::reduce_bytes(k256::FieldBytes::from_slice(payload));
+    let s_inv = s.invert().expect("s is non-zero");
+    let point = k256::ProjectivePoint::GENERATOR * (z * s_inv)
+        + derived_public_key.to_projective() * (r * s_inv);
+
+    let x = <k256::Scalar as Reduce<U256>>::reduce_bytes(&point.to_affine().x());
+    if x != r {
+        return Err("Signature does not match the payload and public key".to_string());
+    }
+
+    Ok(())
+}
+
+/// Finds the recovery id, from 0 to 3, recovering `expected_public_key` from `response` and
+/// `payload`, without trusting the `recovery_id` of the response, which may not match once the
+/// root key is tweaked into the derived key.
+pub fn recover_recovery_id(
+    response: &SignatureResponse,
+    payload: &[u8; 32],
+    expected_public_key: &k256::PublicKey,
+) -> Result<u8, String> {
+    (0..4)
+        .find(|&recovery_id| {
+            recover_public_key(response, payload, recovery_id).as_ref() == Ok(expected_public_key)
+        })
+        .ok_or_else(|| "No recovery id recovers the expected public key".to_string())
+}
+
+/// Finds the recovery id recovering the key of the Ethereum address `expected_address`, see
+/// [`recover_recovery_id`].
+pub fn recover_evm_recovery_id(
+    response: &SignatureResponse,
+    payload: &[u8; 32],
+    expected_address: &[u8; 20],
+) -> Result<u8, String> {
+    (0..4)
+        .find(|&recovery_id| {
+            recover_public_key(response, payload, recovery_id)
+                .is_ok_and(|public_key| &evm_address(&public_key) == expected_address)
+        })
+        .ok_or_else(|| "No recovery id recovers the expected address".to_string())
+}
+
+/// Recovers the public key signing `payload` with the `r` and `s` of `response`, R being the
+/// point of x coordinate `r`, plus the order of the curve if bit 1 of `recovery_id` is set, and
+/// of y parity bit 0 of `recovery_id`.
+fn recover_public_key(
+    response: &SignatureResponse,
+    payload: &[u8; 32],
+    recovery_id: u8,
+) -> Result<k256::PublicKey, String> {
+    let x = response.big_r.x().map_err(|e| e.to_string())?;
+    let s = response.s.to_scalar().map_err(|e| e.to_string())?;
+    let r = <k256::Scalar as Reduce<U256>>::reduce_bytes(&x.into());
+    if bool::from(r.is_zero()) || bool::from(s.is_zero()) {
+        return Err("Invalid signature, r and s must be non-zero".to_string());
+    }
+
+    let mut x = U256::from_be_bytes(x);
+    if recovery_id & 2 != 0 {
+        let (sum, carry) = x.adc(&k256::Secp256k1::ORDER, Limb::ZERO);
+        if carry != Limb::ZERO {
+            return Err("r plus the order of the curve overflows".to_string());
+        }
+        x = sum;
+    }
+    let big_r: k256::AffinePoint = Option::from(k256::AffinePoint::decompress(
+        &x.to_be_bytes().into(),
+        Choice::from(recovery_id & 1),
+    ))
+    .ok_or_else(|| "No point of the curve has x coordinate r".to_string())?;
+
+    let z = <k256::Scalar as Reduce<U256>>::reduce_bytes(k256::FieldBytes::from_slice(payload));
+    let r_inv = r.invert().expect("r is non-zero");
+    let point =
+        (k256::ProjectivePoint::from(big_r) * s - k256::ProjectivePoint::GENERATOR * z) * r_inv;
+
+    k256::PublicKey::from_affine(point.to_affine())
+        .map_err(|_| "Recovered public key is the identity".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::{signature::hazmat::PrehashSigner, RecoveryId, Signature, SigningKey};
+
+    #[test]
+    fn test_verify() {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let public_key = k256::PublicKey::from(signing_key.verifying_key());
+        let payload = [9; 32];
+
+        let (signature, recovery_id): (Signature, RecoveryId) =
+            signing_key.sign_prehash(&payload).unwrap();
+        let response = SignatureResponse::from((signature, recovery_id));
+        assert_eq!(verify(&response, &payload, &public_key), Ok(()));
+
+        // The high-S form of the signature is valid too
+        let high_s = Signature::from_scalars(signature.r(), -*signature.s()).unwrap();
+        let response_high_s = SignatureResponse::from((high_s, recovery_id));
+        assert_eq!(verify(&response_high_s, &payload, &public_key), Ok(()));
+
+        assert!(verify(&response, &[8; 32], &public_key).is_err());
+        let other_key = SigningKey::from_slice(&[8; 32]).unwrap();
+        assert!(verify(
+            &response,
+            &payload,
+            &k256::PublicKey::from(other_key.verifying_key())
+        )
+        .is_err());
+
+        let mut malformed = response.clone();
+        malformed.big_r.affine_point = alloc::format!("04{}", &response.big_r.affine_point[2..]);
+        assert!(verify(&malformed, &payload, &public_key).is_err());
+    }
+
+    #[test]
+    fn test_recover_recovery_id() {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let public_key = k256::PublicKey::from(signing_key.verifying_key());
+        let address = evm_address(&public_key);
+        let payload = [9; 32];
+
+        let (signature, recovery_id): (Signature, RecoveryId) =
+            signing_key.sign_prehash(&payload).unwrap();
+        let mut response = SignatureResponse::from((signature, recovery_id));
+        // The id of the response is not trusted
+        response.recovery_id = 3;
+
+        assert_eq!(
+            recover_recovery_id(&response, &payload, &public_key),
+            Ok(recovery_id.to_byte())
+        );
+        assert_eq!(
+            recover_evm_recovery_id(&response, &payload, &address),
+            Ok(recovery_id.to_byte())
+        );
+
+        // Negating s flips the parity of the recovered R
+        let high_s = Signature::from_scalars(signature.r(), -*signature.s()).unwrap();
+        let response_high_s = SignatureResponse::from((high_s, recovery_id));
+        assert_eq!(
+            recover_recovery_id(&response_high_s, &payload, &public_key),
+            Ok(recovery_id.to_byte() ^ 1)
+        );
+
+        assert!(recover_recovery_id(&response, &[8; 32], &public_key).is_err());
+        assert!(recover_evm_recovery_id(&response, &payload, &[0; 20]).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+//! Signing of XRP Ledger transactions with the signatures returned by the MPC signer.
+use alloc::vec::Vec;
+
+use super::{types::SignatureResponse, Error};
+use crate::xrpl::XrplTransaction;
+
+/// The payload to request the signature of for `tx`, the SHA-512Half of its signing bytes, or
+/// an error if `tx` fails [`XrplTransaction::validate`].
+pub fn xrpl_payload(tx: &XrplTransaction) -> Result<[u8; 32], Error> {
+    tx.validate().map_err(Error::InvalidTransaction)?;
+    Ok(tx.signing_hash())
+}
+
+/// The signed `tx`, ready to be submitted as a `tx_blob`, signed by `response`, the signature
+/// of its [`xrpl_payload`].
+pub fn sign_xrpl(tx: &XrplTransaction, response: &SignatureResponse) -> Result<Vec<u8>, Error> {
+    tx.validate().map_err(Error::InvalidTransaction)?;
+    Ok(tx.build_with_signature(&response.to_xrpl_signature()?))
+}
+
+impl SignatureResponse {
+    /// The DER signature of an XRP Ledger transaction.
+    ///
+    /// S is normalized to low-S, as the ledger only accepts fully canonical signatures.
+    pub fn to_xrpl_signature(&self) -> Result<Vec<u8>, Error> {
+        let (r, s, _) = self.normalized_rs()?;
+        Ok(crate::der::encode_signature(&r, &s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::types::low_and_high_s_responses;
+    use crate::xrpl::types::{Amount, PublicKey, TransactionKind};
+    use k256::ecdsa::{
+        signature::hazmat::{PrehashSigner, PrehashVerifier},
+        RecoveryId, Signature as EcdsaSignature, SigningKey,
+    };
+
+    #[test]
+    fn test_sign_xrpl() {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let public_key = signing_key.verifying_key().to_encoded_point(true);
+        let public_key = PublicKey::from_slice(public_key.as_bytes()).unwrap();
+        let tx = XrplTransaction {
+            account: public_key.account_id(),
+            kind: TransactionKind::Payment {
+                destination: "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".parse().unwrap(),
+                amount: Amount::from_xrp(25).unwrap(),
+                destination_tag: None,
+            },
+            fee: Amount::Xrp(12),
+            sequence: 1,
+            flags: 0,
+            last_ledger_sequence: None,
+            source_tag: None,
+            network_id: None,
+            signing_public_key: public_key,
+        };
+
+        let payload = xrpl_payload(&tx).unwrap();
+        let (signature, recovery_id): (EcdsaSignature, RecoveryId) =
+            signing_key.sign_prehash(&payload).unwrap();
+
+        // The high-S form of the signature is normalized
+        for response in low_and_high_s_responses(signature, recovery_id) {
+            let xrpl_signature = response.to_xrpl_signature().unwrap();
+            assert_eq!(xrpl_signature, signature.to_der().as_bytes());
+
+            let signed = EcdsaSignature::from_der(&xrpl_signature).unwrap();
+            assert!(signed.normalize_s().is_none());
+            signing_key
+                .verifying_key()
+                .verify_prehash(&payload, &signed)
+                .unwrap();
+
+            assert_eq!(
+                sign_xrpl(&tx, &response).unwrap(),
+                tx.build_with_signature(&xrpl_signature)
+            );
+        }
+
+        // Invalid transactions are rejected instead of failing to encode
+        let tx = XrplTransaction {
+            fee: Amount::issued("1", "USD", tx.account).unwrap(),
+            ..tx
+        };
+        assert!(xrpl_payload(&tx).is_err());
+        assert!(sign_xrpl(&tx, &SignatureResponse::from((signature, recovery_id))).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+//! Instructions of the Compute Budget program, setting the compute units a transaction may
+//! consume and the priority fee it pays for them, their data encoded like the borsh-serialized
+//! `ComputeBudgetInstruction` enum.
+//!
+//! The priority fee of a transaction is its compute unit limit times its compute unit price, on
+//! top of the base fee per signature, so both are usually set together.
+use super::types::{Instruction, Pubkey};
+
+/// The address of the Compute Budget program, `ComputeBudget111111111111111111111111111111`.
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = Pubkey([
+    3, 6, 70, 111, 229, 33, 23, 50, 255, 236, 173, 186, 114, 195, 155, 231, 188, 140, 229, 187,
+    197, 247, 18, 107, 44, 67, 155, 58, 64, 0, 0, 0,
+]);
+
+/// The most compute units a transaction may consume.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// The index of the `SetComputeUnitLimit` variant of `ComputeBudgetInstruction`.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+/// The index of the `SetComputeUnitPrice` variant of `ComputeBudgetInstruction`.
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// Sets the most compute units the transaction may consume, up to
+/// [`MAX_COMPUTE_UNIT_LIMIT`], instead of 200 000 per instruction.
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_LIMIT];
+    data.extend_from_slice(&units.to_le_bytes());
+
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Sets the price of a compute unit, in micro-lamports, the transaction paying a priority fee
+/// of its compute unit limit times this price.
+pub fn set_compute_unit_price(micro_lamports: u64) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_PRICE];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::system_program::tests::assert_eq_solana;
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+
+    #[test]
+    fn test_instructions_against_solana() {
+        assert_eq!(
+            COMPUTE_BUDGET_PROGRAM_ID.to_string(),
+            "ComputeBudget111111111111111111111111111111"
+        );
+
+        assert_eq_solana(
+            &set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT),
+            &ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT),
+        );
+        assert_eq_solana(
+            &set_compute_unit_price(25_000),
+            &ComputeBudgetInstruction::set_compute_unit_price(25_000),
+        );
+    }
+}
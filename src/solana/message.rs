@@ -0,0 +1,284 @@
+//! Solana legacy message
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    types::{CompiledInstruction, Hash, Instruction, MessageHeader, Pubkey},
+    utils::encode_length,
+};
+
+/// A Solana legacy message, the instructions of a transaction compiled against the accounts
+/// they use, and the payload its signers sign.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::solana::types::{AccountMeta, Hash, Instruction, Pubkey};
+/// use signet_rs::solana::Message;
+///
+/// let payer = Pubkey([1; 32]);
+/// let instruction = Instruction {
+///     program_id: Pubkey([9; 32]),
+///     accounts: vec![AccountMeta::new(payer, true)],
+///     data: vec![0],
+/// };
+///
+/// let message = Message::new(&[instruction], &payer, Hash([3; 32]));
+/// assert_eq!(message.account_keys, vec![payer, Pubkey([9; 32])]);
+/// assert_eq!(message.header.num_required_signatures, 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    /// The number of signers and read-only accounts.
+    pub header: MessageHeader,
+    /// Every account used by the instructions, the fee payer first.
+    pub account_keys: Vec<Pubkey>,
+    /// A recent blockhash, the transaction expiring about a minute after it.
+    pub recent_blockhash: Hash,
+    /// The instructions, executed in order, either all of them or none.
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+/// Whether an account of a message signs the transaction, whether it is written to, and
+/// whether it is the program of an instruction.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct KeyFlags {
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub is_invoked: bool,
+}
+
+impl Message {
+    /// Compiles `instructions` into a message paid by `payer`.
+    ///
+    /// The account keys are the payer, then the other writable signers, read-only signers,
+    /// writable non-signers and read-only non-signers, each group sorted by address like the
+    /// messages compiled by the Solana SDK. An account used several times is writable, or a
+    /// signer, if any of its uses is.
+    ///
+    /// Panics if the instructions use more than 256 accounts.
+    pub fn new(instructions: &[Instruction], payer: &Pubkey, recent_blockhash: Hash) -> Self {
+        let (header, account_keys) = compile_keys(instructions, payer);
+
+        Self {
+            instructions: compile_instructions(instructions, &account_keys),
+            header,
+            account_keys,
+            recent_blockhash,
+        }
+    }
+
+    /// Encode the message, the payload signed with Ed25519 by each of its signers
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+
+        buffer.push(self.header.num_required_signatures);
+        buffer.push(self.header.num_readonly_signed_accounts);
+        buffer.push(self.header.num_readonly_unsigned_accounts);
+
+        encode_length(&mut buffer, self.account_keys.len());
+        for key in &self.account_keys {
+            buffer.extend_from_slice(&key.0);
+        }
+
+        buffer.extend_from_slice(&self.recent_blockhash.0);
+
+        encode_instructions(&mut buffer, &self.instructions);
+
+        buffer
+    }
+
+    /// The accounts which must sign the transaction, in the order of their signatures.
+    pub fn signer_keys(&self) -> &[Pubkey] {
+        &self.account_keys[..self.header.num_required_signatures as usize]
+    }
+}
+
+/// Collects the accounts used by `instructions`, merging the flags of their uses, the payer
+/// being a writable signer.
+pub(crate) fn collect_keys(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+) -> BTreeMap<Pubkey, KeyFlags> {
+    let mut keys: BTreeMap<Pubkey, KeyFlags> = BTreeMap::new();
+
+    for instruction in instructions {
+        keys.entry(instruction.program_id).or_default().is_invoked = true;
+        for account in &instruction.accounts {
+            let flags = keys.entry(account.pubkey).or_default();
+            flags.is_signer |= account.is_signer;
+            flags.is_writable |= account.is_writable;
+        }
+    }
+    let flags = keys.entry(*payer).or_default();
+    flags.is_signer = true;
+    flags.is_writable = true;
+
+    keys
+}
+
+/// Orders the accounts of `keys` as the account keys of a message, the payer first, and counts
+/// the signers and read-only accounts.
+pub(crate) fn order_keys(
+    keys: &BTreeMap<Pubkey, KeyFlags>,
+    payer: &Pubkey,
+) -> (MessageHeader, Vec<Pubkey>) {
+    let group = |is_signer: bool, is_writable: bool| -> Vec<Pubkey> {
+        keys.iter()
+            .filter(|(key, flags)| {
+                *key != payer && flags.is_signer == is_signer && flags.is_writable == is_writable
+            })
+            .map(|(key, _)| *key)
+            .collect()
+    };
+
+    let writable_signers = group(true, true);
+    let readonly_signers = group(true, false);
+    let writable_non_signers = group(false, true);
+    let readonly_non_signers = group(false, false);
+
+    let header = MessageHeader {
+        num_required_signatures: u8::try_from(1 + writable_signers.len() + readonly_signers.len())
+            .expect("Solana messages have at most 256 accounts"),
+        num_readonly_signed_accounts: readonly_signers.len() as u8,
+        num_readonly_unsigned_accounts: u8::try_from(readonly_non_signers.len())
+            .expect("Solana messages have at most 256 accounts"),
+    };
+
+    let mut account_keys = vec![*payer];
+    account_keys.extend(writable_signers);
+    account_keys.extend(readonly_signers);
+    account_keys.extend(writable_non_signers);
+    account_keys.extend(readonly_non_signers);
+    assert!(
+        account_keys.len() <= 256,
+        "Solana messages have at most 256 accounts"
+    );
+
+    (header, account_keys)
+}
+
+fn compile_keys(instructions: &[Instruction], payer: &Pubkey) -> (MessageHeader, Vec<Pubkey>) {
+    order_keys(&collect_keys(instructions, payer), payer)
+}
+
+/// Replaces the program and accounts of `instructions` by their indexes in `account_keys`.
+pub(crate) fn compile_instructions(
+    instructions: &[Instruction],
+    account_keys: &[Pubkey],
+) -> Vec<CompiledInstruction> {
+    let index = |key: &Pubkey| -> u8 {
+        let position = account_keys
+            .iter()
+            .position(|k| k == key)
+            .expect("instruction accounts are account keys");
+        position as u8
+    };
+
+    instructions
+        .iter()
+        .map(|instruction| CompiledInstruction {
+            program_id_index: index(&instruction.program_id),
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|account| index(&account.pubkey))
+                .collect(),
+            data: instruction.data.clone(),
+        })
+        .collect()
+}
+
+/// Appends the compact array of `instructions`, each one its program index, its compact array
+/// of account indexes and its compact array of data.
+pub(crate) fn encode_instructions(buffer: &mut Vec<u8>, instructions: &[CompiledInstruction]) {
+    encode_length(buffer, instructions.len());
+    for instruction in instructions {
+        buffer.push(instruction.program_id_index);
+        encode_length(buffer, instruction.accounts.len());
+        buffer.extend_from_slice(&instruction.accounts);
+        encode_length(buffer, instruction.data.len());
+        buffer.extend_from_slice(&instruction.data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::types::AccountMeta;
+
+    fn to_solana_instruction(instruction: &Instruction) -> solana_instruction::Instruction {
+        solana_instruction::Instruction {
+            program_id: solana_pubkey::Pubkey::new_from_array(instruction.program_id.0),
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|account| solana_instruction::AccountMeta {
+                    pubkey: solana_pubkey::Pubkey::new_from_array(account.pubkey.0),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: instruction.data.clone(),
+        }
+    }
+
+    #[test]
+    fn test_serialize_against_solana() {
+        let payer = Pubkey([5; 32]);
+        let instructions = vec![
+            Instruction {
+                program_id: Pubkey([0; 32]),
+                accounts: vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(Pubkey([9; 32]), false),
+                ],
+                data: vec![2, 0, 0, 0, 64, 66, 15, 0, 0, 0, 0, 0],
+            },
+            Instruction {
+                program_id: Pubkey([7; 32]),
+                accounts: vec![
+                    AccountMeta::new_readonly(Pubkey([8; 32]), true),
+                    AccountMeta::new(Pubkey([3; 32]), true),
+                    AccountMeta::new_readonly(Pubkey([2; 32]), false),
+                    // Writable through its second use
+                    AccountMeta::new_readonly(Pubkey([9; 32]), false),
+                    AccountMeta::new(Pubkey([1; 32]), false),
+                ],
+                data: vec![1; 200],
+            },
+        ];
+        let recent_blockhash = Hash([4; 32]);
+
+        let message = Message::new(&instructions, &payer, recent_blockhash);
+        assert_eq!(
+            message.account_keys,
+            vec![
+                payer,
+                Pubkey([3; 32]),
+                Pubkey([8; 32]),
+                Pubkey([1; 32]),
+                Pubkey([9; 32]),
+                Pubkey([0; 32]),
+                Pubkey([2; 32]),
+                Pubkey([7; 32]),
+            ]
+        );
+        assert_eq!(
+            message.signer_keys(),
+            &[payer, Pubkey([3; 32]), Pubkey([8; 32])]
+        );
+
+        let solana_message = solana_message::Message::new_with_blockhash(
+            &instructions
+                .iter()
+                .map(to_solana_instruction)
+                .collect::<Vec<_>>(),
+            Some(&solana_pubkey::Pubkey::new_from_array(payer.0)),
+            &solana_hash::Hash::new_from_array(recent_blockhash.0),
+        );
+
+        assert_eq!(message.serialize(), solana_message.serialize());
+    }
+}
@@ -0,0 +1,238 @@
+//! Solana v0 message
+use serde::{Deserialize, Serialize};
+
+use super::{
+    message::{collect_keys, compile_instructions, encode_instructions, order_keys},
+    types::{
+        AddressLookupTableAccount, CompiledInstruction, Hash, Instruction,
+        MessageAddressTableLookup, MessageHeader, Pubkey,
+    },
+    utils::encode_length,
+};
+
+/// The first byte of versioned messages, its high bit telling them from legacy messages, whose
+/// first byte is their number of signers, and its low bits being the version.
+pub const MESSAGE_VERSION_PREFIX: u8 = 0x80;
+
+/// A Solana v0 message, whose accounts can be loaded from address lookup tables besides being
+/// listed in the message, fitting more accounts in a transaction than legacy messages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageV0 {
+    /// The number of signers and read-only accounts of the static account keys.
+    pub header: MessageHeader,
+    /// The accounts listed in the message, the fee payer first.
+    pub account_keys: Vec<Pubkey>,
+    /// A recent blockhash, the transaction expiring about a minute after it.
+    pub recent_blockhash: Hash,
+    /// The instructions, their accounts indexing the static account keys followed by the
+    /// writable and then the read-only accounts loaded from the lookup tables.
+    pub instructions: Vec<CompiledInstruction>,
+    /// The accounts loaded from each lookup table.
+    pub address_table_lookups: Vec<MessageAddressTableLookup>,
+}
+
+impl MessageV0 {
+    /// Compiles `instructions` into a message paid by `payer`, loading from
+    /// `address_lookup_tables` the accounts they hold which are neither signers nor programs.
+    ///
+    /// Accounts found in several tables are loaded from the first one, and tables holding none
+    /// of the accounts are left out.
+    ///
+    /// Panics if the message uses more than 256 accounts, or an account is found past the
+    /// 256th address of its table.
+    pub fn new(
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        address_lookup_tables: &[AddressLookupTableAccount],
+        recent_blockhash: Hash,
+    ) -> Self {
+        let mut keys = collect_keys(instructions, payer);
+
+        let mut address_table_lookups = Vec::new();
+        let mut writable_loaded = Vec::new();
+        let mut readonly_loaded = Vec::new();
+        for table in address_lookup_tables {
+            let mut lookup = MessageAddressTableLookup {
+                account_key: table.key,
+                writable_indexes: Vec::new(),
+                readonly_indexes: Vec::new(),
+            };
+
+            for is_writable in [true, false] {
+                let found: Vec<(Pubkey, u8)> = keys
+                    .iter()
+                    .filter(|(_, flags)| {
+                        !flags.is_signer && !flags.is_invoked && flags.is_writable == is_writable
+                    })
+                    .filter_map(|(key, _)| {
+                        let index = table.addresses.iter().position(|address| address == key)?;
+                        let index = u8::try_from(index)
+                            .expect("Lookup table indexes of v0 messages are at most 255");
+                        Some((*key, index))
+                    })
+                    .collect();
+
+                for (key, index) in found {
+                    keys.remove(&key);
+                    if is_writable {
+                        lookup.writable_indexes.push(index);
+                        writable_loaded.push(key);
+                    } else {
+                        lookup.readonly_indexes.push(index);
+                        readonly_loaded.push(key);
+                    }
+                }
+            }
+
+            if !lookup.writable_indexes.is_empty() || !lookup.readonly_indexes.is_empty() {
+                address_table_lookups.push(lookup);
+            }
+        }
+
+        let (header, account_keys) = order_keys(&keys, payer);
+        let all_keys: Vec<Pubkey> = account_keys
+            .iter()
+            .chain(&writable_loaded)
+            .chain(&readonly_loaded)
+            .copied()
+            .collect();
+        assert!(
+            all_keys.len() <= 256,
+            "Solana messages have at most 256 accounts"
+        );
+
+        Self {
+            instructions: compile_instructions(instructions, &all_keys),
+            header,
+            account_keys,
+            recent_blockhash,
+            address_table_lookups,
+        }
+    }
+
+    /// Encode the message, prefixed by its version, the payload signed with Ed25519 by each of
+    /// its signers
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buffer = vec![MESSAGE_VERSION_PREFIX];
+
+        buffer.push(self.header.num_required_signatures);
+        buffer.push(self.header.num_readonly_signed_accounts);
+        buffer.push(self.header.num_readonly_unsigned_accounts);
+
+        encode_length(&mut buffer, self.account_keys.len());
+        for key in &self.account_keys {
+            buffer.extend_from_slice(&key.0);
+        }
+
+        buffer.extend_from_slice(&self.recent_blockhash.0);
+
+        encode_instructions(&mut buffer, &self.instructions);
+
+        encode_length(&mut buffer, self.address_table_lookups.len());
+        for lookup in &self.address_table_lookups {
+            buffer.extend_from_slice(&lookup.account_key.0);
+            encode_length(&mut buffer, lookup.writable_indexes.len());
+            buffer.extend_from_slice(&lookup.writable_indexes);
+            encode_length(&mut buffer, lookup.readonly_indexes.len());
+            buffer.extend_from_slice(&lookup.readonly_indexes);
+        }
+
+        buffer
+    }
+
+    /// The accounts which must sign the transaction, in the order of their signatures.
+    pub fn signer_keys(&self) -> &[Pubkey] {
+        &self.account_keys[..self.header.num_required_signatures as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::types::AccountMeta;
+
+    fn to_solana_pubkey(pubkey: &Pubkey) -> solana_pubkey::Pubkey {
+        solana_pubkey::Pubkey::new_from_array(pubkey.0)
+    }
+
+    #[test]
+    fn test_serialize_against_solana() {
+        let payer = Pubkey([5; 32]);
+        let instructions = vec![
+            Instruction {
+                program_id: Pubkey([20; 32]),
+                accounts: vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(Pubkey([9; 32]), false),
+                    AccountMeta::new_readonly(Pubkey([10; 32]), false),
+                    AccountMeta::new(Pubkey([11; 32]), false),
+                ],
+                data: vec![1, 2, 3],
+            },
+            Instruction {
+                // A program found in a table is not loaded from it
+                program_id: Pubkey([12; 32]),
+                accounts: vec![
+                    AccountMeta::new_readonly(Pubkey([8; 32]), true),
+                    AccountMeta::new_readonly(Pubkey([13; 32]), false),
+                    AccountMeta::new(Pubkey([14; 32]), false),
+                ],
+                data: vec![4],
+            },
+        ];
+        let tables = vec![
+            AddressLookupTableAccount {
+                key: Pubkey([30; 32]),
+                addresses: vec![Pubkey([13; 32]), Pubkey([9; 32]), Pubkey([12; 32])],
+            },
+            AddressLookupTableAccount {
+                key: Pubkey([31; 32]),
+                addresses: vec![Pubkey([10; 32]), Pubkey([9; 32]), Pubkey([8; 32])],
+            },
+            // Holds none of the accounts left
+            AddressLookupTableAccount {
+                key: Pubkey([32; 32]),
+                addresses: vec![Pubkey([13; 32])],
+            },
+        ];
+        let recent_blockhash = Hash([4; 32]);
+
+        let message = MessageV0::new(&instructions, &payer, &tables, recent_blockhash);
+        assert_eq!(message.address_table_lookups.len(), 2);
+        assert_eq!(message.signer_keys(), &[payer, Pubkey([8; 32])]);
+
+        let solana_message = solana_message::v0::Message::try_compile(
+            &to_solana_pubkey(&payer),
+            &instructions
+                .iter()
+                .map(|instruction| solana_instruction::Instruction {
+                    program_id: to_solana_pubkey(&instruction.program_id),
+                    accounts: instruction
+                        .accounts
+                        .iter()
+                        .map(|account| solana_instruction::AccountMeta {
+                            pubkey: to_solana_pubkey(&account.pubkey),
+                            is_signer: account.is_signer,
+                            is_writable: account.is_writable,
+                        })
+                        .collect(),
+                    data: instruction.data.clone(),
+                })
+                .collect::<Vec<_>>(),
+            &tables
+                .iter()
+                .map(|table| solana_message::AddressLookupTableAccount {
+                    key: to_solana_pubkey(&table.key),
+                    addresses: table.addresses.iter().map(to_solana_pubkey).collect(),
+                })
+                .collect::<Vec<_>>(),
+            solana_hash::Hash::new_from_array(recent_blockhash.0),
+        )
+        .unwrap();
+
+        assert_eq!(
+            message.serialize(),
+            solana_message::VersionedMessage::V0(solana_message).serialize()
+        );
+    }
+}
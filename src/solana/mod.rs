@@ -0,0 +1,22 @@
+//! Transaction builder, encoders, types and utilities for Solana.
+pub mod compute_budget;
+mod message;
+mod message_v0;
+mod solana_transaction;
+mod solana_transaction_builder;
+pub mod spl_token;
+pub mod system_program;
+pub mod types;
+pub mod utils;
+mod versioned_message;
+
+/// Solana legacy message
+pub use message::Message;
+/// Solana v0 message
+pub use message_v0::{MessageV0, MESSAGE_VERSION_PREFIX};
+/// Solana transaction
+pub use solana_transaction::SolanaTransaction;
+/// Solana transaction builder
+pub use solana_transaction_builder::SolanaTransactionBuilder;
+/// Solana legacy or v0 message
+pub use versioned_message::VersionedMessage;
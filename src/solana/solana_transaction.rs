@@ -0,0 +1,137 @@
+//! Solana transaction
+use serde::{Deserialize, Serialize};
+
+use super::{
+    types::{Pubkey, Signature},
+    utils::encode_length,
+    VersionedMessage,
+};
+
+/// A Solana transaction, with a legacy or v0 message, encoded in the wire format of the
+/// `sendTransaction` RPC method.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::solana::types::{AccountMeta, Hash, Instruction, Pubkey, Signature};
+/// use signet_rs::solana::{Message, SolanaTransaction};
+///
+/// let payer = Pubkey([1; 32]);
+/// let instruction = Instruction {
+///     program_id: Pubkey([9; 32]),
+///     accounts: vec![AccountMeta::new(payer, true)],
+///     data: vec![0],
+/// };
+/// let tx = SolanaTransaction {
+///     message: Message::new(&[instruction], &payer, Hash([3; 32])).into(),
+/// };
+///
+/// // The payer signs the encoded message itself with Ed25519
+/// let payload = tx.build_for_signing();
+///
+/// let signature = Signature([7; 64]);
+/// let signed_tx = tx.build_with_signature(&signature);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolanaTransaction {
+    pub message: VersionedMessage,
+}
+
+impl SolanaTransaction {
+    /// Encode the message of the transaction, the payload signed with Ed25519 by its signers
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        self.message.serialize()
+    }
+
+    /// The accounts which must sign the transaction, the fee payer first.
+    pub fn signer_keys(&self) -> &[Pubkey] {
+        self.message.signer_keys()
+    }
+
+    /// Encode the transaction with the signature of its only signer, the fee payer
+    ///
+    /// Panics if the transaction has other signers, see
+    /// [`SolanaTransaction::build_with_signatures`].
+    pub fn build_with_signature(&self, signature: &Signature) -> Vec<u8> {
+        self.build_with_signatures(&[*signature])
+    }
+
+    /// Encode the transaction with the signatures of its signers, in the order of
+    /// [`SolanaTransaction::signer_keys`]
+    ///
+    /// Panics if the number of signatures is not the number of signers.
+    pub fn build_with_signatures(&self, signatures: &[Signature]) -> Vec<u8> {
+        assert_eq!(
+            signatures.len(),
+            self.signer_keys().len(),
+            "Solana transactions need one signature per signer"
+        );
+
+        let mut buffer = Vec::new();
+
+        encode_length(&mut buffer, signatures.len());
+        for signature in signatures {
+            buffer.extend_from_slice(&signature.0);
+        }
+
+        buffer.extend_from_slice(&self.build_for_signing());
+
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::{
+        types::{AccountMeta, Hash, Instruction},
+        Message,
+    };
+
+    #[test]
+    fn test_build_with_signatures() {
+        let payer = Pubkey([1; 32]);
+        let instruction = Instruction {
+            program_id: Pubkey([0; 32]),
+            accounts: vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(Pubkey([2; 32]), false),
+            ],
+            data: vec![2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0],
+        };
+        let tx = SolanaTransaction {
+            message: Message::new(&[instruction], &payer, Hash([3; 32])).into(),
+        };
+
+        let signature = Signature([7; 64]);
+        let signed = tx.build_with_signature(&signature);
+
+        let mut expected = vec![1];
+        expected.extend_from_slice(&[7; 64]);
+        // Header, 3 account keys and the blockhash
+        expected.extend_from_slice(&[1, 0, 1, 3]);
+        expected.extend_from_slice(&[1; 32]);
+        expected.extend_from_slice(&[2; 32]);
+        expected.extend_from_slice(&[0; 32]);
+        expected.extend_from_slice(&[3; 32]);
+        // One instruction, of the system program, over accounts 0 and 1
+        expected.extend_from_slice(&[1, 2, 2, 0, 1, 12, 2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(signed, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "one signature per signer")]
+    fn test_build_with_missing_signature() {
+        let payer = Pubkey([1; 32]);
+        let instruction = Instruction {
+            program_id: Pubkey([0; 32]),
+            accounts: vec![AccountMeta::new_readonly(Pubkey([2; 32]), true)],
+            data: vec![],
+        };
+        let tx = SolanaTransaction {
+            message: Message::new(&[instruction], &payer, Hash([3; 32])).into(),
+        };
+
+        tx.build_with_signature(&Signature([7; 64]));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::transaction_builder::TxBuilder;
+
+use super::{
+    solana_transaction::SolanaTransaction,
+    system_program::advance_nonce_account,
+    types::{AddressLookupTableAccount, Hash, Instruction, Pubkey},
+    Message, MessageV0,
+};
+
+pub struct SolanaTransactionBuilder {
+    payer: Option<Pubkey>,
+    recent_blockhash: Option<Hash>,
+    instructions: Option<Vec<Instruction>>,
+    address_lookup_tables: Option<Vec<AddressLookupTableAccount>>,
+    durable_nonce: Option<(Pubkey, Pubkey, Hash)>,
+}
+
+impl Default for SolanaTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<SolanaTransaction> for SolanaTransactionBuilder {
+    fn build(&self) -> SolanaTransaction {
+        let payer = self.payer.expect("payer is mandatory");
+        let mut instructions = self.instructions.clone().unwrap_or_default();
+
+        let recent_blockhash = match self.durable_nonce {
+            Some((nonce_account, nonce_authority, nonce)) => {
+                instructions.insert(0, advance_nonce_account(&nonce_account, &nonce_authority));
+                nonce
+            }
+            None => self
+                .recent_blockhash
+                .expect("recent_blockhash is mandatory"),
+        };
+
+        let message = match &self.address_lookup_tables {
+            Some(tables) => MessageV0::new(&instructions, &payer, tables, recent_blockhash).into(),
+            None => Message::new(&instructions, &payer, recent_blockhash).into(),
+        };
+
+        SolanaTransaction { message }
+    }
+}
+
+impl SolanaTransactionBuilder {
+    pub const fn new() -> Self {
+        Self {
+            payer: None,
+            recent_blockhash: None,
+            instructions: None,
+            address_lookup_tables: None,
+            durable_nonce: None,
+        }
+    }
+
+    /// Account paying the fees of the transaction, its first signer.
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// A recent blockhash, from the `getLatestBlockhash` RPC method.
+    pub const fn recent_blockhash(mut self, recent_blockhash: Hash) -> Self {
+        self.recent_blockhash = Some(recent_blockhash);
+        self
+    }
+
+    /// Instructions of the transaction, executed in order.
+    pub fn instructions(mut self, instructions: Vec<Instruction>) -> Self {
+        self.instructions = Some(instructions);
+        self
+    }
+
+    /// Address lookup tables to load the accounts of the instructions from, building a v0
+    /// transaction instead of a legacy one.
+    pub fn address_lookup_tables(mut self, tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.address_lookup_tables = Some(tables);
+        self
+    }
+
+    /// Uses the durable `nonce` stored in `nonce_account` in place of the recent blockhash, so
+    /// that the transaction does not expire before it is signed and sent, the nonce being
+    /// advanced by `nonce_authority`, which signs, in the first instruction.
+    ///
+    /// The nonce is read from the account with [`super::system_program::NonceAccount`].
+    pub const fn durable_nonce(
+        mut self,
+        nonce_account: Pubkey,
+        nonce_authority: Pubkey,
+        nonce: Hash,
+    ) -> Self {
+        self.durable_nonce = Some((nonce_account, nonce_authority, nonce));
+        self
+    }
+
+    /// Append an instruction to the ones of the transaction, all of them being executed
+    /// atomically.
+    pub fn add_instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions
+            .get_or_insert_with(Vec::new)
+            .push(instruction);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::types::AccountMeta;
+    use crate::TransactionBuilder;
+
+    #[test]
+    fn test_build() {
+        let payer = Pubkey([1; 32]);
+        let instruction = Instruction {
+            program_id: Pubkey([9; 32]),
+            accounts: vec![AccountMeta::new(Pubkey([2; 32]), false)],
+            data: vec![1, 2, 3],
+        };
+
+        let tx: SolanaTransaction = TransactionBuilder::new::<SolanaTransactionBuilder>()
+            .payer(payer)
+            .recent_blockhash(Hash([3; 32]))
+            .add_instruction(instruction.clone())
+            .build();
+
+        assert_eq!(
+            tx.message,
+            Message::new(core::slice::from_ref(&instruction), &payer, Hash([3; 32])).into()
+        );
+        assert_eq!(tx.signer_keys(), &[payer]);
+
+        let table = AddressLookupTableAccount {
+            key: Pubkey([4; 32]),
+            addresses: vec![Pubkey([2; 32])],
+        };
+        let tx: SolanaTransaction = TransactionBuilder::new::<SolanaTransactionBuilder>()
+            .payer(payer)
+            .recent_blockhash(Hash([3; 32]))
+            .add_instruction(instruction.clone())
+            .address_lookup_tables(vec![table.clone()])
+            .build();
+
+        assert_eq!(
+            tx.message,
+            MessageV0::new(&[instruction], &payer, &[table], Hash([3; 32])).into()
+        );
+        assert_eq!(tx.build_for_signing()[0], 0x80);
+    }
+
+    #[test]
+    fn test_build_with_durable_nonce() {
+        let payer = Pubkey([1; 32]);
+        let nonce_account = Pubkey([5; 32]);
+        let instruction = Instruction {
+            program_id: Pubkey([9; 32]),
+            accounts: vec![AccountMeta::new(Pubkey([2; 32]), false)],
+            data: vec![1, 2, 3],
+        };
+
+        let tx: SolanaTransaction = TransactionBuilder::new::<SolanaTransactionBuilder>()
+            .payer(payer)
+            .add_instruction(instruction.clone())
+            .durable_nonce(nonce_account, payer, Hash([6; 32]))
+            .build();
+
+        assert_eq!(
+            tx.message,
+            Message::new(
+                &[advance_nonce_account(&nonce_account, &payer), instruction],
+                &payer,
+                Hash([6; 32])
+            )
+            .into()
+        );
+        assert_eq!(tx.signer_keys(), &[payer]);
+    }
+
+    #[test]
+    #[should_panic(expected = "recent_blockhash is mandatory")]
+    fn test_build_without_blockhash() {
+        let _: SolanaTransaction = SolanaTransactionBuilder::new()
+            .payer(Pubkey([1; 32]))
+            .build();
+    }
+}
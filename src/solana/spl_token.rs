@@ -0,0 +1,297 @@
+//! Instructions of the SPL Token program, moving tokens between token accounts, and of the
+//! Associated Token Account program, creating the token account of a wallet for a mint at its
+//! canonical address.
+//!
+//! The token instructions take the id of the token program, either [`TOKEN_PROGRAM_ID`] or
+//! [`TOKEN_2022_PROGRAM_ID`], which share their encoding.
+use super::{
+    system_program::SYSTEM_PROGRAM_ID,
+    types::{AccountMeta, Instruction, Pubkey},
+};
+
+/// The address of the SPL Token program, `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`.
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey([
+    6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172, 28, 180, 133, 237,
+    95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169,
+]);
+
+/// The address of the SPL Token-2022 program, `TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb`.
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = Pubkey([
+    6, 221, 246, 225, 238, 117, 143, 222, 24, 66, 93, 188, 228, 108, 205, 218, 182, 26, 252, 77,
+    131, 185, 13, 39, 254, 189, 249, 40, 216, 161, 139, 252,
+]);
+
+/// The address of the Associated Token Account program,
+/// `ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL`.
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = Pubkey([
+    140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142, 13, 131, 11, 90, 19, 153, 218,
+    255, 16, 132, 4, 142, 123, 216, 219, 233, 248, 89,
+]);
+
+/// The tag of the `Approve` token instruction.
+const APPROVE: u8 = 4;
+/// The tag of the `TransferChecked` token instruction.
+const TRANSFER_CHECKED: u8 = 12;
+
+/// The tag of the `Create` associated token account instruction.
+const CREATE: u8 = 0;
+/// The tag of the `CreateIdempotent` associated token account instruction.
+const CREATE_IDEMPOTENT: u8 = 1;
+
+/// Transfers `amount` tokens of `mint`, with `decimals` decimals, from the token account `source`
+/// to the token account `destination`, `owner`, the owner or delegate of `source`, signing.
+///
+/// The transfer fails if `decimals` is not the number of decimals of the mint, guarding against
+/// amounts off by orders of magnitude.
+pub fn transfer_checked(
+    token_program_id: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    let mut data = vec![TRANSFER_CHECKED];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+
+    Instruction {
+        program_id: *token_program_id,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data,
+    }
+}
+
+/// Approves `delegate` to transfer up to `amount` tokens from the token account `source`, its
+/// `owner` signing.
+pub fn approve(
+    token_program_id: &Pubkey,
+    source: &Pubkey,
+    delegate: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = vec![APPROVE];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: *token_program_id,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*delegate, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data,
+    }
+}
+
+/// The address of the associated token account of `wallet` for `mint`, a token account of the
+/// SPL Token program.
+pub fn get_associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(wallet, mint, &TOKEN_PROGRAM_ID)
+}
+
+/// The address of the associated token account of `wallet` for `mint`, a token account of the
+/// token program `token_program_id`, the program derived address of the wallet, token program
+/// and mint for the Associated Token Account program.
+pub fn get_associated_token_address_with_program_id(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[&wallet.0, &token_program_id.0, &mint.0],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Creates the associated token account of `wallet` for `mint`, funded by `payer`, which signs.
+///
+/// The instruction fails if the account already exists.
+pub fn create_associated_token_account(
+    payer: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    associated_token_account_instruction(payer, wallet, mint, token_program_id, CREATE)
+}
+
+/// Creates the associated token account of `wallet` for `mint`, funded by `payer`, which signs,
+/// unless it already exists.
+pub fn create_associated_token_account_idempotent(
+    payer: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    associated_token_account_instruction(payer, wallet, mint, token_program_id, CREATE_IDEMPOTENT)
+}
+
+fn associated_token_account_instruction(
+    payer: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+    tag: u8,
+) -> Instruction {
+    let address = get_associated_token_address_with_program_id(wallet, mint, token_program_id);
+
+    Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(address, false),
+            AccountMeta::new_readonly(*wallet, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+        data: vec![tag],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::system_program::tests::{assert_eq_solana, to_solana_pubkey};
+
+    #[test]
+    fn test_program_ids() {
+        assert_eq!(
+            TOKEN_PROGRAM_ID.to_string(),
+            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        );
+        assert_eq!(
+            TOKEN_2022_PROGRAM_ID.to_string(),
+            "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+        );
+        assert_eq!(
+            ASSOCIATED_TOKEN_PROGRAM_ID.to_string(),
+            "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        );
+    }
+
+    #[test]
+    fn test_token_instructions_against_spl() {
+        let source = Pubkey([1; 32]);
+        let mint = Pubkey([2; 32]);
+        let destination = Pubkey([3; 32]);
+        let owner = Pubkey([4; 32]);
+
+        // spl-token only builds instructions of the original token program
+        let program_id = TOKEN_PROGRAM_ID;
+        assert_eq_solana(
+            &transfer_checked(
+                &program_id,
+                &source,
+                &mint,
+                &destination,
+                &owner,
+                1_500_000,
+                6,
+            ),
+            &spl_token::instruction::transfer_checked(
+                &to_solana_pubkey(&program_id),
+                &to_solana_pubkey(&source),
+                &to_solana_pubkey(&mint),
+                &to_solana_pubkey(&destination),
+                &to_solana_pubkey(&owner),
+                &[],
+                1_500_000,
+                6,
+            )
+            .unwrap(),
+        );
+        assert_eq_solana(
+            &approve(&program_id, &source, &destination, &owner, u64::MAX),
+            &spl_token::instruction::approve(
+                &to_solana_pubkey(&program_id),
+                &to_solana_pubkey(&source),
+                &to_solana_pubkey(&destination),
+                &to_solana_pubkey(&owner),
+                &[],
+                u64::MAX,
+            )
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_associated_token_account_against_spl() {
+        use spl_associated_token_account::{
+            get_associated_token_address_with_program_id, instruction,
+        };
+
+        let payer = Pubkey([1; 32]);
+        let wallet: Pubkey = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"
+            .parse()
+            .unwrap();
+        let mint: Pubkey = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+            .parse()
+            .unwrap();
+
+        for program_id in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+            let address =
+                super::get_associated_token_address_with_program_id(&wallet, &mint, &program_id);
+            assert_eq!(
+                to_solana_pubkey(&address),
+                get_associated_token_address_with_program_id(
+                    &to_solana_pubkey(&wallet),
+                    &to_solana_pubkey(&mint),
+                    &to_solana_pubkey(&program_id),
+                )
+            );
+            assert!(!address.is_on_curve());
+            assert!(wallet.is_on_curve());
+
+            assert_eq_solana(
+                &create_associated_token_account(&payer, &wallet, &mint, &program_id),
+                &instruction::create_associated_token_account(
+                    &to_solana_pubkey(&payer),
+                    &to_solana_pubkey(&wallet),
+                    &to_solana_pubkey(&mint),
+                    &to_solana_pubkey(&program_id),
+                ),
+            );
+            assert_eq_solana(
+                &create_associated_token_account_idempotent(&payer, &wallet, &mint, &program_id),
+                &instruction::create_associated_token_account_idempotent(
+                    &to_solana_pubkey(&payer),
+                    &to_solana_pubkey(&wallet),
+                    &to_solana_pubkey(&mint),
+                    &to_solana_pubkey(&program_id),
+                ),
+            );
+        }
+
+        assert_eq!(
+            get_associated_token_address(&wallet, &mint),
+            super::get_associated_token_address_with_program_id(&wallet, &mint, &TOKEN_PROGRAM_ID)
+        );
+    }
+
+    #[test]
+    fn test_program_address() {
+        let program_id = ASSOCIATED_TOKEN_PROGRAM_ID;
+        let (address, bump) = Pubkey::find_program_address(&[b"seed"], &program_id);
+        assert_eq!(
+            Pubkey::create_program_address(&[b"seed", &[bump]], &program_id),
+            Ok(address)
+        );
+        assert_eq!(
+            (to_solana_pubkey(&address), bump),
+            solana_pubkey::Pubkey::find_program_address(&[b"seed"], &to_solana_pubkey(&program_id))
+        );
+
+        assert!(Pubkey::create_program_address(&[&[0; 33]], &program_id).is_err());
+        assert!(Pubkey::create_program_address(&[b"seed".as_slice(); 17], &program_id).is_err());
+    }
+}
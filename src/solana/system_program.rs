@@ -0,0 +1,232 @@
+//! Instructions of the System Program, creating accounts, transferring SOL and advancing
+//! durable nonces, their data encoded like the bincode-serialized `SystemInstruction` enum.
+use super::types::{AccountMeta, Hash, Instruction, Pubkey};
+
+/// The address of the System Program, `11111111111111111111111111111111`.
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey([0; 32]);
+
+/// The address of the `RecentBlockhashes` sysvar, read when advancing a nonce,
+/// `SysvarRecentB1ockHashes11111111111111111111`.
+pub const SYSVAR_RECENT_BLOCKHASHES_ID: Pubkey = Pubkey([
+    6, 167, 213, 23, 25, 44, 86, 142, 224, 138, 132, 95, 115, 210, 151, 136, 207, 3, 92, 49, 69,
+    178, 26, 179, 68, 216, 6, 46, 169, 64, 0, 0,
+]);
+
+/// The size of the data of a nonce account.
+pub const NONCE_ACCOUNT_LENGTH: usize = 80;
+
+/// The index of the `CreateAccount` variant of `SystemInstruction`.
+const CREATE_ACCOUNT: u32 = 0;
+/// The index of the `Assign` variant of `SystemInstruction`.
+const ASSIGN: u32 = 1;
+/// The index of the `Transfer` variant of `SystemInstruction`.
+const TRANSFER: u32 = 2;
+/// The index of the `AdvanceNonceAccount` variant of `SystemInstruction`.
+const ADVANCE_NONCE_ACCOUNT: u32 = 4;
+
+/// The version of the current layout of nonce accounts.
+const NONCE_VERSION_CURRENT: u32 = 1;
+/// The state of an initialized nonce account.
+const NONCE_STATE_INITIALIZED: u32 = 1;
+
+/// Transfers `lamports` from `from`, which signs, to `to`.
+pub fn transfer(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    let mut data = TRANSFER.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Instruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(*from, true), AccountMeta::new(*to, false)],
+        data,
+    }
+}
+
+/// Creates the account `to`, funded by `from` with `lamports`, with `space` bytes of data and
+/// owned by the program `owner`, both accounts signing.
+pub fn create_account(
+    from: &Pubkey,
+    to: &Pubkey,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+) -> Instruction {
+    let mut data = CREATE_ACCOUNT.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    data.extend_from_slice(&space.to_le_bytes());
+    data.extend_from_slice(&owner.0);
+
+    Instruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(*from, true), AccountMeta::new(*to, true)],
+        data,
+    }
+}
+
+/// Assigns the account `pubkey`, which signs, to the program `owner`.
+pub fn assign(pubkey: &Pubkey, owner: &Pubkey) -> Instruction {
+    let mut data = ASSIGN.to_le_bytes().to_vec();
+    data.extend_from_slice(&owner.0);
+
+    Instruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(*pubkey, true)],
+        data,
+    }
+}
+
+/// Advances the nonce stored in `nonce_account`, its `authority` signing.
+///
+/// A transaction using the nonce in place of a recent blockhash, so that it does not expire,
+/// must advance it in its first instruction, see
+/// [`super::SolanaTransactionBuilder::durable_nonce`].
+pub fn advance_nonce_account(nonce_account: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*nonce_account, false),
+            AccountMeta::new_readonly(SYSVAR_RECENT_BLOCKHASHES_ID, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: ADVANCE_NONCE_ACCOUNT.to_le_bytes().to_vec(),
+    }
+}
+
+/// The state of an initialized nonce account, read from its data as returned by the
+/// `getAccountInfo` RPC method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonceAccount {
+    /// The account allowed to advance the nonce.
+    pub authority: Pubkey,
+    /// The nonce, the recent blockhash of the transactions using it.
+    pub nonce: Hash,
+    /// The fee per signature when the nonce was stored.
+    pub lamports_per_signature: u64,
+}
+
+impl NonceAccount {
+    /// Reads the state of a nonce account from its data, failing unless it is an initialized
+    /// nonce account of the current version.
+    pub fn from_account_data(data: &[u8]) -> Result<Self, String> {
+        if data.len() != NONCE_ACCOUNT_LENGTH {
+            return Err(format!("Invalid nonce account length: {}", data.len()));
+        }
+
+        let version = u32::from_le_bytes(data[0..4].try_into().expect("4 bytes"));
+        if version != NONCE_VERSION_CURRENT {
+            return Err(format!("Unsupported nonce account version: {version}"));
+        }
+        let state = u32::from_le_bytes(data[4..8].try_into().expect("4 bytes"));
+        if state != NONCE_STATE_INITIALIZED {
+            return Err("Nonce account is not initialized".to_string());
+        }
+
+        Ok(Self {
+            authority: Pubkey(data[8..40].try_into().expect("32 bytes")),
+            nonce: Hash(data[40..72].try_into().expect("32 bytes")),
+            lamports_per_signature: u64::from_le_bytes(data[72..80].try_into().expect("8 bytes")),
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn to_solana_pubkey(pubkey: &Pubkey) -> solana_pubkey::Pubkey {
+        solana_pubkey::Pubkey::new_from_array(pubkey.0)
+    }
+
+    pub(crate) fn assert_eq_solana(
+        instruction: &Instruction,
+        expected: &solana_instruction::Instruction,
+    ) {
+        assert_eq!(instruction.program_id.0, expected.program_id.to_bytes());
+        assert_eq!(instruction.data, expected.data);
+        assert_eq!(instruction.accounts.len(), expected.accounts.len());
+        for (account, expected) in instruction.accounts.iter().zip(&expected.accounts) {
+            assert_eq!(account.pubkey.0, expected.pubkey.to_bytes());
+            assert_eq!(account.is_signer, expected.is_signer);
+            assert_eq!(account.is_writable, expected.is_writable);
+        }
+    }
+
+    #[test]
+    fn test_instructions_against_solana() {
+        use solana_system_interface::instruction as system_instruction;
+
+        let from = Pubkey([1; 32]);
+        let to = Pubkey([2; 32]);
+        let owner = Pubkey([3; 32]);
+        assert_eq!(
+            SYSTEM_PROGRAM_ID.to_string(),
+            "11111111111111111111111111111111"
+        );
+
+        assert_eq_solana(
+            &transfer(&from, &to, 1_000_000),
+            &system_instruction::transfer(
+                &to_solana_pubkey(&from),
+                &to_solana_pubkey(&to),
+                1_000_000,
+            ),
+        );
+        assert_eq_solana(
+            &create_account(&from, &to, 2_039_280, 165, &owner),
+            &system_instruction::create_account(
+                &to_solana_pubkey(&from),
+                &to_solana_pubkey(&to),
+                2_039_280,
+                165,
+                &to_solana_pubkey(&owner),
+            ),
+        );
+        assert_eq_solana(
+            &assign(&to, &owner),
+            &system_instruction::assign(&to_solana_pubkey(&to), &to_solana_pubkey(&owner)),
+        );
+        assert_eq_solana(
+            &advance_nonce_account(&to, &from),
+            &system_instruction::advance_nonce_account(
+                &to_solana_pubkey(&to),
+                &to_solana_pubkey(&from),
+            ),
+        );
+        assert_eq!(
+            SYSVAR_RECENT_BLOCKHASHES_ID.to_string(),
+            "SysvarRecentB1ockHashes11111111111111111111"
+        );
+    }
+
+    #[test]
+    fn test_nonce_account_against_solana() {
+        use solana_nonce::{
+            state::{Data, DurableNonce, State},
+            versions::Versions,
+        };
+
+        let authority = Pubkey([1; 32]);
+        let durable_nonce =
+            DurableNonce::from_blockhash(&solana_hash::Hash::new_from_array([2; 32]));
+        let state = State::Initialized(Data::new(
+            to_solana_pubkey(&authority),
+            durable_nonce,
+            5_000,
+        ));
+        let data = bincode::serialize(&Versions::new(state)).unwrap();
+        assert_eq!(data.len(), NONCE_ACCOUNT_LENGTH);
+
+        assert_eq!(
+            NonceAccount::from_account_data(&data),
+            Ok(NonceAccount {
+                authority,
+                nonce: Hash(durable_nonce.as_hash().to_bytes()),
+                lamports_per_signature: 5_000,
+            })
+        );
+
+        let mut data = bincode::serialize(&Versions::new(State::Uninitialized)).unwrap();
+        data.resize(NONCE_ACCOUNT_LENGTH, 0);
+        assert!(NonceAccount::from_account_data(&data).is_err());
+        assert!(NonceAccount::from_account_data(&[0; 8]).is_err());
+    }
+}
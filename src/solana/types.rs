@@ -0,0 +1,232 @@
+//! Types used by the Solana transaction builder.
+use core::{fmt, str::FromStr};
+
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+use crate::signer::types::EddsaSignatureResponse;
+
+/// Implements base58 formatting and parsing, and serialization in JSON as base58 strings, for a
+/// newtype over a byte array.
+macro_rules! base58_bytes {
+    ($name:ident, $len:expr) => {
+        impl FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let bytes = bs58::decode(s)
+                    .into_vec()
+                    .map_err(|e| format!("Invalid base58 {}: {}", stringify!($name), e))?;
+
+                <[u8; $len]>::try_from(bytes)
+                    .map(Self)
+                    .map_err(|_| format!("Invalid {} length: {}", stringify!($name), s))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&bs58::encode(self.0).into_string())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = <String as Deserialize>::deserialize(deserializer)?;
+                s.parse().map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+/// The address of an account, an Ed25519 public key or a program derived address.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pubkey(pub [u8; 32]);
+
+base58_bytes!(Pubkey, 32);
+
+/// The maximum number of seeds of a program derived address.
+pub const MAX_SEEDS: usize = 16;
+/// The maximum length of a seed of a program derived address.
+pub const MAX_SEED_LEN: usize = 32;
+
+/// The marker hashed into program derived addresses, after the seeds and program id.
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+impl Pubkey {
+    /// Whether the address is an Ed25519 public key, a point of the curve, rather than a program
+    /// derived address no private key signs for.
+    pub fn is_on_curve(&self) -> bool {
+        CompressedEdwardsY(self.0).decompress().is_some()
+    }
+
+    /// The program derived address of `seeds` for `program_id`, the SHA-256 of the seeds, the
+    /// program id and a marker, failing if the hash is a point of the curve.
+    pub fn create_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Result<Self, String> {
+        if seeds.len() > MAX_SEEDS {
+            return Err(format!("Too many seeds: {}", seeds.len()));
+        }
+
+        let mut hasher = Sha256::new();
+        for seed in seeds {
+            if seed.len() > MAX_SEED_LEN {
+                return Err(format!("Seed too long: {} bytes", seed.len()));
+            }
+            hasher.update(seed);
+        }
+        hasher.update(program_id.0);
+        hasher.update(PDA_MARKER);
+
+        let address = Self(hasher.finalize().into());
+        if address.is_on_curve() {
+            return Err("Program derived address is on the curve".to_string());
+        }
+
+        Ok(address)
+    }
+
+    /// The canonical program derived address of `seeds` for `program_id`, and its bump seed, the
+    /// highest appended to the seeds giving an address off the curve.
+    pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Self, u8) {
+        for bump in (0..=u8::MAX).rev() {
+            let bump_seed = [bump];
+            let mut seeds_with_bump = seeds.to_vec();
+            seeds_with_bump.push(&bump_seed);
+            if let Ok(address) = Self::create_program_address(&seeds_with_bump, program_id) {
+                return (address, bump);
+            }
+        }
+
+        panic!("No bump seed gives a program derived address off the curve");
+    }
+}
+
+/// A SHA-256 hash, like the recent blockhash referenced by transactions to expire after a while.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Hash(pub [u8; 32]);
+
+base58_bytes!(Hash, 32);
+
+/// An Ed25519 signature of a transaction message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signature(pub [u8; 64]);
+
+base58_bytes!(Signature, 64);
+
+impl From<EddsaSignatureResponse> for Signature {
+    fn from(response: EddsaSignatureResponse) -> Self {
+        Self(response.signature)
+    }
+}
+
+/// An account an instruction reads or writes, and whether it must sign the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    /// A writable account.
+    pub const fn new(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: true,
+        }
+    }
+
+    /// A read-only account.
+    pub const fn new_readonly(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: false,
+        }
+    }
+}
+
+/// A call to a program, with the accounts it reads or writes and its program-specific data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// An instruction of a message, its program and accounts given by their indexes in the account
+/// keys of the message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// The number of signers and read-only accounts of a message, its account keys being ordered
+/// as writable signers, read-only signers, writable non-signers and read-only non-signers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageHeader {
+    pub num_required_signatures: u8,
+    pub num_readonly_signed_accounts: u8,
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+/// The accounts of a v0 message loaded from an address lookup table, by their indexes in the
+/// table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageAddressTableLookup {
+    pub account_key: Pubkey,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+/// An address lookup table, an on-chain account holding the addresses v0 messages can load
+/// their accounts from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressLookupTableAccount {
+    /// The address of the table.
+    pub key: Pubkey,
+    /// The addresses held by the table, as read from its account.
+    pub addresses: Vec<Pubkey>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58() {
+        let system_program: Pubkey = "11111111111111111111111111111111".parse().unwrap();
+        assert_eq!(system_program, Pubkey([0; 32]));
+
+        let pubkey: Pubkey = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            pubkey.to_string(),
+            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        );
+        let json = serde_json::to_string(&pubkey).unwrap();
+        assert_eq!(json, r#""TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA""#);
+        assert_eq!(serde_json::from_str::<Pubkey>(&json).unwrap(), pubkey);
+
+        assert!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5"
+            .parse::<Pubkey>()
+            .is_err());
+        assert!("0okenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+            .parse::<Hash>()
+            .is_err());
+
+        let signature = Signature::from(EddsaSignatureResponse::from([7; 64]));
+        assert_eq!(signature.to_string().parse::<Signature>(), Ok(signature));
+    }
+}
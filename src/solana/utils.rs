@@ -0,0 +1,64 @@
+//! Utilities for Solana, like the compact-u16 lengths prefixing its arrays.
+
+/// Appends `len` to `buffer` as a compact-u16, 7 bits per byte with the high bit set on every
+/// byte but the last, like the `short_vec` encoding of Solana.
+///
+/// Panics if `len` does not fit in a u16.
+pub fn encode_length(buffer: &mut Vec<u8>, len: usize) {
+    let mut rem = u16::try_from(len).expect("Solana arrays have at most u16::MAX items");
+
+    loop {
+        let byte = (rem & 0x7f) as u8;
+        rem >>= 7;
+        if rem == 0 {
+            buffer.push(byte);
+            return;
+        }
+        buffer.push(byte | 0x80);
+    }
+}
+
+/// Decodes the compact-u16 at the start of `bytes`, returning it and the number of bytes it
+/// takes.
+pub fn decode_length(bytes: &[u8]) -> Result<(usize, usize), String> {
+    let mut len: usize = 0;
+
+    for (i, byte) in bytes.iter().take(3).enumerate() {
+        len |= ((byte & 0x7f) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            // Lengths must be minimally encoded and fit in a u16
+            if (i > 0 && *byte == 0) || len > u16::MAX as usize {
+                return Err("Invalid compact-u16 length".to_string());
+            }
+            return Ok((len, i + 1));
+        }
+    }
+
+    Err("Invalid compact-u16 length".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_u16() {
+        for (len, bytes) in [
+            (0, vec![0x00]),
+            (0x7f, vec![0x7f]),
+            (0x80, vec![0x80, 0x01]),
+            (0x3fff, vec![0xff, 0x7f]),
+            (0x4000, vec![0x80, 0x80, 0x01]),
+            (0xffff, vec![0xff, 0xff, 0x03]),
+        ] {
+            let mut buffer = Vec::new();
+            encode_length(&mut buffer, len);
+            assert_eq!(buffer, bytes);
+            assert_eq!(decode_length(&bytes), Ok((len, bytes.len())));
+        }
+
+        assert!(decode_length(&[0x80, 0x00]).is_err());
+        assert!(decode_length(&[0xff, 0xff, 0x04]).is_err());
+        assert!(decode_length(&[0x80]).is_err());
+    }
+}
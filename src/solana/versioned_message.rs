@@ -0,0 +1,41 @@
+//! Solana versioned message
+use serde::{Deserialize, Serialize};
+
+use super::{message_v0::MessageV0, types::Pubkey, Message};
+
+/// A legacy or v0 Solana message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VersionedMessage {
+    Legacy(Message),
+    V0(MessageV0),
+}
+
+impl VersionedMessage {
+    /// Encode the message, the payload signed with Ed25519 by each of its signers
+    pub fn serialize(&self) -> Vec<u8> {
+        match self {
+            Self::Legacy(message) => message.serialize(),
+            Self::V0(message) => message.serialize(),
+        }
+    }
+
+    /// The accounts which must sign the transaction, in the order of their signatures.
+    pub fn signer_keys(&self) -> &[Pubkey] {
+        match self {
+            Self::Legacy(message) => message.signer_keys(),
+            Self::V0(message) => message.signer_keys(),
+        }
+    }
+}
+
+impl From<Message> for VersionedMessage {
+    fn from(message: Message) -> Self {
+        Self::Legacy(message)
+    }
+}
+
+impl From<MessageV0> for VersionedMessage {
+    fn from(message: MessageV0) -> Self {
+        Self::V0(message)
+    }
+}
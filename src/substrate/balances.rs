@@ -0,0 +1,92 @@
+//! Calls of the balances pallet, transferring the native token of a chain, like DOT or KSM.
+//!
+//! Calls are encoded as the index of their pallet in the runtime, the index of the call in the
+//! pallet, then its arguments, the indices of the pallet differing between chains.
+use serde::{Deserialize, Serialize};
+
+use super::{types::MultiAddress, utils::encode_compact};
+
+/// Index of the balances pallet in the runtime of Polkadot.
+pub const POLKADOT_BALANCES_PALLET: u8 = 5;
+/// Index of the balances pallet in the runtimes of Kusama and Westend.
+pub const KUSAMA_BALANCES_PALLET: u8 = 4;
+
+/// Index of `transfer_allow_death` in the balances pallet.
+pub const TRANSFER_ALLOW_DEATH: u8 = 0;
+/// Index of `transfer_keep_alive` in the balances pallet.
+pub const TRANSFER_KEEP_ALIVE: u8 = 3;
+
+/// Transfers `value` planck to `dest`, failing if it would leave the sender below the
+/// existential deposit when `keep_alive` is set, else reaping the account of the sender.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::substrate::balances::{Transfer, POLKADOT_BALANCES_PALLET};
+/// use signet_rs::substrate::types::{AccountId, PLANCK_PER_DOT};
+///
+/// let (dest, _) = AccountId::from_ss58("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5").unwrap();
+/// let call = Transfer {
+///     pallet_index: POLKADOT_BALANCES_PALLET,
+///     dest: dest.into(),
+///     value: PLANCK_PER_DOT,
+///     keep_alive: true,
+/// }
+/// .encode();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transfer {
+    /// The index of the balances pallet in the runtime of the chain.
+    pub pallet_index: u8,
+    pub dest: MultiAddress,
+    pub value: u128,
+    pub keep_alive: bool,
+}
+
+impl Transfer {
+    /// Encode the call, the call data of an extrinsic.
+    pub fn encode(&self) -> Vec<u8> {
+        let call_index = if self.keep_alive {
+            TRANSFER_KEEP_ALIVE
+        } else {
+            TRANSFER_ALLOW_DEATH
+        };
+
+        let mut buffer = vec![self.pallet_index, call_index];
+        buffer.extend_from_slice(&self.dest.encode());
+        encode_compact(&mut buffer, self.value);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::types::{AccountId, PLANCK_PER_DOT};
+
+    #[test]
+    fn test_encode_transfer() {
+        let transfer = Transfer {
+            pallet_index: POLKADOT_BALANCES_PALLET,
+            dest: AccountId([1; 32]).into(),
+            value: PLANCK_PER_DOT,
+            keep_alive: true,
+        };
+
+        assert_eq!(
+            hex::encode(transfer.encode()),
+            concat!(
+                "0503",
+                "00",
+                "0101010101010101010101010101010101010101010101010101010101010101",
+                "0700e40b5402"
+            )
+        );
+
+        let allow_death = Transfer {
+            keep_alive: false,
+            ..transfer
+        };
+        assert_eq!(allow_death.encode()[..2], [5, 0]);
+    }
+}
@@ -0,0 +1,163 @@
+//! The signed extensions of the runtimes of Substrate chains, which differ between chains and
+//! define the data extrinsics include and sign after their call.
+use serde::{Deserialize, Serialize};
+
+/// A signed extension of a runtime, in the order of the `SignedExtra` of the runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignedExtension {
+    CheckNonZeroSender,
+    /// Signs the spec version of the runtime.
+    CheckSpecVersion,
+    /// Signs the transaction version of the runtime.
+    CheckTxVersion,
+    /// Signs the genesis hash.
+    CheckGenesis,
+    /// Includes the era, and signs the checkpoint hash.
+    CheckMortality,
+    /// Includes the nonce.
+    CheckNonce,
+    CheckWeight,
+    /// Includes the tip, paid in the native token.
+    ChargeTransactionPayment,
+    /// Includes the tip and the encoded id of the asset the fees are paid in, in the native
+    /// token if none, like on Asset Hub.
+    ChargeAssetTxPayment,
+    /// Includes whether the metadata hash is checked, and signs it if so.
+    CheckMetadataHash,
+    /// Checks the statements of the claims of the DOT sale, on Polkadot.
+    PrevalidateAttests,
+    /// An extension of a specific chain, with its encoded included and signed data.
+    Custom {
+        /// The name of the extension in the metadata of the runtime.
+        identifier: String,
+        extra: Vec<u8>,
+        additional_signed: Vec<u8>,
+    },
+}
+
+impl SignedExtension {
+    /// The name of the extension in the metadata of the runtime.
+    pub fn identifier(&self) -> &str {
+        match self {
+            Self::CheckNonZeroSender => "CheckNonZeroSender",
+            Self::CheckSpecVersion => "CheckSpecVersion",
+            Self::CheckTxVersion => "CheckTxVersion",
+            Self::CheckGenesis => "CheckGenesis",
+            Self::CheckMortality => "CheckMortality",
+            Self::CheckNonce => "CheckNonce",
+            Self::CheckWeight => "CheckWeight",
+            Self::ChargeTransactionPayment => "ChargeTransactionPayment",
+            Self::ChargeAssetTxPayment => "ChargeAssetTxPayment",
+            Self::CheckMetadataHash => "CheckMetadataHash",
+            Self::PrevalidateAttests => "PrevalidateAttests",
+            Self::Custom { identifier, .. } => identifier,
+        }
+    }
+}
+
+/// The configuration of a chain the extrinsics are built for, its signed extensions, the ones
+/// of Polkadot by default.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::substrate::config::{ChainConfig, SignedExtension};
+///
+/// // A parachain charging fees in the native token without the metadata hash extension
+/// let config = ChainConfig {
+///     signed_extensions: vec![
+///         SignedExtension::CheckNonZeroSender,
+///         SignedExtension::CheckSpecVersion,
+///         SignedExtension::CheckTxVersion,
+///         SignedExtension::CheckGenesis,
+///         SignedExtension::CheckMortality,
+///         SignedExtension::CheckNonce,
+///         SignedExtension::CheckWeight,
+///         SignedExtension::ChargeTransactionPayment,
+///     ],
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainConfig {
+    /// The signed extensions of the runtime, in the order of its metadata.
+    pub signed_extensions: Vec<SignedExtension>,
+}
+
+impl ChainConfig {
+    /// The signed extensions of Polkadot.
+    pub fn polkadot() -> Self {
+        Self {
+            signed_extensions: vec![
+                SignedExtension::CheckNonZeroSender,
+                SignedExtension::CheckSpecVersion,
+                SignedExtension::CheckTxVersion,
+                SignedExtension::CheckGenesis,
+                SignedExtension::CheckMortality,
+                SignedExtension::CheckNonce,
+                SignedExtension::CheckWeight,
+                SignedExtension::ChargeTransactionPayment,
+                SignedExtension::PrevalidateAttests,
+                SignedExtension::CheckMetadataHash,
+            ],
+        }
+    }
+
+    /// The signed extensions of Kusama and Westend.
+    pub fn kusama() -> Self {
+        Self {
+            signed_extensions: Self::polkadot()
+                .signed_extensions
+                .into_iter()
+                .filter(|extension| *extension != SignedExtension::PrevalidateAttests)
+                .collect(),
+        }
+    }
+
+    /// The signed extensions of the Asset Hub parachains, paying fees in the native token or
+    /// in an asset.
+    pub fn asset_hub() -> Self {
+        Self {
+            signed_extensions: Self::kusama()
+                .signed_extensions
+                .into_iter()
+                .map(|extension| match extension {
+                    SignedExtension::ChargeTransactionPayment => {
+                        SignedExtension::ChargeAssetTxPayment
+                    }
+                    extension => extension,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self::polkadot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_configs() {
+        let identifiers = |config: ChainConfig| {
+            config
+                .signed_extensions
+                .iter()
+                .map(|extension| extension.identifier().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ChainConfig::default(), ChainConfig::polkadot());
+        assert_eq!(ChainConfig::polkadot().signed_extensions.len(), 10);
+        assert!(!identifiers(ChainConfig::kusama()).contains(&"PrevalidateAttests".to_string()));
+
+        let asset_hub = identifiers(ChainConfig::asset_hub());
+        assert_eq!(asset_hub[7], "ChargeAssetTxPayment");
+        assert_eq!(asset_hub[8], "CheckMetadataHash");
+        assert!(!asset_hub.contains(&"ChargeTransactionPayment".to_string()));
+    }
+}
@@ -0,0 +1,15 @@
+//! Extrinsic builder, encoders, types and utilities for Substrate chains, like Polkadot and
+//! Kusama.
+pub mod balances;
+pub mod config;
+mod substrate_transaction;
+mod substrate_transaction_builder;
+pub mod types;
+pub mod utils;
+
+/// Substrate extrinsic
+pub use substrate_transaction::{
+    SubstrateTransaction, EXTRINSIC_FORMAT_VERSION, MAX_UNHASHED_PAYLOAD_LEN,
+};
+/// Substrate extrinsic builder
+pub use substrate_transaction_builder::SubstrateTransactionBuilder;
@@ -0,0 +1,337 @@
+//! Substrate extrinsic
+use serde::{Deserialize, Serialize};
+
+use super::{
+    config::{ChainConfig, SignedExtension},
+    types::{Era, MultiAddress, MultiSignature},
+    utils::{blake2_256, encode_compact},
+};
+
+/// Version of the format of extrinsics, the one of signed extensions.
+pub const EXTRINSIC_FORMAT_VERSION: u8 = 4;
+/// Bit of the version byte of signed extrinsics.
+const SIGNED_BIT: u8 = 0x80;
+/// Length of the signing payloads signed as is, longer ones being signed as their hash.
+pub const MAX_UNHASHED_PAYLOAD_LEN: usize = 256;
+
+/// A signed extrinsic of a Substrate chain, with the signed extensions of its
+/// [`ChainConfig`], encoded as the extrinsic submitted with the `author_submitExtrinsic` RPC
+/// method.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::substrate::balances::{Transfer, POLKADOT_BALANCES_PALLET};
+/// use signet_rs::substrate::config::ChainConfig;
+/// use signet_rs::substrate::types::{AccountId, Era, MultiSignature, PLANCK_PER_DOT};
+/// use signet_rs::substrate::SubstrateTransaction;
+///
+/// let call = Transfer {
+///     pallet_index: POLKADOT_BALANCES_PALLET,
+///     dest: AccountId([1; 32]).into(),
+///     value: PLANCK_PER_DOT,
+///     keep_alive: true,
+/// };
+/// let tx = SubstrateTransaction {
+///     signer: AccountId([2; 32]).into(),
+///     call: call.encode(),
+///     era: Era::mortal(64, 22_000_000),
+///     nonce: 7,
+///     tip: 0,
+///     spec_version: 1_003_000,
+///     transaction_version: 26,
+///     genesis_hash: [0x91; 32],
+///     checkpoint_hash: [0xab; 32],
+///     asset_id: None,
+///     metadata_hash: None,
+///     chain_config: ChainConfig::polkadot(),
+/// };
+///
+/// // The signing payload, signed with Ed25519, or its blake2 256 signed with ECDSA
+/// let payload = tx.build_for_signing();
+/// let hash = tx.signing_hash();
+///
+/// let signature = MultiSignature::Ed25519([7; 64]);
+/// let extrinsic = tx.build_with_signature(&signature);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubstrateTransaction {
+    /// The account signing the extrinsic, paying its fees.
+    pub signer: MultiAddress,
+    /// The encoded call of the extrinsic, like a [`super::balances::Transfer`].
+    pub call: Vec<u8>,
+    pub era: Era,
+    /// The number of extrinsics of the signer so far.
+    pub nonce: u32,
+    /// The planck paid on top of the fees to prioritize the extrinsic.
+    pub tip: u128,
+    /// The `specVersion` of the runtime, from the `state_getRuntimeVersion` RPC method.
+    pub spec_version: u32,
+    /// The `transactionVersion` of the runtime.
+    pub transaction_version: u32,
+    /// The hash of the genesis block of the chain.
+    pub genesis_hash: [u8; 32],
+    /// The hash of the first block of the era, [`Era::birth`], the genesis hash for immortal
+    /// extrinsics.
+    pub checkpoint_hash: [u8; 32],
+    /// The encoded id of the asset the fees are paid in with `ChargeAssetTxPayment`, the
+    /// native token if none.
+    pub asset_id: Option<Vec<u8>>,
+    /// The hash of the metadata of the runtime, checked by `CheckMetadataHash` if any.
+    pub metadata_hash: Option<[u8; 32]>,
+    /// The chain the extrinsic is built for, defining its signed extensions.
+    pub chain_config: ChainConfig,
+}
+
+impl SubstrateTransaction {
+    /// Encode the data of the signed extensions included in the extrinsic, like its era,
+    /// nonce and tip.
+    pub fn extra(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        for extension in &self.chain_config.signed_extensions {
+            match extension {
+                SignedExtension::CheckMortality => buffer.extend_from_slice(&self.era.encode()),
+                SignedExtension::CheckNonce => {
+                    encode_compact(&mut buffer, u128::from(self.nonce));
+                }
+                SignedExtension::ChargeTransactionPayment => encode_compact(&mut buffer, self.tip),
+                SignedExtension::ChargeAssetTxPayment => {
+                    encode_compact(&mut buffer, self.tip);
+                    match &self.asset_id {
+                        Some(asset_id) => {
+                            buffer.push(1);
+                            buffer.extend_from_slice(asset_id);
+                        }
+                        None => buffer.push(0),
+                    }
+                }
+                // The mode of the check, enabled with a metadata hash
+                SignedExtension::CheckMetadataHash => {
+                    buffer.push(u8::from(self.metadata_hash.is_some()));
+                }
+                SignedExtension::Custom { extra, .. } => buffer.extend_from_slice(extra),
+                SignedExtension::CheckNonZeroSender
+                | SignedExtension::CheckSpecVersion
+                | SignedExtension::CheckTxVersion
+                | SignedExtension::CheckGenesis
+                | SignedExtension::CheckWeight
+                | SignedExtension::PrevalidateAttests => {}
+            }
+        }
+        buffer
+    }
+
+    /// Encode the data signed by the signed extensions without being included in the
+    /// extrinsic, like the versions of the runtime and the genesis hash.
+    pub fn additional_signed(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        for extension in &self.chain_config.signed_extensions {
+            match extension {
+                SignedExtension::CheckSpecVersion => {
+                    buffer.extend_from_slice(&self.spec_version.to_le_bytes());
+                }
+                SignedExtension::CheckTxVersion => {
+                    buffer.extend_from_slice(&self.transaction_version.to_le_bytes());
+                }
+                SignedExtension::CheckGenesis => buffer.extend_from_slice(&self.genesis_hash),
+                SignedExtension::CheckMortality => buffer.extend_from_slice(&self.checkpoint_hash),
+                SignedExtension::CheckMetadataHash => match &self.metadata_hash {
+                    Some(metadata_hash) => {
+                        buffer.push(1);
+                        buffer.extend_from_slice(metadata_hash);
+                    }
+                    None => buffer.push(0),
+                },
+                SignedExtension::Custom {
+                    additional_signed, ..
+                } => buffer.extend_from_slice(additional_signed),
+                SignedExtension::CheckNonZeroSender
+                | SignedExtension::CheckNonce
+                | SignedExtension::CheckWeight
+                | SignedExtension::ChargeTransactionPayment
+                | SignedExtension::ChargeAssetTxPayment
+                | SignedExtension::PrevalidateAttests => {}
+            }
+        }
+        buffer
+    }
+
+    /// Encode the signing payload of the extrinsic, its call, extra and additional signed
+    /// data, hashed with blake2 256 if longer than [`MAX_UNHASHED_PAYLOAD_LEN`] bytes.
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        let mut payload = self.call.clone();
+        payload.extend_from_slice(&self.extra());
+        payload.extend_from_slice(&self.additional_signed());
+
+        if payload.len() > MAX_UNHASHED_PAYLOAD_LEN {
+            blake2_256(&payload).to_vec()
+        } else {
+            payload
+        }
+    }
+
+    /// The blake2 256 of the signing payload, signed with ECDSA, Ed25519 and Sr25519 keys
+    /// signing the payload itself.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        blake2_256(&self.build_for_signing())
+    }
+
+    /// Encode the extrinsic signed with `signature`, prefixed with its length.
+    pub fn build_with_signature(&self, signature: &MultiSignature) -> Vec<u8> {
+        let mut extrinsic = vec![SIGNED_BIT | EXTRINSIC_FORMAT_VERSION];
+        extrinsic.extend_from_slice(&self.signer.encode());
+        extrinsic.extend_from_slice(&signature.encode());
+        extrinsic.extend_from_slice(&self.extra());
+        extrinsic.extend_from_slice(&self.call);
+
+        let mut buffer = Vec::with_capacity(extrinsic.len() + 4);
+        encode_compact(&mut buffer, extrinsic.len() as u128);
+        buffer.extend_from_slice(&extrinsic);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::{
+        balances::{Transfer, POLKADOT_BALANCES_PALLET},
+        types::{AccountId, PLANCK_PER_DOT},
+    };
+    use ed25519_dalek::{Signer, SigningKey, Verifier};
+
+    fn transfer(signer: AccountId) -> SubstrateTransaction {
+        SubstrateTransaction {
+            signer: signer.into(),
+            call: Transfer {
+                pallet_index: POLKADOT_BALANCES_PALLET,
+                dest: AccountId([1; 32]).into(),
+                value: PLANCK_PER_DOT,
+                keep_alive: true,
+            }
+            .encode(),
+            era: Era::mortal(64, 42),
+            nonce: 7,
+            tip: 0,
+            spec_version: 1_003_000,
+            transaction_version: 26,
+            genesis_hash: [0x91; 32],
+            checkpoint_hash: [0xab; 32],
+            asset_id: None,
+            metadata_hash: None,
+            chain_config: ChainConfig::polkadot(),
+        }
+    }
+
+    #[test]
+    fn test_build_for_signing() {
+        let tx = transfer(AccountId([2; 32]));
+
+        assert_eq!(hex::encode(tx.extra()), concat!("a502", "1c", "00", "00"));
+        assert_eq!(
+            hex::encode(tx.additional_signed()),
+            [
+                "f84d0f00",
+                "1a000000",
+                &"91".repeat(32),
+                &"ab".repeat(32),
+                "00"
+            ]
+            .concat()
+        );
+        assert_eq!(
+            tx.build_for_signing(),
+            [tx.call.clone(), tx.extra(), tx.additional_signed()].concat()
+        );
+        assert_eq!(tx.signing_hash(), blake2_256(&tx.build_for_signing()));
+    }
+
+    #[test]
+    fn test_signed_extensions() {
+        let asset_hub = SubstrateTransaction {
+            tip: 1,
+            asset_id: Some(vec![0x01, 0x02]),
+            metadata_hash: Some([0xcd; 32]),
+            chain_config: ChainConfig::asset_hub(),
+            ..transfer(AccountId([2; 32]))
+        };
+        assert_eq!(
+            hex::encode(asset_hub.extra()),
+            concat!("a502", "1c", "04", "010102", "01")
+        );
+        assert_eq!(
+            asset_hub.additional_signed()[72..],
+            [[1].as_slice(), &[0xcd; 32]].concat()
+        );
+
+        // A parachain without the metadata hash extension, and with an extension of its own
+        let parachain = SubstrateTransaction {
+            chain_config: ChainConfig {
+                signed_extensions: vec![
+                    SignedExtension::CheckSpecVersion,
+                    SignedExtension::CheckGenesis,
+                    SignedExtension::CheckNonce,
+                    SignedExtension::Custom {
+                        identifier: "CheckFee".to_string(),
+                        extra: vec![0xee],
+                        additional_signed: vec![0xff],
+                    },
+                    SignedExtension::ChargeTransactionPayment,
+                ],
+            },
+            ..transfer(AccountId([2; 32]))
+        };
+        assert_eq!(hex::encode(parachain.extra()), concat!("1c", "ee", "00"));
+        assert_eq!(
+            hex::encode(parachain.additional_signed()),
+            ["f84d0f00", &"91".repeat(32), "ff"].concat()
+        );
+    }
+
+    #[test]
+    fn test_build_for_signing_long_payload() {
+        let short = SubstrateTransaction {
+            call: vec![0; MAX_UNHASHED_PAYLOAD_LEN - 78],
+            ..transfer(AccountId([2; 32]))
+        };
+        let payload = short.build_for_signing();
+        assert_eq!(payload.len(), MAX_UNHASHED_PAYLOAD_LEN);
+
+        // Payloads of more than 256 bytes are signed as their hash
+        let long = SubstrateTransaction {
+            call: vec![0; MAX_UNHASHED_PAYLOAD_LEN - 77],
+            ..transfer(AccountId([2; 32]))
+        };
+        let mut preimage = long.call.clone();
+        preimage.extend_from_slice(&long.extra());
+        preimage.extend_from_slice(&long.additional_signed());
+        assert_eq!(preimage.len(), MAX_UNHASHED_PAYLOAD_LEN + 1);
+        assert_eq!(long.build_for_signing(), blake2_256(&preimage));
+    }
+
+    #[test]
+    fn test_build_with_signature() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let signer = AccountId(signing_key.verifying_key().to_bytes());
+        let tx = transfer(signer);
+
+        let signature = signing_key.sign(&tx.build_for_signing());
+        let extrinsic = tx.build_with_signature(&MultiSignature::Ed25519(signature.to_bytes()));
+
+        // Length, version, signer, signature, extra and call
+        let len = 1 + 33 + 65 + 5 + tx.call.len();
+        assert_eq!(extrinsic.len(), 2 + len);
+        assert_eq!(extrinsic[..2], ((len as u16) << 2 | 0b01).to_le_bytes());
+        assert_eq!(extrinsic[2], 0x84);
+        assert_eq!(extrinsic[3..36], MultiAddress::Id(signer).encode());
+        assert_eq!(extrinsic[36], 0);
+        assert_eq!(extrinsic[37..101], signature.to_bytes());
+        assert_eq!(extrinsic[101..106], tx.extra());
+        assert_eq!(extrinsic[106..], tx.call);
+
+        signing_key
+            .verifying_key()
+            .verify(&tx.build_for_signing(), &signature)
+            .unwrap();
+    }
+}
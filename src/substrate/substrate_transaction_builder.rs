@@ -0,0 +1,220 @@
+use crate::transaction_builder::TxBuilder;
+
+use super::{
+    config::ChainConfig,
+    substrate_transaction::SubstrateTransaction,
+    types::{Era, MultiAddress},
+};
+
+pub struct SubstrateTransactionBuilder {
+    signer: Option<MultiAddress>,
+    call: Option<Vec<u8>>,
+    era: Option<Era>,
+    nonce: Option<u32>,
+    tip: Option<u128>,
+    spec_version: Option<u32>,
+    transaction_version: Option<u32>,
+    genesis_hash: Option<[u8; 32]>,
+    checkpoint_hash: Option<[u8; 32]>,
+    asset_id: Option<Vec<u8>>,
+    metadata_hash: Option<[u8; 32]>,
+    chain_config: Option<ChainConfig>,
+}
+
+impl Default for SubstrateTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<SubstrateTransaction> for SubstrateTransactionBuilder {
+    fn build(&self) -> SubstrateTransaction {
+        let era = self.era.unwrap_or_default();
+        let genesis_hash = self.genesis_hash.expect("genesis_hash is mandatory");
+        let checkpoint_hash = match era {
+            Era::Immortal => genesis_hash,
+            Era::Mortal { .. } => self
+                .checkpoint_hash
+                .expect("checkpoint_hash is mandatory for mortal eras"),
+        };
+
+        SubstrateTransaction {
+            signer: self.signer.clone().expect("signer is mandatory"),
+            call: self.call.clone().expect("call is mandatory"),
+            era,
+            nonce: self.nonce.expect("nonce is mandatory"),
+            tip: self.tip.unwrap_or_default(),
+            spec_version: self.spec_version.expect("spec_version is mandatory"),
+            transaction_version: self
+                .transaction_version
+                .expect("transaction_version is mandatory"),
+            genesis_hash,
+            checkpoint_hash,
+            asset_id: self.asset_id.clone(),
+            metadata_hash: self.metadata_hash,
+            chain_config: self.chain_config.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl SubstrateTransactionBuilder {
+    pub const fn new() -> Self {
+        Self {
+            signer: None,
+            call: None,
+            era: None,
+            nonce: None,
+            tip: None,
+            spec_version: None,
+            transaction_version: None,
+            genesis_hash: None,
+            checkpoint_hash: None,
+            asset_id: None,
+            metadata_hash: None,
+            chain_config: None,
+        }
+    }
+
+    /// Account signing the extrinsic.
+    pub fn signer(mut self, signer: MultiAddress) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Encoded call of the extrinsic.
+    pub fn call(mut self, call: Vec<u8>) -> Self {
+        self.call = Some(call);
+        self
+    }
+
+    /// Era the extrinsic is valid in, immortal by default.
+    pub const fn era(mut self, era: Era) -> Self {
+        self.era = Some(era);
+        self
+    }
+
+    /// Nonce of the signer, from the `system_accountNextIndex` RPC method.
+    pub const fn nonce(mut self, nonce: u32) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Planck paid on top of the fees, 0 by default.
+    pub const fn tip(mut self, tip: u128) -> Self {
+        self.tip = Some(tip);
+        self
+    }
+
+    /// Spec version of the runtime.
+    pub const fn spec_version(mut self, spec_version: u32) -> Self {
+        self.spec_version = Some(spec_version);
+        self
+    }
+
+    /// Transaction version of the runtime.
+    pub const fn transaction_version(mut self, transaction_version: u32) -> Self {
+        self.transaction_version = Some(transaction_version);
+        self
+    }
+
+    /// Hash of the genesis block of the chain.
+    pub const fn genesis_hash(mut self, genesis_hash: [u8; 32]) -> Self {
+        self.genesis_hash = Some(genesis_hash);
+        self
+    }
+
+    /// Hash of the first block of a mortal era, the genesis hash for immortal extrinsics.
+    pub const fn checkpoint_hash(mut self, checkpoint_hash: [u8; 32]) -> Self {
+        self.checkpoint_hash = Some(checkpoint_hash);
+        self
+    }
+
+    /// Encoded id of the asset the fees are paid in on chains with `ChargeAssetTxPayment`, the
+    /// native token by default.
+    pub fn asset_id(mut self, asset_id: Vec<u8>) -> Self {
+        self.asset_id = Some(asset_id);
+        self
+    }
+
+    /// Hash of the metadata of the runtime, checked on chains with `CheckMetadataHash`, not
+    /// checked by default.
+    pub const fn metadata_hash(mut self, metadata_hash: [u8; 32]) -> Self {
+        self.metadata_hash = Some(metadata_hash);
+        self
+    }
+
+    /// Chain the extrinsic is built for, defining its signed extensions, Polkadot by default.
+    pub fn chain_config(mut self, chain_config: ChainConfig) -> Self {
+        self.chain_config = Some(chain_config);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::types::AccountId;
+    use crate::TransactionBuilder;
+
+    #[test]
+    fn test_build() {
+        let tx: SubstrateTransaction = TransactionBuilder::new::<SubstrateTransactionBuilder>()
+            .signer(AccountId([2; 32]).into())
+            .call(vec![5, 3])
+            .nonce(7)
+            .spec_version(1_003_000)
+            .transaction_version(26)
+            .genesis_hash([0x91; 32])
+            .build();
+
+        assert_eq!(
+            tx,
+            SubstrateTransaction {
+                signer: AccountId([2; 32]).into(),
+                call: vec![5, 3],
+                era: Era::Immortal,
+                nonce: 7,
+                tip: 0,
+                spec_version: 1_003_000,
+                transaction_version: 26,
+                genesis_hash: [0x91; 32],
+                checkpoint_hash: [0x91; 32],
+                asset_id: None,
+                metadata_hash: None,
+                chain_config: ChainConfig::polkadot(),
+            }
+        );
+
+        let mortal: SubstrateTransaction = SubstrateTransactionBuilder::new()
+            .signer(AccountId([2; 32]).into())
+            .call(vec![5, 3])
+            .era(Era::mortal(64, 42))
+            .nonce(7)
+            .tip(1)
+            .spec_version(1_003_000)
+            .transaction_version(26)
+            .genesis_hash([0x91; 32])
+            .checkpoint_hash([0xab; 32])
+            .asset_id(vec![1])
+            .chain_config(ChainConfig::asset_hub())
+            .build();
+        assert_eq!(mortal.checkpoint_hash, [0xab; 32]);
+        assert_eq!(mortal.tip, 1);
+        assert_eq!(mortal.asset_id, Some(vec![1]));
+        assert_eq!(mortal.chain_config, ChainConfig::asset_hub());
+    }
+
+    #[test]
+    #[should_panic(expected = "checkpoint_hash is mandatory for mortal eras")]
+    fn test_build_mortal_without_checkpoint_hash() {
+        let _: SubstrateTransaction = SubstrateTransactionBuilder::new()
+            .signer(AccountId([2; 32]).into())
+            .call(vec![5, 3])
+            .era(Era::mortal(64, 42))
+            .nonce(7)
+            .spec_version(1_003_000)
+            .transaction_version(26)
+            .genesis_hash([0x91; 32])
+            .build();
+    }
+}
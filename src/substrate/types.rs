@@ -0,0 +1,245 @@
+//! Types used by the Substrate extrinsic builder.
+use serde::{Deserialize, Serialize};
+
+use super::utils::{blake2_256, decode_ss58, encode_compact, encode_ss58};
+
+/// Planck in one DOT.
+pub const PLANCK_PER_DOT: u128 = 10_000_000_000;
+/// Planck in one KSM.
+pub const PLANCK_PER_KSM: u128 = 1_000_000_000_000;
+
+/// SS58 prefix of Polkadot, its addresses starting with `1`.
+pub const POLKADOT_SS58_PREFIX: u16 = 0;
+/// SS58 prefix of Kusama.
+pub const KUSAMA_SS58_PREFIX: u16 = 2;
+/// SS58 prefix of the chains without their own, like Westend.
+pub const GENERIC_SS58_PREFIX: u16 = 42;
+
+/// An account, the Ed25519 or Sr25519 public key of its owner, or the blake2 256 of its
+/// compressed ECDSA public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AccountId(pub [u8; 32]);
+
+impl AccountId {
+    /// The account of the compressed secp256k1 public key `public_key`.
+    pub fn from_ecdsa_public_key(public_key: &[u8; 33]) -> Self {
+        Self(blake2_256(public_key))
+    }
+
+    /// The SS58 address of the account on the chain of prefix `prefix`, like
+    /// [`POLKADOT_SS58_PREFIX`].
+    pub fn to_ss58(&self, prefix: u16) -> String {
+        encode_ss58(prefix, &self.0)
+    }
+
+    /// The account of an SS58 `address`, with the prefix of its chain.
+    pub fn from_ss58(address: &str) -> Result<(Self, u16), String> {
+        let (prefix, account) = decode_ss58(address)?;
+        let account = account
+            .try_into()
+            .map_err(|account: Vec<u8>| format!("Invalid account length: {}", account.len()))?;
+
+        Ok((Self(account), prefix))
+    }
+}
+
+/// The address of an account in calls and extrinsics, `MultiAddress` of most chains.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MultiAddress {
+    Id(AccountId),
+    /// The index of an account in the indices pallet.
+    Index(u32),
+    Raw(Vec<u8>),
+    Address32([u8; 32]),
+    Address20([u8; 20]),
+}
+
+impl MultiAddress {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        match self {
+            Self::Id(account) => {
+                buffer.push(0);
+                buffer.extend_from_slice(&account.0);
+            }
+            Self::Index(index) => {
+                buffer.push(1);
+                encode_compact(&mut buffer, u128::from(*index));
+            }
+            Self::Raw(bytes) => {
+                buffer.push(2);
+                encode_compact(&mut buffer, bytes.len() as u128);
+                buffer.extend_from_slice(bytes);
+            }
+            Self::Address32(bytes) => {
+                buffer.push(3);
+                buffer.extend_from_slice(bytes);
+            }
+            Self::Address20(bytes) => {
+                buffer.push(4);
+                buffer.extend_from_slice(bytes);
+            }
+        }
+        buffer
+    }
+}
+
+impl From<AccountId> for MultiAddress {
+    fn from(account: AccountId) -> Self {
+        Self::Id(account)
+    }
+}
+
+/// The signature of an extrinsic, `MultiSignature` of most chains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiSignature {
+    Ed25519([u8; 64]),
+    Sr25519([u8; 64]),
+    /// A secp256k1 signature of the blake2 256 of the signing payload, its `r` and `s` then its
+    /// recovery id.
+    Ecdsa([u8; 65]),
+}
+
+impl MultiSignature {
+    pub fn encode(&self) -> Vec<u8> {
+        let (variant, signature): (u8, &[u8]) = match self {
+            Self::Ed25519(signature) => (0, signature),
+            Self::Sr25519(signature) => (1, signature),
+            Self::Ecdsa(signature) => (2, signature),
+        };
+
+        let mut buffer = Vec::with_capacity(1 + signature.len());
+        buffer.push(variant);
+        buffer.extend_from_slice(signature);
+        buffer
+    }
+}
+
+/// The lifetime of an extrinsic, either valid forever or for a period of blocks after the
+/// block it is built on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Era {
+    /// Valid forever, signing the genesis hash, and replayable once the account is reaped.
+    #[default]
+    Immortal,
+    /// Valid for `period` blocks, a power of two from 4 to 65536, from the block of number
+    /// `phase` modulo `period`.
+    Mortal { period: u64, phase: u64 },
+}
+
+impl Era {
+    /// The era valid for about `period` blocks from the block of number `current`, its period
+    /// being rounded to a power of two from 4 to 65536 and its phase quantized for its
+    /// encoding.
+    pub fn mortal(period: u64, current: u64) -> Self {
+        let period = period.clamp(4, 1 << 16).next_power_of_two();
+        let quantize_factor = (period >> 12).max(1);
+        let phase = current % period / quantize_factor * quantize_factor;
+
+        Self::Mortal { period, phase }
+    }
+
+    /// The number of the first block of the era for a block of number `current`, the block
+    /// whose hash mortal extrinsics sign rather than the genesis hash.
+    pub fn birth(&self, current: u64) -> u64 {
+        match self {
+            Self::Immortal => 0,
+            Self::Mortal { period, phase } => {
+                (current.max(*phase) - phase) / period * period + phase
+            }
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Immortal => vec![0],
+            Self::Mortal { period, phase } => {
+                let quantize_factor = (period >> 12).max(1);
+                let encoded = (period.trailing_zeros() - 1).clamp(1, 15) as u16
+                    | (((phase / quantize_factor) << 4) as u16);
+                encoded.to_le_bytes().to_vec()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_id() {
+        let (alice, prefix) =
+            AccountId::from_ss58("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5").unwrap();
+        assert_eq!(prefix, POLKADOT_SS58_PREFIX);
+        assert_eq!(
+            hex::encode(alice.0),
+            "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+        assert_eq!(
+            alice.to_ss58(GENERIC_SS58_PREFIX),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+
+        // The account of an ECDSA key is the hash of the key
+        let key = [2; 33];
+        assert_eq!(AccountId::from_ecdsa_public_key(&key).0, blake2_256(&key));
+    }
+
+    #[test]
+    fn test_encode_multi_address() {
+        assert_eq!(
+            MultiAddress::from(AccountId([1; 32])).encode(),
+            [[0].as_slice(), &[1; 32]].concat()
+        );
+        assert_eq!(MultiAddress::Index(69).encode(), [1, 0x15, 0x01]);
+        assert_eq!(MultiAddress::Raw(vec![7; 3]).encode(), [2, 0x0c, 7, 7, 7]);
+        assert_eq!(MultiAddress::Address20([4; 20]).encode()[..2], [4, 4]);
+    }
+
+    #[test]
+    fn test_era() {
+        assert_eq!(Era::Immortal.encode(), [0]);
+        assert_eq!(Era::Immortal.birth(100), 0);
+
+        let era = Era::mortal(64, 42);
+        assert_eq!(
+            era,
+            Era::Mortal {
+                period: 64,
+                phase: 42
+            }
+        );
+        assert_eq!(era.encode(), [5 + 42 % 16 * 16, 42 / 16]);
+        assert_eq!(era.birth(42), 42);
+        assert_eq!(era.birth(100), 42);
+        assert_eq!(era.birth(106), 106);
+
+        // The phase of long eras is quantized
+        let era = Era::mortal(32_768, 20_000);
+        assert_eq!(era.encode(), [0x4e, 0x9c]);
+
+        // Periods are rounded up to a power of two from 4 to 65536
+        assert_eq!(
+            Era::mortal(100, 1),
+            Era::Mortal {
+                period: 128,
+                phase: 1
+            }
+        );
+        assert_eq!(
+            Era::mortal(1, 5),
+            Era::Mortal {
+                period: 4,
+                phase: 1
+            }
+        );
+        assert_eq!(
+            Era::mortal(1 << 20, 5),
+            Era::Mortal {
+                period: 1 << 16,
+                phase: 0
+            }
+        );
+    }
+}
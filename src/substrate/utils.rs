@@ -0,0 +1,150 @@
+//! SCALE encoding of the fields of extrinsics, the hashes of Substrate and SS58 addresses.
+use blake2::{
+    digest::consts::{U32, U64},
+    Blake2b, Digest,
+};
+
+/// Prefix of the preimage of the checksum of SS58 addresses.
+const SS58_CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+/// Largest SS58 address prefix, of 14 bits.
+pub const MAX_SS58_PREFIX: u16 = 0x3fff;
+
+/// Encode `value` in the SCALE compact encoding, in 1, 2 or 4 bytes with its mode in their 2
+/// low bits, or in the least number of bytes after a byte of their number.
+pub fn encode_compact(buffer: &mut Vec<u8>, value: u128) {
+    match value {
+        0..=0x3f => buffer.push((value as u8) << 2),
+        0x40..=0x3fff => buffer.extend_from_slice(&(((value as u16) << 2) | 0b01).to_le_bytes()),
+        0x4000..=0x3fff_ffff => {
+            buffer.extend_from_slice(&(((value as u32) << 2) | 0b10).to_le_bytes())
+        }
+        _ => {
+            let len = 16 - value.leading_zeros() as usize / 8;
+            buffer.push((((len - 4) as u8) << 2) | 0b11);
+            buffer.extend_from_slice(&value.to_le_bytes()[..len]);
+        }
+    }
+}
+
+/// The blake2b hash of 256 bits of `data`, the hash of Substrate chains.
+pub fn blake2_256(data: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(data).into()
+}
+
+/// The SS58 encoding of the account `account` on the chain of prefix `prefix`, like 0 for
+/// Polkadot or 42 for most other chains, the base58 of the prefix, the account and a checksum.
+///
+/// Panics if `prefix` is above [`MAX_SS58_PREFIX`].
+pub fn encode_ss58(prefix: u16, account: &[u8]) -> String {
+    assert!(
+        prefix <= MAX_SS58_PREFIX,
+        "SS58 prefixes are at most {MAX_SS58_PREFIX}: {prefix}"
+    );
+
+    let mut bytes = match prefix {
+        0..=63 => vec![prefix as u8],
+        _ => vec![
+            ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000,
+            (prefix >> 8) as u8 | ((prefix & 0b11) << 6) as u8,
+        ],
+    };
+    bytes.extend_from_slice(account);
+    let checksum = ss58_checksum(&bytes);
+    bytes.extend_from_slice(&checksum);
+
+    bs58::encode(bytes).into_string()
+}
+
+/// Decode an SS58 address into the prefix of its chain and its account.
+pub fn decode_ss58(address: &str) -> Result<(u16, Vec<u8>), String> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| format!("Invalid address {address}: {e}"))?;
+
+    let (prefix, prefix_len) = match bytes.first() {
+        Some(&first @ 0..=63) => (u16::from(first), 1),
+        Some(&first @ 64..=127) if bytes.len() > 1 => {
+            let lower = (first << 2) | (bytes[1] >> 6);
+            let upper = bytes[1] & 0b0011_1111;
+            (u16::from(lower) | (u16::from(upper) << 8), 2)
+        }
+        _ => return Err(format!("Invalid address prefix: {address}")),
+    };
+    if bytes.len() < prefix_len + 2 {
+        return Err(format!("Invalid address length: {address}"));
+    }
+
+    let (payload, checksum) = bytes.split_at(bytes.len() - 2);
+    if ss58_checksum(payload) != checksum {
+        return Err(format!("Invalid address checksum: {address}"));
+    }
+
+    Ok((prefix, payload[prefix_len..].to_vec()))
+}
+
+/// The 2 first bytes of the blake2b-512 of the prefixed preimage of the address.
+fn ss58_checksum(payload: &[u8]) -> [u8; 2] {
+    let hash = Blake2b::<U64>::new()
+        .chain_update(SS58_CHECKSUM_PREFIX)
+        .chain_update(payload)
+        .finalize();
+    [hash[0], hash[1]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_compact() {
+        for (value, encoded) in [
+            (0, "00"),
+            (1, "04"),
+            (42, "a8"),
+            (63, "fc"),
+            (64, "0101"),
+            (69, "1501"),
+            (16_383, "fdff"),
+            (16_384, "02000100"),
+            (65_535, "feff0300"),
+            (1_073_741_823, "feffffff"),
+            (1_073_741_824, "0300000040"),
+            (u128::from(u64::MAX), "13ffffffffffffffff"),
+            (u128::MAX, "33ffffffffffffffffffffffffffffffff"),
+        ] {
+            let mut buffer = Vec::new();
+            encode_compact(&mut buffer, value);
+            assert_eq!(hex::encode(buffer), encoded, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_ss58() {
+        // The account of the development key Alice
+        let alice = hex::decode("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")
+            .unwrap();
+
+        for (prefix, address) in [
+            (0, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"),
+            (2, "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"),
+            (42, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+        ] {
+            assert_eq!(encode_ss58(prefix, &alice), address);
+            assert_eq!(decode_ss58(address).unwrap(), (prefix, alice.clone()));
+        }
+
+        // Prefixes of 2 bytes
+        let address = encode_ss58(1284, &alice);
+        assert_eq!(decode_ss58(&address).unwrap(), (1284, alice.clone()));
+
+        assert!(decode_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ").is_err());
+    }
+
+    #[test]
+    fn test_blake2_256() {
+        assert_eq!(
+            hex::encode(blake2_256(b"")),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Bag of cells serialization, the encoding of the trees of cells sent to the network, like the
+//! external messages of wallets.
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use super::cell::Cell;
+
+/// Magic prefix of the generic serialization of bags of cells.
+pub const BOC_MAGIC: [u8; 4] = [0xb5, 0xee, 0x9c, 0x72];
+/// Flag of serializations ending with the crc32c of the preceding bytes.
+const HAS_CRC32C: u8 = 0x40;
+
+/// Serialize the tree of `root` as a bag of cells with a crc32c, without index, each cell being
+/// serialized once and before the cells it references.
+pub fn serialize_boc(root: &Cell) -> Vec<u8> {
+    let cells = topological_order(root);
+    let indexes: BTreeMap<[u8; 32], usize> = cells
+        .iter()
+        .enumerate()
+        .map(|(index, cell)| (cell.hash(), index))
+        .collect();
+
+    let size_bytes = byte_len(cells.len() as u64);
+    let mut serialized_cells = Vec::new();
+    for cell in &cells {
+        serialized_cells.extend_from_slice(&cell.descriptors());
+        serialized_cells.extend_from_slice(&cell.padded_data());
+        for reference in cell.refs() {
+            push_uint(
+                &mut serialized_cells,
+                indexes[&reference.hash()] as u64,
+                size_bytes,
+            );
+        }
+    }
+    let offset_bytes = byte_len(serialized_cells.len() as u64);
+
+    let mut boc = BOC_MAGIC.to_vec();
+    boc.push(HAS_CRC32C | size_bytes as u8);
+    boc.push(offset_bytes as u8);
+    push_uint(&mut boc, cells.len() as u64, size_bytes);
+    // One root, no absent cells
+    push_uint(&mut boc, 1, size_bytes);
+    push_uint(&mut boc, 0, size_bytes);
+    push_uint(&mut boc, serialized_cells.len() as u64, offset_bytes);
+    // The root is the first cell
+    push_uint(&mut boc, 0, size_bytes);
+    boc.extend_from_slice(&serialized_cells);
+
+    let crc = crc32c(&boc);
+    boc.extend_from_slice(&crc.to_le_bytes());
+    boc
+}
+
+/// The distinct cells of the tree of `root`, `root` first and every cell before the cells it
+/// references, the reverse of their post-order.
+fn topological_order(root: &Cell) -> Vec<&Cell> {
+    fn visit<'a>(cell: &'a Cell, visited: &mut BTreeSet<[u8; 32]>, order: &mut Vec<&'a Cell>) {
+        if !visited.insert(cell.hash()) {
+            return;
+        }
+        for reference in cell.refs().iter().rev() {
+            visit(reference, visited, order);
+        }
+        order.push(cell);
+    }
+
+    let mut order = Vec::new();
+    visit(root, &mut BTreeSet::new(), &mut order);
+    order.reverse();
+    order
+}
+
+/// Number of bytes holding `value`, at least 1.
+fn byte_len(value: u64) -> usize {
+    (8 - value.leading_zeros() as usize / 8).max(1)
+}
+
+fn push_uint(buffer: &mut Vec<u8>, value: u64, bytes: usize) {
+    buffer.extend_from_slice(&value.to_be_bytes()[8 - bytes..]);
+}
+
+/// The crc32c (Castagnoli) checksum of `data`.
+pub fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82f6_3b78
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ton::cell::CellBuilder;
+
+    #[test]
+    fn test_crc32c() {
+        assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+    }
+
+    #[test]
+    fn test_serialize_empty_cell() {
+        assert_eq!(
+            hex::encode(serialize_boc(&Cell::empty())),
+            "b5ee9c724101010100020000004cacb9cd"
+        );
+    }
+
+    #[test]
+    fn test_serialize_tree() {
+        let shared = CellBuilder::new().store_uint(0xaa, 8).build().unwrap();
+        let left = CellBuilder::new()
+            .store_uint(1, 4)
+            .store_ref(shared.clone())
+            .build()
+            .unwrap();
+        let root = CellBuilder::new()
+            .store_ref(left)
+            .store_ref(shared)
+            .build()
+            .unwrap();
+
+        let boc = serialize_boc(&root);
+        let (body, crc) = boc.split_at(boc.len() - 4);
+        assert_eq!(crc, crc32c(body).to_le_bytes());
+        // The shared cell is serialized once, after the two cells referencing it
+        assert_eq!(
+            hex::encode(body),
+            concat!(
+                "b5ee9c72", "41", "01", "03", "01", "00", "0b", "00",       // header
+                "02000102", // root
+                "0101", "18", "02", // left
+                "0002", "aa", // shared
+            )
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Cells, the trees of bits and references every TON structure is serialized into, and the
+//! builder storing values into them.
+use sha2::{Digest, Sha256};
+
+/// Largest number of bits of a cell.
+pub const MAX_BITS: usize = 1023;
+/// Largest number of references of a cell.
+pub const MAX_REFS: usize = 4;
+
+/// An ordinary cell of at most [`MAX_BITS`] bits and [`MAX_REFS`] references to other cells.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Cell {
+    /// The bits of the cell, the bits after the last one being zero.
+    data: Vec<u8>,
+    bit_len: usize,
+    refs: Vec<Cell>,
+}
+
+impl Cell {
+    /// The cell of no bits and no references.
+    pub const fn empty() -> Self {
+        Self {
+            data: Vec::new(),
+            bit_len: 0,
+            refs: Vec::new(),
+        }
+    }
+
+    /// The bits of the cell, packed from the most significant bit of the first byte.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub const fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    pub fn refs(&self) -> &[Cell] {
+        &self.refs
+    }
+
+    /// The two descriptor bytes of the cell, its number of references, and its number of bits
+    /// as the sum of its number of full bytes and its number of bytes.
+    pub fn descriptors(&self) -> [u8; 2] {
+        [
+            self.refs.len() as u8,
+            (self.bit_len / 8 + self.bit_len.div_ceil(8)) as u8,
+        ]
+    }
+
+    /// The bits of the cell in whole bytes, the last byte being completed with a one bit then
+    /// zeros if the bits do not fill it.
+    pub fn padded_data(&self) -> Vec<u8> {
+        let mut data = self.data.clone();
+        if self.bit_len % 8 != 0 {
+            data[self.bit_len / 8] |= 0x80 >> (self.bit_len % 8);
+        }
+        data
+    }
+
+    /// The depth of the cell, 0 without references, else 1 more than its deepest reference.
+    pub fn depth(&self) -> u16 {
+        self.refs
+            .iter()
+            .map(|cell| cell.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The representation hash of the cell, the sha256 of its descriptors, its padded bits,
+    /// then the depths and the hashes of its references, identifying it and signed by wallets.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.descriptors());
+        hasher.update(self.padded_data());
+        for cell in &self.refs {
+            hasher.update(cell.depth().to_be_bytes());
+        }
+        for cell in &self.refs {
+            hasher.update(cell.hash());
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Stores bits and references into a new cell, failing to build it if they do not fit.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::ton::cell::CellBuilder;
+///
+/// let cell = CellBuilder::new()
+///     .store_uint(0x0f8a7ea5, 32)
+///     .store_bit(true)
+///     .store_coins(1_000_000_000)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(cell.bit_len(), 32 + 1 + 4 + 32);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CellBuilder {
+    data: Vec<u8>,
+    bit_len: usize,
+    refs: Vec<Cell>,
+}
+
+impl CellBuilder {
+    pub const fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            bit_len: 0,
+            refs: Vec::new(),
+        }
+    }
+
+    /// Number of bits stored so far.
+    pub const fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    /// Number of bits still fitting in the cell.
+    pub const fn available_bits(&self) -> usize {
+        MAX_BITS.saturating_sub(self.bit_len)
+    }
+
+    pub fn store_bit(&mut self, bit: bool) -> &mut Self {
+        if self.bit_len % 8 == 0 {
+            self.data.push(0);
+        }
+        if bit {
+            self.data[self.bit_len / 8] |= 0x80 >> (self.bit_len % 8);
+        }
+        self.bit_len += 1;
+        self
+    }
+
+    /// Store the `bits` least significant bits of `value`, most significant first.
+    ///
+    /// Panics if `value` does not fit in `bits` bits.
+    pub fn store_uint(&mut self, value: u128, bits: usize) -> &mut Self {
+        assert!(
+            bits <= 128 && (bits == 128 || value >> bits == 0),
+            "{value} does not fit in {bits} bits"
+        );
+
+        for i in (0..bits).rev() {
+            self.store_bit((value >> i) & 1 == 1);
+        }
+        self
+    }
+
+    /// Store `value` as a two's complement integer of `bits` bits.
+    ///
+    /// Panics if `value` does not fit in `bits` bits.
+    pub fn store_int(&mut self, value: i128, bits: usize) -> &mut Self {
+        assert!(
+            (1..=128).contains(&bits)
+                && (bits == 128 || (-(1 << (bits - 1))..1 << (bits - 1)).contains(&value)),
+            "{value} does not fit in {bits} bits"
+        );
+
+        let mask = if bits == 128 {
+            u128::MAX
+        } else {
+            (1 << bits) - 1
+        };
+        self.store_uint(value as u128 & mask, bits)
+    }
+
+    pub fn store_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        if self.bit_len % 8 == 0 {
+            self.data.extend_from_slice(bytes);
+            self.bit_len += bytes.len() * 8;
+        } else {
+            for byte in bytes {
+                self.store_uint(u128::from(*byte), 8);
+            }
+        }
+        self
+    }
+
+    /// Store an amount of nanotons, or of jettons, as `Coins`, its length in bytes in 4 bits
+    /// then its bytes.
+    ///
+    /// Panics if `amount` is 2^120 or more.
+    pub fn store_coins(&mut self, amount: u128) -> &mut Self {
+        let len = 16 - amount.leading_zeros() as usize / 8;
+        assert!(len < 16, "Coins amounts are below 2^120: {amount}");
+
+        self.store_uint(len as u128, 4);
+        self.store_bytes(&amount.to_be_bytes()[16 - len..])
+    }
+
+    /// Store a reference to `cell`.
+    pub fn store_ref(&mut self, cell: Cell) -> &mut Self {
+        self.refs.push(cell);
+        self
+    }
+
+    /// Store the bits and the references of `cell`.
+    pub fn store_cell(&mut self, cell: &Cell) -> &mut Self {
+        if self.bit_len % 8 == 0 {
+            self.data.extend_from_slice(&cell.data);
+            self.bit_len += cell.bit_len;
+        } else {
+            for i in 0..cell.bit_len {
+                self.store_bit(cell.data[i / 8] & (0x80 >> (i % 8)) != 0);
+            }
+        }
+        self.refs.extend(cell.refs.iter().cloned());
+        self
+    }
+
+    /// Build the cell of the bits and references stored, failing if they do not fit in a cell.
+    pub fn build(&self) -> Result<Cell, String> {
+        if self.bit_len > MAX_BITS {
+            return Err(format!(
+                "Cells hold at most {MAX_BITS} bits: {}",
+                self.bit_len
+            ));
+        }
+        if self.refs.len() > MAX_REFS {
+            return Err(format!(
+                "Cells hold at most {MAX_REFS} references: {}",
+                self.refs.len()
+            ));
+        }
+
+        Ok(Cell {
+            data: self.data.clone(),
+            bit_len: self.bit_len,
+            refs: self.refs.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_cell_hash() {
+        assert_eq!(
+            hex::encode(Cell::empty().hash()),
+            "96a296d224f285c67bee93c30f8a309157f0daa35dc5b87e410b78630a09cfc7"
+        );
+        assert_eq!(CellBuilder::new().build().unwrap(), Cell::empty());
+    }
+
+    #[test]
+    fn test_store() {
+        let cell = CellBuilder::new()
+            .store_bit(true)
+            .store_uint(0b010, 3)
+            .store_int(-1, 8)
+            .store_bytes(&[0xab])
+            .build()
+            .unwrap();
+
+        assert_eq!(cell.bit_len(), 20);
+        assert_eq!(cell.data(), [0xaf, 0xfa, 0xb0]);
+        // 20 bits are 2 full bytes out of 3
+        assert_eq!(cell.descriptors(), [0, 5]);
+        assert_eq!(cell.padded_data(), [0xaf, 0xfa, 0xb8]);
+
+        let copy = CellBuilder::new()
+            .store_bit(false)
+            .store_cell(&cell)
+            .build()
+            .unwrap();
+        assert_eq!(copy.data(), [0x57, 0xfd, 0x58]);
+    }
+
+    #[test]
+    fn test_store_coins() {
+        for (amount, bit_len, data) in [
+            (0, 4, vec![0x00]),
+            (1, 12, vec![0x10, 0x10]),
+            (1_000_000_000, 36, vec![0x43, 0xb9, 0xac, 0xa0, 0x00]),
+        ] {
+            let cell = CellBuilder::new().store_coins(amount).build().unwrap();
+            assert_eq!((cell.bit_len(), cell.data().to_vec()), (bit_len, data));
+        }
+    }
+
+    #[test]
+    fn test_hash_with_refs() {
+        let leaf = CellBuilder::new().store_uint(1, 8).build().unwrap();
+        let root = CellBuilder::new()
+            .store_uint(2, 8)
+            .store_ref(leaf.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(leaf.depth(), 0);
+        assert_eq!(root.depth(), 1);
+
+        let expected: [u8; 32] = Sha256::new()
+            .chain_update([1, 2, 2])
+            .chain_update([0, 0])
+            .chain_update(leaf.hash())
+            .finalize()
+            .into();
+        assert_eq!(root.hash(), expected);
+    }
+
+    #[test]
+    fn test_build_overflow() {
+        let mut builder = CellBuilder::new();
+        builder.store_bytes(&[0; 127]).store_uint(0, 7);
+        assert_eq!(builder.available_bits(), 0);
+        assert!(builder.build().is_ok());
+        assert!(builder.store_bit(false).build().is_err());
+
+        let mut builder = CellBuilder::new();
+        for _ in 0..=MAX_REFS {
+            builder.store_ref(Cell::empty());
+        }
+        assert!(builder.build().is_err());
+    }
+}
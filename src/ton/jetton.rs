@@ -0,0 +1,145 @@
+//! Transfers of jettons, the tokens of TON, following the TEP-74 standard.
+//!
+//! Jettons are held by a jetton wallet contract per owner, the transfer being sent by the wallet
+//! of the owner to its jetton wallet, which moves the jettons to the jetton wallet of the
+//! destination.
+use super::{
+    cell::{Cell, CellBuilder},
+    types::{store_either_ref, Address, InternalMessage},
+};
+
+/// Op of jetton transfers, `transfer#0f8a7ea5`.
+pub const JETTON_TRANSFER_OP: u32 = 0x0f8a_7ea5;
+
+/// Transfers `amount` jettons to the owner `destination`, sent to the jetton wallet of the
+/// sender.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JettonTransfer {
+    /// An arbitrary id of the transfer, returned in its notifications.
+    pub query_id: u64,
+    /// The jettons transferred, in their smallest unit.
+    pub amount: u128,
+    /// The owner receiving the jettons, not its jetton wallet.
+    pub destination: Address,
+    /// The address receiving the nanotons left after the transfer, usually the sender.
+    pub response_destination: Option<Address>,
+    pub custom_payload: Option<Cell>,
+    /// The nanotons sent to `destination` with the notification of the transfer, none being
+    /// sent if 0.
+    pub forward_ton_amount: u128,
+    /// The body of the notification of the transfer, like a comment.
+    pub forward_payload: Option<Cell>,
+}
+
+impl JettonTransfer {
+    /// The body of the transfer, the `transfer` message of jetton wallets.
+    pub fn to_cell(&self) -> Result<Cell, String> {
+        let mut builder = CellBuilder::new();
+        builder
+            .store_uint(u128::from(JETTON_TRANSFER_OP), 32)
+            .store_uint(u128::from(self.query_id), 64)
+            .store_coins(self.amount);
+        self.destination.store(&mut builder);
+        match &self.response_destination {
+            Some(response_destination) => response_destination.store(&mut builder),
+            // addr_none
+            None => {
+                builder.store_uint(0b00, 2);
+            }
+        }
+        match &self.custom_payload {
+            Some(custom_payload) => {
+                builder.store_bit(true).store_ref(custom_payload.clone());
+            }
+            None => {
+                builder.store_bit(false);
+            }
+        }
+        builder.store_coins(self.forward_ton_amount);
+        store_either_ref(&mut builder, self.forward_payload.as_ref(), false);
+        builder.build()
+    }
+
+    /// The message sending the transfer to `jetton_wallet`, the jetton wallet of the sender,
+    /// with `value` nanotons paying its fees and its `forward_ton_amount`.
+    pub fn to_message(
+        &self,
+        jetton_wallet: Address,
+        value: u128,
+    ) -> Result<InternalMessage, String> {
+        Ok(InternalMessage {
+            destination: jetton_wallet,
+            value,
+            // The jettons are refunded if the transfer fails
+            bounce: true,
+            state_init: None,
+            body: Some(self.to_cell()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ton::types::comment;
+
+    fn destination() -> Address {
+        "0:0000000000000000000000000000000000000000000000000000000000000001"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_to_cell() {
+        let transfer = JettonTransfer {
+            query_id: 0,
+            amount: 1,
+            destination: destination(),
+            response_destination: None,
+            custom_payload: None,
+            forward_ton_amount: 0,
+            forward_payload: None,
+        };
+        let cell = transfer.to_cell().unwrap();
+
+        // op, query_id, amount, destination, addr_none, no custom payload, no forwarded TON
+        // and an empty inline forward payload
+        assert_eq!(cell.bit_len(), 32 + 64 + 12 + 267 + 2 + 1 + 4 + 1);
+        assert_eq!(
+            hex::encode(cell.data()),
+            concat!(
+                "0f8a7ea5",
+                "0000000000000000",
+                "1018000000000000000000000000000000000000000000000000000000000000000002",
+                "00"
+            )
+        );
+        assert!(cell.refs().is_empty());
+    }
+
+    #[test]
+    fn test_to_cell_with_payloads() {
+        let sender = Address::new(0, [2; 32]);
+        let transfer = JettonTransfer {
+            query_id: 42,
+            amount: 1_000_000,
+            destination: destination(),
+            response_destination: Some(sender),
+            custom_payload: Some(Cell::empty()),
+            forward_ton_amount: 1,
+            forward_payload: Some(comment("gift").unwrap()),
+        };
+        let cell = transfer.to_cell().unwrap();
+
+        assert_eq!(
+            cell.bit_len(),
+            32 + 64 + (4 + 24) + 267 + 267 + 1 + (4 + 8) + 1
+        );
+        assert_eq!(cell.refs(), [Cell::empty(), comment("gift").unwrap()]);
+
+        let message = transfer.to_message(sender, 50_000_000).unwrap();
+        assert!(message.bounce);
+        assert_eq!(message.destination, sender);
+        assert_eq!(message.body, Some(cell));
+    }
+}
@@ -0,0 +1,14 @@
+//! Transaction builder, encoders, types and utilities for TON wallets.
+pub mod boc;
+pub mod cell;
+pub mod jetton;
+mod ton_transaction;
+mod ton_transaction_builder;
+pub mod types;
+
+/// TON wallet transfer
+pub use ton_transaction::{
+    OutMessage, TonTransaction, WalletVersion, DEFAULT_WALLET_ID, MAX_MESSAGES,
+};
+/// TON wallet transfer builder
+pub use ton_transaction_builder::TonTransactionBuilder;
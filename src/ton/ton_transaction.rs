@@ -0,0 +1,247 @@
+//! TON wallet transfer
+use super::{
+    boc::serialize_boc,
+    cell::{Cell, CellBuilder},
+    types::{store_either_ref, Address, InternalMessage, Signature},
+};
+
+/// The subwallet id of the wallets of the basechain, distinguishing wallets of the same key.
+pub const DEFAULT_WALLET_ID: u32 = 698_983_191;
+/// Largest number of messages a wallet sends in a transfer.
+pub const MAX_MESSAGES: usize = 4;
+
+/// The version of the code of a wallet contract, defining the body of its external messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WalletVersion {
+    V3R2,
+    /// The wallet of most users, with plugins.
+    #[default]
+    V4R2,
+}
+
+/// A message of a wallet, and the mode it sends it with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutMessage {
+    /// The flags of the message, like [`super::types::SEND_MODE_PAY_FEES_SEPARATELY`].
+    pub send_mode: u8,
+    pub message: InternalMessage,
+}
+
+/// A transfer from a wallet contract, sent to it as an external message signed with the
+/// Ed25519 key of the wallet and serialized as a bag of cells, the `boc` of the
+/// `sendBoc` method.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::ton::types::{comment, InternalMessage, Signature, NANOTONS_PER_TON};
+/// use signet_rs::ton::{OutMessage, TonTransaction, WalletVersion, DEFAULT_WALLET_ID};
+///
+/// let tx = TonTransaction {
+///     wallet: "EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8xqB2N".parse().unwrap(),
+///     wallet_version: WalletVersion::V4R2,
+///     wallet_id: DEFAULT_WALLET_ID,
+///     seqno: 7,
+///     valid_until: 1_700_000_060,
+///     messages: vec![OutMessage {
+///         send_mode: 3,
+///         message: InternalMessage {
+///             destination: "0:83dfd552e63729b472fcbcc8c45ebcc6691702558b68ec7527e1ba403a0f31a8"
+///                 .parse()
+///                 .unwrap(),
+///             value: NANOTONS_PER_TON / 10,
+///             bounce: false,
+///             state_init: None,
+///             body: Some(comment("Hello").unwrap()),
+///         },
+///     }],
+///     state_init: None,
+/// };
+///
+/// // The hash of the body of the external message, signed with Ed25519
+/// let payload = tx.signing_hash().unwrap();
+///
+/// let signature = Signature([7; 64]);
+/// let boc = tx.build_with_signature(&signature).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TonTransaction {
+    /// The address of the wallet sending the messages.
+    pub wallet: Address,
+    pub wallet_version: WalletVersion,
+    /// The subwallet id of the wallet, [`DEFAULT_WALLET_ID`] for most wallets.
+    pub wallet_id: u32,
+    /// The number of transfers of the wallet so far, 0 for wallets not deployed yet.
+    pub seqno: u32,
+    /// The Unix time after which the wallet rejects the transfer.
+    pub valid_until: u32,
+    /// The messages of the transfer, at most [`MAX_MESSAGES`].
+    pub messages: Vec<OutMessage>,
+    /// The initial state of the wallet, deploying it with its first transfer.
+    pub state_init: Option<Cell>,
+}
+
+impl TonTransaction {
+    /// Build the body of the external message the wallet checks the signature of, its subwallet
+    /// id, expiration and seqno then its messages.
+    pub fn signing_body(&self) -> Result<Cell, String> {
+        if self.messages.len() > MAX_MESSAGES {
+            return Err(format!(
+                "Wallets send at most {MAX_MESSAGES} messages: {}",
+                self.messages.len()
+            ));
+        }
+
+        let mut builder = CellBuilder::new();
+        builder
+            .store_uint(u128::from(self.wallet_id), 32)
+            .store_uint(u128::from(self.valid_until), 32)
+            .store_uint(u128::from(self.seqno), 32);
+        if self.wallet_version == WalletVersion::V4R2 {
+            // The simple send op, rather than the ones of plugins
+            builder.store_uint(0, 8);
+        }
+        for out_message in &self.messages {
+            builder
+                .store_uint(u128::from(out_message.send_mode), 8)
+                .store_ref(out_message.message.to_cell()?);
+        }
+        builder.build()
+    }
+
+    /// The hash of the [`Self::signing_body`], signed with the Ed25519 key of the wallet.
+    pub fn signing_hash(&self) -> Result<[u8; 32], String> {
+        Ok(self.signing_body()?.hash())
+    }
+
+    /// Build the external message of the transfer signed with `signature`, its hash tracking
+    /// the transfer.
+    pub fn external_message(&self, signature: &Signature) -> Result<Cell, String> {
+        let body = CellBuilder::new()
+            .store_bytes(&signature.0)
+            .store_cell(&self.signing_body()?)
+            .build()?;
+
+        let mut builder = CellBuilder::new();
+        builder
+            // ext_in_msg_info$10 src:addr_none
+            .store_uint(0b10, 2)
+            .store_uint(0b00, 2);
+        self.wallet.store(&mut builder);
+        builder.store_coins(0);
+        store_either_ref(&mut builder, self.state_init.as_ref(), true);
+        store_either_ref(&mut builder, Some(&body), false);
+        builder.build()
+    }
+
+    /// Serialize the external message of the transfer signed with `signature` as a bag of
+    /// cells.
+    pub fn build_with_signature(&self, signature: &Signature) -> Result<Vec<u8>, String> {
+        Ok(serialize_boc(&self.external_message(signature)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ton::types::{comment, SEND_MODE_IGNORE_ERRORS, SEND_MODE_PAY_FEES_SEPARATELY};
+    use ed25519_dalek::{Signer, SigningKey, Verifier};
+
+    fn transfer() -> TonTransaction {
+        TonTransaction {
+            wallet: "EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8xqB2N"
+                .parse()
+                .unwrap(),
+            wallet_version: WalletVersion::V4R2,
+            wallet_id: DEFAULT_WALLET_ID,
+            seqno: 7,
+            valid_until: 1_700_000_060,
+            messages: vec![OutMessage {
+                send_mode: SEND_MODE_PAY_FEES_SEPARATELY | SEND_MODE_IGNORE_ERRORS,
+                message: InternalMessage {
+                    destination:
+                        "0:0000000000000000000000000000000000000000000000000000000000000001"
+                            .parse()
+                            .unwrap(),
+                    value: 100_000_000,
+                    bounce: false,
+                    state_init: None,
+                    body: Some(comment("Hello").unwrap()),
+                },
+            }],
+            state_init: None,
+        }
+    }
+
+    #[test]
+    fn test_signing_body() {
+        let tx = transfer();
+        let body = tx.signing_body().unwrap();
+
+        assert_eq!(
+            hex::encode(body.data()),
+            concat!("29a9a317", "6553f13c", "00000007", "00", "03")
+        );
+        assert_eq!(body.refs(), [tx.messages[0].message.to_cell().unwrap()]);
+        assert_eq!(tx.signing_hash().unwrap(), body.hash());
+
+        let v3 = TonTransaction {
+            wallet_version: WalletVersion::V3R2,
+            ..transfer()
+        };
+        assert_eq!(
+            hex::encode(v3.signing_body().unwrap().data()),
+            concat!("29a9a317", "6553f13c", "00000007", "03")
+        );
+
+        let too_many = TonTransaction {
+            messages: vec![tx.messages[0].clone(); MAX_MESSAGES + 1],
+            ..transfer()
+        };
+        assert!(too_many.signing_body().is_err());
+    }
+
+    #[test]
+    fn test_external_message() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let tx = transfer();
+
+        let signature = Signature(signing_key.sign(&tx.signing_hash().unwrap()).to_bytes());
+        let message = tx.external_message(&signature).unwrap();
+
+        // ext_in_msg_info$10, addr_none, the wallet, no import fee, no state, body in a ref
+        assert_eq!(message.bit_len(), 2 + 2 + 267 + 4 + 1 + 1);
+        assert_eq!(message.data()[0] >> 4, 0b1000);
+        let body = &message.refs()[0];
+        assert_eq!(body.data()[..64], signature.0);
+        assert_eq!(body.bit_len(), 512 + 32 * 3 + 8 + 8);
+        assert_eq!(body.refs(), tx.signing_body().unwrap().refs());
+
+        signing_key
+            .verifying_key()
+            .verify(
+                &tx.signing_hash().unwrap(),
+                &ed25519_dalek::Signature::from_bytes(&body.data()[..64].try_into().unwrap()),
+            )
+            .unwrap();
+
+        let boc = tx.build_with_signature(&signature).unwrap();
+        assert_eq!(boc, serialize_boc(&message));
+        // The external message, its body, the transfer and the comment
+        assert_eq!(boc[6], 4);
+    }
+
+    #[test]
+    fn test_external_message_with_state_init() {
+        let state_init = CellBuilder::new().store_uint(0b00110, 5).build().unwrap();
+        let tx = TonTransaction {
+            seqno: 0,
+            state_init: Some(state_init.clone()),
+            ..transfer()
+        };
+
+        let message = tx.external_message(&Signature([0; 64])).unwrap();
+        assert_eq!(message.bit_len(), 2 + 2 + 267 + 4 + 2 + 1);
+        assert_eq!(message.refs()[0], state_init);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::transaction_builder::TxBuilder;
+
+use super::{
+    cell::Cell,
+    ton_transaction::{OutMessage, TonTransaction, WalletVersion, DEFAULT_WALLET_ID},
+    types::{Address, InternalMessage},
+};
+
+pub struct TonTransactionBuilder {
+    wallet: Option<Address>,
+    wallet_version: Option<WalletVersion>,
+    wallet_id: Option<u32>,
+    seqno: Option<u32>,
+    valid_until: Option<u32>,
+    messages: Option<Vec<OutMessage>>,
+    state_init: Option<Cell>,
+}
+
+impl Default for TonTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<TonTransaction> for TonTransactionBuilder {
+    fn build(&self) -> TonTransaction {
+        TonTransaction {
+            wallet: self.wallet.expect("wallet is mandatory"),
+            wallet_version: self.wallet_version.unwrap_or_default(),
+            wallet_id: self.wallet_id.unwrap_or(DEFAULT_WALLET_ID),
+            seqno: self.seqno.expect("seqno is mandatory"),
+            valid_until: self.valid_until.expect("valid_until is mandatory"),
+            messages: self.messages.clone().unwrap_or_default(),
+            state_init: self.state_init.clone(),
+        }
+    }
+}
+
+impl TonTransactionBuilder {
+    pub const fn new() -> Self {
+        Self {
+            wallet: None,
+            wallet_version: None,
+            wallet_id: None,
+            seqno: None,
+            valid_until: None,
+            messages: None,
+            state_init: None,
+        }
+    }
+
+    /// Address of the wallet sending the messages.
+    pub const fn wallet(mut self, wallet: Address) -> Self {
+        self.wallet = Some(wallet);
+        self
+    }
+
+    /// Version of the code of the wallet, [`WalletVersion::V4R2`] by default.
+    pub const fn wallet_version(mut self, wallet_version: WalletVersion) -> Self {
+        self.wallet_version = Some(wallet_version);
+        self
+    }
+
+    /// Subwallet id of the wallet, [`DEFAULT_WALLET_ID`] by default.
+    pub const fn wallet_id(mut self, wallet_id: u32) -> Self {
+        self.wallet_id = Some(wallet_id);
+        self
+    }
+
+    /// Seqno of the wallet, from its `seqno` get method, 0 if not deployed yet.
+    pub const fn seqno(mut self, seqno: u32) -> Self {
+        self.seqno = Some(seqno);
+        self
+    }
+
+    /// Unix time after which the wallet rejects the transfer, usually a minute from now.
+    pub const fn valid_until(mut self, valid_until: u32) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+
+    /// Messages of the transfer, at most 4.
+    pub fn messages(mut self, messages: Vec<OutMessage>) -> Self {
+        self.messages = Some(messages);
+        self
+    }
+
+    /// Append a message to the ones of the transfer, sent with the flags `send_mode`.
+    pub fn add_message(mut self, send_mode: u8, message: InternalMessage) -> Self {
+        self.messages
+            .get_or_insert_with(Vec::new)
+            .push(OutMessage { send_mode, message });
+        self
+    }
+
+    /// Initial state of the wallet, its code and data, to deploy it with its first transfer.
+    pub fn state_init(mut self, state_init: Cell) -> Self {
+        self.state_init = Some(state_init);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ton::types::{comment, SEND_MODE_PAY_FEES_SEPARATELY};
+    use crate::TransactionBuilder;
+
+    #[test]
+    fn test_build() {
+        let wallet: Address = "EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8xqB2N"
+            .parse()
+            .unwrap();
+        let message = InternalMessage {
+            destination: wallet,
+            value: 1,
+            bounce: true,
+            state_init: None,
+            body: Some(comment("Hello").unwrap()),
+        };
+
+        let tx: TonTransaction = TransactionBuilder::new::<TonTransactionBuilder>()
+            .wallet(wallet)
+            .seqno(7)
+            .valid_until(1_700_000_060)
+            .add_message(SEND_MODE_PAY_FEES_SEPARATELY, message.clone())
+            .build();
+
+        assert_eq!(
+            tx,
+            TonTransaction {
+                wallet,
+                wallet_version: WalletVersion::V4R2,
+                wallet_id: DEFAULT_WALLET_ID,
+                seqno: 7,
+                valid_until: 1_700_000_060,
+                messages: vec![OutMessage {
+                    send_mode: SEND_MODE_PAY_FEES_SEPARATELY,
+                    message,
+                }],
+                state_init: None,
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "valid_until is mandatory")]
+    fn test_build_without_valid_until() {
+        let _: TonTransaction = TonTransactionBuilder::new()
+            .wallet(Address::new(0, [1; 32]))
+            .seqno(7)
+            .build();
+    }
+}
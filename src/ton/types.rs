@@ -0,0 +1,331 @@
+//! Types used by the TON transaction builder.
+use core::{fmt, str::FromStr};
+
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE},
+    Engine,
+};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::cell::{Cell, CellBuilder};
+use crate::signer::types::EddsaSignatureResponse;
+
+/// Nanotons in one TON.
+pub const NANOTONS_PER_TON: u128 = 1_000_000_000;
+
+/// Send mode paying the forwarding fees of a message apart from its value.
+pub const SEND_MODE_PAY_FEES_SEPARATELY: u8 = 1;
+/// Send mode ignoring the errors of a message, rather than failing the whole transaction.
+pub const SEND_MODE_IGNORE_ERRORS: u8 = 2;
+/// Send mode carrying the whole remaining balance of the wallet with a message.
+pub const SEND_MODE_CARRY_ALL_BALANCE: u8 = 128;
+
+/// Flag of user-friendly addresses of contracts receiving bounceable messages.
+const BOUNCEABLE_TAG: u8 = 0x11;
+/// Flag of user-friendly addresses of wallets not deployed yet, receiving non-bounceable
+/// messages.
+const NON_BOUNCEABLE_TAG: u8 = 0x51;
+/// Flag of user-friendly addresses of the testnet.
+const TESTNET_FLAG: u8 = 0x80;
+
+/// The address of a contract, its workchain and the hash of its initial state, displayed in
+/// its raw form like `0:83dfd552e63729b472fcbcc8c45ebcc6691702558b68ec7527e1ba403a0f31a8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Address {
+    /// The workchain of the contract, 0 for the basechain, -1 for the masterchain.
+    pub workchain: i8,
+    pub hash_part: [u8; 32],
+}
+
+impl Address {
+    pub const fn new(workchain: i8, hash_part: [u8; 32]) -> Self {
+        Self {
+            workchain,
+            hash_part,
+        }
+    }
+
+    /// The URL-safe user-friendly form of the address, like
+    /// `EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8xqB2N`, telling wallets whether to send it
+    /// bounceable messages.
+    pub fn to_friendly(&self, bounceable: bool, testnet: bool) -> String {
+        let mut tag = if bounceable {
+            BOUNCEABLE_TAG
+        } else {
+            NON_BOUNCEABLE_TAG
+        };
+        if testnet {
+            tag |= TESTNET_FLAG;
+        }
+
+        let mut bytes = vec![tag, self.workchain as u8];
+        bytes.extend_from_slice(&self.hash_part);
+        bytes.extend_from_slice(&crc16(&bytes).to_be_bytes());
+        URL_SAFE.encode(bytes)
+    }
+
+    /// Parse a user-friendly address, URL-safe or not, checking its checksum.
+    fn from_friendly(s: &str) -> Result<Self, String> {
+        let bytes = URL_SAFE
+            .decode(s)
+            .or_else(|_| STANDARD.decode(s))
+            .map_err(|e| format!("Invalid address {s}: {e}"))?;
+        if bytes.len() != 36 {
+            return Err(format!("Invalid address length: {s}"));
+        }
+        if ![BOUNCEABLE_TAG, NON_BOUNCEABLE_TAG].contains(&(bytes[0] & !TESTNET_FLAG)) {
+            return Err(format!("Invalid address tag: {s}"));
+        }
+        if crc16(&bytes[..34]).to_be_bytes() != bytes[34..] {
+            return Err(format!("Invalid address checksum: {s}"));
+        }
+
+        let mut hash_part = [0; 32];
+        hash_part.copy_from_slice(&bytes[2..34]);
+        Ok(Self::new(bytes[1] as i8, hash_part))
+    }
+
+    /// Store the address as a `MsgAddressInt`, an `addr_std` without anycast.
+    pub fn store(&self, builder: &mut CellBuilder) {
+        builder
+            .store_uint(0b100, 3)
+            .store_int(i128::from(self.workchain), 8)
+            .store_bytes(&self.hash_part);
+    }
+}
+
+/// Parse an address in its raw form, or in its user-friendly form.
+impl FromStr for Address {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((workchain, hash_part)) = s.split_once(':') else {
+            return Self::from_friendly(s);
+        };
+
+        let workchain = workchain
+            .parse()
+            .map_err(|e| format!("Invalid address workchain {s}: {e}"))?;
+        let mut bytes = [0; 32];
+        hex::decode_to_slice(hash_part, &mut bytes)
+            .map_err(|e| format!("Invalid address {s}: {e}"))?;
+        Ok(Self::new(workchain, bytes))
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.workchain, hex::encode(self.hash_part))
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// An Ed25519 signature of the hash of the body of an external message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signature(pub [u8; 64]);
+
+impl From<EddsaSignatureResponse> for Signature {
+    fn from(response: EddsaSignatureResponse) -> Self {
+        Self(response.signature)
+    }
+}
+
+/// A message sent by a wallet to `destination`, carrying `value` nanotons and an optional body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternalMessage {
+    pub destination: Address,
+    /// The nanotons carried by the message.
+    pub value: u128,
+    /// Whether the message bounces back with its value if the destination fails to process it,
+    /// unset for wallets not deployed yet.
+    pub bounce: bool,
+    /// The initial state of the destination, deploying it if not deployed yet.
+    pub state_init: Option<Cell>,
+    /// The body of the message, like a comment or a jetton transfer.
+    pub body: Option<Cell>,
+}
+
+impl InternalMessage {
+    /// The message as a `Message` cell, with its `int_msg_info` header and its state and body
+    /// as references.
+    pub fn to_cell(&self) -> Result<Cell, String> {
+        let mut builder = CellBuilder::new();
+        builder
+            // int_msg_info$0 ihr_disabled:Bool bounce:Bool bounced:Bool
+            .store_bit(false)
+            .store_bit(true)
+            .store_bit(self.bounce)
+            .store_bit(false)
+            // src:addr_none, filled in by the wallet
+            .store_uint(0b00, 2);
+        self.destination.store(&mut builder);
+        builder
+            .store_coins(self.value)
+            // No extra currencies, no fees, created_lt and created_at set by the validators
+            .store_bit(false)
+            .store_coins(0)
+            .store_coins(0)
+            .store_uint(0, 64)
+            .store_uint(0, 32);
+        store_either_ref(&mut builder, self.state_init.as_ref(), true);
+        store_either_ref(&mut builder, self.body.as_ref(), false);
+        builder.build()
+    }
+}
+
+/// Store `cell` as the reference of an `Either X ^X`, or an empty inline `X` if absent, in a
+/// `Maybe` if `maybe` is set.
+pub(crate) fn store_either_ref(builder: &mut CellBuilder, cell: Option<&Cell>, maybe: bool) {
+    match cell {
+        Some(cell) => {
+            if maybe {
+                builder.store_bit(true);
+            }
+            builder.store_bit(true).store_ref(cell.clone());
+        }
+        None => {
+            builder.store_bit(false);
+        }
+    }
+}
+
+/// The body of a transfer with the text comment `text`, the op 0 then the text, continued in
+/// references if it does not fit in a cell.
+pub fn comment(text: &str) -> Result<Cell, String> {
+    let mut builder = CellBuilder::new();
+    builder.store_uint(0, 32);
+    let (head, mut tail) = text.as_bytes().split_at(text.len().min(123));
+    builder.store_bytes(head);
+
+    let mut cells = vec![builder];
+    while !tail.is_empty() {
+        let (head, rest) = tail.split_at(tail.len().min(127));
+        let mut builder = CellBuilder::new();
+        builder.store_bytes(head);
+        cells.push(builder);
+        tail = rest;
+    }
+
+    let mut cell: Option<Cell> = None;
+    for mut builder in cells.into_iter().rev() {
+        if let Some(next) = cell {
+            builder.store_ref(next);
+        }
+        cell = Some(builder.build()?);
+    }
+    Ok(cell.expect("a comment has a first cell"))
+}
+
+/// The crc16 (XMODEM) checksum of `data`, ending user-friendly addresses.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAW_ADDRESS: &str = "0:83dfd552e63729b472fcbcc8c45ebcc6691702558b68ec7527e1ba403a0f31a8";
+
+    #[test]
+    fn test_crc16() {
+        assert_eq!(crc16(b"123456789"), 0x31c3);
+    }
+
+    #[test]
+    fn test_address() {
+        let address: Address = RAW_ADDRESS.parse().unwrap();
+        assert_eq!(address.workchain, 0);
+        assert_eq!(address.to_string(), RAW_ADDRESS);
+
+        let friendly = address.to_friendly(true, false);
+        assert_eq!(friendly, "EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8xqB2N");
+        assert_eq!(friendly.parse::<Address>().unwrap(), address);
+        for (bounceable, testnet) in [(false, false), (true, true), (false, true)] {
+            let friendly = address.to_friendly(bounceable, testnet);
+            assert_eq!(friendly.parse::<Address>().unwrap(), address);
+        }
+
+        let masterchain: Address = format!("-1:{}", "ab".repeat(32)).parse().unwrap();
+        assert_eq!(masterchain.workchain, -1);
+        assert_eq!(masterchain.to_string(), format!("-1:{}", "ab".repeat(32)));
+
+        for invalid in [
+            "EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8xqB2M",
+            "EQCD39VS5jcptHL8vMjEXrzGaRcCVYto7HUn4bpAOg8x",
+            "0:83dfd552",
+            "x:83dfd552e63729b472fcbcc8c45ebcc6691702558b68ec7527e1ba403a0f31a8",
+        ] {
+            assert!(invalid.parse::<Address>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_store_address() {
+        let address: Address = RAW_ADDRESS.parse().unwrap();
+        let mut builder = CellBuilder::new();
+        address.store(&mut builder);
+        let cell = builder.build().unwrap();
+
+        assert_eq!(cell.bit_len(), 267);
+        // addr_std$10, no anycast, workchain 0, then the hash part
+        assert_eq!(cell.data()[..2], [0x80, 0x10]);
+        assert_eq!(cell.data()[1] & 0x1f, address.hash_part[0] >> 3);
+    }
+
+    #[test]
+    fn test_internal_message() {
+        let message = InternalMessage {
+            destination: RAW_ADDRESS.parse().unwrap(),
+            value: NANOTONS_PER_TON,
+            bounce: true,
+            state_init: None,
+            body: Some(comment("hi").unwrap()),
+        };
+        let cell = message.to_cell().unwrap();
+
+        // Header, addresses, value, no extra currencies nor fees, lt and time, no state, body
+        assert_eq!(
+            cell.bit_len(),
+            4 + 2 + 267 + 36 + 1 + 4 + 4 + 64 + 32 + 1 + 1
+        );
+        assert_eq!(cell.data()[0] >> 4, 0b0110);
+        assert_eq!(cell.refs(), [comment("hi").unwrap()]);
+    }
+
+    #[test]
+    fn test_comment() {
+        let cell = comment("hi").unwrap();
+        assert_eq!(cell.data(), [0, 0, 0, 0, b'h', b'i']);
+        assert!(cell.refs().is_empty());
+
+        let text = "a".repeat(123 + 127 + 1);
+        let cell = comment(&text).unwrap();
+        assert_eq!(cell.bit_len(), 32 + 123 * 8);
+        assert_eq!(cell.refs()[0].bit_len(), 127 * 8);
+        assert_eq!(cell.refs()[0].refs()[0].data(), b"a");
+    }
+}
@@ -1,12 +1,60 @@
 //! Low level transaction builders for different blockchains.
+#[cfg(feature = "aptos")]
+use crate::aptos::AptosTransactionBuilder;
+
 #[cfg(feature = "bitcoin")]
 use crate::bitcoin::BitcoinTransactionBuilder;
 
+#[cfg(feature = "cosmos")]
+use crate::cosmos::CosmosTransactionBuilder;
+
 #[cfg(feature = "evm")]
 use crate::evm::EVMTransactionBuilder;
 
+#[cfg(feature = "near")]
+use crate::near::NEARTransactionBuilder;
+
+#[cfg(feature = "solana")]
+use crate::solana::SolanaTransactionBuilder;
+
+#[cfg(feature = "substrate")]
+use crate::substrate::SubstrateTransactionBuilder;
+
+#[cfg(feature = "ton")]
+use crate::ton::TonTransactionBuilder;
+
+#[cfg(feature = "tron")]
+use crate::tron::TronTransactionBuilder;
+
+#[cfg(feature = "xrpl")]
+use crate::xrpl::XrplTransactionBuilder;
+
 #[cfg(feature = "evm")]
 pub type EVM = EVMTransactionBuilder;
 
 #[cfg(feature = "bitcoin")]
 pub type BITCOIN = BitcoinTransactionBuilder;
+
+#[cfg(feature = "cosmos")]
+pub type COSMOS = CosmosTransactionBuilder;
+
+#[cfg(feature = "near")]
+pub type NEAR = NEARTransactionBuilder;
+
+#[cfg(feature = "solana")]
+pub type SOLANA = SolanaTransactionBuilder;
+
+#[cfg(feature = "substrate")]
+pub type SUBSTRATE = SubstrateTransactionBuilder;
+
+#[cfg(feature = "ton")]
+pub type TON = TonTransactionBuilder;
+
+#[cfg(feature = "tron")]
+pub type TRON = TronTransactionBuilder;
+
+#[cfg(feature = "xrpl")]
+pub type XRPL = XrplTransactionBuilder;
+
+#[cfg(feature = "aptos")]
+pub type APTOS = AptosTransactionBuilder;
@@ -0,0 +1,11 @@
+//! Transaction builder, encoders, types and utilities for Tron.
+pub mod trc20;
+mod tron_transaction;
+mod tron_transaction_builder;
+pub mod types;
+pub mod utils;
+
+/// Tron transaction
+pub use tron_transaction::TronTransaction;
+/// Tron transaction builder
+pub use tron_transaction_builder::TronTransactionBuilder;
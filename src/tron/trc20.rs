@@ -0,0 +1,128 @@
+//! Transfers of TRC-20 tokens, like USDT on Tron, calling the ERC-20 `transfer` function of
+//! their contract with a TriggerSmartContract.
+//!
+//! The calldata is ABI encoded like on Ethereum, addresses being their 20 bytes without the
+//! [`super::types::ADDRESS_PREFIX`].
+use super::types::{Address, Contract};
+
+/// Selector of `transfer(address,uint256)`.
+pub const TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+/// The ABI encoding of `address`, its 20 bytes left padded to 32 bytes.
+pub fn encode_address(address: &Address) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[12..].copy_from_slice(&address.0);
+    word
+}
+
+/// The ABI encoding of the `uint256` `value`.
+pub fn encode_uint256(value: u128) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Transfers `amount` tokens of the TRC-20 contract `contract_address` to `to_address`.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::tron::trc20::Trc20Transfer;
+///
+/// let transfer = Trc20Transfer {
+///     // USDT
+///     contract_address: "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap(),
+///     to_address: "T9yD14Nj9j7xAB4dbGeiX9h8unkKHxuWwb".parse().unwrap(),
+///     // 1 USDT, of 6 decimals
+///     amount: 1_000_000,
+/// };
+///
+/// let owner_address = "TJCnKsPa7y5okkXvQAidZBzqx3QyQ6sxMW".parse().unwrap();
+/// let contract = transfer.to_contract(owner_address);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trc20Transfer {
+    pub contract_address: Address,
+    pub to_address: Address,
+    /// The tokens transferred, in their smallest unit.
+    pub amount: u128,
+}
+
+impl Trc20Transfer {
+    /// The calldata of the transfer, `transfer(to_address, amount)`.
+    pub fn calldata(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(4 + 32 * 2);
+        data.extend_from_slice(&TRANSFER_SELECTOR);
+        data.extend_from_slice(&encode_address(&self.to_address));
+        data.extend_from_slice(&encode_uint256(self.amount));
+        data
+    }
+
+    /// The contract of the transaction of `owner_address` sending the tokens, without TRX.
+    ///
+    /// The transaction needs a fee limit covering the energy of the call, unless the owner
+    /// has staked enough energy.
+    pub fn to_contract(&self, owner_address: Address) -> Contract {
+        Contract::TriggerSmartContract {
+            owner_address,
+            contract_address: self.contract_address,
+            call_value: 0,
+            data: self.calldata(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tron::tron_transaction::tests::proto;
+    use prost::Message;
+
+    fn usdt_transfer() -> Trc20Transfer {
+        Trc20Transfer {
+            contract_address: "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap(),
+            to_address: "TJCnKsPa7y5okkXvQAidZBzqx3QyQ6sxMW".parse().unwrap(),
+            amount: 1_000_000,
+        }
+    }
+
+    #[test]
+    fn test_calldata() {
+        assert_eq!(
+            hex::encode(usdt_transfer().calldata()),
+            concat!(
+                "a9059cbb",
+                "0000000000000000000000005a523b449890854c8fc460ab602df9f31fe4293f",
+                "00000000000000000000000000000000000000000000000000000000000f4240"
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_contract() {
+        let transfer = usdt_transfer();
+        let owner_address = Address([1; 20]);
+        let contract = transfer.to_contract(owner_address);
+
+        assert_eq!(contract.owner_address(), &owner_address);
+        let trigger_smart_contract = proto::TriggerSmartContract {
+            owner_address: owner_address.to_bytes().to_vec(),
+            contract_address: hex::decode("41a614f803b6fd780986a42c78ec9c7f77e6ded13c").unwrap(),
+            call_value: 0,
+            data: transfer.calldata(),
+        };
+        assert_eq!(
+            contract.encode_parameter(),
+            trigger_smart_contract.encode_to_vec()
+        );
+
+        let encoded = proto::Contract {
+            r#type: 31,
+            parameter: Some(proto::Any {
+                type_url: "type.googleapis.com/protocol.TriggerSmartContract".to_string(),
+                value: trigger_smart_contract.encode_to_vec(),
+            }),
+        };
+        assert_eq!(contract.encode(), encoded.encode_to_vec());
+    }
+}
@@ -0,0 +1,228 @@
+//! Tron transaction
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{
+    types::{Contract, Signature},
+    utils::{encode_bytes, encode_message, encode_string, encode_uint64},
+};
+
+/// A Tron transaction with a single contract and a single signer, encoded as the protobuf
+/// `Transaction` broadcast with the `broadcasthex` method of nodes.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::tron::types::{Contract, Signature, SUN_PER_TRX};
+/// use signet_rs::tron::TronTransaction;
+///
+/// let tx = TronTransaction {
+///     ref_block_bytes: [0xa3, 0xe1],
+///     ref_block_hash: [0xc1, 0xb8, 0xef, 0x5b, 0x9a, 0x0b, 0x5e, 0x34],
+///     expiration: 1_700_000_060_000,
+///     timestamp: 1_700_000_000_000,
+///     fee_limit: 0,
+///     memo: String::new(),
+///     contract: Contract::Transfer {
+///         owner_address: "TJCnKsPa7y5okkXvQAidZBzqx3QyQ6sxMW".parse().unwrap(),
+///         to_address: "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap(),
+///         amount: 10 * SUN_PER_TRX,
+///     },
+/// };
+///
+/// // The sha256 of the raw data, the id of the transaction, signed with secp256k1
+/// let payload = tx.signing_hash();
+///
+/// let signature = Signature([7; 65]);
+/// let signed_tx = tx.build_with_signature(&signature);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TronTransaction {
+    /// The bytes 6 and 7 of the number of the reference block, see
+    /// [`super::utils::ref_block`].
+    pub ref_block_bytes: [u8; 2],
+    /// The bytes 8 to 15 of the id of the reference block.
+    pub ref_block_hash: [u8; 8],
+    /// The time in milliseconds after which the transaction is no longer valid, at most 24
+    /// hours after the time of the reference block.
+    pub expiration: u64,
+    /// The time in milliseconds the transaction is built at.
+    pub timestamp: u64,
+    /// The most sun burnt for the energy of smart contract calls, 0 for none.
+    pub fee_limit: u64,
+    /// A note stored with the transaction, its `data`, charged for by the byte.
+    pub memo: String,
+    pub contract: Contract,
+}
+
+impl TronTransaction {
+    /// Encode the `raw_data` of the transaction, the bytes hashed into its id.
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_bytes(&mut buffer, 1, &self.ref_block_bytes);
+        encode_bytes(&mut buffer, 4, &self.ref_block_hash);
+        encode_uint64(&mut buffer, 8, self.expiration);
+        encode_string(&mut buffer, 10, &self.memo);
+        encode_message(&mut buffer, 11, &self.contract.encode());
+        encode_uint64(&mut buffer, 14, self.timestamp);
+        encode_uint64(&mut buffer, 18, self.fee_limit);
+        buffer
+    }
+
+    /// The sha256 of the raw data of the transaction, its id, signed with secp256k1.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.build_for_signing()).into()
+    }
+
+    /// Encode the transaction as a `Transaction` with the signature of its owner.
+    pub fn build_with_signature(&self, signature: &Signature) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_message(&mut buffer, 1, &self.build_for_signing());
+        encode_message(&mut buffer, 2, &signature.0);
+        buffer
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::tron::types::{Address, SUN_PER_TRX};
+    use prost::Message;
+
+    /// The protobuf definitions of java-tron, encoded with prost.
+    pub(crate) mod proto {
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct Any {
+            #[prost(string, tag = "1")]
+            pub type_url: String,
+            #[prost(bytes, tag = "2")]
+            pub value: Vec<u8>,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct TransferContract {
+            #[prost(bytes, tag = "1")]
+            pub owner_address: Vec<u8>,
+            #[prost(bytes, tag = "2")]
+            pub to_address: Vec<u8>,
+            #[prost(int64, tag = "3")]
+            pub amount: i64,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct TriggerSmartContract {
+            #[prost(bytes, tag = "1")]
+            pub owner_address: Vec<u8>,
+            #[prost(bytes, tag = "2")]
+            pub contract_address: Vec<u8>,
+            #[prost(int64, tag = "3")]
+            pub call_value: i64,
+            #[prost(bytes, tag = "4")]
+            pub data: Vec<u8>,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct Contract {
+            #[prost(int32, tag = "1")]
+            pub r#type: i32,
+            #[prost(message, optional, tag = "2")]
+            pub parameter: Option<Any>,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct Raw {
+            #[prost(bytes, tag = "1")]
+            pub ref_block_bytes: Vec<u8>,
+            #[prost(bytes, tag = "4")]
+            pub ref_block_hash: Vec<u8>,
+            #[prost(int64, tag = "8")]
+            pub expiration: i64,
+            #[prost(bytes, tag = "10")]
+            pub data: Vec<u8>,
+            #[prost(message, repeated, tag = "11")]
+            pub contract: Vec<Contract>,
+            #[prost(int64, tag = "14")]
+            pub timestamp: i64,
+            #[prost(int64, tag = "18")]
+            pub fee_limit: i64,
+        }
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct Transaction {
+            #[prost(message, optional, tag = "1")]
+            pub raw_data: Option<Raw>,
+            #[prost(bytes, repeated, tag = "2")]
+            pub signature: Vec<Vec<u8>>,
+        }
+    }
+
+    pub(crate) fn transfer() -> TronTransaction {
+        TronTransaction {
+            ref_block_bytes: [0xa3, 0xe1],
+            ref_block_hash: [0xc1, 0xb8, 0xef, 0x5b, 0x9a, 0x0b, 0x5e, 0x34],
+            expiration: 1_700_000_060_000,
+            timestamp: 1_700_000_000_000,
+            fee_limit: 0,
+            memo: String::new(),
+            contract: Contract::Transfer {
+                owner_address: Address([1; 20]),
+                to_address: "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap(),
+                amount: 10 * SUN_PER_TRX,
+            },
+        }
+    }
+
+    #[test]
+    fn test_encode_against_prost() {
+        let tx = TronTransaction {
+            memo: "invoice 42".to_string(),
+            ..transfer()
+        };
+
+        let transfer_contract = proto::TransferContract {
+            owner_address: Address([1; 20]).to_bytes().to_vec(),
+            to_address: hex::decode("41a614f803b6fd780986a42c78ec9c7f77e6ded13c").unwrap(),
+            amount: 10_000_000,
+        };
+        let raw = proto::Raw {
+            ref_block_bytes: vec![0xa3, 0xe1],
+            ref_block_hash: vec![0xc1, 0xb8, 0xef, 0x5b, 0x9a, 0x0b, 0x5e, 0x34],
+            expiration: 1_700_000_060_000,
+            data: b"invoice 42".to_vec(),
+            contract: vec![proto::Contract {
+                r#type: 1,
+                parameter: Some(proto::Any {
+                    type_url: "type.googleapis.com/protocol.TransferContract".to_string(),
+                    value: transfer_contract.encode_to_vec(),
+                }),
+            }],
+            timestamp: 1_700_000_000_000,
+            fee_limit: 0,
+        };
+        assert_eq!(tx.build_for_signing(), raw.encode_to_vec());
+        assert_eq!(
+            tx.signing_hash(),
+            <[u8; 32]>::from(Sha256::digest(raw.encode_to_vec()))
+        );
+
+        let signature = Signature([7; 65]);
+        let signed = proto::Transaction {
+            raw_data: Some(raw),
+            signature: vec![signature.0.to_vec()],
+        };
+        assert_eq!(tx.build_with_signature(&signature), signed.encode_to_vec());
+    }
+
+    #[test]
+    fn test_build_for_signing() {
+        let raw = transfer().build_for_signing();
+
+        assert_eq!(
+            hex::encode(&raw[..21]),
+            concat!("0a02a3e1", "2208c1b8ef5b9a0b5e34", "40e0a499ffbc31")
+        );
+        // The contract follows, then the timestamp, without fee limit nor memo
+        assert_eq!(raw[21], 0x5a);
+        assert!(hex::encode(&raw).ends_with("7080d095ffbc31"));
+    }
+}
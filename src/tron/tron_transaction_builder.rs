@@ -0,0 +1,192 @@
+use crate::transaction_builder::TxBuilder;
+
+use super::{
+    tron_transaction::TronTransaction,
+    types::{Address, Contract},
+    utils::ref_block,
+};
+
+pub struct TronTransactionBuilder {
+    ref_block_bytes: Option<[u8; 2]>,
+    ref_block_hash: Option<[u8; 8]>,
+    expiration: Option<u64>,
+    timestamp: Option<u64>,
+    fee_limit: Option<u64>,
+    memo: Option<String>,
+    contract: Option<Contract>,
+}
+
+impl Default for TronTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<TronTransaction> for TronTransactionBuilder {
+    fn build(&self) -> TronTransaction {
+        TronTransaction {
+            ref_block_bytes: self.ref_block_bytes.expect("ref_block is mandatory"),
+            ref_block_hash: self.ref_block_hash.expect("ref_block is mandatory"),
+            expiration: self.expiration.expect("expiration is mandatory"),
+            timestamp: self.timestamp.expect("timestamp is mandatory"),
+            fee_limit: self.fee_limit.unwrap_or_default(),
+            memo: self.memo.clone().unwrap_or_default(),
+            contract: self.contract.clone().expect("contract is mandatory"),
+        }
+    }
+}
+
+impl TronTransactionBuilder {
+    pub const fn new() -> Self {
+        Self {
+            ref_block_bytes: None,
+            ref_block_hash: None,
+            expiration: None,
+            timestamp: None,
+            fee_limit: None,
+            memo: None,
+            contract: None,
+        }
+    }
+
+    /// Block the transaction is built on, of number `block_number` and id `block_id`, usually
+    /// the latest solidified block.
+    pub fn ref_block(mut self, block_number: u64, block_id: &[u8; 32]) -> Self {
+        let (ref_block_bytes, ref_block_hash) = ref_block(block_number, block_id);
+        self.ref_block_bytes = Some(ref_block_bytes);
+        self.ref_block_hash = Some(ref_block_hash);
+        self
+    }
+
+    /// Time in milliseconds after which the transaction is no longer valid, usually a minute
+    /// after the time of the reference block.
+    pub const fn expiration(mut self, expiration: u64) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+
+    /// Time in milliseconds the transaction is built at.
+    pub const fn timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Most sun burnt for the energy of smart contract calls, 0 by default.
+    pub const fn fee_limit(mut self, fee_limit: u64) -> Self {
+        self.fee_limit = Some(fee_limit);
+        self
+    }
+
+    /// Note stored with the transaction.
+    pub fn memo(mut self, memo: String) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+
+    /// Action of the transaction and its parameters.
+    pub fn contract(mut self, contract: Contract) -> Self {
+        self.contract = Some(contract);
+        self
+    }
+
+    /// Transfer of `amount` sun from `owner_address`, which signs, to `to_address`.
+    pub fn transfer(mut self, owner_address: Address, to_address: Address, amount: u64) -> Self {
+        self.contract = Some(Contract::Transfer {
+            owner_address,
+            to_address,
+            amount,
+        });
+        self
+    }
+
+    /// Call of the smart contract `contract_address` by `owner_address` with the ABI encoded
+    /// `data`, like the calldata of a [`super::trc20::Trc20Transfer`], without TRX.
+    pub fn trigger_smart_contract(
+        mut self,
+        owner_address: Address,
+        contract_address: Address,
+        data: Vec<u8>,
+    ) -> Self {
+        self.contract = Some(Contract::TriggerSmartContract {
+            owner_address,
+            contract_address,
+            call_value: 0,
+            data,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tron::{trc20::Trc20Transfer, types::SUN_PER_TRX};
+    use crate::TransactionBuilder;
+
+    #[test]
+    fn test_build() {
+        let mut block_id = [0; 32];
+        block_id[6..8].copy_from_slice(&[0xa3, 0xe1]);
+        block_id[8..16].copy_from_slice(&[0xc1, 0xb8, 0xef, 0x5b, 0x9a, 0x0b, 0x5e, 0x34]);
+        let owner_address = Address([1; 20]);
+        let to_address: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+
+        let tx: TronTransaction = TransactionBuilder::new::<TronTransactionBuilder>()
+            .ref_block(0xa3e1, &block_id)
+            .expiration(1_700_000_060_000)
+            .timestamp(1_700_000_000_000)
+            .transfer(owner_address, to_address, 10 * SUN_PER_TRX)
+            .build();
+
+        assert_eq!(
+            tx,
+            TronTransaction {
+                ref_block_bytes: [0xa3, 0xe1],
+                ref_block_hash: [0xc1, 0xb8, 0xef, 0x5b, 0x9a, 0x0b, 0x5e, 0x34],
+                expiration: 1_700_000_060_000,
+                timestamp: 1_700_000_000_000,
+                fee_limit: 0,
+                memo: String::new(),
+                contract: Contract::Transfer {
+                    owner_address,
+                    to_address,
+                    amount: 10 * SUN_PER_TRX,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_build_trc20_transfer() {
+        let transfer = Trc20Transfer {
+            contract_address: "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap(),
+            to_address: Address([2; 20]),
+            amount: 1_000_000,
+        };
+
+        let tx: TronTransaction = TronTransactionBuilder::new()
+            .ref_block(0xa3e1, &[0; 32])
+            .expiration(1_700_000_060_000)
+            .timestamp(1_700_000_000_000)
+            .fee_limit(30 * SUN_PER_TRX)
+            .trigger_smart_contract(
+                Address([1; 20]),
+                transfer.contract_address,
+                transfer.calldata(),
+            )
+            .build();
+
+        assert_eq!(tx.fee_limit, 30 * SUN_PER_TRX);
+        assert_eq!(tx.contract, transfer.to_contract(Address([1; 20])));
+    }
+
+    #[test]
+    #[should_panic(expected = "ref_block is mandatory")]
+    fn test_build_without_ref_block() {
+        let _: TronTransaction = TronTransactionBuilder::new()
+            .expiration(1_700_000_060_000)
+            .timestamp(1_700_000_000_000)
+            .transfer(Address([1; 20]), Address([2; 20]), 1)
+            .build();
+    }
+}
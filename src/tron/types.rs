@@ -0,0 +1,242 @@
+//! Types used by the Tron transaction builder.
+use core::{fmt, str::FromStr};
+
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::utils::{encode_bytes, encode_message, encode_string, encode_uint64};
+
+/// Sun in one TRX.
+pub const SUN_PER_TRX: u64 = 1_000_000;
+/// Prefix of the addresses of the mainnet, making their base58check encoding start with `T`.
+pub const ADDRESS_PREFIX: u8 = 0x41;
+
+/// An account or a contract, the last 20 bytes of the keccak256 of its public key like
+/// Ethereum addresses, displayed in base58check with the [`ADDRESS_PREFIX`], like
+/// `TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Address(pub [u8; 20]);
+
+impl Address {
+    /// The 21 bytes of the address in transactions, prefixed with [`ADDRESS_PREFIX`].
+    pub fn to_bytes(&self) -> [u8; 21] {
+        let mut bytes = [ADDRESS_PREFIX; 21];
+        bytes[1..].copy_from_slice(&self.0);
+        bytes
+    }
+
+    /// An address from its 21 bytes, prefixed with [`ADDRESS_PREFIX`].
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        match bytes {
+            [ADDRESS_PREFIX, address @ ..] if address.len() == 20 => Ok(Self(
+                address.try_into().expect("the length is checked above"),
+            )),
+            [prefix, ..] if bytes.len() == 21 => {
+                Err(format!("Invalid address prefix: {prefix:#04x}"))
+            }
+            _ => Err(format!("Invalid address length: {}", bytes.len())),
+        }
+    }
+}
+
+/// Parses base58check addresses, or their 21 bytes in hex like the HTTP API of nodes returns
+/// them.
+impl FromStr for Address {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = if s.len() == 42 {
+            hex::decode(s).map_err(|e| format!("Invalid address {s}: {e}"))?
+        } else {
+            bs58::decode(s)
+                .with_check(None)
+                .into_vec()
+                .map_err(|e| format!("Invalid address {s}: {e}"))?
+        };
+
+        Self::from_slice(&bytes)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&bs58::encode(self.to_bytes()).with_check().into_string())
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// A secp256k1 signature of a transaction, its `r` and `s` then its recovery id plus 27, like
+/// Ethereum personal signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature(pub [u8; 65]);
+
+/// The type of a contract, the action of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContractType {
+    TransferContract = 1,
+    TriggerSmartContract = 31,
+}
+
+/// The contract of a transaction, its action and its parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Contract {
+    /// Sends `amount` sun from `owner_address`, which signs, to `to_address`, activating it if
+    /// it is a new account.
+    Transfer {
+        owner_address: Address,
+        to_address: Address,
+        amount: u64,
+    },
+    /// Calls the smart contract `contract_address` with the ABI encoded `data`, sending it
+    /// `call_value` sun, the energy of the call being paid up to the `fee_limit` of the
+    /// transaction.
+    TriggerSmartContract {
+        owner_address: Address,
+        contract_address: Address,
+        call_value: u64,
+        data: Vec<u8>,
+    },
+}
+
+impl Contract {
+    pub const fn contract_type(&self) -> ContractType {
+        match self {
+            Self::Transfer { .. } => ContractType::TransferContract,
+            Self::TriggerSmartContract { .. } => ContractType::TriggerSmartContract,
+        }
+    }
+
+    /// The type URL of the parameter of the contract, in its `Any`.
+    pub const fn type_url(&self) -> &'static str {
+        match self {
+            Self::Transfer { .. } => "type.googleapis.com/protocol.TransferContract",
+            Self::TriggerSmartContract { .. } => {
+                "type.googleapis.com/protocol.TriggerSmartContract"
+            }
+        }
+    }
+
+    /// The account signing the transaction.
+    pub const fn owner_address(&self) -> &Address {
+        match self {
+            Self::Transfer { owner_address, .. }
+            | Self::TriggerSmartContract { owner_address, .. } => owner_address,
+        }
+    }
+
+    /// Encode the parameter of the contract, the message of its type.
+    pub fn encode_parameter(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        match self {
+            Self::Transfer {
+                owner_address,
+                to_address,
+                amount,
+            } => {
+                encode_bytes(&mut buffer, 1, &owner_address.to_bytes());
+                encode_bytes(&mut buffer, 2, &to_address.to_bytes());
+                encode_uint64(&mut buffer, 3, *amount);
+            }
+            Self::TriggerSmartContract {
+                owner_address,
+                contract_address,
+                call_value,
+                data,
+            } => {
+                encode_bytes(&mut buffer, 1, &owner_address.to_bytes());
+                encode_bytes(&mut buffer, 2, &contract_address.to_bytes());
+                encode_uint64(&mut buffer, 3, *call_value);
+                encode_bytes(&mut buffer, 4, data);
+            }
+        }
+        buffer
+    }
+
+    /// Encode the `Contract` message, its type and its parameter in an `Any`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut parameter = Vec::new();
+        encode_string(&mut parameter, 1, self.type_url());
+        encode_bytes(&mut parameter, 2, &self.encode_parameter());
+
+        let mut buffer = Vec::new();
+        encode_uint64(&mut buffer, 1, self.contract_type() as u64);
+        encode_message(&mut buffer, 2, &parameter);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address() {
+        let usdt: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+        assert_eq!(
+            hex::encode(usdt.to_bytes()),
+            "41a614f803b6fd780986a42c78ec9c7f77e6ded13c"
+        );
+        assert_eq!(usdt.to_string(), "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t");
+        assert_eq!(
+            "41a614f803b6fd780986a42c78ec9c7f77e6ded13c"
+                .parse::<Address>()
+                .unwrap(),
+            usdt
+        );
+        assert_eq!(
+            Address([0; 20]).to_string(),
+            "T9yD14Nj9j7xAB4dbGeiX9h8unkKHxuWwb"
+        );
+
+        // Wrong checksum, Ethereum prefix and length
+        assert!("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u"
+            .parse::<Address>()
+            .is_err());
+        assert!("a0a614f803b6fd780986a42c78ec9c7f77e6ded13c"
+            .parse::<Address>()
+            .is_err());
+        assert!(Address::from_slice(&[ADDRESS_PREFIX; 20]).is_err());
+
+        let json = serde_json::to_string(&usdt).unwrap();
+        assert_eq!(json, "\"TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t\"");
+        assert_eq!(serde_json::from_str::<Address>(&json).unwrap(), usdt);
+    }
+
+    #[test]
+    fn test_encode_transfer_contract() {
+        let contract = Contract::Transfer {
+            owner_address: Address([1; 20]),
+            to_address: Address([2; 20]),
+            amount: SUN_PER_TRX,
+        };
+
+        let parameter = contract.encode_parameter();
+        assert_eq!(
+            hex::encode(&parameter),
+            concat!(
+                "0a1541",
+                "0101010101010101010101010101010101010101",
+                "121541",
+                "0202020202020202020202020202020202020202",
+                "18c0843d"
+            )
+        );
+
+        let encoded = contract.encode();
+        // type 1, then the Any of 45 bytes of type URL and 50 bytes of parameter
+        assert_eq!(encoded[..4], [0x08, 0x01, 0x12, 2 + 45 + 2 + 50]);
+        assert_eq!(encoded[6..51], *contract.type_url().as_bytes());
+        assert!(encoded.ends_with(&parameter));
+    }
+}
@@ -0,0 +1,98 @@
+//! Protobuf encoding of the fields of Tron transactions, and the reference to the block they
+//! are built on.
+//!
+//! Fields holding the default value of their type are omitted, like the protobuf encoders of the
+//! nodes do, as the transaction id is the hash of the raw data the node re-encodes.
+
+/// Wire type of varint fields.
+const WIRE_TYPE_VARINT: u64 = 0;
+/// Wire type of length-delimited fields, strings, bytes and embedded messages.
+const WIRE_TYPE_LEN: u64 = 2;
+
+/// Encode `value` as a base 128 varint.
+pub fn encode_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn encode_key(buffer: &mut Vec<u8>, field: u32, wire_type: u64) {
+    encode_varint(buffer, (u64::from(field) << 3) | wire_type);
+}
+
+/// Encode an integer field, omitted if zero, the `int64` fields of Tron holding no negative
+/// values.
+pub fn encode_uint64(buffer: &mut Vec<u8>, field: u32, value: u64) {
+    if value != 0 {
+        encode_key(buffer, field, WIRE_TYPE_VARINT);
+        encode_varint(buffer, value);
+    }
+}
+
+/// Encode a bytes field, omitted if empty.
+pub fn encode_bytes(buffer: &mut Vec<u8>, field: u32, value: &[u8]) {
+    if !value.is_empty() {
+        encode_message(buffer, field, value);
+    }
+}
+
+/// Encode a string field, omitted if empty.
+pub fn encode_string(buffer: &mut Vec<u8>, field: u32, value: &str) {
+    encode_bytes(buffer, field, value.as_bytes());
+}
+
+/// Encode an embedded message field from its encoding, present even if empty, or an element of
+/// a repeated bytes field.
+pub fn encode_message(buffer: &mut Vec<u8>, field: u32, message: &[u8]) {
+    encode_key(buffer, field, WIRE_TYPE_LEN);
+    encode_varint(buffer, message.len() as u64);
+    buffer.extend_from_slice(message);
+}
+
+/// The `ref_block_bytes` and `ref_block_hash` of transactions built on the block of number
+/// `block_number` and id `block_id`, the bytes 6 and 7 of its number and the bytes 8 to 15 of
+/// its id.
+///
+/// Nodes reject transactions whose reference block is not one of their last 65536 blocks, the
+/// latest solidified block usually being referenced.
+pub fn ref_block(block_number: u64, block_id: &[u8; 32]) -> ([u8; 2], [u8; 8]) {
+    let ref_block_bytes = block_number.to_be_bytes()[6..8]
+        .try_into()
+        .expect("u64 are 8 bytes");
+    let ref_block_hash = block_id[8..16].try_into().expect("block ids are 32 bytes");
+
+    (ref_block_bytes, ref_block_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_fields() {
+        let mut buffer = Vec::new();
+        encode_uint64(&mut buffer, 3, 0);
+        encode_bytes(&mut buffer, 1, &[]);
+        assert!(buffer.is_empty());
+
+        encode_uint64(&mut buffer, 3, 1_000_000);
+        encode_bytes(&mut buffer, 1, &[0x41, 0x01]);
+        encode_uint64(&mut buffer, 18, 1);
+        assert_eq!(hex::encode(buffer), "18c0843d0a024101900101");
+    }
+
+    #[test]
+    fn test_ref_block() {
+        let block_id =
+            hex::decode("0000000003b1a3e1c1b8ef5b9a0b5e34a5f9d6dbe0c7d3e9b1d4e1c2a3f4b5c6")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let (ref_block_bytes, ref_block_hash) = ref_block(61_973_473, &block_id);
+
+        assert_eq!(ref_block_bytes, [0xa3, 0xe1]);
+        assert_eq!(hex::encode(ref_block_hash), "c1b8ef5b9a0b5e34");
+    }
+}
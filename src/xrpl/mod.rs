@@ -0,0 +1,10 @@
+//! Transaction builder, encoders, types and utilities for the XRP Ledger.
+pub mod types;
+pub mod utils;
+mod xrpl_transaction;
+mod xrpl_transaction_builder;
+
+/// XRP Ledger transaction
+pub use xrpl_transaction::XrplTransaction;
+/// XRP Ledger transaction builder
+pub use xrpl_transaction_builder::XrplTransactionBuilder;
@@ -0,0 +1,734 @@
+//! Types used by the XRP Ledger transaction builder.
+use core::{fmt, str::FromStr};
+
+use ripemd::Ripemd160;
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+use super::utils::{decode_address, encode_address, encode_vl, FieldId};
+
+/// Drops in one XRP.
+pub const DROPS_PER_XRP: u64 = 1_000_000;
+/// Largest amount of drops, the 100 billion XRP created with the ledger.
+pub const MAX_DROPS: u64 = 100_000_000_000 * DROPS_PER_XRP;
+
+/// Smallest mantissa of nonzero issued currency values, of 16 significant digits.
+pub const MIN_MANTISSA: u64 = 1_000_000_000_000_000;
+/// Largest mantissa of issued currency values.
+pub const MAX_MANTISSA: u64 = 9_999_999_999_999_999;
+/// Smallest exponent of nonzero issued currency values.
+pub const MIN_EXPONENT: i32 = -96;
+/// Largest exponent of issued currency values.
+pub const MAX_EXPONENT: i32 = 80;
+
+/// Bit of positive amounts, set on XRP amounts.
+const POSITIVE_BIT: u64 = 0x4000_0000_0000_0000;
+/// Bit of issued currency amounts, unset on XRP amounts.
+const ISSUED_BIT: u64 = 0x8000_0000_0000_0000;
+/// Offset of the exponents of issued currency values, so that they are encoded unsigned.
+const EXPONENT_OFFSET: i32 = 97;
+
+/// Flag of TrustSet transactions authorizing the other party to hold the currency issued by the
+/// sender, if the sender requires authorization.
+pub const TF_SETF_AUTH: u32 = 0x0001_0000;
+/// Flag of TrustSet transactions blocking rippling through the trust line.
+pub const TF_SET_NO_RIPPLE: u32 = 0x0002_0000;
+/// Flag of TrustSet transactions allowing rippling through the trust line.
+pub const TF_CLEAR_NO_RIPPLE: u32 = 0x0004_0000;
+/// Flag of TrustSet transactions freezing the trust line.
+pub const TF_SET_FREEZE: u32 = 0x0010_0000;
+/// Flag of TrustSet transactions unfreezing the trust line.
+pub const TF_CLEAR_FREEZE: u32 = 0x0020_0000;
+
+/// An account, the ripemd160 of the sha256 of its master public key, displayed as its classic
+/// address like `rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccountId(pub [u8; 20]);
+
+impl AccountId {
+    /// The account of `public_key`, a compressed secp256k1 key or an Ed25519 key prefixed with
+    /// `0xED`.
+    pub fn from_public_key(public_key: &PublicKey) -> Self {
+        Self(Ripemd160::digest(Sha256::digest(public_key.0)).into())
+    }
+
+    /// Encode the account as the value of an account field, prefixed with its length.
+    pub fn encode(&self) -> Vec<u8> {
+        encode_vl(&self.0)
+    }
+}
+
+impl FromStr for AccountId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_address(s).map(Self)
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&encode_address(&self.0))
+    }
+}
+
+impl Serialize for AccountId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AccountId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// The public key of the signer of a transaction, a compressed secp256k1 key or an Ed25519 key
+/// prefixed with `0xED`, set as the `SigningPubKey` of the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PublicKey(pub [u8; 33]);
+
+impl PublicKey {
+    /// Prefix of Ed25519 keys, telling them from secp256k1 keys.
+    pub const ED25519_PREFIX: u8 = 0xed;
+
+    /// A public key from its 33 bytes, a compressed secp256k1 key or a prefixed Ed25519 key.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        let key = <[u8; 33]>::try_from(bytes)
+            .map_err(|_| format!("Invalid public key length: {}", bytes.len()))?;
+
+        match key[0] {
+            0x02 | 0x03 | Self::ED25519_PREFIX => Ok(Self(key)),
+            prefix => Err(format!("Invalid public key prefix: {prefix:#04x}")),
+        }
+    }
+
+    /// The public key of the Ed25519 key `key`.
+    pub fn from_ed25519(key: &[u8; 32]) -> Self {
+        let mut bytes = [Self::ED25519_PREFIX; 33];
+        bytes[1..].copy_from_slice(key);
+        Self(bytes)
+    }
+
+    /// Whether the key is an Ed25519 key, signing the bytes of transactions rather than their
+    /// hash.
+    pub fn is_ed25519(&self) -> bool {
+        self.0[0] == Self::ED25519_PREFIX
+    }
+
+    /// The account of the key.
+    pub fn account_id(&self) -> AccountId {
+        AccountId::from_public_key(self)
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode_upper(self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        let bytes = hex::decode(&s).map_err(D::Error::custom)?;
+        Self::from_slice(&bytes).map_err(D::Error::custom)
+    }
+}
+
+/// A code of currency issued on the ledger, a standard 3 characters code like `USD` or 20 bytes
+/// displayed as 40 hexadecimal characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency(pub [u8; 20]);
+
+impl Currency {
+    /// Whether the currency is a standard code, its 3 characters at bytes 12 to 14.
+    pub fn is_standard(&self) -> bool {
+        self.0[..12]
+            .iter()
+            .chain(&self.0[15..])
+            .all(|&byte| byte == 0)
+    }
+}
+
+impl FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0; 20];
+        if s.len() == 3 {
+            if s == "XRP" {
+                return Err("XRP is not an issued currency".to_string());
+            }
+            if !s
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"?!@#$%^&*<>(){}[]|".contains(&byte))
+            {
+                return Err(format!("Invalid currency code: {s}"));
+            }
+            bytes[12..15].copy_from_slice(s.as_bytes());
+        } else {
+            hex::decode_to_slice(s, &mut bytes)
+                .map_err(|e| format!("Invalid currency code {s}: {e}"))?;
+            // The first byte of standard codes only
+            if bytes[0] == 0 {
+                return Err(format!("Invalid nonstandard currency code: {s}"));
+            }
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match core::str::from_utf8(&self.0[12..15]) {
+            Ok(code) if self.is_standard() => f.write_str(code),
+            _ => f.write_str(&hex::encode_upper(self.0)),
+        }
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// A value of an issued currency, `mantissa * 10^exponent`, normalized to a mantissa of 16
+/// significant digits, parsed from and displayed as a decimal string like `7072.8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IssuedValue {
+    mantissa: i64,
+    exponent: i32,
+}
+
+impl IssuedValue {
+    pub const ZERO: Self = Self {
+        mantissa: 0,
+        exponent: 0,
+    };
+
+    /// The value `mantissa * 10^exponent`, failing if it does not fit in 16 significant digits
+    /// and the range of exponents.
+    pub fn new(mantissa: i64, exponent: i32) -> Result<Self, String> {
+        if mantissa == 0 {
+            return Ok(Self::ZERO);
+        }
+
+        let mut unsigned = mantissa.unsigned_abs();
+        let mut exponent = i64::from(exponent);
+        while unsigned < MIN_MANTISSA {
+            unsigned *= 10;
+            exponent -= 1;
+        }
+        while unsigned > MAX_MANTISSA {
+            if unsigned % 10 != 0 {
+                return Err(format!(
+                    "Issued currency values have at most 16 significant digits: {mantissa}"
+                ));
+            }
+            unsigned /= 10;
+            exponent += 1;
+        }
+
+        if !(i64::from(MIN_EXPONENT)..=i64::from(MAX_EXPONENT)).contains(&exponent) {
+            return Err(format!(
+                "Issued currency value out of range: {mantissa}e{exponent}"
+            ));
+        }
+        Ok(Self {
+            mantissa: mantissa.signum() * unsigned as i64,
+            exponent: exponent as i32,
+        })
+    }
+
+    /// The normalized mantissa, 0 or of 16 significant digits.
+    pub const fn mantissa(&self) -> i64 {
+        self.mantissa
+    }
+
+    /// The exponent of the normalized mantissa.
+    pub const fn exponent(&self) -> i32 {
+        self.exponent
+    }
+
+    /// Encode the value in 64 bits, its sign, its offset exponent and its mantissa below the
+    /// bit of issued currency amounts.
+    pub fn encode(&self) -> [u8; 8] {
+        if self.mantissa == 0 {
+            return ISSUED_BIT.to_be_bytes();
+        }
+
+        let sign = if self.mantissa > 0 { POSITIVE_BIT } else { 0 };
+        let exponent = (self.exponent + EXPONENT_OFFSET) as u64;
+        (ISSUED_BIT | sign | (exponent << 54) | self.mantissa.unsigned_abs()).to_be_bytes()
+    }
+}
+
+impl FromStr for IssuedValue {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid issued currency value: {s}");
+
+        let (negative, unsigned) = match s.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (decimal, exponent): (_, i32) = match unsigned.split_once(['e', 'E']) {
+            Some((decimal, exponent)) => (decimal, exponent.parse().map_err(|_| invalid())?),
+            None => (unsigned, 0),
+        };
+        let (integer, fraction) = decimal.split_once('.').unwrap_or((decimal, ""));
+        if (integer.is_empty() && fraction.is_empty())
+            || !integer
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|byte| byte.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let digits = format!("{integer}{fraction}");
+        let digits = digits.trim_start_matches('0');
+        let significant = digits.trim_end_matches('0');
+        if significant.len() > 16 {
+            return Err(format!(
+                "Issued currency values have at most 16 significant digits: {s}"
+            ));
+        }
+
+        let mantissa: i64 = significant.parse().unwrap_or(0);
+        let exponent = i64::from(exponent)
+            .checked_sub(fraction.len() as i64)
+            .and_then(|exponent| exponent.checked_add((digits.len() - significant.len()) as i64))
+            .and_then(|exponent| i32::try_from(exponent).ok())
+            .ok_or_else(invalid)?;
+        Self::new(if negative { -mantissa } else { mantissa }, exponent)
+    }
+}
+
+impl fmt::Display for IssuedValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.mantissa == 0 {
+            return f.write_str("0");
+        }
+        if self.mantissa < 0 {
+            f.write_str("-")?;
+        }
+
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let significant = digits.trim_end_matches('0');
+        let exponent = self.exponent + (digits.len() - significant.len()) as i32;
+        if exponent >= 0 {
+            write!(f, "{significant}{}", "0".repeat(exponent as usize))
+        } else {
+            let point = significant.len() as i32 + exponent;
+            if point > 0 {
+                let (integer, fraction) = significant.split_at(point as usize);
+                write!(f, "{integer}.{fraction}")
+            } else {
+                write!(f, "0.{}{significant}", "0".repeat(-point as usize))
+            }
+        }
+    }
+}
+
+impl Serialize for IssuedValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for IssuedValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// An amount of XRP, in drops, or of a currency issued by an account, like a stablecoin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Amount {
+    Xrp(u64),
+    Issued {
+        value: IssuedValue,
+        currency: Currency,
+        issuer: AccountId,
+    },
+}
+
+impl Amount {
+    /// An amount of `xrp` whole XRP, or `None` if above [`MAX_DROPS`].
+    pub const fn from_xrp(xrp: u64) -> Option<Self> {
+        match xrp.checked_mul(DROPS_PER_XRP) {
+            Some(drops) if drops <= MAX_DROPS => Some(Self::Xrp(drops)),
+            _ => None,
+        }
+    }
+
+    /// An amount of the currency `currency` issued by `issuer`, its value a decimal string
+    /// like `7072.8`.
+    pub fn issued(value: &str, currency: &str, issuer: AccountId) -> Result<Self, String> {
+        Ok(Self::Issued {
+            value: value.parse()?,
+            currency: currency.parse()?,
+            issuer,
+        })
+    }
+
+    pub const fn is_xrp(&self) -> bool {
+        matches!(self, Self::Xrp(_))
+    }
+
+    /// Checks that the amount can be encoded: an XRP amount must be at most [`MAX_DROPS`].
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Xrp(drops) if *drops > MAX_DROPS => {
+                Err(format!("XRP amounts are at most {MAX_DROPS} drops"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Encode the amount, XRP amounts being 64 bits with the positive bit set, and issued
+    /// currency amounts 384 bits, their value followed by their currency and their issuer.
+    ///
+    /// Panics if an XRP amount is above [`MAX_DROPS`].
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Xrp(drops) => {
+                assert!(
+                    *drops <= MAX_DROPS,
+                    "XRP amounts are at most {MAX_DROPS} drops"
+                );
+                (drops | POSITIVE_BIT).to_be_bytes().to_vec()
+            }
+            Self::Issued {
+                value,
+                currency,
+                issuer,
+            } => [&value.encode()[..], &currency.0, &issuer.0].concat(),
+        }
+    }
+}
+
+/// The type of a transaction, set as its `TransactionType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionType {
+    Payment = 0,
+    TrustSet = 20,
+}
+
+/// The fields specific to the type of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionKind {
+    /// Sends `amount` to `destination`, which must not require a destination tag if none is
+    /// given, like most exchanges do.
+    Payment {
+        destination: AccountId,
+        amount: Amount,
+        destination_tag: Option<u32>,
+    },
+    /// Creates or modifies the trust line of the sender to the issuer of `limit_amount`, the
+    /// most the sender accepts to hold of its currency, or deletes it with a zero limit.
+    TrustSet {
+        limit_amount: Amount,
+        /// The ratio, in billionths, at which the sender values incoming balances, 0 or none
+        /// for their face value.
+        quality_in: Option<u32>,
+        /// The ratio, in billionths, at which the sender values outgoing balances.
+        quality_out: Option<u32>,
+    },
+}
+
+impl TransactionKind {
+    pub const fn transaction_type(&self) -> TransactionType {
+        match self {
+            Self::Payment { .. } => TransactionType::Payment,
+            Self::TrustSet { .. } => TransactionType::TrustSet,
+        }
+    }
+
+    /// Checks that the fields can be encoded: amounts of XRP must be at most [`MAX_DROPS`] and
+    /// the limit of a TrustSet transaction must be in an issued currency.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Payment { amount, .. } => amount.validate(),
+            Self::TrustSet { limit_amount, .. } if limit_amount.is_xrp() => {
+                Err("Trust lines are limited in an issued currency".to_string())
+            }
+            Self::TrustSet { limit_amount, .. } => limit_amount.validate(),
+        }
+    }
+
+    /// The fields of the transaction specific to its type, with the encodings of their values.
+    ///
+    /// Panics if the fields fail [`Self::validate`].
+    pub fn fields(&self) -> Vec<(FieldId, Vec<u8>)> {
+        match self {
+            Self::Payment {
+                destination,
+                amount,
+                destination_tag,
+            } => {
+                let mut fields = vec![
+                    (FieldId::DESTINATION, destination.encode()),
+                    (FieldId::AMOUNT, amount.encode()),
+                ];
+                if let Some(destination_tag) = destination_tag {
+                    fields.push((
+                        FieldId::DESTINATION_TAG,
+                        destination_tag.to_be_bytes().to_vec(),
+                    ));
+                }
+                fields
+            }
+            Self::TrustSet {
+                limit_amount,
+                quality_in,
+                quality_out,
+            } => {
+                if let Err(error) = self.validate() {
+                    panic!("{error}");
+                }
+
+                let mut fields = vec![(FieldId::LIMIT_AMOUNT, limit_amount.encode())];
+                for (id, quality) in [
+                    (FieldId::QUALITY_IN, quality_in),
+                    (FieldId::QUALITY_OUT, quality_out),
+                ] {
+                    if let Some(quality) = quality {
+                        fields.push((id, quality.to_be_bytes().to_vec()));
+                    }
+                }
+                fields
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_id() {
+        let public_key = PublicKey::from_slice(
+            &hex::decode("03ee83bb432547885c219634a1bc407a9db0474145d69737d09ccdc63e1dee7fe3")
+                .unwrap(),
+        )
+        .unwrap();
+
+        let account_id = public_key.account_id();
+        assert_eq!(
+            hex::encode(account_id.0),
+            "dd76483facdee26e60d8a586bb58d09f27045c46"
+        );
+        assert_eq!(account_id.to_string(), "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys");
+        assert_eq!(
+            "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys"
+                .parse::<AccountId>()
+                .unwrap(),
+            account_id
+        );
+        assert_eq!(
+            serde_json::to_string(&account_id).unwrap(),
+            r#""rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys""#
+        );
+    }
+
+    #[test]
+    fn test_public_key() {
+        let public_key = PublicKey::from_ed25519(&[7; 32]);
+        assert!(public_key.is_ed25519());
+        assert_eq!(PublicKey::from_slice(&public_key.0).unwrap(), public_key);
+
+        assert!(!PublicKey([2; 33]).is_ed25519());
+        assert!(PublicKey::from_slice(&[4; 33]).is_err());
+        assert!(PublicKey::from_slice(&[2; 32]).is_err());
+    }
+
+    #[test]
+    fn test_encode_xrp_amount() {
+        assert_eq!(
+            Amount::Xrp(10).encode(),
+            hex::decode("400000000000000a").unwrap()
+        );
+        assert_eq!(
+            Amount::from_xrp(15_000).unwrap().encode(),
+            hex::decode("400000037e11d600").unwrap()
+        );
+        assert_eq!(
+            Amount::Xrp(MAX_DROPS).encode(),
+            hex::decode("416345785d8a0000").unwrap()
+        );
+
+        assert_eq!(
+            Amount::from_xrp(100_000_000_000),
+            Some(Amount::Xrp(MAX_DROPS))
+        );
+        assert_eq!(Amount::from_xrp(100_000_000_001), None);
+        assert_eq!(Amount::from_xrp(u64::MAX), None);
+        assert!(Amount::Xrp(MAX_DROPS + 1).validate().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "XRP amounts are at most")]
+    fn test_encode_xrp_amount_above_supply() {
+        Amount::Xrp(MAX_DROPS + 1).encode();
+    }
+
+    #[test]
+    fn test_issued_value() {
+        for (value, mantissa, exponent, display) in [
+            ("7072.8", 7_072_800_000_000_000, -12, "7072.8"),
+            ("1", 1_000_000_000_000_000, -15, "1"),
+            ("-2", -2_000_000_000_000_000, -15, "-2"),
+            ("+0.00100", 1_000_000_000_000_000, -18, "0.001"),
+            ("1.5e3", 1_500_000_000_000_000, -12, "1500"),
+            (
+                "12345678901234560000",
+                1_234_567_890_123_456,
+                4,
+                "12345678901234560000",
+            ),
+            ("0.000", 0, 0, "0"),
+            ("-0", 0, 0, "0"),
+        ] {
+            let parsed: IssuedValue = value.parse().unwrap();
+            assert_eq!((parsed.mantissa(), parsed.exponent()), (mantissa, exponent));
+            assert_eq!(parsed.to_string(), display);
+            assert_eq!(display.parse::<IssuedValue>().unwrap(), parsed);
+        }
+
+        assert_eq!(
+            IssuedValue::new(9_999_999_999_999_999, MAX_EXPONENT)
+                .unwrap()
+                .encode(),
+            0xec63_86f2_6fc0_ffffu64.to_be_bytes()
+        );
+        assert_eq!(IssuedValue::ZERO.encode(), ISSUED_BIT.to_be_bytes());
+        assert_eq!(
+            "1".parse::<IssuedValue>().unwrap().encode(),
+            0xd483_8d7e_a4c6_8000u64.to_be_bytes()
+        );
+        assert_eq!(
+            "-2".parse::<IssuedValue>().unwrap().encode(),
+            0x9487_1afd_498d_0000u64.to_be_bytes()
+        );
+
+        for invalid in [
+            "",
+            ".",
+            "1.2.3",
+            "1e",
+            "abc",
+            "--1",
+            "1e97",
+            "1e-200",
+            "10e2147483647",
+            "1.5e-2147483648",
+        ] {
+            assert!(invalid.parse::<IssuedValue>().is_err(), "{invalid}");
+        }
+        assert!("12345678901234567".parse::<IssuedValue>().is_err());
+        assert!(IssuedValue::new(12_345_678_901_234_567, 0).is_err());
+    }
+
+    #[test]
+    fn test_currency() {
+        let usd: Currency = "USD".parse().unwrap();
+        assert_eq!(
+            hex::encode(usd.0),
+            "0000000000000000000000005553440000000000"
+        );
+        assert!(usd.is_standard());
+        assert_eq!(usd.to_string(), "USD");
+
+        let rlusd: Currency = "524C555344000000000000000000000000000000".parse().unwrap();
+        assert!(!rlusd.is_standard());
+        assert_eq!(
+            rlusd.to_string(),
+            "524C555344000000000000000000000000000000"
+        );
+        assert_eq!(
+            serde_json::to_string(&rlusd).unwrap(),
+            r#""524C555344000000000000000000000000000000""#
+        );
+
+        for invalid in [
+            "XRP",
+            "US",
+            "U D",
+            "0000000000000000000000005553440000000000",
+            "524C5553440000000000000000000000000000",
+        ] {
+            assert!(invalid.parse::<Currency>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_encode_issued_amount() {
+        // The TakerPays of the OfferCreate of the serialization docs of the XRP Ledger
+        let amount = Amount::issued(
+            "7072.8",
+            "USD",
+            "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".parse().unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            hex::encode_upper(amount.encode()),
+            concat!(
+                "D55920AC93914000",
+                "0000000000000000000000005553440000000000",
+                "0A20B3C85F482532A9578DBB3950B85CA06594D1"
+            )
+        );
+        assert!(!amount.is_xrp());
+        assert!(Amount::issued("1", "XRP", AccountId([0; 20])).is_err());
+    }
+
+    #[test]
+    fn test_trust_set_fields() {
+        let limit_amount = Amount::issued("1e9", "USD", AccountId([1; 20])).unwrap();
+        let kind = TransactionKind::TrustSet {
+            limit_amount: limit_amount.clone(),
+            quality_in: None,
+            quality_out: Some(1_000_000_000),
+        };
+
+        assert_eq!(kind.transaction_type(), TransactionType::TrustSet);
+        assert_eq!(
+            kind.fields(),
+            vec![
+                (FieldId::LIMIT_AMOUNT, limit_amount.encode()),
+                (
+                    FieldId::QUALITY_OUT,
+                    1_000_000_000u32.to_be_bytes().to_vec()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Trust lines are limited in an issued currency")]
+    fn test_trust_set_in_xrp() {
+        TransactionKind::TrustSet {
+            limit_amount: Amount::Xrp(1),
+            quality_in: None,
+            quality_out: None,
+        }
+        .fields();
+    }
+}
@@ -0,0 +1,280 @@
+//! Canonical binary serialization of XRP Ledger objects, their hashes, and the base58 classic
+//! addresses of accounts.
+//!
+//! The fields of an object are serialized sorted by their type code then their field code, each
+//! preceded by its field id, as the ledger re-serializes transactions canonically to check their
+//! signatures.
+use sha2::{Digest, Sha512};
+
+/// Prefix of the bytes signed by the single signer of a transaction, `STX\0`.
+pub const SIGNING_PREFIX: [u8; 4] = *b"STX\0";
+/// Prefix of the signed transaction hashed into its id, `TXN\0`.
+pub const TRANSACTION_ID_PREFIX: [u8; 4] = *b"TXN\0";
+
+/// Type code of 16 bits unsigned integers.
+pub const TYPE_UINT16: u8 = 1;
+/// Type code of 32 bits unsigned integers.
+pub const TYPE_UINT32: u8 = 2;
+/// Type code of amounts of XRP or of issued currencies.
+pub const TYPE_AMOUNT: u8 = 6;
+/// Type code of variable length blobs.
+pub const TYPE_BLOB: u8 = 7;
+/// Type code of accounts.
+pub const TYPE_ACCOUNT_ID: u8 = 8;
+
+/// Largest length of a variable length field, encoded in 3 bytes.
+pub const MAX_VL_LENGTH: usize = 918_744;
+
+/// The id of a field, its type code and its code among the fields of its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FieldId {
+    pub type_code: u8,
+    pub field_code: u8,
+}
+
+impl FieldId {
+    pub const TRANSACTION_TYPE: Self = Self::new(TYPE_UINT16, 2);
+    pub const NETWORK_ID: Self = Self::new(TYPE_UINT32, 1);
+    pub const FLAGS: Self = Self::new(TYPE_UINT32, 2);
+    pub const SOURCE_TAG: Self = Self::new(TYPE_UINT32, 3);
+    pub const SEQUENCE: Self = Self::new(TYPE_UINT32, 4);
+    pub const DESTINATION_TAG: Self = Self::new(TYPE_UINT32, 14);
+    pub const QUALITY_IN: Self = Self::new(TYPE_UINT32, 20);
+    pub const QUALITY_OUT: Self = Self::new(TYPE_UINT32, 21);
+    pub const LAST_LEDGER_SEQUENCE: Self = Self::new(TYPE_UINT32, 27);
+    pub const AMOUNT: Self = Self::new(TYPE_AMOUNT, 1);
+    pub const LIMIT_AMOUNT: Self = Self::new(TYPE_AMOUNT, 3);
+    pub const FEE: Self = Self::new(TYPE_AMOUNT, 8);
+    pub const SIGNING_PUB_KEY: Self = Self::new(TYPE_BLOB, 3);
+    pub const TXN_SIGNATURE: Self = Self::new(TYPE_BLOB, 4);
+    pub const ACCOUNT: Self = Self::new(TYPE_ACCOUNT_ID, 1);
+    pub const DESTINATION: Self = Self::new(TYPE_ACCOUNT_ID, 3);
+
+    pub const fn new(type_code: u8, field_code: u8) -> Self {
+        Self {
+            type_code,
+            field_code,
+        }
+    }
+
+    /// Encode the field id, in 1 byte if both codes are below 16, else in 2 or 3 bytes.
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        let (type_code, field_code) = (self.type_code, self.field_code);
+        match (type_code < 16, field_code < 16) {
+            (true, true) => buffer.push((type_code << 4) | field_code),
+            (true, false) => buffer.extend_from_slice(&[type_code << 4, field_code]),
+            (false, true) => buffer.extend_from_slice(&[field_code, type_code]),
+            (false, false) => buffer.extend_from_slice(&[0, type_code, field_code]),
+        }
+    }
+}
+
+/// Encode the length prefix of a variable length field, in 1 to 3 bytes.
+///
+/// Panics if `length` is above [`MAX_VL_LENGTH`].
+pub fn encode_vl_length(buffer: &mut Vec<u8>, length: usize) {
+    match length {
+        0..=192 => buffer.push(length as u8),
+        193..=12_480 => {
+            let length = length - 193;
+            buffer.extend_from_slice(&[193 + (length >> 8) as u8, length as u8]);
+        }
+        12_481..=MAX_VL_LENGTH => {
+            let length = length - 12_481;
+            buffer.extend_from_slice(&[
+                241 + (length >> 16) as u8,
+                (length >> 8) as u8,
+                length as u8,
+            ]);
+        }
+        _ => panic!("Variable length fields are at most {MAX_VL_LENGTH} bytes long"),
+    }
+}
+
+/// Encode `value` prefixed with its length, the encoding of blobs and accounts.
+pub fn encode_vl(value: &[u8]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(value.len() + 3);
+    encode_vl_length(&mut buffer, value.len());
+    buffer.extend_from_slice(value);
+    buffer
+}
+
+/// Encode an object from its fields and the encodings of their values, sorting them in the
+/// canonical order.
+///
+/// Panics if a field is present twice.
+pub fn encode_object(mut fields: Vec<(FieldId, Vec<u8>)>) -> Vec<u8> {
+    fields.sort_by_key(|(id, _)| *id);
+    assert!(
+        fields.windows(2).all(|pair| pair[0].0 != pair[1].0),
+        "Fields of objects are unique"
+    );
+
+    let mut buffer = Vec::new();
+    for (id, value) in fields {
+        id.encode(&mut buffer);
+        buffer.extend_from_slice(&value);
+    }
+    buffer
+}
+
+/// The first half of the sha512 of `data`, the hash used across the ledger.
+pub fn sha512_half(data: &[u8]) -> [u8; 32] {
+    let hash = Sha512::digest(data);
+    let mut half = [0; 32];
+    half.copy_from_slice(&hash[..32]);
+    half
+}
+
+/// The id of the signed transaction `signed_tx`, the hash explorers and the `tx` method look it
+/// up by.
+pub fn transaction_id(signed_tx: &[u8]) -> [u8; 32] {
+    sha512_half(&[&TRANSACTION_ID_PREFIX[..], signed_tx].concat())
+}
+
+/// Encode the 20 bytes of an account as a classic address, base58check with the ripple
+/// alphabet and the version 0, like `rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh`.
+pub fn encode_address(account_id: &[u8; 20]) -> String {
+    bs58::encode(account_id)
+        .with_alphabet(bs58::Alphabet::RIPPLE)
+        .with_check_version(0)
+        .into_string()
+}
+
+/// Decode a classic address into the 20 bytes of its account.
+pub fn decode_address(address: &str) -> Result<[u8; 20], String> {
+    let bytes = bs58::decode(address)
+        .with_alphabet(bs58::Alphabet::RIPPLE)
+        .with_check(Some(0))
+        .into_vec()
+        .map_err(|e| format!("Invalid address {address}: {e}"))?;
+
+    // The version byte is kept by the decoder
+    <[u8; 20]>::try_from(&bytes[1..]).map_err(|_| format!("Invalid address length: {address}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xrpl::types::Amount;
+
+    #[test]
+    fn test_encode_field_id() {
+        for (type_code, field_code, expected) in [
+            (1, 2, vec![0x12]),
+            (2, 27, vec![0x20, 0x1b]),
+            (16, 1, vec![0x01, 0x10]),
+            (16, 16, vec![0x00, 0x10, 0x10]),
+        ] {
+            let mut buffer = Vec::new();
+            FieldId::new(type_code, field_code).encode(&mut buffer);
+            assert_eq!(buffer, expected);
+        }
+    }
+
+    #[test]
+    fn test_encode_vl_length() {
+        for (length, expected) in [
+            (0, vec![0x00]),
+            (192, vec![0xc0]),
+            (193, vec![0xc1, 0x00]),
+            (12_480, vec![0xf0, 0xff]),
+            (12_481, vec![0xf1, 0x00, 0x00]),
+            (MAX_VL_LENGTH, vec![0xfe, 0xd4, 0x17]),
+        ] {
+            let mut buffer = Vec::new();
+            encode_vl_length(&mut buffer, length);
+            assert_eq!(buffer, expected);
+        }
+    }
+
+    #[test]
+    fn test_encode_object() {
+        // The OfferCreate of the serialization docs of the XRP Ledger, its fields out of order
+        let taker_pays = Amount::issued(
+            "7072.8",
+            "USD",
+            "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".parse().unwrap(),
+        )
+        .unwrap();
+        let fields = vec![
+            (
+                FieldId::ACCOUNT,
+                encode_vl(&hex::decode("dd76483facdee26e60d8a586bb58d09f27045c46").unwrap()),
+            ),
+            (
+                FieldId::TXN_SIGNATURE,
+                encode_vl(
+                    &hex::decode(concat!(
+                        "30440220143759437c04f7b61f012563afe90d8dafc46e86035e1d965a9ced282c97d4ce",
+                        "02204cfd241e86f17e011298fc1a39b63386c74306a5de047e213b0f29efa4571c2c"
+                    ))
+                    .unwrap(),
+                ),
+            ),
+            (
+                FieldId::SIGNING_PUB_KEY,
+                encode_vl(
+                    &hex::decode(
+                        "03ee83bb432547885c219634a1bc407a9db0474145d69737d09ccdc63e1dee7fe3",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (FieldId::FEE, hex::decode("400000000000000a").unwrap()),
+            (FieldId::new(TYPE_AMOUNT, 4), taker_pays.encode()),
+            (
+                FieldId::new(TYPE_AMOUNT, 5),
+                hex::decode("400000037e11d600").unwrap(),
+            ),
+            (
+                FieldId::new(TYPE_UINT32, 25),
+                1_752_791u32.to_be_bytes().to_vec(),
+            ),
+            (
+                FieldId::new(TYPE_UINT32, 10),
+                595_640_108u32.to_be_bytes().to_vec(),
+            ),
+            (FieldId::SEQUENCE, 1_752_792u32.to_be_bytes().to_vec()),
+            (FieldId::FLAGS, 524_288u32.to_be_bytes().to_vec()),
+            (FieldId::TRANSACTION_TYPE, 7u16.to_be_bytes().to_vec()),
+        ];
+
+        assert_eq!(
+            hex::encode_upper(encode_object(fields)),
+            concat!(
+                "120007220008000024001ABED82A2380BF2C2019001ABED7",
+                "64D55920AC9391400000000000000000000000000055534400000000000A20B3C85F482532",
+                "A9578DBB3950B85CA06594D1",
+                "65400000037E11D60068400000000000000A732103EE83BB432547885C219634A1BC407A9D",
+                "B0474145D69737D09CCDC63E1DEE7FE3744630440220143759437C04F7B61F012563AFE90D",
+                "8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C743",
+                "06A5DE047E213B0F29EFA4571C2C8114DD76483FACDEE26E60D8A586BB58D09F27045C46"
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Fields of objects are unique")]
+    fn test_encode_object_with_duplicate_fields() {
+        encode_object(vec![
+            (FieldId::FLAGS, vec![0; 4]),
+            (FieldId::FLAGS, vec![0; 4]),
+        ]);
+    }
+
+    #[test]
+    fn test_addresses() {
+        let account_id = hex::decode("dd76483facdee26e60d8a586bb58d09f27045c46").unwrap();
+        let account_id: [u8; 20] = account_id.try_into().unwrap();
+
+        let address = encode_address(&account_id);
+        assert_eq!(address, "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys");
+        assert_eq!(decode_address(&address).unwrap(), account_id);
+
+        // The account zero, and a typo breaking the checksum
+        assert_eq!(encode_address(&[0; 20]), "rrrrrrrrrrrrrrrrrrrrrhoLvTp");
+        assert!(decode_address("rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3yt").is_err());
+        assert!(decode_address("").is_err());
+    }
+}
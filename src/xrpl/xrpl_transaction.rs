@@ -0,0 +1,289 @@
+//! XRP Ledger transaction
+use serde::{Deserialize, Serialize};
+
+use super::{
+    types::{AccountId, Amount, PublicKey, TransactionKind},
+    utils::{encode_object, encode_vl, sha512_half, FieldId, SIGNING_PREFIX},
+};
+
+/// An XRP Ledger transaction with a single signer, encoded in the canonical binary format
+/// submitted as the `tx_blob` of the `submit` method.
+///
+/// ###### Example:
+///
+/// ```rust
+/// use signet_rs::xrpl::types::{Amount, PublicKey, TransactionKind};
+/// use signet_rs::xrpl::XrplTransaction;
+///
+/// let public_key = PublicKey([2; 33]);
+/// let tx = XrplTransaction {
+///     account: public_key.account_id(),
+///     kind: TransactionKind::Payment {
+///         destination: "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh".parse().unwrap(),
+///         amount: Amount::from_xrp(25).unwrap(),
+///         destination_tag: Some(12_345),
+///     },
+///     fee: Amount::Xrp(12),
+///     sequence: 7,
+///     flags: 0,
+///     last_ledger_sequence: Some(90_000_000),
+///     source_tag: None,
+///     network_id: None,
+///     signing_public_key: public_key,
+/// };
+///
+/// // The SHA-512Half of the signing bytes, signed with secp256k1
+/// let payload = tx.signing_hash();
+///
+/// let der_signature = [0x30; 70];
+/// let signed_tx = tx.build_with_signature(&der_signature);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XrplTransaction {
+    /// The account sending the transaction, paying its fee.
+    pub account: AccountId,
+    /// The type of the transaction and its fields specific to it.
+    pub kind: TransactionKind,
+    /// The XRP destroyed to send the transaction, at least the base fee of the ledger, never
+    /// an issued currency.
+    pub fee: Amount,
+    /// The sequence of the account, the number of the next transaction it sends.
+    pub sequence: u32,
+    pub flags: u32,
+    /// The last ledger the transaction can be included in, so that it expires if not validated.
+    pub last_ledger_sequence: Option<u32>,
+    pub source_tag: Option<u32>,
+    /// The id of the network, only set on networks of id above 1024, not on the mainnet.
+    pub network_id: Option<u32>,
+    /// The public key of the signer, which the account must be the account of, unless it is the
+    /// regular key of the account.
+    pub signing_public_key: PublicKey,
+}
+
+impl XrplTransaction {
+    /// Checks that the transaction can be encoded: its fee must be an amount of XRP, and its
+    /// amounts must pass [`Amount::validate`] and [`TransactionKind::validate`].
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.fee.is_xrp() {
+            return Err("Fees are paid in XRP".to_string());
+        }
+        self.fee.validate()?;
+        self.kind.validate()
+    }
+
+    /// The fields of the transaction, with the encodings of their values, without its
+    /// signature.
+    fn fields(&self) -> Vec<(FieldId, Vec<u8>)> {
+        if let Err(error) = self.validate() {
+            panic!("{error}");
+        }
+
+        let mut fields = self.kind.fields();
+        fields.extend([
+            (
+                FieldId::TRANSACTION_TYPE,
+                (self.kind.transaction_type() as u16).to_be_bytes().to_vec(),
+            ),
+            (FieldId::ACCOUNT, self.account.encode()),
+            (FieldId::FEE, self.fee.encode()),
+            (FieldId::SEQUENCE, self.sequence.to_be_bytes().to_vec()),
+            (FieldId::FLAGS, self.flags.to_be_bytes().to_vec()),
+            (
+                FieldId::SIGNING_PUB_KEY,
+                encode_vl(&self.signing_public_key.0),
+            ),
+        ]);
+        for (id, value) in [
+            (FieldId::LAST_LEDGER_SEQUENCE, self.last_ledger_sequence),
+            (FieldId::SOURCE_TAG, self.source_tag),
+            (FieldId::NETWORK_ID, self.network_id),
+        ] {
+            if let Some(value) = value {
+                fields.push((id, value.to_be_bytes().to_vec()));
+            }
+        }
+        fields
+    }
+
+    /// Encode the transaction without its signature.
+    ///
+    /// Panics if the transaction fails [`Self::validate`].
+    pub fn encode(&self) -> Vec<u8> {
+        encode_object(self.fields())
+    }
+
+    /// Encode the bytes signed by the signer of the transaction, the transaction prefixed with
+    /// [`SIGNING_PREFIX`].
+    ///
+    /// Ed25519 keys sign these bytes, while secp256k1 keys sign their [`Self::signing_hash`].
+    pub fn build_for_signing(&self) -> Vec<u8> {
+        [&SIGNING_PREFIX[..], &self.encode()].concat()
+    }
+
+    /// The SHA-512Half of the bytes signed by the signer, the payload of secp256k1 signatures.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        sha512_half(&self.build_for_signing())
+    }
+
+    /// Encode the transaction signed with `signature`, its `TxnSignature`, a DER secp256k1
+    /// signature with a low S, or a 64 bytes Ed25519 signature.
+    pub fn build_with_signature(&self, signature: &[u8]) -> Vec<u8> {
+        let mut fields = self.fields();
+        fields.push((FieldId::TXN_SIGNATURE, encode_vl(signature)));
+        encode_object(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xrpl::{types::TF_SET_NO_RIPPLE, utils::transaction_id};
+
+    fn payment() -> XrplTransaction {
+        XrplTransaction {
+            account: "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".parse().unwrap(),
+            kind: TransactionKind::Payment {
+                destination: "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".parse().unwrap(),
+                amount: Amount::Xrp(25_000_000),
+                destination_tag: Some(12_345),
+            },
+            fee: Amount::Xrp(12),
+            sequence: 1_752_792,
+            flags: 0,
+            last_ledger_sequence: Some(90_000_000),
+            source_tag: None,
+            network_id: None,
+            signing_public_key: PublicKey::from_slice(
+                &hex::decode("03ee83bb432547885c219634a1bc407a9db0474145d69737d09ccdc63e1dee7fe3")
+                    .unwrap(),
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_encode_payment() {
+        let tx = payment();
+
+        let expected = concat!(
+            "120000",             // TransactionType
+            "2200000000",         // Flags
+            "24001abed8",         // Sequence
+            "2e00003039",         // DestinationTag
+            "201b055d4a80",       // LastLedgerSequence
+            "6140000000017d7840", // Amount
+            "68400000000000000c", // Fee
+            "7321",               // SigningPubKey
+            "03ee83bb432547885c219634a1bc407a9db0474145d69737d09ccdc63e1dee7fe3",
+            "8114dd76483facdee26e60d8a586bb58d09f27045c46", // Account
+            "83140a20b3c85f482532a9578dbb3950b85ca06594d1", // Destination
+        );
+        assert_eq!(hex::encode(tx.encode()), expected);
+        assert_eq!(
+            hex::encode(tx.build_for_signing()),
+            format!("53545800{expected}")
+        );
+        assert_eq!(tx.signing_hash(), sha512_half(&tx.build_for_signing()));
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let tx = XrplTransaction {
+            kind: TransactionKind::Payment {
+                destination: "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".parse().unwrap(),
+                amount: Amount::Xrp(1),
+                destination_tag: None,
+            },
+            last_ledger_sequence: None,
+            source_tag: Some(7),
+            network_id: Some(21_338),
+            ..payment()
+        };
+
+        // NetworkID is the first UInt32 field, SourceTag following Flags
+        let expected = concat!(
+            "120000",
+            "210000535a",
+            "2200000000",
+            "2300000007",
+            "24001abed8",
+            "614000000000000001",
+            "68400000000000000c",
+            "732103ee83bb432547885c219634a1bc407a9db0474145d69737d09ccdc63e1dee7fe3",
+            "8114dd76483facdee26e60d8a586bb58d09f27045c46",
+            "83140a20b3c85f482532a9578dbb3950b85ca06594d1",
+        );
+        assert_eq!(hex::encode(tx.encode()), expected);
+    }
+
+    #[test]
+    fn test_build_with_signature() {
+        let tx = payment();
+        let signature = hex::decode(concat!(
+            "30440220143759437c04f7b61f012563afe90d8dafc46e86035e1d965a9ced282c97d4ce",
+            "02204cfd241e86f17e011298fc1a39b63386c74306a5de047e213b0f29efa4571c2c"
+        ))
+        .unwrap();
+
+        let signed = tx.build_with_signature(&signature);
+        let encoded = tx.encode();
+        // TxnSignature is the blob field following SigningPubKey, before the accounts
+        let split = encoded.len() - 44;
+        assert_eq!(
+            signed,
+            [
+                &encoded[..split],
+                &[0x74, 0x46],
+                &signature,
+                &encoded[split..]
+            ]
+            .concat()
+        );
+        assert_eq!(
+            transaction_id(&signed),
+            sha512_half(&[b"TXN\0".as_slice(), &signed].concat())
+        );
+    }
+
+    #[test]
+    fn test_encode_trust_set() {
+        let tx = XrplTransaction {
+            kind: TransactionKind::TrustSet {
+                limit_amount: Amount::issued(
+                    "100",
+                    "USD",
+                    "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".parse().unwrap(),
+                )
+                .unwrap(),
+                quality_in: None,
+                quality_out: None,
+            },
+            flags: TF_SET_NO_RIPPLE,
+            last_ledger_sequence: None,
+            ..payment()
+        };
+
+        let expected = concat!(
+            "120014",     // TransactionType
+            "2200020000", // Flags
+            "24001abed8", // Sequence
+            "63d5038d7ea4c68000",
+            "0000000000000000000000005553440000000000",
+            "0a20b3c85f482532a9578dbb3950b85ca06594d1", // LimitAmount
+            "68400000000000000c",                       // Fee
+            "732103ee83bb432547885c219634a1bc407a9db0474145d69737d09ccdc63e1dee7fe3",
+            "8114dd76483facdee26e60d8a586bb58d09f27045c46", // Account
+        );
+        assert_eq!(hex::encode(tx.encode()), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fees are paid in XRP")]
+    fn test_encode_with_issued_fee() {
+        let tx = XrplTransaction {
+            fee: Amount::issued("1", "USD", AccountId([1; 20])).unwrap(),
+            ..payment()
+        };
+        tx.encode();
+    }
+}
@@ -0,0 +1,270 @@
+use crate::transaction_builder::TxBuilder;
+
+use super::{
+    types::{AccountId, Amount, PublicKey, TransactionKind},
+    xrpl_transaction::XrplTransaction,
+};
+
+pub struct XrplTransactionBuilder {
+    account: Option<AccountId>,
+    kind: Option<TransactionKind>,
+    fee: Option<Amount>,
+    sequence: Option<u32>,
+    flags: Option<u32>,
+    last_ledger_sequence: Option<u32>,
+    source_tag: Option<u32>,
+    network_id: Option<u32>,
+    signing_public_key: Option<PublicKey>,
+}
+
+impl Default for XrplTransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxBuilder<XrplTransaction> for XrplTransactionBuilder {
+    fn build(&self) -> XrplTransaction {
+        match self.try_build() {
+            Ok(tx) => tx,
+            Err(error) => panic!("{error}"),
+        }
+    }
+}
+
+impl XrplTransactionBuilder {
+    pub const fn new() -> Self {
+        Self {
+            account: None,
+            kind: None,
+            fee: None,
+            sequence: None,
+            flags: None,
+            last_ledger_sequence: None,
+            source_tag: None,
+            network_id: None,
+            signing_public_key: None,
+        }
+    }
+
+    /// Account sending the transaction, the account of the signing public key by default.
+    pub const fn account(mut self, account: AccountId) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    /// Type of the transaction and its fields specific to it.
+    pub fn kind(mut self, kind: TransactionKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Payment of `amount` to `destination`, with the destination tag exchanges credit
+    /// deposits by.
+    pub fn payment(
+        mut self,
+        destination: AccountId,
+        amount: Amount,
+        destination_tag: Option<u32>,
+    ) -> Self {
+        self.kind = Some(TransactionKind::Payment {
+            destination,
+            amount,
+            destination_tag,
+        });
+        self
+    }
+
+    /// Trust line of the signer to the issuer of `limit_amount`, holding at most
+    /// `limit_amount` of its currency.
+    pub fn trust_set(mut self, limit_amount: Amount) -> Self {
+        self.kind = Some(TransactionKind::TrustSet {
+            limit_amount,
+            quality_in: None,
+            quality_out: None,
+        });
+        self
+    }
+
+    /// XRP destroyed to send the transaction, at least the base fee of the ledger, 10 drops on
+    /// the mainnet.
+    pub fn fee(mut self, fee: Amount) -> Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Sequence of the account, from the `account_info` method.
+    pub const fn sequence(mut self, sequence: u32) -> Self {
+        self.sequence = Some(sequence);
+        self
+    }
+
+    /// Flags of the transaction, 0 by default.
+    pub const fn flags(mut self, flags: u32) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Last ledger the transaction can be included in, usually a few ledgers after the current
+    /// one.
+    pub const fn last_ledger_sequence(mut self, last_ledger_sequence: u32) -> Self {
+        self.last_ledger_sequence = Some(last_ledger_sequence);
+        self
+    }
+
+    /// Tag identifying the sender of the transaction, like a hosted wallet or a customer.
+    pub const fn source_tag(mut self, source_tag: u32) -> Self {
+        self.source_tag = Some(source_tag);
+        self
+    }
+
+    /// Id of the network, required on sidechains and networks of id above 1024.
+    pub const fn network_id(mut self, network_id: u32) -> Self {
+        self.network_id = Some(network_id);
+        self
+    }
+
+    /// Public key of the signer, a compressed secp256k1 key or a prefixed Ed25519 key.
+    pub const fn signing_public_key(mut self, signing_public_key: PublicKey) -> Self {
+        self.signing_public_key = Some(signing_public_key);
+        self
+    }
+
+    /// Builds the transaction, returning an error instead of panicking if a mandatory field is
+    /// missing or if it fails [`XrplTransaction::validate`], like a fee in an issued currency
+    /// or a trust line limited in XRP.
+    pub fn try_build(&self) -> Result<XrplTransaction, String> {
+        let signing_public_key = self
+            .signing_public_key
+            .ok_or("signing_public_key is mandatory")?;
+
+        let tx = XrplTransaction {
+            account: self
+                .account
+                .unwrap_or_else(|| signing_public_key.account_id()),
+            kind: self.kind.clone().ok_or("kind is mandatory")?,
+            fee: self.fee.clone().ok_or("fee is mandatory")?,
+            sequence: self.sequence.ok_or("sequence is mandatory")?,
+            flags: self.flags.unwrap_or_default(),
+            last_ledger_sequence: self.last_ledger_sequence,
+            source_tag: self.source_tag,
+            network_id: self.network_id,
+            signing_public_key,
+        };
+        tx.validate()?;
+
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xrpl::types::{MAX_DROPS, TF_SET_NO_RIPPLE};
+    use crate::TransactionBuilder;
+
+    #[test]
+    fn test_build() {
+        let public_key = PublicKey([2; 33]);
+        let destination: AccountId = "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".parse().unwrap();
+
+        let tx: XrplTransaction = TransactionBuilder::new::<XrplTransactionBuilder>()
+            .payment(destination, Amount::from_xrp(25).unwrap(), Some(12_345))
+            .fee(Amount::Xrp(12))
+            .sequence(7)
+            .last_ledger_sequence(90_000_000)
+            .signing_public_key(public_key)
+            .build();
+
+        assert_eq!(
+            tx,
+            XrplTransaction {
+                account: public_key.account_id(),
+                kind: TransactionKind::Payment {
+                    destination,
+                    amount: Amount::Xrp(25_000_000),
+                    destination_tag: Some(12_345),
+                },
+                fee: Amount::Xrp(12),
+                sequence: 7,
+                flags: 0,
+                last_ledger_sequence: Some(90_000_000),
+                source_tag: None,
+                network_id: None,
+                signing_public_key: public_key,
+            }
+        );
+    }
+
+    #[test]
+    fn test_build_trust_set() {
+        let limit_amount = Amount::issued(
+            "1000000",
+            "524C555344000000000000000000000000000000",
+            "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".parse().unwrap(),
+        )
+        .unwrap();
+
+        let tx: XrplTransaction = TransactionBuilder::new::<XrplTransactionBuilder>()
+            .trust_set(limit_amount.clone())
+            .fee(Amount::Xrp(12))
+            .sequence(7)
+            .flags(TF_SET_NO_RIPPLE)
+            .signing_public_key(PublicKey([2; 33]))
+            .build();
+
+        assert_eq!(
+            tx.kind,
+            TransactionKind::TrustSet {
+                limit_amount,
+                quality_in: None,
+                quality_out: None,
+            }
+        );
+        assert_eq!(tx.flags, TF_SET_NO_RIPPLE);
+    }
+
+    #[test]
+    fn test_try_build_invalid() {
+        let issued = Amount::issued("1", "USD", AccountId([1; 20])).unwrap();
+        let builder = || {
+            XrplTransactionBuilder::new()
+                .payment(AccountId([1; 20]), Amount::Xrp(1), None)
+                .fee(Amount::Xrp(12))
+                .sequence(7)
+                .signing_public_key(PublicKey([2; 33]))
+        };
+        assert!(builder().try_build().is_ok());
+
+        assert_eq!(
+            XrplTransactionBuilder::new().try_build(),
+            Err("signing_public_key is mandatory".to_string())
+        );
+        assert_eq!(
+            builder().trust_set(Amount::Xrp(1)).try_build(),
+            Err("Trust lines are limited in an issued currency".to_string())
+        );
+        assert_eq!(
+            builder().fee(issued).try_build(),
+            Err("Fees are paid in XRP".to_string())
+        );
+        assert!(builder()
+            .fee(Amount::Xrp(MAX_DROPS + 1))
+            .try_build()
+            .is_err());
+        assert!(builder()
+            .payment(AccountId([1; 20]), Amount::Xrp(MAX_DROPS + 1), None)
+            .try_build()
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "kind is mandatory")]
+    fn test_build_without_kind() {
+        let _: XrplTransaction = XrplTransactionBuilder::new()
+            .fee(Amount::Xrp(12))
+            .sequence(7)
+            .signing_public_key(PublicKey([2; 33]))
+            .build();
+    }
+}